@@ -22,8 +22,8 @@ use qail_core::transpiler::{Dialect, ToSql};
 use qail::introspection;
 use qail::lint::lint_schema;
 use qail::migrations::{
-    migrate_analyze, migrate_apply, migrate_down, migrate_plan, migrate_status, migrate_up,
-    watch_schema, MigrateDirection,
+    migrate_analyze, migrate_apply, migrate_down, migrate_plan, migrate_run, migrate_status,
+    migrate_up, watch_schema, MigrateDirection,
 };
 use qail::repl::run_repl;
 use qail::schema::{OutputFormat as SchemaOutputFormat, check_schema, diff_schemas_cmd};
@@ -65,10 +65,29 @@ enum OutputFormat {
     Pretty,
 }
 
+#[derive(Clone, ValueEnum)]
+enum ExecOutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl From<ExecOutputFormat> for qail::exec::ExecFormat {
+    fn from(val: ExecOutputFormat) -> Self {
+        match val {
+            ExecOutputFormat::Text => qail::exec::ExecFormat::Text,
+            ExecOutputFormat::Json => qail::exec::ExecFormat::Json,
+            ExecOutputFormat::Csv => qail::exec::ExecFormat::Csv,
+        }
+    }
+}
+
 #[derive(Clone, ValueEnum)]
 enum CliDialect {
     Postgres,
     Sqlite,
+    Mysql,
+    Sqlserver,
 }
 
 impl From<CliDialect> for Dialect {
@@ -76,6 +95,8 @@ impl From<CliDialect> for Dialect {
         match val {
             CliDialect::Postgres => Dialect::Postgres,
             CliDialect::Sqlite => Dialect::SQLite,
+            CliDialect::Mysql => Dialect::MySQL,
+            CliDialect::Sqlserver => Dialect::SqlServer,
         }
     }
 }
@@ -91,9 +112,18 @@ enum Commands {
         #[arg(short, long)]
         mode: Option<String>,
     },
-    /// Parse and explain a QAIL query
-    Explain { query: String },
-    Repl,
+    /// Show the SQL, params and PostgreSQL plan of a QAIL query
+    Explain {
+        query: String,
+        /// Database URL (defaults to postgres.url in qail.toml)
+        #[arg(short, long)]
+        url: Option<String>,
+    },
+    Repl {
+        /// Schema file used for column name completion
+        #[arg(short, long, default_value = "schema.qail")]
+        schema: String,
+    },
     /// Show the symbol reference
     Symbols,
     /// Generate a migration file
@@ -185,6 +215,7 @@ SUBCOMMANDS:
     up       - Apply migrations forward
     down     - Rollback migrations
     apply    - Run all pending migrations from migrations/ folder
+    run      - Apply one multi-statement migration file atomically
     create   - Generate a new named migration file
     shadow   - Apply to shadow database (blue-green deployment)
     promote  - Swap shadow to primary
@@ -383,6 +414,9 @@ EXAMPLES:
         /// Dry-run: print generated SQL without executing
         #[arg(long)]
         dry_run: bool,
+        /// Result output format (json/csv print rows to stdout)
+        #[arg(long, value_enum, default_value = "text")]
+        format: ExecOutputFormat,
     },
     /// Generate typed Rust schema from schema.qail
     Types {
@@ -452,6 +486,14 @@ enum MigrateAction {
         #[arg(short, long)]
         url: Option<String>,
     },
+    /// Apply one multi-statement migration file in a single transaction
+    Run {
+        /// Migration file; its stem is recorded as the version
+        file: String,
+        /// Database URL (reads from qail.toml if not provided)
+        #[arg(short, long)]
+        url: Option<String>,
+    },
     /// Create a new named migration file
     Create {
         /// Name for the migration (e.g., add_user_avatars)
@@ -566,6 +608,25 @@ fn parse_schema_diff_with_old(schema_diff: &str) -> Result<(Vec<qail_core::ast::
     }
 }
 
+/// Database URL from the flag, falling back to `postgres.url` in qail.toml.
+fn resolve_db_url(url: Option<&str>) -> Result<String> {
+    if let Some(u) = url {
+        return Ok(u.to_string());
+    }
+    let config_path = std::path::Path::new("qail.toml");
+    if !config_path.exists() {
+        anyhow::bail!("No URL provided and qail.toml not found");
+    }
+    let content = std::fs::read_to_string(config_path)?;
+    let config: toml::Value = toml::from_str(&content)?;
+    config
+        .get("postgres")
+        .and_then(|p| p.get("url"))
+        .and_then(|u| u.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("No postgres.url in qail.toml"))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -574,8 +635,10 @@ async fn main() -> Result<()> {
         Some(Commands::Init { name, mode }) => {
             qail::init::run_init(name.clone(), mode.clone())?;
         }
-        Some(Commands::Explain { query }) => explain_query(query),
-        Some(Commands::Repl) => run_repl(),
+        Some(Commands::Explain { query, url }) => {
+            qail::explain::run_explain(query, url.as_deref()).await?;
+        }
+        Some(Commands::Repl { schema }) => run_repl(schema),
         Some(Commands::Symbols) => show_symbols(),
         Some(Commands::Mig { query, name }) => {
             generate_migration(query, name.clone())?;
@@ -622,26 +685,13 @@ async fn main() -> Result<()> {
             MigrateAction::Up { schema_diff, url, codebase, force } => migrate_up(schema_diff, url, codebase.as_deref(), *force).await?,
             MigrateAction::Down { schema_diff, url } => migrate_down(schema_diff, url).await?,
             MigrateAction::Apply { url } => {
-                // Get URL from qail.toml if not provided
-                let db_url = if let Some(u) = url {
-                    u.clone()
-                } else {
-                    // Try to load from qail.toml
-                    let config_path = std::path::Path::new("qail.toml");
-                    if config_path.exists() {
-                        let content = std::fs::read_to_string(config_path)?;
-                        let config: toml::Value = toml::from_str(&content)?;
-                        config.get("postgres")
-                            .and_then(|p| p.get("url"))
-                            .and_then(|u| u.as_str())
-                            .map(|s| s.to_string())
-                            .ok_or_else(|| anyhow::anyhow!("No postgres.url in qail.toml"))?
-                    } else {
-                        anyhow::bail!("No URL provided and qail.toml not found");
-                    }
-                };
+                let db_url = resolve_db_url(url.as_deref())?;
                 migrate_apply(&db_url, MigrateDirection::Up).await?;
             }
+            MigrateAction::Run { file, url } => {
+                let db_url = resolve_db_url(url.as_deref())?;
+                migrate_run(&db_url, file).await?;
+            }
             MigrateAction::Create {
                 name,
                 depends,
@@ -708,7 +758,7 @@ async fn main() -> Result<()> {
         Some(Commands::Worker { interval, batch }) => {
             qail::worker::run_worker(*interval, *batch).await?;
         },
-        Some(Commands::Exec { query, file, url, ssh, tx, dry_run }) => {
+        Some(Commands::Exec { query, file, url, ssh, tx, dry_run, format }) => {
             qail::exec::run_exec(qail::exec::ExecConfig {
                 query: query.clone(),
                 file: file.clone(),
@@ -716,6 +766,7 @@ async fn main() -> Result<()> {
                 ssh: ssh.clone(),
                 tx: *tx,
                 dry_run: *dry_run,
+                format: format.clone().into(),
             }).await?;
         },
         Some(Commands::Types { schema, output }) => {
@@ -799,34 +850,6 @@ fn generate_migration(query: &str, name_override: Option<String>) -> Result<()>
     Ok(())
 }
 
-fn explain_query(query: &str) {
-    println!("{}", "🔍 Query Analysis".cyan().bold());
-    println!();
-    println!("  {} {}", "Query:".dimmed(), query.yellow());
-    println!();
-
-    match qail_core::parse(query) {
-        Ok(cmd) => {
-            println!(
-                "  {} {}",
-                "Action:".dimmed(),
-                format!("{}", cmd.action).green()
-            );
-            println!("  {} {}", "Table:".dimmed(), cmd.table.white());
-
-            if !cmd.columns.is_empty() {
-                println!("  {} {}", "Columns:".dimmed(), cmd.columns.len());
-            }
-
-            println!();
-            println!("  {} {}", "SQL:".cyan(), cmd.to_sql().white().bold());
-        }
-        Err(e) => {
-            eprintln!("{} {}", "Parse Error:".red().bold(), e);
-        }
-    }
-}
-
 pub fn show_symbols() {
     println!("{}", "🪝 QAIL Symbol Reference (v2.0)".cyan().bold());
     println!();
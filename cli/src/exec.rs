@@ -41,13 +41,32 @@
 //!
 //! # Wrap in transaction
 //! qail exec -f batch.qail --url postgres://... --tx
+//!
+//! # Scriptable output (status lines go to stderr)
+//! qail exec "get users fields id, email" --url postgres://... --format json
+//! qail exec "get users fields id, email" --url postgres://... --format csv
 //! ```
 
 use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use colored::*;
 use qail_core::prelude::*;
 use qail_core::transpiler::ToSql;
-use qail_pg::PgDriver;
+use qail_pg::protocol::oid;
+use qail_pg::driver::ColumnInfo;
+use qail_pg::{PgDriver, PgRow};
+
+/// Output format for exec results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecFormat {
+    /// Human-readable progress, no result rows
+    #[default]
+    Text,
+    /// One JSON array of objects per statement
+    Json,
+    /// Header line plus one CSV record per row
+    Csv,
+}
 
 /// Configuration for exec command
 pub struct ExecConfig {
@@ -57,6 +76,18 @@ pub struct ExecConfig {
     pub ssh: Option<String>,
     pub tx: bool,
     pub dry_run: bool,
+    pub format: ExecFormat,
+}
+
+/// Print a status line; machine-readable formats keep stdout for results.
+macro_rules! status {
+    ($format:expr, $($arg:tt)*) => {
+        if $format == ExecFormat::Text {
+            println!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
 }
 
 /// SSH tunnel wrapper - kills tunnel on drop
@@ -172,6 +203,25 @@ fn split_qail_statements(content: &str) -> Vec<String> {
     statements
 }
 
+/// Database URL from `--url`, falling back to `postgres.url` in qail.toml.
+pub fn resolve_db_url(url: Option<&str>) -> Result<String> {
+    if let Some(url) = url {
+        return Ok(url.to_string());
+    }
+    let config_path = std::path::Path::new("qail.toml");
+    if !config_path.exists() {
+        anyhow::bail!("No URL provided and qail.toml not found. Use --url or create qail.toml");
+    }
+    let content = std::fs::read_to_string(config_path)?;
+    let toml_config: toml::Value = toml::from_str(&content)?;
+    toml_config
+        .get("postgres")
+        .and_then(|p| p.get("url"))
+        .and_then(|u| u.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("No postgres.url in qail.toml"))
+}
+
 /// Run the exec command (type-safe QAIL AST only)
 pub async fn run_exec(config: ExecConfig) -> Result<()> {
     // Get content from file or inline
@@ -188,7 +238,7 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
     let statements_str = split_qail_statements(&content);
     
     if statements_str.is_empty() {
-        println!("{}", "No QAIL statements to execute.".yellow());
+        status!(config.format, "{}", "No QAIL statements to execute.".yellow());
         return Ok(());
     }
 
@@ -200,7 +250,8 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
         statements.push(ast);
     }
 
-    println!(
+    status!(
+        config.format,
         "{} Parsed {} QAIL statement(s)",
         "📋".cyan(),
         statements.len().to_string().green()
@@ -219,28 +270,12 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
     }
 
     // Get database URL
-    let db_url = if let Some(url) = &config.url {
-        url.clone()
-    } else {
-        let config_path = std::path::Path::new("qail.toml");
-        if config_path.exists() {
-            let content = std::fs::read_to_string(config_path)?;
-            let toml_config: toml::Value = toml::from_str(&content)?;
-            toml_config
-                .get("postgres")
-                .and_then(|p| p.get("url"))
-                .and_then(|u| u.as_str())
-                .map(|s| s.to_string())
-                .ok_or_else(|| anyhow::anyhow!("No postgres.url in qail.toml"))?
-        } else {
-            anyhow::bail!("No URL provided and qail.toml not found. Use --url or create qail.toml");
-        }
-    };
+    let db_url = resolve_db_url(config.url.as_deref())?;
 
     // Set up SSH tunnel if requested
     let _tunnel: Option<SshTunnel>;
     let connect_url = if let Some(ssh_host) = &config.ssh {
-        println!("{} Opening SSH tunnel to {}...", "🔐".cyan(), ssh_host.green());
+        status!(config.format, "{} Opening SSH tunnel to {}...", "🔐".cyan(), ssh_host.green());
         
         // Parse the URL to extract host and port
         let parsed = url::Url::parse(&db_url)
@@ -258,7 +293,7 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
         tunneled_url.set_host(Some("127.0.0.1")).ok();
         tunneled_url.set_port(Some(local_port)).ok();
         
-        println!("{} Tunnel established: localhost:{} -> {}:{}", 
+        status!(config.format, "{} Tunnel established: localhost:{} -> {}:{}",
             "✓".green(), local_port, remote_host, remote_port);
         
         _tunnel = Some(tunnel);
@@ -269,7 +304,7 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
     };
 
     // Connect to database
-    println!("{} Connecting to database...", "🔌".cyan());
+    status!(config.format, "{} Connecting to database...", "🔌".cyan());
     let mut driver = PgDriver::connect_url(&connect_url).await
         .map_err(|e| anyhow::anyhow!("Connection failed: {}", e))?;
 
@@ -278,25 +313,47 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
     let mut error_count = 0;
 
     if config.tx {
-        println!("{} Starting transaction...", "🔒".cyan());
+        status!(config.format, "{} Starting transaction...", "🔒".cyan());
         driver.begin().await.map_err(|e| anyhow::anyhow!("BEGIN failed: {}", e))?;
     }
 
     for (i, ast) in statements.iter().enumerate() {
         let stmt_num = i + 1;
-        print!("  {} Executing statement {}... ", "→".dimmed(), stmt_num);
+        if config.format == ExecFormat::Text {
+            print!("  {} Executing statement {}... ", "→".dimmed(), stmt_num);
+        }
+
+        let result = match config.format {
+            ExecFormat::Text => driver.execute(ast).await.map(|_| ()),
+            ExecFormat::Json => driver.fetch_all_uncached(ast).await.map(|rows| {
+                println!("{}", serde_json::to_string_pretty(&rows_to_json(&rows)).unwrap_or_default());
+            }),
+            ExecFormat::Csv => match driver.fetch_all_uncached(ast).await {
+                // No rows carry no row description; describe the statement for the header
+                Ok(rows) if rows.is_empty() => driver.describe(ast).await.map(|info| {
+                    print!("{}", rows_to_csv(&rows, Some(&info)));
+                }),
+                result => result.map(|rows| print!("{}", rows_to_csv(&rows, None))),
+            },
+        };
 
-        match driver.execute(ast).await {
-            Ok(_) => {
-                println!("{}", "✓".green());
+        match result {
+            Ok(()) => {
+                if config.format == ExecFormat::Text {
+                    println!("{}", "✓".green());
+                }
                 success_count += 1;
             }
             Err(e) => {
-                println!("{} {}", "✗".red(), e.to_string().red());
+                if config.format == ExecFormat::Text {
+                    println!("{} {}", "✗".red(), e.to_string().red());
+                } else {
+                    eprintln!("{} Statement {}: {}", "✗".red(), stmt_num, e.to_string().red());
+                }
                 error_count += 1;
 
                 if config.tx {
-                    println!("{} Rolling back transaction...", "⚠️".yellow());
+                    status!(config.format, "{} Rolling back transaction...", "⚠️".yellow());
                     let _ = driver.rollback().await;
                     anyhow::bail!("Execution failed at statement {}: {}", stmt_num, e);
                 }
@@ -305,20 +362,22 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
     }
 
     if config.tx {
-        println!("{} Committing transaction...", "🔓".cyan());
+        status!(config.format, "{} Committing transaction...", "🔓".cyan());
         driver.commit().await.map_err(|e| anyhow::anyhow!("COMMIT failed: {}", e))?;
     }
 
     // Summary
-    println!();
+    status!(config.format, "");
     if error_count == 0 {
-        println!(
+        status!(
+            config.format,
             "{} All {} statement(s) executed successfully!",
             "✅".green(),
             success_count.to_string().green()
         );
     } else {
-        println!(
+        status!(
+            config.format,
             "{} {} succeeded, {} failed",
             "⚠️".yellow(),
             success_count.to_string().green(),
@@ -328,3 +387,231 @@ pub async fn run_exec(config: ExecConfig) -> Result<()> {
 
     Ok(())
 }
+
+/// Decode one text-format column into JSON using its type OID.
+/// NULL becomes `null`; bytea and non-UTF-8 data become base64 strings.
+fn column_to_json(column: &Option<Vec<u8>>, type_oid: u32) -> serde_json::Value {
+    use serde_json::Value as Json;
+
+    let Some(bytes) = column else {
+        return Json::Null;
+    };
+    if type_oid == oid::BYTEA {
+        return Json::String(bytea_to_base64(bytes));
+    }
+    let Ok(s) = std::str::from_utf8(bytes) else {
+        return Json::String(BASE64.encode(bytes));
+    };
+
+    match type_oid {
+        oid::BOOL => Json::Bool(s == "t" || s == "true"),
+        oid::INT2 | oid::INT4 | oid::INT8 => s
+            .parse::<i64>()
+            .map(Json::from)
+            .unwrap_or_else(|_| Json::String(s.to_string())),
+        // NUMERIC stays a string: f64 would silently drop precision.
+        oid::FLOAT4 | oid::FLOAT8 => s
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Json::Number)
+            .unwrap_or_else(|| Json::String(s.to_string())),
+        oid::JSON | oid::JSONB => {
+            serde_json::from_str(s).unwrap_or_else(|_| Json::String(s.to_string()))
+        }
+        _ => Json::String(s.to_string()),
+    }
+}
+
+/// bytea arrives hex-encoded (`\x0a0b`) in text format; re-encode as base64.
+fn bytea_to_base64(bytes: &[u8]) -> String {
+    let decoded = bytes.strip_prefix(b"\\x").and_then(|hex| {
+        hex.chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+    });
+    BASE64.encode(decoded.as_deref().unwrap_or(bytes))
+}
+
+/// Render rows as a JSON array of objects keyed by column name, in column order.
+pub fn rows_to_json(rows: &[PgRow]) -> serde_json::Value {
+    let out = rows
+        .iter()
+        .map(|row| {
+            let (names, oids) = match &row.column_info {
+                Some(info) => (info.names.as_slice(), info.oids.as_slice()),
+                None => (&[][..], &[][..]),
+            };
+            let obj = row
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(i, col)| {
+                    let name = names.get(i).cloned().unwrap_or_else(|| format!("column{}", i + 1));
+                    let type_oid = oids.get(i).copied().unwrap_or(oid::TEXT);
+                    (name, column_to_json(col, type_oid))
+                })
+                .collect();
+            serde_json::Value::Object(obj)
+        })
+        .collect();
+    serde_json::Value::Array(out)
+}
+
+/// Render rows as CSV with a header line. NULL is an empty field.
+/// Column names come from the rows, or from `columns` when there are none,
+/// so an empty result still has its header.
+pub fn rows_to_csv(rows: &[PgRow], columns: Option<&ColumnInfo>) -> String {
+    let mut out = String::new();
+    let names = match rows.first() {
+        Some(first) => first
+            .column_info
+            .as_ref()
+            .map(|info| info.names.clone())
+            .unwrap_or_else(|| (1..=first.columns.len()).map(|i| format!("column{}", i)).collect()),
+        None => columns.map(|info| info.names.clone()).unwrap_or_default(),
+    };
+    if names.is_empty() {
+        return out;
+    }
+    let header: Vec<String> = names.iter().map(|n| csv_field(n)).collect();
+    out.push_str(&header.join(","));
+    out.push('\n');
+
+    for row in rows {
+        let oids = row.column_info.as_ref().map(|info| info.oids.as_slice()).unwrap_or(&[]);
+        let fields: Vec<String> = row
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| match col {
+                None => String::new(),
+                Some(bytes) => {
+                    let type_oid = oids.get(i).copied().unwrap_or(oid::TEXT);
+                    match std::str::from_utf8(bytes) {
+                        Ok(s) if type_oid != oid::BYTEA => csv_field(s),
+                        _ if type_oid == oid::BYTEA => bytea_to_base64(bytes),
+                        _ => BASE64.encode(bytes),
+                    }
+                }
+            })
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn sample_rows() -> Vec<PgRow> {
+        let info = Arc::new(ColumnInfo {
+            names: vec!["id".to_string(), "name".to_string(), "avatar".to_string()],
+            name_to_index: HashMap::from([
+                ("id".to_string(), 0),
+                ("name".to_string(), 1),
+                ("avatar".to_string(), 2),
+            ]),
+            oids: vec![oid::INT4, oid::TEXT, oid::BYTEA],
+            formats: vec![0, 0, 0],
+            table_oids: vec![0, 0, 0],
+            column_attrs: vec![0, 0, 0],
+        });
+        vec![
+            PgRow {
+                columns: vec![
+                    Some(b"1".to_vec()),
+                    Some(b"Smith, \"Jo\"".to_vec()),
+                    Some(b"\\x6869".to_vec()),
+                ],
+                column_info: Some(info.clone()),
+            },
+            PgRow {
+                columns: vec![Some(b"2".to_vec()), None, None],
+                column_info: Some(info),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_rows_to_json() {
+        let json = rows_to_json(&sample_rows());
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"id": 1, "name": "Smith, \"Jo\"", "avatar": "aGk="},
+                {"id": 2, "name": null, "avatar": null}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rows_to_json_keeps_column_order_and_numeric_precision() {
+        let info = Arc::new(ColumnInfo {
+            names: vec!["z".to_string(), "a".to_string()],
+            name_to_index: HashMap::from([("z".to_string(), 0), ("a".to_string(), 1)]),
+            oids: vec![oid::NUMERIC, oid::FLOAT8],
+            formats: vec![0, 0],
+            table_oids: vec![0, 0],
+            column_attrs: vec![0, 0],
+        });
+        let rows = vec![PgRow {
+            columns: vec![Some(b"12345678901234567890.123456789".to_vec()), Some(b"1.5".to_vec())],
+            column_info: Some(info),
+        }];
+        let out = serde_json::to_string(&rows_to_json(&rows)).unwrap();
+        assert_eq!(out, r#"[{"z":"12345678901234567890.123456789","a":1.5}]"#);
+    }
+
+    #[test]
+    fn test_duplicate_column_names_keep_field_order() {
+        // `users.id` and `orders.id` from a join share one output name
+        let info = Arc::new(ColumnInfo {
+            names: vec!["id".to_string(), "id".to_string(), "total".to_string()],
+            name_to_index: HashMap::from([("id".to_string(), 1), ("total".to_string(), 2)]),
+            oids: vec![oid::INT4, oid::INT4, oid::INT4],
+            formats: vec![0, 0, 0],
+            table_oids: vec![0, 0, 0],
+            column_attrs: vec![0, 0, 0],
+        });
+        let rows = vec![PgRow {
+            columns: vec![Some(b"1".to_vec()), Some(b"7".to_vec()), Some(b"30".to_vec())],
+            column_info: Some(info),
+        }];
+        assert_eq!(rows_to_csv(&rows, None), "id,id,total\n1,7,30\n");
+        let out = serde_json::to_string(&rows_to_json(&rows)).unwrap();
+        assert!(!out.contains("\"\""), "{}", out);
+    }
+
+    #[test]
+    fn test_rows_to_csv() {
+        let csv = rows_to_csv(&sample_rows(), None);
+        assert_eq!(csv, "id,name,avatar\n1,\"Smith, \"\"Jo\"\"\",aGk=\n2,,\n");
+    }
+
+    #[test]
+    fn test_rows_to_csv_header_without_rows() {
+        let rows = sample_rows();
+        let info = rows[0].column_info.as_deref();
+        assert_eq!(rows_to_csv(&[], info), "id,name,avatar\n");
+        // Statements that return no columns print nothing
+        assert_eq!(rows_to_csv(&[], None), "");
+    }
+}
@@ -0,0 +1,193 @@
+//! Explain module - show what a QAIL query turns into
+//!
+//! Prints the generated SQL, the parameters extracted from it and, when a
+//! database is reachable, the PostgreSQL plan with its estimated cost.
+//!
+//! The plan comes from preparing the parameterized SQL and running
+//! `EXPLAIN EXECUTE` on it, so it is the plan for the statement the driver
+//! actually sends. Placeholders without a value (`$1`, `:name`) are planned
+//! generically (`plan_cache_mode = force_generic_plan`), as a prepared
+//! statement would be once PostgreSQL stops re-planning it. Nothing is
+//! executed.
+//!
+//! # Examples
+//!
+//! ```bash
+//! qail explain "get users where active = true" --url postgres://...
+//!
+//! # No --url and no qail.toml: SQL and params only
+//! qail explain "get orders fields id where total > 100"
+//! ```
+
+use anyhow::Result;
+use colored::*;
+use qail_core::ast::Value;
+use qail_core::transpiler::{ToSqlParameterized, TranspileResult};
+use qail_pg::PgDriver;
+
+use crate::exec::resolve_db_url;
+
+/// Name of the prepared statement used for `EXPLAIN EXECUTE`.
+const STATEMENT: &str = "qail_explain";
+
+/// Run `qail explain`.
+pub async fn run_explain(query: &str, url: Option<&str>) -> Result<()> {
+    let cmd = qail_core::parse(query).map_err(|e| anyhow::anyhow!("Parse error: {}", e))?;
+    let result = cmd.to_sql_parameterized();
+
+    let plan = match resolve_db_url(url) {
+        Ok(url) => fetch_plan(&url, &result).await,
+        Err(e) => Err(e.to_string()),
+    };
+
+    println!("{}", "🔍 Query Explain".cyan().bold());
+    println!();
+    print!("{}", render_explain(query, &result, &plan));
+    Ok(())
+}
+
+/// Prepare the parameterized SQL and ask PostgreSQL for its plan.
+async fn fetch_plan(url: &str, result: &TranspileResult) -> Result<Vec<String>, String> {
+    let mut driver = PgDriver::connect_url(url)
+        .await
+        .map_err(|e| format!("Connection failed: {}", e))?;
+
+    let count = placeholder_count(&result.sql);
+    if count > result.params.len() {
+        driver
+            .execute_raw("SET plan_cache_mode = force_generic_plan")
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    driver
+        .execute_raw(&format!("PREPARE {} AS {}", STATEMENT, result.sql))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let explain = if count == 0 {
+        format!("EXPLAIN EXECUTE {}", STATEMENT)
+    } else {
+        let args: Vec<String> = (0..count)
+            .map(|i| result.params.get(i).map_or("NULL".to_string(), param_literal))
+            .collect();
+        format!("EXPLAIN EXECUTE {}({})", STATEMENT, args.join(", "))
+    };
+    let rows = driver.fetch_raw(&explain).await;
+    let _ = driver.execute_raw(&format!("DEALLOCATE {}", STATEMENT)).await;
+
+    rows.map(|rows| rows.iter().map(|row| row.text(0)).collect())
+        .map_err(|e| e.to_string())
+}
+
+/// Highest `$n` placeholder in `sql`, skipping string literals.
+fn placeholder_count(sql: &str) -> usize {
+    let bytes = sql.as_bytes();
+    let mut max = 0;
+    let mut in_string = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => in_string = !in_string,
+            b'$' if !in_string => {
+                let digits = bytes[i + 1..].iter().take_while(|b| b.is_ascii_digit()).count();
+                if let Ok(n) = sql[i + 1..i + 1 + digits].parse::<usize>() {
+                    max = max.max(n);
+                }
+                i += digits;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    max
+}
+
+/// SQL literal for a parameter value passed to `EXECUTE`.
+fn param_literal(value: &Value) -> String {
+    match value {
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(param_literal).collect();
+            format!("ARRAY[{}]", items.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Render the SQL, params and plan sections.
+///
+/// `plan` holds the `EXPLAIN` lines, or why there is no plan.
+pub fn render_explain(
+    query: &str,
+    result: &TranspileResult,
+    plan: &Result<Vec<String>, String>,
+) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("Query:\n  {}\n\n", query));
+    out.push_str(&format!("SQL:\n  {}\n\n", result.sql));
+
+    out.push_str("Params:\n");
+    let count = placeholder_count(&result.sql).max(result.params.len());
+    if count == 0 {
+        out.push_str("  (none)\n");
+    }
+    for i in 0..count {
+        let bound = match (result.params.get(i), result.named_params.get(i)) {
+            (Some(value), _) => value.to_string(),
+            (None, Some(name)) => format!(":{}", name),
+            (None, None) => "(unbound)".to_string(),
+        };
+        out.push_str(&format!("  ${} = {}\n", i + 1, bound));
+    }
+    out.push('\n');
+
+    match plan {
+        Ok(lines) => {
+            out.push_str("Plan:\n");
+            for line in lines {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+        Err(reason) => out.push_str(&format!("Plan: unavailable ({})\n", reason)),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_without_plan() {
+        let query = "get users fields id where active = true and name = :name and org = $2";
+        let result = qail_core::parse(query).unwrap().to_sql_parameterized();
+        let out = render_explain(query, &result, &Err("Connection failed: refused".to_string()));
+
+        assert_eq!(
+            out,
+            "Query:\n  get users fields id where active = true and name = :name and org = $2\n\n\
+             SQL:\n  SELECT id FROM users WHERE active = true AND name = $1 AND org = $2\n\n\
+             Params:\n  $1 = :name\n  $2 = (unbound)\n\n\
+             Plan: unavailable (Connection failed: refused)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_with_plan() {
+        let query = "get users";
+        let result = qail_core::parse(query).unwrap().to_sql_parameterized();
+        let plan = Ok(vec!["Seq Scan on users  (cost=0.00..22.70 rows=1270 width=36)".to_string()]);
+        let out = render_explain(query, &result, &plan);
+
+        assert!(out.contains("Params:\n  (none)\n"), "{}", out);
+        assert!(
+            out.ends_with("Plan:\n  Seq Scan on users  (cost=0.00..22.70 rows=1270 width=36)\n"),
+            "{}",
+            out
+        );
+        assert_eq!(
+            param_literal(&Value::Array(vec![Value::Int(1), Value::Int(2)])),
+            "ARRAY[1, 2]"
+        );
+        assert_eq!(placeholder_count("SELECT '$9' WHERE a = $1 AND b = $12"), 12);
+    }
+}
@@ -5,6 +5,7 @@ pub use qail_core::{ast, error, parser, transpiler};
 // CLI modules
 pub mod backup;
 pub mod exec;
+pub mod explain;
 pub mod introspection;
 pub mod lint;
 pub mod migrations;
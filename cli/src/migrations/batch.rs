@@ -0,0 +1,141 @@
+//! Apply a multi-statement migration file atomically
+//!
+//! Every statement plus the `_qail_migrations` record goes through
+//! `PgDriver::execute_batch`, so one failing statement rolls back the
+//! whole migration and nothing is recorded.
+
+use anyhow::{Context, Result};
+use colored::*;
+use qail_core::migrate::Migration;
+use qail_core::prelude::Qail;
+use qail_pg::driver::PgDriver;
+
+use crate::migrations::migration_table_ddl;
+use crate::sql_gen::cmd_to_sql;
+use std::path::Path;
+
+/// `qail migrate run`: apply one migration file, versioned by its file stem.
+pub async fn migrate_run(url: &str, file: &str) -> Result<()> {
+    let path = Path::new(file);
+    let content =
+        std::fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+    let version = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow::anyhow!("Invalid migration file name: {}", file))?;
+    let migration = Migration::new(version, content);
+
+    let mut driver = PgDriver::connect_url(url)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect: {}", e))?;
+
+    println!("{} Applying {}", "→".cyan(), migration.name().cyan());
+    let results = apply_migration(&mut driver, &migration).await?;
+    println!(
+        "{} Applied {} statements ({} rows affected), recorded version {}",
+        "✓".green(),
+        results.len(),
+        results.iter().sum::<u64>(),
+        migration.version.yellow()
+    );
+    Ok(())
+}
+
+/// Apply `migration` in one transaction and record its version.
+/// Returns the affected-row count of each migration statement.
+pub async fn apply_migration(driver: &mut PgDriver, migration: &Migration) -> Result<Vec<u64>> {
+    let mut cmds = migration.statements()?;
+    if cmds.is_empty() {
+        anyhow::bail!("Migration {} has no statements", migration.version);
+    }
+
+    // Ensure migration table exists (AST-native bootstrap)
+    driver
+        .execute_raw(&migration_table_ddl())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create migration table: {}", e))?;
+
+    let sql_up: String = cmds.iter().map(|cmd| format!("{};\n", cmd_to_sql(cmd))).collect();
+    let checksum = format!("{:x}", md5::compute(&sql_up));
+    let statement_count = cmds.len();
+
+    cmds.push(
+        Qail::add("_qail_migrations")
+            .columns(["version", "name", "checksum", "sql_up"])
+            .values([migration.version.clone(), migration.name(), checksum, sql_up]),
+    );
+
+    let mut results = driver.execute_batch(&cmds).await.map_err(|e| {
+        anyhow::anyhow!(
+            "Migration {} failed: {}\nTransaction rolled back - database unchanged.",
+            migration.version,
+            e
+        )
+    })?;
+    results.truncate(statement_count);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "Requires PostgreSQL server - run manually"]
+    async fn test_failed_statement_rolls_back_migration() -> Result<()> {
+        let mut driver =
+            PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+        driver.execute_raw("DROP TABLE IF EXISTS mig_batch_items").await?;
+
+        let migration = Migration::new(
+            "20260116000001",
+            "-- migration: batch_rollback\n\
+             make mig_batch_items id:int:pk;\n\
+             add mig_batch_missing fields id values 1;\n\
+             add mig_batch_items fields id values 1\n",
+        );
+        assert!(apply_migration(&mut driver, &migration).await.is_err());
+
+        let tables = driver
+            .fetch_raw("SELECT count(*) FROM pg_tables WHERE tablename = 'mig_batch_items'")
+            .await?;
+        assert_eq!(tables[0].get_string(0), Some("0".to_string()));
+
+        let recorded = driver
+            .fetch_raw("SELECT count(*) FROM _qail_migrations WHERE version = '20260116000001'")
+            .await?;
+        assert_eq!(recorded[0].get_string(0), Some("0".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires PostgreSQL server - run manually"]
+    async fn test_migration_applies_and_records_version() -> Result<()> {
+        let mut driver =
+            PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+        driver.execute_raw("DROP TABLE IF EXISTS mig_batch_ok").await?;
+        driver
+            .execute_raw("DELETE FROM _qail_migrations WHERE version = '20260116000002'")
+            .await
+            .ok();
+
+        let migration = Migration::new(
+            "20260116000002",
+            "make mig_batch_ok id:int:pk;\n\
+             add mig_batch_ok\n\
+                 fields id values 1;\n\
+             add mig_batch_ok fields id values 2\n",
+        );
+        let results = apply_migration(&mut driver, &migration).await?;
+        assert_eq!(results, vec![0, 1, 1]);
+
+        let recorded = driver
+            .fetch_raw("SELECT name FROM _qail_migrations WHERE version = '20260116000002'")
+            .await?;
+        assert_eq!(recorded[0].get_string(0), Some("20260116000002".to_string()));
+
+        driver.execute_raw("DROP TABLE mig_batch_ok").await?;
+        Ok(())
+    }
+}
@@ -11,9 +11,11 @@
 //! - `analyze`: Impact analysis on codebase
 //! - `watch`: Live schema monitoring
 //! - `create`: Create new migration files
+//! - `batch`: Apply a multi-statement migration atomically
 
 mod analyze;
 mod apply;
+mod batch;
 mod create;
 mod down;
 mod plan;
@@ -24,6 +26,7 @@ mod watch;
 
 pub use analyze::migrate_analyze;
 pub use apply::{migrate_apply, MigrateDirection};
+pub use batch::{apply_migration, migrate_run};
 pub use create::migrate_create;
 pub use down::migrate_down;
 pub use plan::migrate_plan;
@@ -1,12 +1,118 @@
 //! REPL mode for interactive QAIL queries
 
 use colored::*;
+use qail_core::migrate::parse_qail;
 use qail_core::transpiler::ToSql;
+use qail_core::validator::Validator;
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::{Context, Helper};
+
+/// Verbs whose next word names the table in v2 keyword syntax.
+const TABLE_VERBS: &[&str] = &["get", "set", "del", "add", "put", "cnt", "export"];
+
+/// Tab completion of column names from a loaded schema.
+struct ReplHelper {
+    validator: Option<Validator>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(match &self.validator {
+            Some(validator) => complete_columns(validator, &line[..pos]),
+            None => (pos, Vec::new()),
+        })
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl rustyline::validate::Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Load `schema.qail`-style tables into a validator for completion.
+fn load_schema(path: &str) -> Result<Validator, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let schema = parse_qail(&content)?;
+
+    let mut validator = Validator::new();
+    for table in schema.tables.values() {
+        let cols: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        validator.add_table(&table.name, &cols);
+    }
+    Ok(validator)
+}
+
+/// Complete the column name being typed at the end of `line`.
+///
+/// The table comes from a `table.` qualifier on the partial word, or else
+/// from the query head (`get users ...` or legacy `get::users:...`).
+/// Returns the byte offset where the partial word starts and the matching
+/// column names, sorted.
+pub fn complete_columns(validator: &Validator, line: &str) -> (usize, Vec<String>) {
+    let start = line
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .map_or(0, |i| i + line[i..].chars().next().map_or(1, char::len_utf8));
+    let word = &line[start..];
+
+    let (table, prefix, start) = match word.rsplit_once('.') {
+        Some((table, prefix)) => (Some(table.to_string()), prefix, line.len() - prefix.len()),
+        None => (query_table(&line[..start]), word, start),
+    };
+
+    let mut matches: Vec<String> = table
+        .and_then(|t| validator.column_names(&t))
+        .into_iter()
+        .flatten()
+        .filter(|col| col.starts_with(prefix))
+        .cloned()
+        .collect();
+    matches.sort();
+    (start, matches)
+}
+
+/// Table named at the head of a (possibly partial) query.
+fn query_table(head: &str) -> Option<String> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    // Legacy symbol syntax: get::users:'email
+    if let Some((verb, rest)) = head.trim_start().split_once("::")
+        && TABLE_VERBS.contains(&verb)
+    {
+        let table: String = rest.chars().take_while(|&c| is_ident(c)).collect();
+        return (!table.is_empty()).then_some(table);
+    }
+
+    let mut words = head.split_whitespace();
+    if !TABLE_VERBS.contains(&words.next()?) {
+        return None;
+    }
+    words
+        .find(|w| *w != "distinct")
+        .filter(|w| w.chars().all(is_ident))
+        .map(str::to_string)
+}
 
 /// Run the interactive REPL mode.
-pub fn run_repl() {
-    use rustyline::DefaultEditor;
+///
+/// Columns of tables in `schema_path` (if it exists) are offered on Tab.
+pub fn run_repl(schema_path: &str) {
+    use rustyline::Editor;
     use rustyline::error::ReadlineError;
+    use rustyline::history::DefaultHistory;
 
     println!("{}", "🪝 QAIL REPL — Interactive Mode".cyan().bold());
     println!(
@@ -16,15 +122,36 @@ pub fn run_repl() {
     println!("  {}  - Exit the REPL", ".exit".yellow());
     println!("  {} - Show symbol reference", ".help".yellow());
     println!("  {} - Clear screen", ".clear".yellow());
+
+    let validator = if std::path::Path::new(schema_path).exists() {
+        match load_schema(schema_path) {
+            Ok(validator) => {
+                println!(
+                    "{} {} {}",
+                    "Loaded".dimmed(),
+                    schema_path.yellow(),
+                    "(Tab completes column names)".dimmed()
+                );
+                Some(validator)
+            }
+            Err(e) => {
+                eprintln!("{} {}: {}", "Failed to load schema".red(), schema_path, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
     println!();
 
-    let mut rl = match DefaultEditor::new() {
+    let mut rl: Editor<ReplHelper, DefaultHistory> = match Editor::new() {
         Ok(editor) => editor,
         Err(e) => {
             eprintln!("{} {}", "Failed to initialize REPL:".red(), e);
             return;
         }
     };
+    rl.set_helper(Some(ReplHelper { validator }));
 
     // Load history if available
     let history_path = dirs::home_dir()
@@ -112,3 +239,32 @@ pub fn show_repl_help() {
     println!("  del users where id = $1");
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users() -> Validator {
+        let mut validator = Validator::new();
+        validator.add_table("users", &["id", "email", "name", "created_at"]);
+        validator
+    }
+
+    #[test]
+    fn test_complete_column_prefix() {
+        let v = users();
+        assert_eq!(complete_columns(&v, "get users fields em"), (17, vec!["email".to_string()]));
+        assert_eq!(complete_columns(&v, "get::users:'em"), (12, vec!["email".to_string()]));
+        assert_eq!(
+            complete_columns(&v, "get users fields id where users.c"),
+            (32, vec!["created_at".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_complete_unknown_table_or_no_verb() {
+        let v = users();
+        assert!(complete_columns(&v, "get orders fields em").1.is_empty());
+        assert!(complete_columns(&v, "em").1.is_empty());
+    }
+}
@@ -39,7 +39,7 @@ pub async fn vector_create(
     let port = parsed.port().unwrap_or(6334);
 
     let mut driver = QdrantDriver::connect(host, port).await?;
-    driver.create_collection(collection, size, dist, false).await?;
+    driver.create_collection(collection, qail_qdrant::VectorParams::new(size, dist)).await?;
 
     println!("{} Collection '{}' created successfully!", "✓".green(), collection);
     Ok(())
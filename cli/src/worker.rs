@@ -475,6 +475,7 @@ async fn process_item(
                         id: qail_qdrant::PointId::Num(item.ref_id.parse().unwrap_or(0)),
                         vector,
                         payload: std::collections::HashMap::new(),
+                        named_vectors: std::collections::HashMap::new(),
                     };
                     
                     qdrant.upsert(&rule.target_collection, &[point], true).await?;
@@ -0,0 +1,34 @@
+//! Benchmark: parse vs parse_cached on a repeated query
+//!
+//! Run with: cargo run --release -p qail-core --example bench_parse_cache
+
+use qail_core::parser::cache::{clear_parse_cache, parse_cache_stats};
+use qail_core::{parse, parse_cached};
+use std::time::Instant;
+
+const ITERATIONS: usize = 100_000;
+
+fn main() {
+    let query = "get users fields id, email, name where active = true and role = 'admin' order by created_at desc limit 50";
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let cmd = parse(query).unwrap();
+        std::hint::black_box(cmd);
+    }
+    let uncached = start.elapsed();
+
+    clear_parse_cache();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let cmd = parse_cached(query).unwrap();
+        std::hint::black_box(cmd);
+    }
+    let cached = start.elapsed();
+
+    let stats = parse_cache_stats();
+    println!("parse:        {:?} ({:.0} ns/op)", uncached, uncached.as_nanos() as f64 / ITERATIONS as f64);
+    println!("parse_cached: {:?} ({:.0} ns/op)", cached, cached.as_nanos() as f64 / ITERATIONS as f64);
+    println!("cache hits: {}, misses: {}", stats.hits, stats.misses);
+    println!("speedup: {:.1}x", uncached.as_secs_f64() / cached.as_secs_f64());
+}
@@ -15,24 +15,31 @@ fn main() {
     // COUNT(DISTINCT phone_number) AS total_contacts
     stats_query.columns.push(Expr::Aggregate {
         col: "phone_number".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: true,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: Some("total_contacts".to_string()),
     });
 
     // COUNT(*) AS total_messages
     stats_query.columns.push(Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: Some("total_messages".to_string()),
     });
 
     // COUNT(*) FILTER (WHERE direction = 'outbound' AND created_at > NOW() - INTERVAL '24 hours') AS messages_sent_24h
     stats_query.columns.push(Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(vec![
@@ -49,12 +56,15 @@ fn main() {
                 is_array_unnest: false,
             },
         ]),
+        delimiter: None,
+        order_by: None,
         alias: Some("messages_sent_24h".to_string()),
     });
 
     // COUNT(*) FILTER (WHERE direction = 'inbound' AND created_at > NOW() - INTERVAL '24 hours') AS messages_received_24h
     stats_query.columns.push(Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(vec![
@@ -71,12 +81,15 @@ fn main() {
                 is_array_unnest: false,
             },
         ]),
+        delimiter: None,
+        order_by: None,
         alias: Some("messages_received_24h".to_string()),
     });
 
     // COUNT(*) FILTER (WHERE direction = 'inbound' AND status = 'received') AS unread_messages
     stats_query.columns.push(Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(vec![
@@ -93,12 +106,15 @@ fn main() {
                 is_array_unnest: false,
             },
         ]),
+        delimiter: None,
+        order_by: None,
         alias: Some("unread_messages".to_string()),
     });
 
     // COUNT(*) FILTER (WHERE direction = 'outbound' AND created_at > NOW() - INTERVAL '24 hours' AND status IN ('delivered', 'read')) AS successful_deliveries_24h
     stats_query.columns.push(Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(vec![
@@ -124,6 +140,8 @@ fn main() {
                 is_array_unnest: false,
             },
         ]),
+        delimiter: None,
+        order_by: None,
         alias: Some("successful_deliveries_24h".to_string()),
     });
 
@@ -0,0 +1,11 @@
+//! `parse` must return `Ok` or `Err` for any UTF-8 input, never panic.
+//!
+//! Run with `cargo +nightly fuzz run parse` from `core/`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = qail_core::parser::parse(input);
+});
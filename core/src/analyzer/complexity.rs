@@ -0,0 +1,123 @@
+//! Structural complexity of a query AST.
+//!
+//! Used by servers that accept ASTs from untrusted clients (e.g. the gateway)
+//! to reject deeply nested or oversized queries before they reach the database.
+
+use crate::ast::visit::{walk_cmd, walk_condition, walk_expr, walk_value};
+use crate::ast::{Condition, Expr, Qail, Value, Visitor};
+
+/// Size and nesting of a query AST, see [`Qail::complexity`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Complexity {
+    /// Deepest nesting of subqueries and CTEs (a flat query is 0)
+    pub depth: usize,
+    /// Joins across the query and all of its subqueries
+    pub joins: usize,
+    /// Total AST nodes: commands, expressions, conditions and values
+    pub nodes: usize,
+    /// Raw SQL fragments (`Expr::Raw`), whose contents cannot be analyzed
+    pub raw: usize,
+}
+
+impl Qail {
+    /// Measure subquery/CTE depth, join count and node count, and count
+    /// raw SQL fragments, which are opaque to every analysis.
+    ///
+    /// ```ignore
+    /// let c = parse("get users where id = any (get orders fields user_id)")?.complexity();
+    /// assert_eq!(c.depth, 1);
+    /// ```
+    pub fn complexity(&self) -> Complexity {
+        let mut measure = Measure::default();
+        self.walk(&mut measure);
+        measure.complexity
+    }
+}
+
+/// Accumulates a [`Complexity`] while tracking the current nesting depth.
+#[derive(Default)]
+struct Measure {
+    complexity: Complexity,
+    depth: usize,
+}
+
+impl Visitor for Measure {
+    fn visit_cmd(&mut self, cmd: &Qail) {
+        self.complexity.nodes += 1;
+        self.complexity.depth = self.complexity.depth.max(self.depth);
+        self.complexity.joins += cmd.joins.len();
+        walk_cmd(self, cmd);
+    }
+
+    fn visit_subquery(&mut self, cmd: &Qail) {
+        self.depth += 1;
+        self.visit_cmd(cmd);
+        self.depth -= 1;
+    }
+
+    fn visit_condition(&mut self, cond: &Condition) {
+        self.complexity.nodes += 1;
+        walk_condition(self, cond);
+    }
+
+    fn visit_value(&mut self, value: &Value) {
+        self.complexity.nodes += 1;
+        walk_value(self, value);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        self.complexity.nodes += 1;
+        if let Expr::Raw(_) = expr {
+            self.complexity.raw += 1;
+        }
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+
+    #[test]
+    fn test_flat_query() {
+        let c = parse("get users fields id, email where active = true").unwrap().complexity();
+        assert_eq!(c.depth, 0);
+        assert_eq!(c.joins, 0);
+        // command + 2 columns + condition (left expr + value)
+        assert_eq!(c.nodes, 6);
+    }
+
+    #[test]
+    fn test_nested_subquery_depth() {
+        let cmd = parse(
+            "get users where id = any (get orders fields user_id \
+             where product_id = any (get products fields id \
+             where vendor_id = any (get vendors fields id where active = true)))",
+        )
+        .unwrap();
+        assert_eq!(cmd.complexity().depth, 3);
+
+        let cmd = parse(
+            "get users join orders on orders.user_id = users.id \
+             fields id, (get orders fields count(*) where user_id = users.id) as n",
+        )
+        .unwrap();
+        let c = cmd.complexity();
+        assert_eq!(c.depth, 1);
+        assert_eq!(c.joins, 1);
+    }
+
+    #[test]
+    fn test_raw_fragments_are_counted() {
+        let cmd = parse("get users fields raw!(vendor_fn(id)) where id = any (get t fields raw!(x))")
+            .unwrap();
+        assert_eq!(cmd.complexity().raw, 2);
+        assert_eq!(parse("get users fields id").unwrap().complexity().raw, 0);
+    }
+
+    #[test]
+    fn test_cte_counts_as_nesting() {
+        let cmd = parse("with big as (get orders fields id) get big").unwrap();
+        assert_eq!(cmd.complexity().depth, 1);
+    }
+}
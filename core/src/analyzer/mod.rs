@@ -6,11 +6,16 @@
 //! Supports tiered analysis:
 //! - Rust files: Full AST parsing with `syn` (100% accurate)
 //! - Other files: Regex-based scanning (90% accurate)
+//!
+//! Also measures query AST complexity ([`Complexity`]) for servers that
+//! execute client-supplied queries.
 
+mod complexity;
 mod impact;
 pub mod rust_ast;  // Public for LSP access to query_extractor
 mod scanner;
 
+pub use complexity::Complexity;
 pub use impact::{BreakingChange, MigrationImpact};
 pub use rust_ast::{detect_raw_sql, detect_raw_sql_in_file, RawSqlMatch, RustAnalyzer};
 pub use rust_ast::{detect_query_calls, QueryCall};
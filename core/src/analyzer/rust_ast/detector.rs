@@ -248,7 +248,8 @@ impl RustAnalyzer {
 // =============================================================================
 
 /// A raw SQL statement detected in Rust source code
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RawSqlMatch {
     /// Line number (1-indexed)
     pub line: usize,
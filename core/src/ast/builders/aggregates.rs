@@ -1,14 +1,17 @@
 //! Aggregate function builders (COUNT, SUM, AVG, etc.)
 
-use crate::ast::{AggregateFunc, Condition, Expr};
+use crate::ast::{AggregateFunc, Condition, Expr, SortOrder};
 
 /// COUNT(*) aggregate
 pub fn count() -> AggregateBuilder {
     AggregateBuilder {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -17,9 +20,12 @@ pub fn count() -> AggregateBuilder {
 pub fn count_distinct(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: true,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -28,9 +34,12 @@ pub fn count_distinct(column: &str) -> AggregateBuilder {
 pub fn count_filter(conditions: Vec<Condition>) -> AggregateBuilder {
     AggregateBuilder {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(conditions),
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -39,9 +48,12 @@ pub fn count_filter(conditions: Vec<Condition>) -> AggregateBuilder {
 pub fn sum(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::Sum,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -50,9 +62,12 @@ pub fn sum(column: &str) -> AggregateBuilder {
 pub fn avg(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::Avg,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -61,9 +76,12 @@ pub fn avg(column: &str) -> AggregateBuilder {
 pub fn min(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::Min,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -72,9 +90,12 @@ pub fn min(column: &str) -> AggregateBuilder {
 pub fn max(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::Max,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -83,9 +104,34 @@ pub fn max(column: &str) -> AggregateBuilder {
 pub fn array_agg(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::ArrayAgg,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
+        alias: None,
+    }
+}
+
+/// STRING_AGG(column, delimiter) as an aggregate, so it can take `order_by`.
+/// # Example
+/// ```ignore
+/// aggregates::string_agg("name", ", ").order_by("name", SortOrder::Asc)  // STRING_AGG(name, ', ' ORDER BY name ASC)
+/// ```
+pub fn string_agg(column: impl Into<Expr>, delimiter: &str) -> AggregateBuilder {
+    let (col, arg) = match column.into() {
+        Expr::Named(name) => (name, None),
+        expr => (expr.to_string(), Some(Box::new(expr))),
+    };
+    AggregateBuilder {
+        col,
+        arg,
+        func: AggregateFunc::StringAgg,
+        distinct: false,
+        filter: None,
+        delimiter: Some(delimiter.to_string()),
+        order_by: None,
         alias: None,
     }
 }
@@ -94,9 +140,12 @@ pub fn array_agg(column: &str) -> AggregateBuilder {
 pub fn json_agg(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::JsonAgg,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -105,9 +154,12 @@ pub fn json_agg(column: &str) -> AggregateBuilder {
 pub fn jsonb_agg(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::JsonbAgg,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -116,9 +168,12 @@ pub fn jsonb_agg(column: &str) -> AggregateBuilder {
 pub fn bool_and(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::BoolAnd,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -127,9 +182,12 @@ pub fn bool_and(column: &str) -> AggregateBuilder {
 pub fn bool_or(column: &str) -> AggregateBuilder {
     AggregateBuilder {
         col: column.to_string(),
+        arg: None,
         func: AggregateFunc::BoolOr,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     }
 }
@@ -138,9 +196,12 @@ pub fn bool_or(column: &str) -> AggregateBuilder {
 #[derive(Debug, Clone)]
 pub struct AggregateBuilder {
     pub(crate) col: String,
+    pub(crate) arg: Option<Box<Expr>>,
     pub(crate) func: AggregateFunc,
     pub(crate) distinct: bool,
     pub(crate) filter: Option<Vec<Condition>>,
+    pub(crate) delimiter: Option<String>,
+    pub(crate) order_by: Option<Vec<(String, SortOrder)>>,
     pub(crate) alias: Option<String>,
 }
 
@@ -157,6 +218,15 @@ impl AggregateBuilder {
         self
     }
 
+    /// Order the aggregated values (ORDER BY inside the call).
+    /// Call repeatedly for several sort keys.
+    pub fn order_by(mut self, column: &str, order: SortOrder) -> Self {
+        self.order_by
+            .get_or_insert_with(Vec::new)
+            .push((column.to_string(), order));
+        self
+    }
+
     /// Add alias (AS name)
     pub fn alias(mut self, name: &str) -> Expr {
         self.alias = Some(name.to_string());
@@ -167,9 +237,12 @@ impl AggregateBuilder {
     pub fn build(self) -> Expr {
         Expr::Aggregate {
             col: self.col,
+            arg: self.arg,
             func: self.func,
             distinct: self.distinct,
             filter: self.filter,
+            delimiter: self.delimiter,
+            order_by: self.order_by,
             alias: self.alias,
         }
     }
@@ -45,6 +45,12 @@ pub fn not_in<V: Into<Value>>(column: &str, values: impl IntoIterator<Item = V>)
     make_condition(column, Operator::NotIn, Value::Array(vals))
 }
 
+/// Match any element of one array parameter (column = ANY($1))
+pub fn eq_any<V: Into<Value>>(column: &str, values: impl IntoIterator<Item = V>) -> Condition {
+    let vals: Vec<Value> = values.into_iter().map(|v| v.into()).collect();
+    make_condition(column, Operator::EqAny, Value::Array(vals))
+}
+
 pub fn is_null(column: &str) -> Condition {
     make_condition(column, Operator::IsNull, Value::Null)
 }
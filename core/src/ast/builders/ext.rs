@@ -66,15 +66,21 @@ impl ExprExt for Expr {
             },
             Expr::Aggregate {
                 col,
+                arg,
                 func,
                 distinct,
                 filter,
+                delimiter,
+                order_by,
                 ..
             } => Expr::Aggregate {
                 col,
+                arg,
                 func,
                 distinct,
                 filter,
+                delimiter,
+                order_by,
                 alias: Some(alias.to_string()),
             },
             Expr::Cast {
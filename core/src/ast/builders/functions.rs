@@ -1,5 +1,6 @@
 //! Function call builders (COALESCE, REPLACE, SUBSTRING, etc.)
 
+use super::aggregates::AggregateBuilder;
 use super::literals::int;
 use crate::ast::{BinaryOp, Expr};
 
@@ -35,16 +36,14 @@ pub fn replace(
     func("REPLACE", vec![source.into(), from.into(), to.into()])
 }
 
-/// STRING_AGG(column, delimiter) - concatenate all values with delimiter
+/// STRING_AGG(column, delimiter) - concatenate all values with delimiter.
+/// Same as `aggregates::string_agg`.
 /// # Example
 /// ```ignore
 /// string_agg("name", ", ")  // STRING_AGG(name, ', ')
 /// ```
-pub fn string_agg(column: impl Into<Expr>, delimiter: &str) -> FunctionBuilder {
-    func(
-        "STRING_AGG",
-        vec![column.into(), Expr::Literal(crate::ast::Value::String(delimiter.to_string()))],
-    )
+pub fn string_agg(column: impl Into<Expr>, delimiter: &str) -> AggregateBuilder {
+    super::aggregates::string_agg(column, delimiter)
 }
 
 /// Builder for function call expressions
@@ -68,7 +68,7 @@ pub use literals::{bind, boolean, float, int, null, text};
 
 // Conditions
 pub use conditions::{
-    between, cond, contains, eq, gt, gte, ilike, is_in, is_not_null, is_null, key_exists, like, lt,
+    between, cond, contains, eq, eq_any, gt, gte, ilike, is_in, is_not_null, is_null, key_exists, like, lt,
     lte, ne, not_between, not_in, not_like, overlaps, regex, regex_i, similar_to,
 };
 
@@ -1,8 +1,10 @@
 use crate::ast::{Condition, LogicalOp, SortOrder};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A cage (constraint block) in the query.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cage {
     pub kind: CageKind,
     pub conditions: Vec<Condition>,
@@ -10,12 +12,15 @@ pub struct Cage {
 }
 
 /// The type of cage.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CageKind {
     Filter,
     Payload,
     Sort(SortOrder),
     Limit(usize),
+    /// `LIMIT ALL`: no row limit, overriding an earlier `Limit`
+    LimitAll,
     Offset(usize),
     Sample(usize),
     Qualify,
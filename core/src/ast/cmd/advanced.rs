@@ -92,6 +92,39 @@ impl Qail {
         self
     }
 
+    pub fn from(mut self, table: impl Into<String>) -> Self {
+        self.from_tables.push(table.into());
+        self
+    }
+
+    pub fn using(mut self, table: impl Into<String>) -> Self {
+        self.using_tables.push(table.into());
+        self
+    }
+
+    pub fn from_select(mut self, query: Qail) -> Self {
+        self.source_query = Some(Box::new(query));
+        self
+    }
+
+    /// Qualify the main, joined and auxiliary tables with `schema`
+    /// (`tenant_42.users`). See [`schema_for`](Self::schema_for).
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Schema to qualify `table` with, if any.
+    /// Already-qualified names and CTE references are left alone.
+    pub fn schema_for(&self, table: &str) -> Option<&str> {
+        let schema = self.schema.as_deref()?;
+        let bare = table.split_whitespace().next().unwrap_or(table);
+        if bare.contains('.') || self.ctes.iter().any(|cte| cte.name == bare) {
+            return None;
+        }
+        Some(schema)
+    }
+
     pub fn for_update(mut self) -> Self {
         self.lock_mode = Some(LockMode::Update);
         self
@@ -159,6 +192,42 @@ impl Qail {
         self
     }
 
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// TRUNCATE: reset sequences owned by the table's columns.
+    pub fn restart_identity(mut self) -> Self {
+        self.restart_identity = true;
+        self
+    }
+
+    /// TRUNCATE: also truncate tables that reference this one by foreign key.
+    pub fn cascade(mut self) -> Self {
+        self.cascade = true;
+        self
+    }
+
+    /// Label the query for attribution in `pg_stat_statements` and server logs.
+    /// Tags are emitted space-separated as a leading comment:
+    /// `.tag("app:checkout").tag("route:/pay")` → `/* app:checkout route:/pay */ SELECT ...`
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// The leading comment for this command's tags, if any.
+    /// Comment delimiters inside a tag are broken up so a tag cannot end
+    /// the comment (or nest another one) early.
+    pub fn tag_comment(&self) -> Option<String> {
+        if self.tags.is_empty() {
+            return None;
+        }
+        let text = self.tags.join(" ").replace("/*", "/ *").replace("*/", "* /");
+        Some(format!("/* {} */", text))
+    }
+
     pub fn left_join_as(
         mut self,
         table: impl AsRef<str>,
@@ -176,6 +245,7 @@ impl Qail {
                 is_array_unnest: false,
             }]),
             on_true: false,
+            subquery: None,
         });
         self
     }
@@ -197,6 +267,39 @@ impl Qail {
                 is_array_unnest: false,
             }]),
             on_true: false,
+            subquery: None,
+        });
+        self
+    }
+
+    /// `LEFT JOIN LATERAL (query) AS alias ON TRUE`: the subquery can refer
+    /// to columns of the tables before it, e.g. for top-N-per-group.
+    ///
+    /// ```
+    /// use qail_core::prelude::*;
+    ///
+    /// let recent = Qail::get("orders")
+    ///     .columns(["id", "total"])
+    ///     .filter("orders.user_id", Operator::Eq, Value::Column("users.id".into()))
+    ///     .order_desc("created_at")
+    ///     .limit(3);
+    /// let cmd = Qail::get("users")
+    ///     .columns(["users.name", "recent.total"])
+    ///     .left_join_lateral(recent, "recent");
+    /// assert_eq!(
+    ///     cmd.to_sql(),
+    ///     "SELECT users.name, recent.total FROM users LEFT JOIN LATERAL \
+    ///      (SELECT id, total FROM orders WHERE orders.user_id = users.id \
+    ///      ORDER BY created_at DESC LIMIT 3) AS recent ON TRUE"
+    /// );
+    /// ```
+    pub fn left_join_lateral(mut self, query: Qail, alias: impl Into<String>) -> Self {
+        self.joins.push(Join {
+            kind: JoinKind::Lateral,
+            table: alias.into(),
+            on: None,
+            on_true: true,
+            subquery: Some(Box::new(query)),
         });
         self
     }
@@ -0,0 +1,258 @@
+//! Structural cache keys.
+//!
+//! Two commands that differ only in the literal values of their `WHERE`
+//! filter and `INSERT`/`UPDATE` payload get the same key. Those values are
+//! sent as bind parameters, so both commands prepare the same statement.
+//! Everything else (tables, columns, operators, placeholders, value kinds,
+//! list lengths) is part of the key.
+
+use crate::ast::values::float_bits;
+use crate::ast::{Cage, CageKind, Condition, Qail, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+impl Qail {
+    /// Hash of the command's shape, for prepared-statement caching.
+    ///
+    /// Literal values in filter and payload cages are ignored; `$n` and
+    /// `:name` placeholders are kept.
+    ///
+    /// ```
+    /// use qail_core::ast::{Operator, Qail};
+    ///
+    /// let a = Qail::get("users").columns(["id"]).filter("name", Operator::Eq, "alice");
+    /// let b = Qail::get("users").columns(["id"]).filter("name", Operator::Eq, "bob");
+    /// assert_eq!(a.cache_key(), b.cache_key());
+    /// ```
+    pub fn cache_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash_fields(&mut hasher, true);
+        hasher.finish()
+    }
+
+    /// Feed every field to `state`. With `mask_literals`, values in this
+    /// command's filter and payload cages only contribute their kind.
+    pub(crate) fn hash_fields<H: Hasher>(&self, state: &mut H, mask_literals: bool) {
+        // Destructured so a new field can't be left out of the key
+        let Qail {
+            action,
+            table,
+            schema,
+            columns,
+            joins,
+            cages,
+            distinct,
+            index_def,
+            table_constraints,
+            set_ops,
+            having,
+            group_by_mode,
+            ctes,
+            distinct_on,
+            returning,
+            on_conflict,
+            source_query,
+            channel,
+            payload,
+            savepoint_name,
+            from_tables,
+            using_tables,
+            lock_mode,
+            fetch,
+            default_values,
+            overriding,
+            sample,
+            only_table,
+            if_not_exists,
+            restart_identity,
+            cascade,
+            tags,
+            vector,
+            score_threshold,
+            vector_name,
+            with_vector,
+            vector_size,
+            distance,
+            on_disk,
+            function_def,
+            trigger_def,
+            raw_value,
+            redis_ttl,
+            redis_set_condition,
+        } = self;
+
+        action.hash(state);
+        table.hash(state);
+        schema.hash(state);
+        columns.hash(state);
+        joins.hash(state);
+        cages.len().hash(state);
+        for cage in cages {
+            if mask_literals && matches!(cage.kind, CageKind::Filter | CageKind::Payload) {
+                hash_cage_shape(cage, state);
+            } else {
+                cage.hash(state);
+            }
+        }
+        distinct.hash(state);
+        index_def.hash(state);
+        table_constraints.hash(state);
+        set_ops.hash(state);
+        having.hash(state);
+        group_by_mode.hash(state);
+        ctes.hash(state);
+        distinct_on.hash(state);
+        returning.hash(state);
+        on_conflict.hash(state);
+        source_query.hash(state);
+        channel.hash(state);
+        payload.hash(state);
+        savepoint_name.hash(state);
+        from_tables.hash(state);
+        using_tables.hash(state);
+        lock_mode.hash(state);
+        fetch.hash(state);
+        default_values.hash(state);
+        overriding.hash(state);
+        sample.map(|(method, pct, seed)| (method, float_bits(pct), seed)).hash(state);
+        only_table.hash(state);
+        if_not_exists.hash(state);
+        restart_identity.hash(state);
+        cascade.hash(state);
+        tags.hash(state);
+        vector
+            .as_ref()
+            .map(|v| v.iter().map(|x| float_bits(f64::from(*x))).collect::<Vec<_>>())
+            .hash(state);
+        score_threshold.map(|t| float_bits(f64::from(t))).hash(state);
+        vector_name.hash(state);
+        with_vector.hash(state);
+        vector_size.hash(state);
+        distance.hash(state);
+        on_disk.hash(state);
+        function_def.hash(state);
+        trigger_def.hash(state);
+        raw_value.hash(state);
+        redis_ttl.hash(state);
+        redis_set_condition.hash(state);
+    }
+}
+
+fn hash_cage_shape<H: Hasher>(cage: &Cage, state: &mut H) {
+    let Cage {
+        kind,
+        conditions,
+        logical_op,
+    } = cage;
+    kind.hash(state);
+    logical_op.hash(state);
+    conditions.len().hash(state);
+    for Condition {
+        left,
+        op,
+        value,
+        is_array_unnest,
+    } in conditions
+    {
+        left.hash(state);
+        op.hash(state);
+        hash_value_shape(value, state);
+        is_array_unnest.hash(state);
+    }
+}
+
+/// Hash a bound literal by kind only; placeholders, columns, subqueries and
+/// expressions are structure and hash in full.
+fn hash_value_shape<H: Hasher>(value: &Value, state: &mut H) {
+    match value {
+        Value::Bool(_)
+        | Value::Int(_)
+        | Value::Float(_)
+        | Value::Decimal(_)
+        | Value::String(_)
+        | Value::Timestamp(_)
+        | Value::Json(_)
+        | Value::Uuid(_)
+        | Value::Bytes(_)
+        | Value::Vector(_) => std::mem::discriminant(value).hash(state),
+        Value::Interval { unit, .. } => {
+            std::mem::discriminant(value).hash(state);
+            unit.hash(state);
+        }
+        // Lists may expand to one placeholder per element: keep the length
+        Value::Array(items) => {
+            std::mem::discriminant(value).hash(state);
+            items.len().hash(state);
+            for item in items {
+                hash_value_shape(item, state);
+            }
+        }
+        _ => value.hash(state),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{Operator, Qail, Value};
+
+    #[test]
+    fn test_cache_key_ignores_literal_values() {
+        let a = Qail::get("users")
+            .columns(["id", "name"])
+            .filter("age", Operator::Gt, 18)
+            .filter("id", Operator::In, Value::Array(vec![Value::Int(1), Value::Int(2)]));
+        let b = Qail::get("users")
+            .columns(["id", "name"])
+            .filter("age", Operator::Gt, 65)
+            .filter("id", Operator::In, Value::Array(vec![Value::Int(7), Value::Int(9)]));
+        assert_eq!(a.cache_key(), b.cache_key());
+
+        let insert_a = Qail::add("users").set_value("name", "alice").set_value("age", 30);
+        let insert_b = Qail::add("users").set_value("name", "bob").set_value("age", 41);
+        assert_eq!(insert_a.cache_key(), insert_b.cache_key());
+    }
+
+    #[test]
+    fn test_cache_key_keeps_structure() {
+        let base = Qail::get("users").columns(["id"]).filter("age", Operator::Gt, 18);
+
+        let more_columns = Qail::get("users").columns(["id", "name"]).filter("age", Operator::Gt, 18);
+        assert_ne!(base.cache_key(), more_columns.cache_key());
+
+        let other_op = Qail::get("users").columns(["id"]).filter("age", Operator::Lt, 18);
+        assert_ne!(base.cache_key(), other_op.cache_key());
+
+        // `IN (...)` gets one placeholder per element
+        let two = Qail::get("users").filter("id", Operator::In, Value::Array(vec![Value::Int(1), Value::Int(2)]));
+        let three = Qail::get("users").filter("id", Operator::In, Value::Array(vec![Value::Int(1); 3]));
+        assert_ne!(two.cache_key(), three.cache_key());
+
+        // Placeholders are structure
+        let param = Qail::get("users").filter("age", Operator::Gt, Value::Param(1));
+        let named = Qail::get("users").filter("age", Operator::Gt, Value::NamedParam("age".into()));
+        assert_ne!(param.cache_key(), named.cache_key());
+        assert_ne!(param.cache_key(), Qail::get("users").filter("age", Operator::Gt, 18).cache_key());
+
+        // LIMIT is inlined into the SQL
+        assert_ne!(base.clone().limit(10).cache_key(), base.clone().limit(20).cache_key());
+
+        // The value's kind picks the parameter type
+        let text = Qail::get("users").columns(["id"]).filter("age", Operator::Gt, "18");
+        assert_ne!(base.cache_key(), text.cache_key());
+    }
+
+    #[test]
+    fn test_hash_matches_eq_for_floats() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |v: &Value| {
+            let mut h = DefaultHasher::new();
+            v.hash(&mut h);
+            h.finish()
+        };
+        assert_eq!(Value::Float(0.0), Value::Float(-0.0));
+        assert_eq!(hash(&Value::Float(0.0)), hash(&Value::Float(-0.0)));
+        assert_ne!(hash(&Value::Float(1.5)), hash(&Value::Float(2.5)));
+    }
+}
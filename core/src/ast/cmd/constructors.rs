@@ -2,7 +2,7 @@
 //!
 //! Methods like get(), set(), add(), del(), make(), etc.
 
-use crate::ast::{Action, Qail};
+use crate::ast::{Action, Expr, Qail};
 
 impl Qail {
     pub fn get(table: impl Into<String>) -> Self {
@@ -21,6 +21,17 @@ impl Qail {
         }
     }
 
+    /// Raw SQL fragment for a column or a condition's right-hand side.
+    ///
+    /// Emitted verbatim: it is not escaped, quoted or parameterized, so it
+    /// must never contain user input. Analyzers cannot see inside it.
+    /// ```ignore
+    /// Qail::get("users").column_expr(Qail::raw_fragment("vendor_hash(email)"))
+    /// ```
+    pub fn raw_fragment(sql: impl Into<String>) -> Expr {
+        Expr::Raw(sql.into())
+    }
+
     pub fn set(table: impl Into<String>) -> Self {
         Self {
             action: Action::Set,
@@ -101,6 +112,28 @@ impl Qail {
         }
     }
 
+    /// SELECT from a derived table: `SELECT ... FROM (query) AS alias`.
+    /// Columns and filters added afterwards apply to the outer query.
+    ///
+    /// ```
+    /// use qail_core::prelude::*;
+    ///
+    /// let paid = Qail::get("orders").filter("status", Operator::Eq, "paid");
+    /// let cmd = Qail::get_from(paid, "p").filter("total", Operator::Gt, 100);
+    /// assert_eq!(
+    ///     cmd.to_sql(),
+    ///     "SELECT * FROM (SELECT * FROM orders WHERE status = 'paid') AS p WHERE total > 100"
+    /// );
+    /// ```
+    pub fn get_from(query: Qail, alias: impl Into<String>) -> Self {
+        Self {
+            action: Action::Get,
+            table: alias.into(),
+            source_query: Some(Box::new(query)),
+            ..Default::default()
+        }
+    }
+
     pub fn create_materialized_view(name: impl Into<String>, query: Qail) -> Self {
         Self {
             action: Action::CreateMaterializedView,
@@ -22,6 +22,7 @@ impl Qail {
             base_query: Box::new(self),
             recursive_query: None,
             source_table: None,
+            materialized: None,
         }
     }
 
@@ -48,6 +49,7 @@ impl Qail {
         Self {
             action: Action::With,
             table: cte_name.clone(),
+            schema: None,
             columns: vec![],
             joins: vec![],
             cages: vec![],
@@ -72,6 +74,10 @@ impl Qail {
             overriding: None,
             sample: None,
             only_table: false,
+            if_not_exists: false,
+            restart_identity: false,
+            cascade: false,
+            tags: vec![],
             vector: None,
             score_threshold: None,
             vector_name: None,
@@ -91,6 +97,7 @@ impl Qail {
                 base_query: Box::new(self),
                 recursive_query: None,
                 source_table: None,
+                materialized: None,
             }],
         }
     }
@@ -103,6 +110,14 @@ impl Qail {
         self
     }
 
+    /// Set the `MATERIALIZED` (`true`) or `NOT MATERIALIZED` (`false`) hint on the last CTE.
+    pub fn materialized(mut self, materialized: bool) -> Self {
+        if let Some(cte) = self.ctes.last_mut() {
+            cte.materialized = Some(materialized);
+        }
+        self
+    }
+
     pub fn from_cte(mut self, cte_name: impl Into<String>) -> Self {
         if let Some(cte) = self.ctes.last_mut() {
             cte.source_table = Some(cte_name.into());
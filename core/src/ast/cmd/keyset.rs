@@ -0,0 +1,92 @@
+//! Keyset (seek) pagination.
+//!
+//! Instead of `OFFSET n`, which still reads the skipped rows, each page
+//! starts strictly after the sort key of the previous page's last row:
+//! `WHERE (created, id) > ($1, $2) ORDER BY created, id LIMIT 20`.
+//! The sort key must be unique (end it with the primary key) for pages
+//! to be stable.
+
+use crate::ast::{Cage, CageKind, Condition, Expr, LogicalOp, Operator, Qail, SortOrder, Value};
+use crate::error::QailError;
+
+/// A tuple of sort-key columns or of their values, e.g. `("created", "id")`.
+pub trait KeysetTuple<T> {
+    fn into_keyset(self) -> Vec<T>;
+}
+
+macro_rules! impl_keyset_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: AsRef<str>),+> KeysetTuple<String> for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_keyset(self) -> Vec<String> {
+                let ($($name,)+) = self;
+                vec![$($name.as_ref().to_string()),+]
+            }
+        }
+
+        impl<$($name: Into<Value>),+> KeysetTuple<Value> for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_keyset(self) -> Vec<Value> {
+                let ($($name,)+) = self;
+                vec![$($name.into()),+]
+            }
+        }
+    };
+}
+
+impl_keyset_tuple!(A);
+impl_keyset_tuple!(A, B);
+impl_keyset_tuple!(A, B, C);
+impl_keyset_tuple!(A, B, C, D);
+
+impl Qail {
+    /// Continue after the row whose sort key is `values`: adds
+    /// `(col1, col2) > (v1, v2)` and orders by the same columns ascending.
+    ///
+    /// ```ignore
+    /// Qail::get("posts")
+    ///     .keyset_after(("created", "id"), (last_created, last_id))?
+    ///     .limit(20)
+    /// ```
+    ///
+    /// # Errors
+    /// [`QailError::InvalidValue`] if `columns` and `values` have different lengths.
+    pub fn keyset_after<C, V>(mut self, columns: C, values: V) -> Result<Self, QailError>
+    where
+        C: KeysetTuple<String>,
+        V: KeysetTuple<Value>,
+    {
+        let columns = columns.into_keyset();
+        let values = values.into_keyset();
+        if columns.len() != values.len() {
+            return Err(QailError::InvalidValue(format!(
+                "keyset_after: {} columns but {} values",
+                columns.len(),
+                values.len()
+            )));
+        }
+
+        let condition = Condition {
+            left: Expr::RowConstructor {
+                elements: columns.iter().cloned().map(Expr::Named).collect(),
+                alias: None,
+            },
+            op: Operator::Gt,
+            value: Value::Array(values),
+            is_array_unnest: false,
+        };
+        // ANDed into the existing filter, like `filter()`
+        match self.cages.iter_mut().find(|c| matches!(c.kind, CageKind::Filter)) {
+            Some(cage) => cage.conditions.push(condition),
+            None => self.cages.push(Cage {
+                kind: CageKind::Filter,
+                conditions: vec![condition],
+                logical_op: LogicalOp::And,
+            }),
+        }
+        for column in columns {
+            self = self.order_by(column, SortOrder::Asc);
+        }
+        Ok(self)
+    }
+}
@@ -2,97 +2,124 @@ use crate::ast::{
     Action, Cage, CageKind, Condition, Distance, Expr, GroupByMode, IndexDef, Join, LockMode,
     LogicalOp, Operator, OverridingKind, SampleMethod, SetOp, TableConstraint, Value,
 };
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Qail {
     pub action: Action,
     pub table: String,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Schema/namespace the table lives in (e.g. `tenant_42` for `tenant_42.users`)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub schema: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub columns: Vec<Expr>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub joins: Vec<Join>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub cages: Vec<Cage>,
-    #[serde(default, skip_serializing_if = "is_false")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
     pub distinct: bool,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub index_def: Option<IndexDef>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub table_constraints: Vec<TableConstraint>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub set_ops: Vec<(SetOp, Box<Qail>)>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub having: Vec<Condition>,
-    #[serde(default, skip_serializing_if = "GroupByMode::is_simple")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "GroupByMode::is_simple"))]
     pub group_by_mode: GroupByMode,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub ctes: Vec<CTEDef>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub distinct_on: Vec<Expr>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub returning: Option<Vec<Expr>>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub on_conflict: Option<OnConflict>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Nested query: the `INSERT ... SELECT` source, a materialized view's
+    /// definition, or for `get` a derived table in FROM (aliased as `table`)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub source_query: Option<Box<Qail>>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub channel: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub payload: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub savepoint_name: Option<String>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub from_tables: Vec<String>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub using_tables: Vec<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub lock_mode: Option<LockMode>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub fetch: Option<(u64, bool)>,
-    #[serde(default, skip_serializing_if = "is_false")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
     pub default_values: bool,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub overriding: Option<OverridingKind>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub sample: Option<(SampleMethod, f64, Option<u64>)>,
-    #[serde(default, skip_serializing_if = "is_false")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
     pub only_table: bool,
+    /// CREATE TABLE / CREATE INDEX ... IF NOT EXISTS
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub if_not_exists: bool,
+    /// TRUNCATE ... RESTART IDENTITY
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub restart_identity: bool,
+    /// TRUNCATE ... CASCADE
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub cascade: bool,
+    /// Labels emitted as a leading `/* ... */` comment, for query attribution
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub tags: Vec<String>,
     // Vector database fields (Qdrant)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub vector: Option<Vec<f32>>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub score_threshold: Option<f32>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub vector_name: Option<String>,
-    #[serde(default, skip_serializing_if = "is_false")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
     pub with_vector: bool,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub vector_size: Option<u64>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub distance: Option<Distance>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub on_disk: Option<bool>,
     // PostgreSQL procedural objects
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub function_def: Option<crate::ast::FunctionDef>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub trigger_def: Option<crate::ast::TriggerDef>,
     // Redis fields
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub raw_value: Option<Vec<u8>>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub redis_ttl: Option<i64>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub redis_set_condition: Option<String>,
 }
 
+impl std::hash::Hash for Qail {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash_fields(state, false);
+    }
+}
+
 /// Helper for skip_serializing_if on bool fields
+#[cfg(feature = "serde")]
 fn is_false(b: &bool) -> bool {
     !*b
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CTEDef {
     pub name: String,
     pub recursive: bool,
@@ -100,15 +127,25 @@ pub struct CTEDef {
     pub base_query: Box<Qail>,
     pub recursive_query: Option<Box<Qail>>,
     pub source_table: Option<String>,
+    /// `Some(true)` → `AS MATERIALIZED`, `Some(false)` → `AS NOT MATERIALIZED`,
+    /// `None` leaves the choice to the planner.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub materialized: Option<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OnConflict {
     pub columns: Vec<String>,
+    /// `ON CONFLICT (cols) WHERE ...`: the predicate of a partial unique
+    /// index used as the conflict target. Conditions are ANDed.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub target_where: Vec<Condition>,
     pub action: ConflictAction,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ConflictAction {
     DoNothing,
     DoUpdate {
@@ -120,6 +157,7 @@ impl Default for OnConflict {
     fn default() -> Self {
         Self {
             columns: vec![],
+            target_where: vec![],
             action: ConflictAction::DoNothing,
         }
     }
@@ -130,6 +168,7 @@ impl Default for Qail {
         Self {
             action: Action::Get,
             table: String::new(),
+            schema: None,
             columns: vec![],
             joins: vec![],
             cages: vec![],
@@ -155,6 +194,10 @@ impl Default for Qail {
             overriding: None,
             sample: None,
             only_table: false,
+            if_not_exists: false,
+            restart_identity: false,
+            cascade: false,
+            tags: vec![],
             // Vector database fields
             vector: None,
             score_threshold: None,
@@ -176,11 +219,15 @@ impl Default for Qail {
 
 // Submodules with builder methods
 mod advanced;
+mod cache_key;
 mod constructors;
 mod cte;
+mod keyset;
 mod query;
 mod vector;
 
+pub use keyset::KeysetTuple;
+
 // Deprecated methods kept in main module for backward compatibility
 impl Qail {
     #[deprecated(since = "0.11.0", note = "Use .columns([...]) instead")]
@@ -205,6 +252,14 @@ impl Qail {
     }
 }
 
+impl Qail {
+    /// Render this command as canonical v2 QAIL text.
+    /// For every construct the parser accepts, `parse(&cmd.to_qail())` yields `cmd` back.
+    pub fn to_qail(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl std::fmt::Display for Qail {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Use the Formatter from the fmt module for canonical output
@@ -17,6 +17,16 @@ impl Qail {
         self
     }
 
+    /// `LIMIT ALL`: remove the row limit, e.g. to override a default `.limit(n)`
+    pub fn limit_all(mut self) -> Self {
+        self.cages.push(Cage {
+            kind: CageKind::LimitAll,
+            conditions: vec![],
+            logical_op: LogicalOp::And,
+        });
+        self
+    }
+
     #[deprecated(since = "0.11.0", note = "Use .order_asc(column) instead")]
     pub fn sort_asc(mut self, column: &str) -> Self {
         self.cages.push(Cage {
@@ -233,6 +243,7 @@ impl Qail {
                 is_array_unnest: false,
             }]),
             on_true: false,
+            subquery: None,
         });
         self
     }
@@ -407,6 +418,7 @@ impl Qail {
         
         self.on_conflict = Some(OnConflict {
             columns: conflict_cols.iter().map(|c| c.as_ref().to_string()).collect(),
+            target_where: vec![],
             action: ConflictAction::DoUpdate {
                 assignments: updates.iter()
                     .map(|(col, expr)| (col.as_ref().to_string(), expr.clone()))
@@ -432,8 +444,39 @@ impl Qail {
         
         self.on_conflict = Some(OnConflict {
             columns: conflict_cols.iter().map(|c| c.as_ref().to_string()).collect(),
+            target_where: vec![],
             action: ConflictAction::DoNothing,
         });
         self
     }
+
+    /// Restrict the ON CONFLICT target to a partial unique index:
+    /// `ON CONFLICT (email) WHERE deleted_at IS NULL`. Call after
+    /// `on_conflict_update` / `on_conflict_nothing` with at least one conflict
+    /// column (without one, rendering fails); repeated calls are ANDed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Qail::add("users")
+    ///     .set_value("email", "a@b.c")
+    ///     .on_conflict_nothing(&["email"])
+    ///     .on_conflict_where("deleted_at", Operator::IsNull, Value::Null)
+    /// ```
+    pub fn on_conflict_where(
+        mut self,
+        column: impl AsRef<str>,
+        op: Operator,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.on_conflict
+            .get_or_insert_with(Default::default)
+            .target_where
+            .push(Condition {
+                left: Expr::Named(column.as_ref().to_string()),
+                op,
+                value: value.into(),
+                is_array_unnest: false,
+            });
+        self
+    }
 }
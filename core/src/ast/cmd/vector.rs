@@ -1,6 +1,6 @@
 //! Vector database builder methods for Qdrant.
 
-use crate::ast::{Action, Qail};
+use crate::ast::{Action, Distance, Qail};
 
 impl Qail {
     /// Create a vector similarity search command.
@@ -49,6 +49,53 @@ impl Qail {
         }
     }
 
+    /// Create a collection-creation command.
+    ///
+    /// # Example
+    /// ```
+    /// use qail_core::prelude::*;
+    /// use qail_core::ast::Distance;
+    ///
+    /// let cmd = Qail::create_collection("products")
+    ///     .vector_size(384)
+    ///     .distance(Distance::Cosine);
+    /// assert_eq!(cmd.vector_size, Some(384));
+    /// ```
+    pub fn create_collection(collection: &str) -> Self {
+        Self {
+            action: Action::CreateCollection,
+            table: collection.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a collection deletion command.
+    pub fn delete_collection(collection: &str) -> Self {
+        Self {
+            action: Action::DeleteCollection,
+            table: collection.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the vector dimension of a new collection.
+    pub fn vector_size(mut self, size: u64) -> Self {
+        self.vector_size = Some(size);
+        self
+    }
+
+    /// Set the distance metric of a new collection.
+    pub fn distance(mut self, distance: Distance) -> Self {
+        self.distance = Some(distance);
+        self
+    }
+
+    /// Store a new collection's vectors on disk.
+    pub fn on_disk(mut self, on_disk: bool) -> Self {
+        self.on_disk = Some(on_disk);
+        self
+    }
+
     /// Set the query vector for similarity search.
     ///
     /// # Example
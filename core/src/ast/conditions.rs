@@ -1,13 +1,15 @@
 use crate::ast::{Expr, Operator, Value};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A single condition within a cage.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Condition {
     pub left: Expr,
     pub op: Operator,
     pub value: Value,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_array_unnest: bool,
 }
 
@@ -1,8 +1,12 @@
-use crate::ast::{AggregateFunc, Cage, Condition, ModKind, Value};
+use crate::ast::{AggregateFunc, Cage, Condition, ModKind, SortOrder, Value};
+use crate::transpiler::SqlGenerator;
+use crate::transpiler::sql::postgres::PostgresGenerator;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Binary operators for expressions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BinaryOp {
     // Arithmetic
     Concat,
@@ -49,7 +53,8 @@ impl std::fmt::Display for BinaryOp {
         }
     }
 }
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Expr {
     /// All columns (*)
     Star,
@@ -59,10 +64,20 @@ pub enum Expr {
     /// An aggregate function (COUNT(col)) with optional FILTER and DISTINCT
     Aggregate {
         col: String,
+        /// Argument that is not a plain column (`lower(name)`, `json_build_object(...)`).
+        /// Rendered as an expression in place of `col`, never quoted as an identifier.
+        #[cfg_attr(feature = "serde", serde(default))]
+        arg: Option<Box<Expr>>,
         func: AggregateFunc,
         distinct: bool,
         /// PostgreSQL FILTER (WHERE ...) clause for aggregates
         filter: Option<Vec<Condition>>,
+        /// Separator for STRING_AGG(col, 'delimiter')
+        #[cfg_attr(feature = "serde", serde(default))]
+        delimiter: Option<String>,
+        /// ORDER BY inside the call: ARRAY_AGG(col ORDER BY other DESC)
+        #[cfg_attr(feature = "serde", serde(default))]
+        order_by: Option<Vec<(String, SortOrder)>>,
         alias: Option<String>,
     },
     /// Type cast expression (expr::type)
@@ -177,6 +192,9 @@ pub enum Expr {
         negated: bool, // NOT EXISTS
         alias: Option<String>,
     },
+    /// Raw SQL fragment emitted verbatim: `raw!(...)`.
+    /// Never escaped, quoted or parameterized; must not contain user input.
+    Raw(String),
 }
 
 impl std::fmt::Display for Expr {
@@ -187,16 +205,35 @@ impl std::fmt::Display for Expr {
             Expr::Aliased { name, alias } => write!(f, "{} AS {}", name, alias),
             Expr::Aggregate {
                 col,
+                arg,
                 func,
                 distinct,
                 filter,
+                delimiter,
+                order_by,
                 alias,
             } => {
+                write!(f, "{}(", func)?;
                 if *distinct {
-                    write!(f, "{}(DISTINCT {})", func, col)?;
-                } else {
-                    write!(f, "{}({})", func, col)?;
+                    write!(f, "DISTINCT ")?;
                 }
+                match arg {
+                    Some(arg) => write!(f, "{}", arg)?,
+                    None => write!(f, "{}", col)?,
+                }
+                if let Some(sep) = delimiter {
+                    write!(f, ", {}", PostgresGenerator.string_literal(sep))?;
+                }
+                if let Some(order) = order_by
+                    && !order.is_empty()
+                {
+                    let items: Vec<String> = order
+                        .iter()
+                        .map(|(c, o)| format!("{} {}", c, o))
+                        .collect();
+                    write!(f, " ORDER BY {}", items.join(", "))?;
+                }
+                write!(f, ")")?;
                 if let Some(conditions) = filter {
                     write!(
                         f,
@@ -422,11 +459,13 @@ impl std::fmt::Display for Expr {
                 }
                 Ok(())
             }
+            Expr::Raw(sql) => write!(f, "{}", sql),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Constraint {
     PrimaryKey,
     Unique,
@@ -439,7 +478,8 @@ pub enum Constraint {
 }
 
 /// Generated column type (STORED or VIRTUAL)
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ColumnGeneration {
     /// GENERATED ALWAYS AS (expr) STORED - computed and stored
     Stored(String),
@@ -448,7 +488,8 @@ pub enum ColumnGeneration {
 }
 
 /// Window frame definition for window functions
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowFrame {
     /// ROWS BETWEEN start AND end
     Rows { start: FrameBound, end: FrameBound },
@@ -457,7 +498,8 @@ pub enum WindowFrame {
 }
 
 /// Window frame boundary
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FrameBound {
     UnboundedPreceding,
     Preceding(i32),
@@ -485,7 +527,8 @@ impl std::fmt::Display for Constraint {
 }
 
 /// Index definition for CREATE INDEX
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IndexDef {
     /// Index name
     pub name: String,
@@ -495,12 +538,13 @@ pub struct IndexDef {
     pub columns: Vec<String>,
     pub unique: bool,
     /// Index type (e.g., "keyword", "integer", "float", "geo", "text")
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub index_type: Option<String>,
 }
 
 /// Table-level constraints for composite keys
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TableConstraint {
     Unique(Vec<String>),
     PrimaryKey(Vec<String>),
@@ -531,7 +575,8 @@ impl From<&String> for Expr {
 // ==================== Function and Trigger Definitions ====================
 
 /// PostgreSQL function definition
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FunctionDef {
     pub name: String,
     pub returns: String,  // e.g., "trigger", "integer", "void"
@@ -540,7 +585,8 @@ pub struct FunctionDef {
 }
 
 /// Trigger timing (BEFORE or AFTER)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TriggerTiming {
     Before,
     After,
@@ -548,7 +594,8 @@ pub enum TriggerTiming {
 }
 
 /// Trigger event types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TriggerEvent {
     Insert,
     Update,
@@ -557,7 +604,8 @@ pub enum TriggerEvent {
 }
 
 /// PostgreSQL trigger definition
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TriggerDef {
     pub name: String,
     pub table: String,
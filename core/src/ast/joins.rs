@@ -1,13 +1,19 @@
-use crate::ast::{Condition, JoinKind};
+use crate::ast::{Condition, JoinKind, Qail};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Join {
     pub table: String,
     pub kind: JoinKind,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub on: Option<Vec<Condition>>,
     /// If true, use ON TRUE (unconditional join). Used for joining CTEs.
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub on_true: bool,
+    /// Correlated subquery joined in place of a table: `LATERAL (...) AS table`,
+    /// where `table` is the alias.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub subquery: Option<Box<Qail>>,
 }
@@ -6,11 +6,12 @@ pub mod expr;
 pub mod joins;
 pub mod operators;
 pub mod values;
+pub mod visit;
 
 pub use self::cages::{Cage, CageKind};
 #[deprecated(since = "0.12.0", note = "Use `Qail` instead of `Qail`")]
 pub use self::cmd::Qail;
-pub use self::cmd::{CTEDef, ConflictAction, OnConflict};
+pub use self::cmd::{CTEDef, ConflictAction, KeysetTuple, OnConflict};
 pub use self::conditions::Condition;
 pub use self::expr::{
     BinaryOp, ColumnGeneration, Constraint, Expr, FrameBound, IndexDef, TableConstraint,
@@ -18,7 +19,8 @@ pub use self::expr::{
 };
 pub use self::joins::Join;
 pub use self::operators::{
-    Action, AggregateFunc, Distance, GroupByMode, JoinKind, LockMode, LogicalOp, ModKind, Operator,
-    OverridingKind, SampleMethod, SetOp, SortOrder,
+    Action, AggregateFunc, CUSTOM_OPERATORS, CustomOp, Distance, GroupByMode, JoinKind, LockMode,
+    LogicalOp, ModKind, Operator, OverridingKind, SampleMethod, SetOp, SortOrder, escape_like,
 };
 pub use self::values::Value;
+pub use self::visit::Visitor;
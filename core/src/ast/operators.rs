@@ -1,7 +1,9 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The action type (SQL operation).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Action {
     Get,
     Set,
@@ -129,14 +131,16 @@ impl std::fmt::Display for Action {
 }
 
 /// Logical operator between conditions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LogicalOp {
     #[default]
     And,
     Or,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SortOrder {
     Asc,
     Desc,
@@ -146,7 +150,21 @@ pub enum SortOrder {
     DescNullsLast,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl std::fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortOrder::Asc => write!(f, "ASC"),
+            SortOrder::Desc => write!(f, "DESC"),
+            SortOrder::AscNullsFirst => write!(f, "ASC NULLS FIRST"),
+            SortOrder::AscNullsLast => write!(f, "ASC NULLS LAST"),
+            SortOrder::DescNullsFirst => write!(f, "DESC NULLS FIRST"),
+            SortOrder::DescNullsLast => write!(f, "DESC NULLS LAST"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Operator {
     Eq,
     Ne,
@@ -155,8 +173,13 @@ pub enum Operator {
     Lt,
     Lte,
     Fuzzy,
+    /// `~=`: fuzzy match with `%`/`_` in the value taken literally (`ILIKE ... ESCAPE '\'`).
+    FuzzyEscaped,
     In,
     NotIn,
+    /// `= ANY(array)`: the whole list is bound as one array parameter,
+    /// however long it is (unlike `In`, which binds every element).
+    EqAny,
     IsNull,
     IsNotNull,
     Contains,
@@ -177,6 +200,60 @@ pub enum Operator {
     SimilarTo,
     ContainedBy,
     Overlaps,
+    /// A PostgreSQL operator from [`CUSTOM_OPERATORS`], emitted verbatim:
+    /// `embedding <-> $1`, `document @@ $1`.
+    Custom(CustomOp),
+}
+
+/// PostgreSQL operators usable through [`Operator::Custom`].
+///
+/// Only these symbols can be emitted, so a custom operator cannot carry
+/// arbitrary SQL. Longer symbols come before their prefixes, so the parser
+/// can take the first match.
+pub const CUSTOM_OPERATORS: &[&str] = &[
+    // pgvector distances: L2, negative inner product, cosine, L1, Hamming, Jaccard
+    "<->", "<#>", "<=>", "<+>", "<~>", "<%>",
+    // Range / network containment and position
+    "<<=", ">>=", "<<", ">>", "&<", "&>", "-|-",
+    // Full-text search match, jsonpath predicate
+    "@@", "@?",
+    // jsonb: any / all of the keys exist
+    "?|", "?&",
+];
+
+/// An operator symbol checked against [`CUSTOM_OPERATORS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomOp(&'static str);
+
+impl CustomOp {
+    /// `None` unless `symbol` is in [`CUSTOM_OPERATORS`].
+    pub fn new(symbol: &str) -> Option<Self> {
+        CUSTOM_OPERATORS
+            .iter()
+            .find(|op| **op == symbol)
+            .map(|op| CustomOp(op))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CustomOp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CustomOp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let symbol = String::deserialize(deserializer)?;
+        CustomOp::new(&symbol).ok_or_else(|| {
+            serde::de::Error::custom(format!("operator '{}' is not an allowed custom operator", symbol))
+        })
+    }
 }
 
 impl Operator {
@@ -191,8 +268,10 @@ impl Operator {
             Operator::Lt => "<",
             Operator::Lte => "<=",
             Operator::Fuzzy => "ILIKE",
+            Operator::FuzzyEscaped => "ILIKE",
             Operator::In => "IN",
             Operator::NotIn => "NOT IN",
+            Operator::EqAny => "= ANY",
             Operator::IsNull => "IS NULL",
             Operator::IsNotNull => "IS NOT NULL",
             Operator::Contains => "@>",
@@ -213,9 +292,31 @@ impl Operator {
             Operator::SimilarTo => "SIMILAR TO",
             Operator::ContainedBy => "<@",
             Operator::Overlaps => "&&",
+            Operator::Custom(op) => op.as_str(),
         }
     }
 
+    /// [`Operator::Custom`] for an allowlisted symbol.
+    ///
+    /// ```
+    /// use qail_core::ast::{Operator, Qail};
+    /// use qail_core::transpiler::ToSql;
+    ///
+    /// let nearest = Qail::get("items")
+    ///     .filter("embedding", Operator::custom("<->").unwrap(), "[1,2,3]");
+    /// assert_eq!(nearest.to_sql(), "SELECT * FROM items WHERE embedding <-> '[1,2,3]'");
+    /// assert!(Operator::custom("; drop table items").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// [`QailError::InvalidOperator`](crate::error::QailError) for a symbol
+    /// not in [`CUSTOM_OPERATORS`].
+    pub fn custom(symbol: &str) -> Result<Self, crate::error::QailError> {
+        CustomOp::new(symbol)
+            .map(Operator::Custom)
+            .ok_or_else(|| crate::error::QailError::InvalidOperator(symbol.to_string()))
+    }
+
     /// IS NULL, IS NOT NULL, EXISTS, NOT EXISTS don't need values.
     pub fn needs_value(&self) -> bool {
         !matches!(
@@ -241,7 +342,21 @@ impl Operator {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Escape LIKE wildcards (`%`, `_`) and the escape character itself with `\`.
+/// Pair the result with `ESCAPE '\'`.
+pub fn escape_like(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AggregateFunc {
     Count,
     Sum,
@@ -275,7 +390,8 @@ impl std::fmt::Display for AggregateFunc {
 }
 
 /// Join Type
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum JoinKind {
     Inner,
     Left,
@@ -286,7 +402,8 @@ pub enum JoinKind {
 }
 
 /// Set operation type for combining queries
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SetOp {
     Union,
     UnionAll,
@@ -294,14 +411,16 @@ pub enum SetOp {
     Except,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ModKind {
     Add,
     Drop,
 }
 
 /// GROUP BY mode for advanced aggregations
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GroupByMode {
     #[default]
     Simple,
@@ -318,7 +437,8 @@ impl GroupByMode {
 }
 
 /// Row locking mode for SELECT...FOR UPDATE/SHARE
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LockMode {
     Update,
     NoKeyUpdate,
@@ -327,21 +447,24 @@ pub enum LockMode {
 }
 
 /// OVERRIDING clause for INSERT with GENERATED columns
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OverridingKind {
     SystemValue,
     UserValue,
 }
 
 /// TABLESAMPLE sampling method
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SampleMethod {
     Bernoulli,
     System,
 }
 
 /// Distance metric for vector similarity
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Distance {
     #[default]
     Cosine,
@@ -1,9 +1,13 @@
 use crate::ast::Qail;
+use crate::error::QailError;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
 use uuid::Uuid;
 
 /// Time interval unit for duration expressions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IntervalUnit {
     Second,
     Minute,
@@ -29,12 +33,19 @@ impl std::fmt::Display for IntervalUnit {
 }
 
 /// A value in a condition.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Value {
     Null,
     Bool(bool),
     Int(i64),
     Float(f64),
+    /// Exact decimal kept as its literal text (NUMERIC), e.g. money
+    ///
+    /// Build with [`Value::decimal`] to validate the text. A string that is
+    /// not a numeric literal is emitted as a quoted `'...'::numeric` cast.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_decimal"))]
+    Decimal(String),
     String(String),
     Param(usize),
     /// Named parameter reference (:name, :id, etc.)
@@ -42,6 +53,10 @@ pub enum Value {
     Function(String),
     Array(Vec<Value>),
     Subquery(Box<Qail>),
+    /// Quantified subquery on the right of a comparison: `= ANY (SELECT ...)`
+    AnySubquery(Box<Qail>),
+    /// Quantified subquery on the right of a comparison: `> ALL (SELECT ...)`
+    AllSubquery(Box<Qail>),
     Column(String),
     Uuid(Uuid),
     NullUuid,
@@ -55,6 +70,104 @@ pub enum Value {
     /// Vector embedding for similarity search (Qdrant)
     Vector(Vec<f32>),
     Json(String),
+    /// `DEFAULT` in INSERT VALUES / UPDATE SET: use the column's default
+    Default,
+}
+
+// Floats hash by bit pattern, with -0.0 folded into 0.0 so equal values
+// hash equally.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Null | Value::NullUuid | Value::Default => {}
+            Value::Bool(b) => b.hash(state),
+            Value::Int(n) => n.hash(state),
+            Value::Float(f) => float_bits(*f).hash(state),
+            Value::Decimal(s)
+            | Value::String(s)
+            | Value::NamedParam(s)
+            | Value::Function(s)
+            | Value::Column(s)
+            | Value::Timestamp(s)
+            | Value::Json(s) => s.hash(state),
+            Value::Param(n) => n.hash(state),
+            Value::Array(items) => items.hash(state),
+            Value::Subquery(q) | Value::AnySubquery(q) | Value::AllSubquery(q) => q.hash(state),
+            Value::Uuid(u) => u.hash(state),
+            Value::Interval { amount, unit } => {
+                amount.hash(state);
+                unit.hash(state);
+            }
+            Value::Bytes(bytes) => bytes.hash(state),
+            Value::Expr(expr) => expr.hash(state),
+            Value::Vector(v) => {
+                v.len().hash(state);
+                for x in v {
+                    float_bits(f64::from(*x)).hash(state);
+                }
+            }
+        }
+    }
+}
+
+/// Bit pattern of `f` for hashing; `-0.0` and `0.0` compare equal so they
+/// must hash the same.
+pub(crate) fn float_bits(f: f64) -> u64 {
+    if f == 0.0 { 0 } else { f.to_bits() }
+}
+
+impl Value {
+    /// Exact decimal from its literal text, e.g. `Value::decimal("19.99")`
+    ///
+    /// # Errors
+    /// [`QailError::InvalidValue`] if `text` is not a numeric literal.
+    pub fn decimal(text: impl Into<String>) -> Result<Self, QailError> {
+        let text = text.into();
+        if !is_decimal_literal(&text) {
+            return Err(QailError::InvalidValue(format!("Invalid decimal literal '{}'", text)));
+        }
+        Ok(Value::Decimal(text))
+    }
+}
+
+/// Whether `s` is a plain numeric literal: `[+-]digits[.digits][e[+-]digits]`
+///
+/// Only such text is written into SQL verbatim.
+pub(crate) fn is_decimal_literal(s: &str) -> bool {
+    fn digits(s: &str) -> (&str, usize) {
+        let n = s.bytes().take_while(u8::is_ascii_digit).count();
+        (&s[n..], n)
+    }
+
+    let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let (rest, int_digits) = digits(s);
+    let (rest, frac_digits) = match rest.strip_prefix('.') {
+        Some(frac) => digits(frac),
+        None => (rest, 0),
+    };
+    if int_digits + frac_digits == 0 {
+        return false;
+    }
+    match rest.strip_prefix(['e', 'E']) {
+        Some(exp) => {
+            let (rest, exp_digits) = digits(exp.strip_prefix(['+', '-']).unwrap_or(exp));
+            exp_digits > 0 && rest.is_empty()
+        }
+        None => rest.is_empty(),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_decimal<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    if !is_decimal_literal(&text) {
+        return Err(serde::de::Error::custom(format!("invalid decimal literal '{}'", text)));
+    }
+    Ok(text)
 }
 
 impl std::fmt::Display for Value {
@@ -64,7 +177,9 @@ impl std::fmt::Display for Value {
             Value::Bool(b) => write!(f, "{}", b),
             Value::Int(n) => write!(f, "{}", n),
             Value::Float(n) => write!(f, "{}", n),
-            Value::String(s) => write!(f, "'{}'", s),
+            Value::Decimal(d) if is_decimal_literal(d) => write!(f, "{}", d),
+            Value::Decimal(d) => write!(f, "'{}'::numeric", d.replace('\'', "''")),
+            Value::String(s) => write!(f, "'{}'", s.replace('\'', "''")),
             Value::Param(n) => write!(f, "${}", n),
             Value::NamedParam(name) => write!(f, ":{}", name),
             Value::Function(s) => write!(f, "{}", s),
@@ -79,6 +194,8 @@ impl std::fmt::Display for Value {
                 write!(f, ")")
             }
             Value::Subquery(_) => write!(f, "(SUBQUERY)"),
+            Value::AnySubquery(_) => write!(f, "ANY (SUBQUERY)"),
+            Value::AllSubquery(_) => write!(f, "ALL (SUBQUERY)"),
             Value::Column(s) => write!(f, "{}", s),
             Value::Uuid(u) => write!(f, "'{}'", u),
             Value::NullUuid => write!(f, "NULL"),
@@ -101,6 +218,7 @@ impl std::fmt::Display for Value {
                 write!(f, "]")
             }
             Value::Json(json) => write!(f, "'{}'::jsonb", json.replace('\'', "''")),
+            Value::Default => write!(f, "DEFAULT"),
         }
     }
 }
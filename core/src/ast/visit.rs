@@ -0,0 +1,309 @@
+//! Read-only traversal of the query AST.
+//!
+//! Implement [`Visitor`] and override the hooks you care about; the default
+//! of every hook is the matching `walk_*` function, which visits the node's
+//! children. An override that still wants the children calls `walk_*`
+//! itself:
+//!
+//! ```ignore
+//! struct Columns(Vec<String>);
+//!
+//! impl Visitor for Columns {
+//!     fn visit_expr(&mut self, expr: &Expr) {
+//!         if let Expr::Named(name) = expr {
+//!             self.0.push(name.clone());
+//!         }
+//!         walk_expr(self, expr);
+//!     }
+//! }
+//!
+//! let mut columns = Columns(vec![]);
+//! cmd.walk(&mut columns);
+//! ```
+
+use crate::ast::{Cage, Condition, ConflictAction, Expr, Join, Qail, Value};
+
+/// Hooks called while walking a [`Qail`] tree.
+pub trait Visitor {
+    /// A command: the root, a set-operation member, or (via
+    /// [`visit_subquery`](Self::visit_subquery)) a nested query.
+    fn visit_cmd(&mut self, cmd: &Qail) {
+        walk_cmd(self, cmd);
+    }
+
+    /// A query nested inside another: CTE bodies, `INSERT ... SELECT`
+    /// sources, lateral join subqueries, and subqueries in values and
+    /// expressions. UNION / INTERSECT / EXCEPT members sit beside the query
+    /// and go straight to `visit_cmd`.
+    fn visit_subquery(&mut self, cmd: &Qail) {
+        self.visit_cmd(cmd);
+    }
+
+    fn visit_join(&mut self, join: &Join) {
+        walk_join(self, join);
+    }
+
+    fn visit_cage(&mut self, cage: &Cage) {
+        walk_cage(self, cage);
+    }
+
+    fn visit_condition(&mut self, cond: &Condition) {
+        walk_condition(self, cond);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_value(&mut self, value: &Value) {
+        walk_value(self, value);
+    }
+}
+
+impl Qail {
+    /// Walk this command with `visitor`, starting at `visit_cmd`.
+    pub fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_cmd(self);
+    }
+}
+
+/// Visit CTEs, then the clauses in SQL order, then set-operation members.
+pub fn walk_cmd<V: Visitor + ?Sized>(visitor: &mut V, cmd: &Qail) {
+    for cte in &cmd.ctes {
+        visitor.visit_subquery(&cte.base_query);
+        if let Some(recursive) = &cte.recursive_query {
+            visitor.visit_subquery(recursive);
+        }
+    }
+    for expr in cmd.distinct_on.iter().chain(&cmd.columns) {
+        visitor.visit_expr(expr);
+    }
+    for join in &cmd.joins {
+        visitor.visit_join(join);
+    }
+    for cage in &cmd.cages {
+        visitor.visit_cage(cage);
+    }
+    for cond in &cmd.having {
+        visitor.visit_condition(cond);
+    }
+    if let Some(source) = &cmd.source_query {
+        visitor.visit_subquery(source);
+    }
+    if let Some(conflict) = &cmd.on_conflict {
+        for cond in &conflict.target_where {
+            visitor.visit_condition(cond);
+        }
+        if let ConflictAction::DoUpdate { assignments } = &conflict.action {
+            for (_, expr) in assignments {
+                visitor.visit_expr(expr);
+            }
+        }
+    }
+    for expr in cmd.returning.iter().flatten() {
+        visitor.visit_expr(expr);
+    }
+    for (_, other) in &cmd.set_ops {
+        visitor.visit_cmd(other);
+    }
+}
+
+pub fn walk_join<V: Visitor + ?Sized>(visitor: &mut V, join: &Join) {
+    if let Some(query) = &join.subquery {
+        visitor.visit_subquery(query);
+    }
+    for cond in join.on.iter().flatten() {
+        visitor.visit_condition(cond);
+    }
+}
+
+pub fn walk_cage<V: Visitor + ?Sized>(visitor: &mut V, cage: &Cage) {
+    for cond in &cage.conditions {
+        visitor.visit_condition(cond);
+    }
+}
+
+pub fn walk_condition<V: Visitor + ?Sized>(visitor: &mut V, cond: &Condition) {
+    visitor.visit_expr(&cond.left);
+    visitor.visit_value(&cond.value);
+}
+
+pub fn walk_value<V: Visitor + ?Sized>(visitor: &mut V, value: &Value) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                visitor.visit_value(item);
+            }
+        }
+        Value::Subquery(query) | Value::AnySubquery(query) | Value::AllSubquery(query) => {
+            visitor.visit_subquery(query)
+        }
+        Value::Expr(expr) => visitor.visit_expr(expr),
+        _ => {}
+    }
+}
+
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::Literal(value) => visitor.visit_value(value),
+        Expr::Aggregate { arg, filter, .. } => {
+            if let Some(arg) = arg {
+                visitor.visit_expr(arg);
+            }
+            for cond in filter.iter().flatten() {
+                visitor.visit_condition(cond);
+            }
+        }
+        Expr::Cast { expr, .. }
+        | Expr::Mod { col: expr, .. }
+        | Expr::Collate { expr, .. }
+        | Expr::FieldAccess { expr, .. } => visitor.visit_expr(expr),
+        Expr::Window { params, order, .. } => {
+            for param in params {
+                visitor.visit_expr(param);
+            }
+            for cage in order {
+                visitor.visit_cage(cage);
+            }
+        }
+        Expr::Case { when_clauses, else_value, .. } => {
+            for (cond, then) in when_clauses {
+                visitor.visit_condition(cond);
+                visitor.visit_expr(then);
+            }
+            if let Some(else_value) = else_value {
+                visitor.visit_expr(else_value);
+            }
+        }
+        Expr::FunctionCall { args, .. }
+        | Expr::ArrayConstructor { elements: args, .. }
+        | Expr::RowConstructor { elements: args, .. } => {
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        Expr::SpecialFunction { args, .. } => {
+            for (_, arg) in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        Expr::Binary { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Subscript { expr, index, .. } => {
+            visitor.visit_expr(expr);
+            visitor.visit_expr(index);
+        }
+        Expr::Subquery { query, .. } | Expr::Exists { query, .. } => {
+            visitor.visit_subquery(query)
+        }
+        Expr::Star
+        | Expr::Named(_)
+        | Expr::Aliased { .. }
+        | Expr::Def { .. }
+        | Expr::JsonAccess { .. }
+        | Expr::Raw(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::SetOp;
+    use crate::parser::parse;
+    use std::collections::BTreeSet;
+
+    /// Every column name referenced anywhere in a query.
+    #[derive(Default)]
+    struct ColumnCollector(BTreeSet<String>);
+
+    impl Visitor for ColumnCollector {
+        fn visit_expr(&mut self, expr: &Expr) {
+            match expr {
+                // `NOT EXISTS` conditions have an empty left-hand side
+                Expr::Named(name) | Expr::Aliased { name, .. } if !name.is_empty() => {
+                    self.0.insert(name.clone());
+                }
+                Expr::Aggregate { col, arg: None, .. } if col != "*" => {
+                    self.0.insert(col.clone());
+                }
+                Expr::Window { partition, .. } => self.0.extend(partition.iter().cloned()),
+                _ => {}
+            }
+            walk_expr(self, expr);
+        }
+
+        fn visit_value(&mut self, value: &Value) {
+            if let Value::Column(name) = value {
+                self.0.insert(name.clone());
+            }
+            walk_value(self, value);
+        }
+    }
+
+    #[test]
+    fn test_collect_columns_from_complex_query() {
+        let recent = parse("get orders fields user_id where created_at > :since").unwrap();
+        let cmd = parse(
+            "get users join recent on recent.user_id = users.id \
+             fields id, email, coalesce(nickname, name) as display, \
+             (get reviews fields count(*) where reviews.author_id = users.id) as n \
+             where org_id = any (get orgs fields id where plan = 'pro') \
+             and not exists (get bans where bans.user_id = users.id) \
+             order by email",
+        )
+        .unwrap()
+        .with("recent", recent);
+
+        let mut columns = ColumnCollector::default();
+        cmd.walk(&mut columns);
+        let columns: Vec<&str> = columns.0.iter().map(String::as_str).collect();
+        assert_eq!(
+            columns,
+            vec![
+                "bans.user_id",
+                "created_at",
+                "email",
+                "id",
+                "name",
+                "nickname",
+                "org_id",
+                "plan",
+                "recent.user_id",
+                "reviews.author_id",
+                "user_id",
+                "users.id",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subqueries_and_set_ops_are_distinguished() {
+        #[derive(Default)]
+        struct Counts {
+            cmds: usize,
+            subqueries: usize,
+        }
+
+        impl Visitor for Counts {
+            fn visit_cmd(&mut self, cmd: &Qail) {
+                self.cmds += 1;
+                walk_cmd(self, cmd);
+            }
+
+            fn visit_subquery(&mut self, cmd: &Qail) {
+                self.subqueries += 1;
+                self.visit_cmd(cmd);
+            }
+        }
+
+        let mut cmd = parse("get a fields id where id = any (get b fields id)").unwrap();
+        cmd.set_ops
+            .push((SetOp::Union, Box::new(parse("get c fields id").unwrap())));
+        let mut counts = Counts::default();
+        cmd.walk(&mut counts);
+        assert_eq!(counts.cmds, 3);
+        assert_eq!(counts.subqueries, 1);
+    }
+}
@@ -125,6 +125,41 @@ fn generate_table_module(table_name: &str, table: &crate::build::TableSchema) ->
     code
 }
 
+/// Generate a Rust row struct for `table`.
+///
+/// Nullable columns become `Option<T>`. The struct derives `sqlx::FromRow`
+/// and serde, so the generated file needs `sqlx`, `serde` and the crates
+/// behind the column types (`uuid`, `chrono`, `rust_decimal`, `serde_json`).
+///
+/// ```
+/// use qail_core::codegen::generate_struct;
+/// use qail_core::schema::TableDef;
+///
+/// let code = generate_struct(&TableDef::new("users").pk("id", "uuid").column("bio", "text"));
+/// assert!(code.contains("pub struct Users {"));
+/// assert!(code.contains("pub bio: Option<String>,"));
+/// ```
+pub fn generate_struct(table: &crate::schema::TableDef) -> String {
+    let mut code = format!(
+        "/// Row of `{}`\n\
+         #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]\n\
+         pub struct {} {{\n",
+        table.name,
+        to_pascal_case(&table.name)
+    );
+    for col in &table.columns {
+        let rust_type = pg_type_to_rust(&col.typ);
+        let field = escape_keyword(&col.name);
+        if col.nullable {
+            code.push_str(&format!("    pub {}: Option<{}>,\n", field, rust_type));
+        } else {
+            code.push_str(&format!("    pub {}: {},\n", field, rust_type));
+        }
+    }
+    code.push_str("}\n");
+    code
+}
+
 /// Map SQL types to Rust types
 pub fn sql_type_to_rust(sql_type: &str) -> &'static str {
     let upper = sql_type.to_uppercase();
@@ -178,6 +213,47 @@ pub fn sql_type_to_rust(sql_type: &str) -> &'static str {
     "String"
 }
 
+/// Map a PostgreSQL column type to the Rust type sqlx decodes it into.
+///
+/// Case-insensitive; length and precision modifiers are ignored
+/// (`VARCHAR(255)` is `String`, `timestamp(3) with time zone` is
+/// `timestamptz`) and arrays become `Vec<T>`. Only the types where a row
+/// struct needs the exact width are mapped here; the rest go through
+/// [`sql_type_to_rust`].
+pub fn pg_type_to_rust(sql_type: &str) -> String {
+    let typ = sql_type.trim().to_lowercase();
+    if let Some(elem) = typ.strip_suffix("[]") {
+        return format!("Vec<{}>", pg_type_to_rust(elem));
+    }
+    let base = strip_type_modifiers(&typ);
+    let rust = match base.as_str() {
+        "int2" | "smallint" | "smallserial" => "i16",
+        "int4" | "int" | "integer" | "serial" => "i32",
+        "float4" | "real" => "f32",
+        "numeric" | "decimal" => "rust_decimal::Decimal",
+        "timestamp" | "timestamp without time zone" => "chrono::NaiveDateTime",
+        "date" => "chrono::NaiveDate",
+        "time" | "time without time zone" => "chrono::NaiveTime",
+        other => sql_type_to_rust(other),
+    };
+    rust.to_string()
+}
+
+/// Drop `(n)` / `(p,s)` modifiers wherever they sit and collapse whitespace.
+fn strip_type_modifiers(typ: &str) -> String {
+    let mut out = String::with_capacity(typ.len());
+    let mut depth = 0usize;
+    for c in typ.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 /// Convert snake_case to PascalCase
 fn to_pascal_case(s: &str) -> String {
     s.split('_')
@@ -227,4 +303,52 @@ mod tests {
         assert_eq!(sql_type_to_rust("BOOLEAN"), "bool");
         assert_eq!(sql_type_to_rust("JSONB"), "serde_json::Value");
     }
+
+    #[test]
+    fn test_pg_type_mapping() {
+        assert_eq!(pg_type_to_rust("INT"), "i32");
+        assert_eq!(pg_type_to_rust("BIGINT"), "i64");
+        assert_eq!(pg_type_to_rust("VARCHAR(255)"), "String");
+        assert_eq!(pg_type_to_rust("timestamptz"), "chrono::DateTime<chrono::Utc>");
+        assert_eq!(pg_type_to_rust("numeric(10,2)"), "rust_decimal::Decimal");
+        assert_eq!(pg_type_to_rust("int4[]"), "Vec<i32>");
+        assert_eq!(
+            pg_type_to_rust("timestamp(3) with time zone"),
+            "chrono::DateTime<chrono::Utc>"
+        );
+        assert_eq!(pg_type_to_rust("timestamp(6)"), "chrono::NaiveDateTime");
+        assert_eq!(pg_type_to_rust("time(0) without time zone"), "chrono::NaiveTime");
+        assert_eq!(pg_type_to_rust("jsonb"), "serde_json::Value");
+        assert_eq!(pg_type_to_rust("bytea"), "Vec<u8>");
+    }
+
+    #[test]
+    fn test_generate_struct_wraps_nullable_in_option() {
+        use crate::schema::{ColumnDef, TableDef};
+
+        let mut table = TableDef::new("user_profiles")
+            .pk("id", "uuid")
+            .column("bio", "text")
+            .column("age", "int4")
+            .column("type", "varchar(32)");
+        table.add_column(ColumnDef {
+            name: "created_at".to_string(),
+            typ: "timestamptz".to_string(),
+            nullable: false,
+            primary_key: false,
+        });
+
+        assert_eq!(
+            generate_struct(&table),
+            "/// Row of `user_profiles`\n\
+             #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]\n\
+             pub struct UserProfiles {\n\
+             \x20   pub id: uuid::Uuid,\n\
+             \x20   pub bio: Option<String>,\n\
+             \x20   pub age: Option<i32>,\n\
+             \x20   pub r#type: Option<String>,\n\
+             \x20   pub created_at: chrono::DateTime<chrono::Utc>,\n\
+             }\n"
+        );
+    }
 }
@@ -2,11 +2,29 @@
 
 use thiserror::Error;
 
+/// Byte range `[start, end)` in the parsed source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum QailError {
     /// Failed to parse the QAIL query string.
+    /// `span` covers the offending token when the parser could locate it.
     #[error("Parse error at position {position}: {message}")]
-    Parse { position: usize, message: String },
+    Parse {
+        position: usize,
+        message: String,
+        span: Option<Span>,
+    },
 
     /// Invalid action (must be get, set, del, or add).
     #[error("Invalid action: '{0}'. Expected: get, set, del, or add")]
@@ -18,6 +36,10 @@ pub enum QailError {
         description: &'static str,
     },
 
+    /// v1 symbolic syntax in a query that does not parse as v2.
+    #[error("v1 syntax '{token}' at position {position} is not allowed; use v2 keyword syntax")]
+    SyntaxVersionForbidden { position: usize, token: String },
+
     #[error("Invalid operator: '{0}'")]
     InvalidOperator(String),
 
@@ -49,6 +71,27 @@ impl QailError {
         Self::Parse {
             position,
             message: message.into(),
+            span: None,
+        }
+    }
+
+    /// Create a parse error covering `span`.
+    pub fn parse_at(span: Span, message: impl Into<String>) -> Self {
+        Self::Parse {
+            position: span.start,
+            message: message.into(),
+            span: Some(span),
+        }
+    }
+
+    /// Source span of the error, if it points into the query.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Parse { span, .. } => *span,
+            Self::SyntaxVersionForbidden { position, token } => {
+                Some(Span::new(*position, position + token.len()))
+            }
+            _ => None,
         }
     }
 
@@ -64,6 +107,19 @@ impl QailError {
 /// Result type alias for QAIL operations.
 pub type QailResult<T> = Result<T, QailError>;
 
+/// Convert a byte offset in `source` to a 0-based `(line, column)`.
+/// Columns count chars; offsets past the end clamp to the end of `source`.
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &source[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,4 +132,15 @@ mod tests {
             "Parse error at position 5: unexpected character"
         );
     }
+
+    #[test]
+    fn test_line_col() {
+        let src = "get users\nfields id,\n  née";
+        assert_eq!(line_col(src, 0), (0, 0));
+        assert_eq!(line_col(src, 4), (0, 4));
+        assert_eq!(line_col(src, 10), (1, 0));
+        // "née": column counts chars, not bytes
+        assert_eq!(line_col(src, src.len()), (2, 5));
+        assert_eq!(line_col(src, 1000), (2, 5));
+    }
 }
@@ -1,6 +1,8 @@
 use crate::ast::{
-    Action, Cage, CageKind, Condition, Expr, Join, LogicalOp, Operator, Qail, SortOrder, Value,
+    Action, Cage, CageKind, Condition, ConflictAction, Constraint, Expr, FrameBound, Join,
+    LogicalOp, Operator, Qail, SortOrder, TableConstraint, Value, WindowFrame,
 };
+use crate::ast::values::is_decimal_literal;
 use std::fmt::{Result, Write};
 
 #[cfg(test)]
@@ -30,6 +32,45 @@ impl Formatter {
         Ok(self.buffer)
     }
 
+    /// `index[?] name on table a, b [unique]`
+    fn format_index(&mut self, cmd: &Qail) -> Result {
+        let Some(index) = &cmd.index_def else {
+            return Err(std::fmt::Error);
+        };
+        write!(self.buffer, "index")?;
+        if cmd.if_not_exists {
+            write!(self.buffer, "?")?;
+        }
+        write!(self.buffer, " {} on {} {}", index.name, index.table, index.columns.join(", "))?;
+        if index.unique {
+            write!(self.buffer, " unique")?;
+        }
+        Ok(())
+    }
+
+    /// `make[?] table col:type[:constraint...], ... [primary key (...)] [unique (...)]`
+    fn format_make(&mut self, cmd: &Qail, table: &str) -> Result {
+        write!(self.buffer, "make")?;
+        if cmd.if_not_exists {
+            write!(self.buffer, "?")?;
+        }
+        write!(self.buffer, " {} ", table)?;
+        for (i, col) in cmd.columns.iter().enumerate() {
+            if i > 0 {
+                write!(self.buffer, ", ")?;
+            }
+            self.format_column(col)?;
+        }
+        for constraint in &cmd.table_constraints {
+            let (keyword, cols) = match constraint {
+                TableConstraint::PrimaryKey(cols) => ("primary key", cols),
+                TableConstraint::Unique(cols) => ("unique", cols),
+            };
+            write!(self.buffer, " {} ({})", keyword, cols.join(", "))?;
+        }
+        Ok(())
+    }
+
     fn indent(&mut self) -> Result {
         for _ in 0..self.indent_level {
             write!(self.buffer, "  ")?;
@@ -38,8 +79,26 @@ impl Formatter {
     }
 
     fn visit_cmd(&mut self, cmd: &Qail) -> Result {
-        for cte in &cmd.ctes {
-            write!(self.buffer, "with {} = ", cte.name)?;
+        for (i, cte) in cmd.ctes.iter().enumerate() {
+            if i == 0 {
+                write!(self.buffer, "with ")?;
+                if cmd.ctes.iter().any(|c| c.recursive) {
+                    write!(self.buffer, "recursive ")?;
+                }
+            } else {
+                write!(self.buffer, ", ")?;
+            }
+            write!(self.buffer, "{}", cte.name)?;
+            if !cte.columns.is_empty() {
+                write!(self.buffer, " ({})", cte.columns.join(", "))?;
+            }
+            write!(self.buffer, " as ")?;
+            match cte.materialized {
+                Some(true) => write!(self.buffer, "materialized ")?,
+                Some(false) => write!(self.buffer, "not materialized ")?,
+                None => {}
+            }
+            write!(self.buffer, "(")?;
             self.indent_level += 1;
             writeln!(self.buffer)?;
             self.indent()?;
@@ -48,7 +107,6 @@ impl Formatter {
             if cte.recursive
                 && let Some(ref recursive_query) = cte.recursive_query
             {
-                writeln!(self.buffer)?;
                 self.indent()?;
                 writeln!(self.buffer, "union all")?;
                 self.indent()?;
@@ -56,53 +114,72 @@ impl Formatter {
             }
 
             self.indent_level -= 1;
+            self.indent()?;
+            write!(self.buffer, ")")?;
+        }
+        if !cmd.ctes.is_empty() {
             writeln!(self.buffer)?;
         }
 
-        // Action and Table
+        // Action and Table (schema-qualified if set)
+        let table = match &cmd.schema {
+            Some(schema) => format!("{}.{}", schema, cmd.table),
+            None => cmd.table.clone(),
+        };
+        if matches!(cmd.action, Action::Truncate) {
+            write!(self.buffer, "truncate {}", table)?;
+            if cmd.restart_identity {
+                write!(self.buffer, " restart identity")?;
+            }
+            if cmd.cascade {
+                write!(self.buffer, " cascade")?;
+            }
+            return Ok(());
+        }
         match cmd.action {
-            Action::Get => write!(self.buffer, "get {}", cmd.table)?,
-            Action::Set => write!(self.buffer, "set {}", cmd.table)?,
-            Action::Del => write!(self.buffer, "del {}", cmd.table)?,
-            Action::Add => write!(self.buffer, "add {}", cmd.table)?,
-            _ => write!(self.buffer, "{} {}", cmd.action, cmd.table)?, // Fallback for others
-        }
-        writeln!(self.buffer)?;
-
-        // self.indent_level += 1; // Removed: Clauses should act at same level as command
-
-        // Cages: Group By (if any "by" equivalent exists? No, "by" is usually implicit in AST or explicit in group_by_mode?)
-        // The proposal example shows "by phone_number".
-        // In AST `cmd.rs`, there isn't a direct "Group By" list, usually inferred or group_by_mode.
-        // Wait, where is `by phone_number` stored in AST?
-        // Checking `ast/cmd.rs`: `group_by_mode: GroupByMode`.
-        // Usually group by is inferred from aggregates or explicit.
-        // If the AST doesn't have explicit group by columns, we might need to derive it or it's in `cages`?
-        // Let's check `cages.rs` again. `CageKind` has `Filter`, `Sort`, `Limit`... no `GroupBy`.
-        // Maybe it's implied by non-aggregated columns in a `Get` with aggregates?
-        // For now, I will skip "by" unless I find it in AST.
-
-        if !cmd.columns.is_empty() {
-            // But proposal says "Canonical".
-            // "get table" implies "get table fields *" usually?
-            // If manual explicit columns:
-            if !(cmd.columns.len() == 1 && matches!(cmd.columns[0], Expr::Star)) {
-                self.indent()?;
-                writeln!(self.buffer, "fields")?;
-                self.indent_level += 1;
-                for (i, col) in cmd.columns.iter().enumerate() {
-                    self.indent()?;
-                    self.format_column(col)?;
-                    if i < cmd.columns.len() - 1 {
-                        writeln!(self.buffer, ",")?;
-                    } else {
-                        writeln!(self.buffer)?;
+            Action::TxnStart => return write!(self.buffer, "begin"),
+            Action::TxnCommit => return write!(self.buffer, "commit"),
+            Action::TxnRollback => return write!(self.buffer, "rollback"),
+            Action::Index => return self.format_index(cmd),
+            Action::Make => return self.format_make(cmd, &table),
+            _ => {}
+        }
+        match cmd.action {
+            Action::Get => write!(self.buffer, "get")?,
+            Action::Set => write!(self.buffer, "set")?,
+            Action::Del => write!(self.buffer, "del")?,
+            Action::Add => write!(self.buffer, "add")?,
+            _ => write!(self.buffer, "{}", cmd.action)?, // Fallback for others
+        }
+        if cmd.distinct {
+            write!(self.buffer, " distinct")?;
+            if !cmd.distinct_on.is_empty() {
+                write!(self.buffer, " on (")?;
+                for (i, expr) in cmd.distinct_on.iter().enumerate() {
+                    if i > 0 {
+                        write!(self.buffer, ", ")?;
                     }
+                    self.format_column(expr)?;
                 }
+                write!(self.buffer, ")")?;
+            }
+        }
+        match &cmd.source_query {
+            // Derived table: get (get ...) as alias
+            Some(derived) if matches!(cmd.action, Action::Get) => {
+                writeln!(self.buffer, " (")?;
+                self.indent_level += 1;
+                self.indent()?;
+                self.visit_cmd(derived)?;
                 self.indent_level -= 1;
+                self.indent()?;
+                writeln!(self.buffer, ") as {}", table)?;
             }
+            _ => writeln!(self.buffer, " {}", table)?,
         }
 
+        // Clauses follow the order the parser expects them in
+
         // Joins
         for join in &cmd.joins {
             self.indent()?;
@@ -110,6 +187,87 @@ impl Formatter {
             writeln!(self.buffer)?;
         }
 
+        // DELETE ... USING
+        if !cmd.using_tables.is_empty() {
+            self.indent()?;
+            writeln!(self.buffer, "using {}", cmd.using_tables.join(", "))?;
+        }
+
+        let payload = cmd
+            .cages
+            .iter()
+            .find(|c| matches!(c.kind, CageKind::Payload));
+
+        // UPDATE assignments: values col = val, ...
+        if let Some(cage) = payload
+            && matches!(cmd.action, Action::Set)
+        {
+            self.indent()?;
+            write!(self.buffer, "values ")?;
+            for (i, cond) in cage.conditions.iter().enumerate() {
+                if i > 0 {
+                    write!(self.buffer, ", ")?;
+                }
+                write!(self.buffer, "{} = ", cond.left)?;
+                self.format_value(&cond.value)?;
+            }
+            writeln!(self.buffer)?;
+        }
+
+        // UPDATE ... FROM
+        if !cmd.from_tables.is_empty() {
+            self.indent()?;
+            writeln!(self.buffer, "from {}", cmd.from_tables.join(", "))?;
+        }
+
+        // "get table" is the same as "get table fields *"
+        let star_only = cmd.columns.is_empty()
+            || (cmd.columns.len() == 1 && matches!(cmd.columns[0], Expr::Star));
+        if !star_only {
+            self.indent()?;
+            writeln!(self.buffer, "fields")?;
+            self.indent_level += 1;
+            for (i, col) in cmd.columns.iter().enumerate() {
+                self.indent()?;
+                self.format_column(col)?;
+                if i < cmd.columns.len() - 1 {
+                    writeln!(self.buffer, ",")?;
+                } else {
+                    writeln!(self.buffer)?;
+                }
+            }
+            self.indent_level -= 1;
+        }
+
+        // INSERT ... SELECT source
+        if let Some(ref source) = cmd.source_query
+            && matches!(cmd.action, Action::Add)
+        {
+            self.indent()?;
+            writeln!(self.buffer, "from (")?;
+            self.indent_level += 1;
+            self.indent()?;
+            self.visit_cmd(source)?;
+            self.indent_level -= 1;
+            self.indent()?;
+            writeln!(self.buffer, ")")?;
+        }
+
+        // INSERT values: values v1, v2, ...
+        if let Some(cage) = payload
+            && matches!(cmd.action, Action::Add)
+        {
+            self.indent()?;
+            write!(self.buffer, "values ")?;
+            for (i, cond) in cage.conditions.iter().enumerate() {
+                if i > 0 {
+                    write!(self.buffer, ", ")?;
+                }
+                self.format_value(&cond.value)?;
+            }
+            writeln!(self.buffer)?;
+        }
+
         // Where (Filter Cages)
         let filters: Vec<&Cage> = cmd
             .cages
@@ -117,19 +275,48 @@ impl Formatter {
             .filter(|c| matches!(c.kind, CageKind::Filter))
             .collect();
         if !filters.is_empty() {
-            // We need to merge them or print them?
-            // Proposal says: "where rn = 1"
             self.indent()?;
             write!(self.buffer, "where ")?;
             for (i, cage) in filters.iter().enumerate() {
                 if i > 0 {
-                    write!(self.buffer, " and ")?; // Assuming AND between cages for now
+                    write!(self.buffer, " and ")?; // Cages are ANDed together
                 }
                 self.format_conditions(&cage.conditions, cage.logical_op)?;
             }
             writeln!(self.buffer)?;
         }
 
+        if !cmd.having.is_empty() {
+            self.indent()?;
+            write!(self.buffer, "having ")?;
+            self.format_conditions(&cmd.having, LogicalOp::And)?;
+            writeln!(self.buffer)?;
+        }
+
+        if let Some(ref on_conflict) = cmd.on_conflict {
+            self.indent()?;
+            write!(self.buffer, "conflict ({}) ", on_conflict.columns.join(", "))?;
+            if !on_conflict.target_where.is_empty() {
+                write!(self.buffer, "where ")?;
+                self.format_conditions(&on_conflict.target_where, LogicalOp::And)?;
+                write!(self.buffer, " ")?;
+            }
+            match &on_conflict.action {
+                ConflictAction::DoNothing => writeln!(self.buffer, "nothing")?,
+                ConflictAction::DoUpdate { assignments } => {
+                    write!(self.buffer, "update ")?;
+                    for (i, (col, expr)) in assignments.iter().enumerate() {
+                        if i > 0 {
+                            write!(self.buffer, ", ")?;
+                        }
+                        write!(self.buffer, "{} = ", col)?;
+                        self.format_column(expr)?;
+                    }
+                    writeln!(self.buffer)?;
+                }
+            }
+        }
+
         // Order By (Sort Cages)
         let sorts: Vec<&Cage> = cmd
             .cages
@@ -163,6 +350,10 @@ impl Formatter {
                     self.indent()?;
                     writeln!(self.buffer, "limit {}", n)?;
                 }
+                CageKind::LimitAll => {
+                    self.indent()?;
+                    writeln!(self.buffer, "limit all")?;
+                }
                 CageKind::Offset(n) => {
                     self.indent()?;
                     writeln!(self.buffer, "offset {}", n)?;
@@ -171,7 +362,12 @@ impl Formatter {
             }
         }
 
-        // self.indent_level -= 1; // Removed matching decrement
+        if let Some((count, with_ties)) = cmd.fetch {
+            self.indent()?;
+            let tail = if with_ties { "with ties" } else { "only" };
+            writeln!(self.buffer, "fetch first {} rows {}", count, tail)?;
+        }
+
         Ok(())
     }
 
@@ -182,9 +378,12 @@ impl Formatter {
             Expr::Aliased { name, alias } => write!(self.buffer, "{} as {}", name, alias)?,
             Expr::Aggregate {
                 col,
+                arg,
                 func,
                 distinct,
                 filter,
+                delimiter,
+                order_by,
                 alias,
             } => {
                 let func_name = match func {
@@ -200,11 +399,30 @@ impl Formatter {
                     crate::ast::AggregateFunc::BoolAnd => "bool_and",
                     crate::ast::AggregateFunc::BoolOr => "bool_or",
                 };
+                write!(self.buffer, "{}(", func_name)?;
                 if *distinct {
-                    write!(self.buffer, "{}(distinct {})", func_name, col)?;
-                } else {
-                    write!(self.buffer, "{}({})", func_name, col)?;
+                    write!(self.buffer, "distinct ")?;
+                }
+                match arg {
+                    Some(arg) => self.format_column(arg)?,
+                    None => write!(self.buffer, "{}", col)?,
+                }
+                if let Some(sep) = delimiter {
+                    write!(self.buffer, ", '{}'", sep.replace('\'', "''"))?;
+                }
+                if let Some(order) = order_by
+                    && !order.is_empty()
+                {
+                    write!(self.buffer, " order by ")?;
+                    for (i, (c, o)) in order.iter().enumerate() {
+                        if i > 0 {
+                            write!(self.buffer, ", ")?;
+                        }
+                        write!(self.buffer, "{}", c)?;
+                        self.format_sort_order(*o)?;
+                    }
                 }
+                write!(self.buffer, ")")?;
                 if let Some(conditions) = filter {
                     write!(
                         self.buffer,
@@ -227,19 +445,46 @@ impl Formatter {
                     write!(self.buffer, " as {}", a)?;
                 }
             }
-            Expr::Window { name, func, params, partition, .. } => {
-                // Use Window function format: func(params) OVER (PARTITION BY ...)
+            Expr::Window { name, func, params, partition, order, frame } => {
+                // func(params) over (partition by ... order by ... rows between ...) as name
                 let params_str: Vec<String> = params.iter().map(|p| p.to_string()).collect();
-                write!(self.buffer, "{}({})", func, params_str.join(", "))?;
+                write!(self.buffer, "{}({}) over (", func, params_str.join(", "))?;
+                let mut sep = "";
                 if !partition.is_empty() {
-                    write!(self.buffer, " over (partition by {})", partition.join(", "))?;
+                    write!(self.buffer, "partition by {}", partition.join(", "))?;
+                    sep = " ";
                 }
-                write!(self.buffer, " as {}", name)?;
+                if !order.is_empty() {
+                    write!(self.buffer, "{}order by ", sep)?;
+                    for (i, cage) in order.iter().enumerate() {
+                        if i > 0 {
+                            write!(self.buffer, ", ")?;
+                        }
+                        if let (CageKind::Sort(sort), Some(cond)) = (&cage.kind, cage.conditions.first()) {
+                            write!(self.buffer, "{}", cond.left)?;
+                            self.format_sort_order(*sort)?;
+                        }
+                    }
+                    sep = " ";
+                }
+                if let Some(frame) = frame {
+                    let (unit, start, end) = match frame {
+                        WindowFrame::Rows { start, end } => ("rows", start, end),
+                        WindowFrame::Range { start, end } => ("range", start, end),
+                    };
+                    write!(self.buffer, "{}{} between ", sep, unit)?;
+                    self.format_frame_bound(start)?;
+                    write!(self.buffer, " and ")?;
+                    self.format_frame_bound(end)?;
+                }
+                write!(self.buffer, ") as {}", name)?;
             }
             Expr::Case { when_clauses, else_value, alias } => {
                 write!(self.buffer, "case")?;
                 for (cond, val) in when_clauses {
-                    write!(self.buffer, " when {} then {}", cond.left, val)?;
+                    write!(self.buffer, " when ")?;
+                    self.format_condition(cond)?;
+                    write!(self.buffer, " then {}", val)?;
                 }
                 if let Some(e) = else_value {
                     write!(self.buffer, " else {}", e)?;
@@ -293,7 +538,17 @@ impl Formatter {
             Expr::Def { name, data_type, constraints } => {
                 write!(self.buffer, "{}:{}", name, data_type)?;
                 for c in constraints {
-                    write!(self.buffer, "^{}", c)?;
+                    // The spellings `make` parses; the rest have no text form yet
+                    match c {
+                        Constraint::PrimaryKey => write!(self.buffer, ":pk")?,
+                        Constraint::Unique => write!(self.buffer, ":unique")?,
+                        Constraint::Nullable => write!(self.buffer, ":notnull")?,
+                        Constraint::Default(val) => write!(self.buffer, ":default={}", val)?,
+                        Constraint::Check(exprs) if exprs.len() == 1 => {
+                            write!(self.buffer, ":check={}", exprs[0])?
+                        }
+                        other => write!(self.buffer, "^{}", other)?,
+                    }
                 }
             }
             Expr::Mod { kind, col } => {
@@ -349,6 +604,7 @@ impl Formatter {
                 write!(self.buffer, ")")?;
                 if let Some(a) = alias { write!(self.buffer, " as {}", a)?; }
             }
+            Expr::Raw(sql) => write!(self.buffer, "raw!({})", sql)?,
         }
         Ok(())
     }
@@ -360,7 +616,14 @@ impl Formatter {
             crate::ast::JoinKind::Right => write!(self.buffer, "right join {}", join.table)?,
             crate::ast::JoinKind::Full => write!(self.buffer, "full join {}", join.table)?,
             crate::ast::JoinKind::Cross => write!(self.buffer, "cross join {}", join.table)?,
-            crate::ast::JoinKind::Lateral => write!(self.buffer, "lateral join {}", join.table)?,
+            crate::ast::JoinKind::Lateral => match &join.subquery {
+                Some(query) => {
+                    write!(self.buffer, "lateral join (")?;
+                    self.visit_cmd(query)?;
+                    write!(self.buffer, ") as {}", join.table)?;
+                }
+                None => write!(self.buffer, "lateral join {}", join.table)?,
+            },
         }
 
         if let Some(conditions) = &join.on
@@ -384,32 +647,73 @@ impl Formatter {
                     LogicalOp::Or => write!(self.buffer, " or ")?,
                 }
             }
+            self.format_condition(cond)?;
+        }
+        Ok(())
+    }
 
-            write!(self.buffer, "{}", cond.left)?;
-
-            match cond.op {
-                Operator::Eq => write!(self.buffer, " = ")?,
-                Operator::Ne => write!(self.buffer, " != ")?,
-                Operator::Gt => write!(self.buffer, " > ")?,
-                Operator::Gte => write!(self.buffer, " >= ")?,
-                Operator::Lt => write!(self.buffer, " < ")?,
-                Operator::Lte => write!(self.buffer, " <= ")?,
-                Operator::Fuzzy => write!(self.buffer, " ~ ")?, // ILIKE
-                Operator::In => write!(self.buffer, " in ")?,
-                Operator::NotIn => write!(self.buffer, " not in ")?,
-                Operator::IsNull => write!(self.buffer, " is null")?,
-                Operator::IsNotNull => write!(self.buffer, " is not null")?,
-                Operator::Contains => write!(self.buffer, " @> ")?,
-                Operator::KeyExists => write!(self.buffer, " ? ")?,
-                _ => write!(self.buffer, " {:?} ", cond.op)?,
+    fn format_condition(&mut self, cond: &Condition) -> Result {
+        // Unary subquery operators ignore the left side
+        match cond.op {
+            Operator::Exists | Operator::NotExists => {
+                if matches!(cond.op, Operator::NotExists) {
+                    write!(self.buffer, "not ")?;
+                }
+                write!(self.buffer, "exists ")?;
+                return self.format_value(&cond.value);
             }
+            _ => {}
+        }
 
-            // Some operators like IsNull don't need a value printed
-            if !matches!(cond.op, Operator::IsNull | Operator::IsNotNull) {
-                self.format_value(&cond.value)?;
+        write!(self.buffer, "{}", cond.left)?;
+
+        let op = match cond.op {
+            Operator::Eq => "=",
+            Operator::Ne => "!=",
+            Operator::Gt => ">",
+            Operator::Gte => ">=",
+            Operator::Lt => "<",
+            Operator::Lte => "<=",
+            Operator::Fuzzy => "~", // ILIKE
+            Operator::FuzzyEscaped => "~=",
+            Operator::Regex => "~r",
+            Operator::RegexI => "~*",
+            Operator::Like => "like",
+            Operator::NotLike => "not like",
+            Operator::ILike => "ilike",
+            Operator::NotILike => "not ilike",
+            Operator::In => "in",
+            Operator::NotIn => "not in",
+            Operator::EqAny => "= any",
+            Operator::Between => "between",
+            Operator::NotBetween => "not between",
+            Operator::IsNull => return write!(self.buffer, " is null"),
+            Operator::IsNotNull => return write!(self.buffer, " is not null"),
+            Operator::Contains => "@>",
+            Operator::KeyExists => "?",
+            _ => return write!(self.buffer, " {} {}", cond.op.sql_symbol(), cond.value),
+        };
+        write!(self.buffer, " {} ", op)?;
+
+        match (&cond.op, &cond.value) {
+            // [min, max]
+            (Operator::Between | Operator::NotBetween, Value::Array(vals)) if vals.len() == 2 => {
+                self.format_value(&vals[0])?;
+                write!(self.buffer, " and ")?;
+                self.format_value(&vals[1])
             }
+            (Operator::In | Operator::NotIn | Operator::EqAny, Value::Array(vals)) => {
+                write!(self.buffer, "(")?;
+                for (i, v) in vals.iter().enumerate() {
+                    if i > 0 {
+                        write!(self.buffer, ", ")?;
+                    }
+                    self.format_value(v)?;
+                }
+                write!(self.buffer, ")")
+            }
+            (_, value) => self.format_value(value),
         }
-        Ok(())
     }
 
     fn format_value(&mut self, val: &Value) -> Result {
@@ -418,10 +722,12 @@ impl Formatter {
             Value::Bool(b) => write!(self.buffer, "{}", b)?,
             Value::Int(n) => write!(self.buffer, "{}", n)?,
             Value::Float(n) => write!(self.buffer, "{}", n)?,
+            Value::Decimal(d) if is_decimal_literal(d) => write!(self.buffer, "{}n", d)?,
+            Value::Decimal(d) => write!(self.buffer, "'{}'", d.replace('\'', "''"))?,
             Value::Param(n) => write!(self.buffer, "${}", n)?,
             Value::Function(f) => write!(self.buffer, "{}", f)?,
             Value::Column(c) => write!(self.buffer, "{}", c)?,
-            Value::String(s) => write!(self.buffer, "'{}'", s)?, // Simple quoting, might need escaping
+            Value::String(s) => write!(self.buffer, "'{}'", s.replace('\'', "''"))?,
             // Value::Date and Value::Interval are not in AST, likely Strings
             // Value::Date(d) => write!(self.buffer, "'{}'", d)?,
             // Value::Interval(i) => write!(self.buffer, "interval '{}'", i)?,
@@ -438,6 +744,7 @@ impl Formatter {
             Value::NamedParam(name) => write!(self.buffer, ":{}", name)?,
             Value::Uuid(u) => write!(self.buffer, "'{}'", u)?,
             Value::NullUuid => write!(self.buffer, "null")?,
+            Value::Default => write!(self.buffer, "default")?,
             Value::Interval { amount, unit } => write!(self.buffer, "interval '{} {}'", amount, unit)?,
             Value::Timestamp(ts) => write!(self.buffer, "'{}'", ts)?,
             Value::Bytes(bytes) => {
@@ -450,6 +757,13 @@ impl Formatter {
                 self.visit_cmd(cmd)?;
                 write!(self.buffer, ")")?;
             }
+            Value::AnySubquery(cmd) | Value::AllSubquery(cmd) => {
+                let quantifier = if matches!(val, Value::AnySubquery(_)) { "any" } else { "all" };
+                write!(self.buffer, "{} (", quantifier)?;
+                self.visit_cmd(cmd)?;
+                write!(self.buffer, ")")?;
+            }
+            Value::Expr(expr) if matches!(**expr, Expr::Raw(_)) => self.format_column(expr)?,
             Value::Expr(expr) => write!(self.buffer, "{}", expr)?,
             Value::Vector(v) => {
                 write!(self.buffer, "[")?;
@@ -464,6 +778,16 @@ impl Formatter {
         Ok(())
     }
 
+    fn format_frame_bound(&mut self, bound: &FrameBound) -> Result {
+        match bound {
+            FrameBound::UnboundedPreceding => write!(self.buffer, "unbounded preceding"),
+            FrameBound::Preceding(n) => write!(self.buffer, "{} preceding", n),
+            FrameBound::CurrentRow => write!(self.buffer, "current row"),
+            FrameBound::Following(n) => write!(self.buffer, "{} following", n),
+            FrameBound::UnboundedFollowing => write!(self.buffer, "unbounded following"),
+        }
+    }
+
     fn format_sort_order(&mut self, order: SortOrder) -> Result {
         match order {
             SortOrder::Asc => {}
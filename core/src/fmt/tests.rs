@@ -38,11 +38,11 @@ fields
 #[test]
 fn test_fmt_complex_query() {
     // get whatsapp_contacts
+    // join message_stats
     // fields
     //   id
     //   phone_number
     // where rn = 1
-    // join message_stats
     // order by created_at desc
 
     let mut cmd = Qail::get("whatsapp_contacts");
@@ -57,6 +57,7 @@ fn test_fmt_complex_query() {
         kind: JoinKind::Inner,
         on: None, // Implicit join for now to match proposal simplification or explicit? Proposal had explicit ON in example 4.
         on_true: false,
+        subquery: None,
     }];
 
     // Proposal example 4: join message_stats on ...
@@ -98,11 +99,11 @@ fn test_fmt_complex_query() {
 
     let expected = r#"
 get whatsapp_contacts
+join message_stats
+  on phone_number = null
 fields
   id,
   phone_number
-join message_stats
-  on phone_number = null
 where rn = 1
 order by
   created_at desc
@@ -123,16 +124,109 @@ fn test_fmt_cte() {
         base_query: Box::new(Qail::get("table")),
         recursive_query: None,
         source_table: None,
+        materialized: None,
     });
 
     let formatter = Formatter::new();
     let output = formatter.format(&cmd).unwrap();
 
     let expected = r#"
-with cte = 
+with cte as (
   get table
-
+)
 get cte
 "#;
     assert_eq!(output.trim(), expected.trim());
 }
+
+#[test]
+fn test_fmt_escapes_single_quotes() {
+    let cmd = Qail::get("users").filter("name", Operator::Eq, "O'Brien");
+    let output = Formatter::new().format(&cmd).unwrap();
+    assert!(output.contains("where name = 'O''Brien'"), "{}", output);
+}
+
+#[test]
+fn test_fmt_insert_select_round_trip() {
+    let cmd = crate::parser::parse("add archive from (get orders where created < $1)").unwrap();
+    let output = Formatter::new().format(&cmd).unwrap();
+    assert!(output.contains("from (\n  get orders\n"), "{}", output);
+
+    let reparsed = crate::parser::parse(&output).unwrap();
+    assert_eq!(reparsed.source_query, cmd.source_query);
+}
+
+#[test]
+fn test_fmt_decimal_round_trip() {
+    let cmd = crate::parser::parse("get products fields id where price = 19.99n").unwrap();
+    let output = Formatter::new().format(&cmd).unwrap();
+    assert!(output.contains("price = 19.99n"), "{}", output);
+
+    let reparsed = crate::parser::parse(&output).unwrap();
+    assert_eq!(reparsed.cages, cmd.cages);
+}
+
+#[test]
+fn test_to_qail_round_trip() {
+    let queries = [
+        "get users",
+        "get users fields id, email where active = true",
+        "get distinct users fields email",
+        "get tenant_42.users fields id",
+        "get users fields id, name as full_name where age >= 18 and name ~ 'bob' order by name asc, id desc limit 10 offset 20",
+        "get users where id in (1, 2, 3) and status not in ('banned')",
+        "get users where deleted_at is null and email is not null",
+        "get users where age between 18 and 65",
+        "get users where name like 'A%' and email not ilike '%spam%'",
+        "get users where name ~r '^A' and bio ~* 'rust'",
+        "get users where exists (get orders where orders.user_id = users.id)",
+        "get users where id = any (get allowed fields user_id)",
+        "get users where id = any (1, 2, 3) and org = any :orgs",
+        "get users where id in (get allowed fields user_id)",
+        "get users fields count(*) as n, sum(total) as s",
+        "get orders fields user_id, array_agg(id order by created_at desc nulls last, id) as ids",
+        "get users fields string_agg(name, ', ' order by name) as names",
+        "get users fields array_agg(lower(name) order by name) as names",
+        "get users fields metadata->>'theme' as theme",
+        "get orders fields total::numeric(10,2) as amt where price::int > 100",
+        "get users fields case when age > 18 then 'adult' else 'minor' end as bracket",
+        "get users fields row_number() over (partition by dept order by salary desc) as rn",
+        "get users left join orders on users.id = orders.user_id fields users.id",
+        "get users where id = $1 and org = :org",
+        "get products where price = 19.99n",
+        "set users values name = 'bob', age = 30 where id = 1",
+        "set users values hits = hits + 1, total = (get orders fields sum(amount) where user_id = users.id) where id = 1",
+        "del users where id = 1",
+        "add users fields name, age values 'bob', 30",
+        "add archive from (get orders where created < $1)",
+        "get (get orders fields id, total where status = 'paid') as p fields id where total > 100",
+        "add users fields email values 'a' conflict (email) where deleted_at is null nothing",
+        "add counters fields id, n values 1, 5 conflict (id) update n = counters.n + excluded.n, hits = 1 + counters.hits",
+        "get orders fields status, count(*) as n having count(*) > 1",
+        "get users limit all offset 20",
+        "get scores order by points desc offset 10 fetch first 5 rows with ties",
+        "get users lateral join (get orders fields total where orders.user_id = users.id order by total desc limit 3) as recent fields users.name, recent.total",
+        "get posts fields id where tags && $1 and embedding <-> $2 and body @@ $3",
+        "with active as (get users where active = true) get active fields id",
+        "with a as materialized (get users), b (id) as not materialized (get orders fields user_id) get a",
+        "make users id:uuid:pk, email:text:unique:notnull, n:int:default=0 primary key (id, email) unique (n)",
+        "make? tenant_42.users id:uuid:pk",
+        "index idx_email on users email, id unique",
+        "index? idx_name on users name",
+        "truncate users restart identity cascade",
+        "begin",
+        "commit",
+        "rollback",
+    ];
+    let mut failures = Vec::new();
+    for q in queries {
+        let cmd = crate::parser::parse(q).unwrap_or_else(|e| panic!("{}: {}", q, e));
+        let qail = cmd.to_qail();
+        match crate::parser::parse(&qail) {
+            Ok(reparsed) if reparsed == cmd => {}
+            Ok(_) => failures.push(format!("{}\n  => {}\n  (different AST)", q, qail)),
+            Err(e) => failures.push(format!("{}\n  => {}\n  ({})", q, qail, e)),
+        }
+    }
+    assert!(failures.is_empty(), "round-trip failures:\n{}", failures.join("\n"));
+}
@@ -21,7 +21,7 @@ pub mod transpiler;
 pub mod typed;
 pub mod validator;
 
-pub use parser::parse;
+pub use parser::{parse, parse_cached, parse_many};
 
 /// Ergonomic alias for Qail - the primary query builder type.
 pub type Qail = ast::Qail;
@@ -0,0 +1,118 @@
+//! Multi-statement migration files
+//!
+//! A migration file holds QAIL statements ending at a `;` or a blank line,
+//! as in [`parse_many`](crate::parser::parse_many), so a statement may span
+//! several lines:
+//! ```text
+//! -- migration: 004_archive_orders
+//! make orders_archive id:uuid:pk, total:int;
+//! add orders_archive from (
+//!     get orders where created < '2024-01-01'
+//! );
+//! del orders where created < '2024-01-01'
+//! ```
+//! `--` and `/* */` comments (including the named-migration header) and
+//! lines starting with `#` are ignored. Runners execute `statements()` in a
+//! single transaction so a failing statement leaves the database untouched.
+
+use super::named_migration::parse_migration_meta;
+use crate::ast::Qail;
+use crate::error::{QailError, QailResult};
+
+/// A versioned migration made of QAIL statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// Version recorded once the migration is applied (e.g. "20260116120000")
+    pub version: String,
+    /// Raw file content
+    pub content: String,
+}
+
+impl Migration {
+    pub fn new(version: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            content: content.into(),
+        }
+    }
+
+    /// Name from the `-- migration:` header, falling back to the version.
+    pub fn name(&self) -> String {
+        parse_migration_meta(&self.content)
+            .map(|meta| meta.name)
+            .unwrap_or_else(|| self.version.clone())
+    }
+
+    /// Parse every statement in file order.
+    /// Errors name the 1-based line where the failing statement's error is.
+    pub fn statements(&self) -> QailResult<Vec<Qail>> {
+        // `#` lines become `--` comments; only line numbers are reported,
+        // so the extra byte per line does not matter
+        let source: String = self
+            .content
+            .lines()
+            .map(|line| match line.trim_start().strip_prefix('#') {
+                Some(rest) => format!("--{}\n", rest),
+                None => format!("{}\n", line),
+            })
+            .collect();
+
+        crate::parser::parse_many(&source)
+            .into_iter()
+            .map(|result| {
+                result.map_err(|e| match e {
+                    QailError::Parse { position, message, span } => {
+                        let line_no = source[..position.min(source.len())]
+                            .matches('\n')
+                            .count()
+                            + 1;
+                        QailError::Parse {
+                            position,
+                            message: format!("migration {} line {}: {}", self.version, line_no, message),
+                            span,
+                        }
+                    }
+                    other => other,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Action;
+
+    #[test]
+    fn test_migration_statements() {
+        let migration = Migration::new(
+            "004",
+            "-- migration: 004_archive\n\
+             make archive id:int:pk;\n\
+             \n\
+             # copy old rows\n\
+             add archive from (\n\
+                 get orders where id < 10\n\
+             )\n\
+             \n\
+             del orders where id < 10\n",
+        );
+
+        assert_eq!(migration.name(), "004_archive");
+        let actions: Vec<Action> = migration
+            .statements()
+            .unwrap()
+            .iter()
+            .map(|cmd| cmd.action)
+            .collect();
+        assert_eq!(actions, vec![Action::Make, Action::Add, Action::Del]);
+    }
+
+    #[test]
+    fn test_migration_statements_parse_error_line() {
+        let migration = Migration::new("005", "make t id:int;\n\nget orders\n  where ==\n");
+        let err = migration.statements().unwrap_err().to_string();
+        assert!(err.contains("line 4"), "{}", err);
+    }
+}
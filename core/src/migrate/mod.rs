@@ -19,6 +19,7 @@
 
 pub mod alter;
 pub mod diff;
+pub mod migration;
 pub mod named_migration;
 pub mod parser;
 pub mod schema;
@@ -26,6 +27,7 @@ pub mod types;
 
 pub use alter::{AlterOp, AlterTable, TableConstraint};
 pub use diff::diff_schemas;
+pub use migration::Migration;
 pub use named_migration::{MigrationMeta, parse_migration_meta, validate_dependencies};
 pub use parser::parse_qail;
 pub use schema::{
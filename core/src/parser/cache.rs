@@ -0,0 +1,100 @@
+//! Opt-in LRU parse cache.
+//!
+//! Macros and the gateway parse the same QAIL strings over and over.
+//! `parse_cached` keeps a small per-thread LRU of parsed ASTs keyed by the
+//! input text so hot loops skip lexing/parsing entirely. Nothing is cached
+//! unless `parse_cached` is called, so plain `parse` has no memory overhead.
+//!
+//! ```ignore
+//! use qail_core::parser::parse_cached;
+//! let cmd = parse_cached("get users fields id where active = true")?;
+//! ```
+
+use super::parse;
+use crate::ast::Qail;
+use crate::error::QailResult;
+use lru::LruCache;
+use std::cell::RefCell;
+use std::num::NonZeroUsize;
+
+/// Default number of parsed queries kept per thread.
+pub const DEFAULT_PARSE_CACHE_CAPACITY: usize = 256;
+
+/// Hit/miss counters for the current thread's parse cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+struct ParseCache {
+    entries: LruCache<String, Qail>,
+    stats: ParseCacheStats,
+}
+
+impl ParseCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: LruCache::new(NonZeroUsize::new(capacity.max(1)).unwrap()),
+            stats: ParseCacheStats::default(),
+        }
+    }
+}
+
+thread_local! {
+    static PARSE_CACHE: RefCell<ParseCache> =
+        RefCell::new(ParseCache::new(DEFAULT_PARSE_CACHE_CAPACITY));
+}
+
+/// Parse a QAIL query, reusing a previously parsed AST for identical input.
+///
+/// Returns a clone of the cached AST. Parse errors are not cached.
+pub fn parse_cached(input: &str) -> QailResult<Qail> {
+    let cached = PARSE_CACHE.with(|c| {
+        let mut cache = c.borrow_mut();
+        let hit = cache.entries.get(input).cloned();
+        if hit.is_some() {
+            cache.stats.hits += 1;
+        } else {
+            cache.stats.misses += 1;
+        }
+        hit
+    });
+    if let Some(cmd) = cached {
+        return Ok(cmd);
+    }
+
+    let cmd = parse(input)?;
+    PARSE_CACHE.with(|c| {
+        c.borrow_mut().entries.put(input.to_string(), cmd.clone());
+    });
+    Ok(cmd)
+}
+
+/// Resize the current thread's parse cache, evicting the oldest entries if needed.
+pub fn set_parse_cache_capacity(capacity: usize) {
+    PARSE_CACHE.with(|c| {
+        c.borrow_mut()
+            .entries
+            .resize(NonZeroUsize::new(capacity.max(1)).unwrap());
+    });
+}
+
+/// Drop all cached ASTs and reset the hit/miss counters for the current thread.
+pub fn clear_parse_cache() {
+    PARSE_CACHE.with(|c| {
+        let mut cache = c.borrow_mut();
+        cache.entries.clear();
+        cache.stats = ParseCacheStats::default();
+    });
+}
+
+/// Hit/miss counters for the current thread's parse cache.
+pub fn parse_cache_stats() -> ParseCacheStats {
+    PARSE_CACHE.with(|c| c.borrow().stats)
+}
+
+/// Number of ASTs currently cached on this thread.
+pub fn parse_cache_len() -> usize {
+    PARSE_CACHE.with(|c| c.borrow().entries.len())
+}
@@ -4,14 +4,46 @@ use nom::{
     IResult, Parser,
     branch::alt,
     bytes::complete::{tag, tag_no_case, take_while1},
-    character::complete::{char, digit1, multispace1},
-    combinator::{map, opt, recognize, value},
+    character::complete::{char, digit1, multispace1, satisfy},
+    combinator::{map, not, opt, recognize, value},
+    multi::many1,
     sequence::{delimited, preceded},
 };
 
 /// Parse checking identifier (table name, column name, or qualified name like table.column)
+/// Bare identifiers accept Unicode letters; `"..."` parts take any characters but `"`
+/// and keep their quotes (`"My Schema".users`).
 pub fn parse_identifier(input: &str) -> IResult<&str, &str> {
-    take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '.').parse(input)
+    recognize(many1(alt((
+        parse_quoted_identifier,
+        take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '.'),
+    ))))
+    .parse(input)
+}
+
+/// Parse a double-quoted identifier: "Prénom Nom".
+/// The quotes are kept so the transpiler always quotes it and never splits it on `.`.
+pub fn parse_quoted_identifier(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(char('"'), take_while1(|c: char| c != '"'), char('"'))).parse(input)
+}
+
+/// Split a schema-qualified table name (`tenant_42.users`) into schema and table.
+/// Only the last dot outside double quotes separates the schema; unqualified names
+/// have no schema.
+pub fn split_schema(name: &str) -> (Option<&str>, &str) {
+    let mut in_quotes = false;
+    let mut split = None;
+    for (i, c) in name.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '.' if !in_quotes => split = Some(i),
+            _ => {}
+        }
+    }
+    match split {
+        Some(i) if i > 0 && i + 1 < name.len() => (Some(&name[..i]), &name[i + 1..]),
+        _ => (None, name),
+    }
 }
 
 /// Parse interval shorthand: 24h, 7d, 1w, 30m, 6mo, 1y
@@ -66,14 +98,16 @@ pub fn parse_value(input: &str) -> IResult<&str, Value> {
             ),
             |s: &str| Value::String(s.to_string()),
         ),
-        // String (single quoted) - allow empty strings
+        // String (single quoted) - allow empty strings, '' escapes a quote
+        parse_single_quoted_string,
+        // Exact decimal: 19.99n -> NUMERIC, never goes through f64
         map(
-            delimited(
-                char('\''),
-                nom::bytes::complete::take_while(|c| c != '\''),
-                char('\''),
+            (
+                recognize((opt(char('-')), digit1, opt((char('.'), digit1)))),
+                char('n'),
+                not(satisfy(|c: char| c.is_alphanumeric() || c == '_')),
             ),
-            |s: &str| Value::String(s.to_string()),
+            |(s, _, _): (&str, char, ())| Value::Decimal(s.to_string()),
         ),
         // Float (must check before int)
         map(
@@ -90,6 +124,29 @@ pub fn parse_value(input: &str) -> IResult<&str, Value> {
     .parse(input)
 }
 
+/// Parse single-quoted string: 'O''Brien' -> O'Brien
+fn parse_single_quoted_string(input: &str) -> IResult<&str, Value> {
+    let (rest, _) = char('\'').parse(input)?;
+    let mut out = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c != '\'' {
+            out.push(c);
+            continue;
+        }
+        if rest[i + 1..].starts_with('\'') {
+            out.push('\'');
+            chars.next();
+            continue;
+        }
+        return Ok((&rest[i + 1..], Value::String(out)));
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Char,
+    )))
+}
+
 /// Parse triple-quoted multi-line string: '''content''' or """content"""
 fn parse_triple_quoted_string(input: &str) -> IResult<&str, Value> {
     alt((
@@ -181,6 +238,33 @@ fn parse_json_literal(input: &str) -> IResult<&str, Value> {
 
 /// Parse comparison operator
 pub fn parse_operator(input: &str) -> IResult<&str, Operator> {
+    alt((parse_symbolic_operator, parse_keyword_operator)).parse(input)
+}
+
+/// PostgreSQL symbolic operators: `@>`, `<@`, `&&` and the allowlisted
+/// [`CUSTOM_OPERATORS`] (`<->`, `@@`, ...). Tried before `<`, `>` and `=`.
+fn parse_symbolic_operator(input: &str) -> IResult<&str, Operator> {
+    let builtin = [
+        ("@>", Operator::Contains),
+        ("<@", Operator::ContainedBy),
+        ("&&", Operator::Overlaps),
+    ];
+    for (symbol, op) in builtin {
+        if let Some(rest) = input.strip_prefix(symbol) {
+            return Ok((rest, op));
+        }
+    }
+    for symbol in CUSTOM_OPERATORS {
+        if let Some(rest) = input.strip_prefix(symbol)
+            && let Some(op) = CustomOp::new(symbol)
+        {
+            return Ok((rest, Operator::Custom(op)));
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Tag)))
+}
+
+fn parse_keyword_operator(input: &str) -> IResult<&str, Operator> {
     alt((
         // Multi-char operators first
         value(Operator::NotBetween, tag_no_case("not between")),
@@ -201,6 +285,14 @@ pub fn parse_operator(input: &str) -> IResult<&str, Operator> {
         value(Operator::Eq, tag("=")),
         value(Operator::Gt, tag(">")),
         value(Operator::Lt, tag("<")),
+        // `~*` / `~r` are POSIX regex; `~=` is a wildcard-escaped fuzzy match;
+        // bare `~` stays the ILIKE fuzzy match
+        value(Operator::RegexI, tag("~*")),
+        value(
+            Operator::Regex,
+            (tag("~r"), not(satisfy(|c: char| c.is_alphanumeric() || c == '_'))),
+        ),
+        value(Operator::FuzzyEscaped, tag("~=")),
         value(Operator::Fuzzy, tag("~")),
     ))
     .parse(input)
@@ -251,6 +343,7 @@ pub fn parse_txn_command(input: &str) -> IResult<&str, Qail> {
         Qail {
             action,
             table: String::new(),
+            schema: None,
             columns: vec![],
             joins: vec![],
             cages: vec![],
@@ -276,6 +369,10 @@ pub fn parse_txn_command(input: &str) -> IResult<&str, Qail> {
             overriding: None,
             sample: None,
             only_table: false,
+            if_not_exists: false,
+            restart_identity: false,
+            cascade: false,
+            tags: vec![],
             vector: None,
             score_threshold: None,
             vector_name: None,
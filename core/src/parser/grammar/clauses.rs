@@ -1,5 +1,5 @@
 use super::base::{parse_identifier, parse_operator, parse_value};
-use super::expressions::parse_expression;
+use super::expressions::{check_raw_alias, parse_expression, parse_json_or_ident};
 use crate::ast::*;
 use nom::{
     IResult, Parser,
@@ -69,6 +69,7 @@ pub fn parse_single_column(input: &str) -> IResult<&str, Expr> {
         opt(preceded((tag_no_case("as"), multispace1), parse_identifier)).parse(input)?;
 
     if let Some(a) = alias {
+        check_raw_alias(&expr, a)?;
         // Wrap whatever expr we found in Aliased?
         // Wait, Expr::Aliased has { name: String, alias: String }.
         // This suggests only named columns can be aliased?
@@ -111,15 +112,21 @@ pub fn parse_single_column(input: &str) -> IResult<&str, Expr> {
             },
             Expr::Aggregate {
                 col,
+                arg,
                 func,
                 distinct,
                 filter,
+                delimiter,
+                order_by,
                 ..
             } => Expr::Aggregate {
                 col,
+                arg,
                 func,
                 distinct,
                 filter,
+                delimiter,
+                order_by,
                 alias: Some(a.to_string()),
             },
             Expr::Cast {
@@ -131,6 +138,11 @@ pub fn parse_single_column(input: &str) -> IResult<&str, Expr> {
                 target_type,
                 alias: Some(a.to_string()),
             },
+            Expr::Subquery { query, .. } => Expr::Subquery {
+                query,
+                alias: Some(a.to_string()),
+            },
+            Expr::Raw(sql) => Expr::Raw(format!("{} AS {}", sql, a)),
             _ => expr,
         };
     }
@@ -227,7 +239,7 @@ pub fn parse_condition(input: &str) -> IResult<&str, Condition> {
     let (input, left_expr) = parse_expression(input)?;
     let (input, _) = multispace0(input)?;
 
-    let (input, op) = parse_operator(input)?;
+    let (input, mut op) = parse_operator(input)?;
     let (input, _) = multispace0(input)?;
 
     let (input, value) = if matches!(op, Operator::IsNull | Operator::IsNotNull) {
@@ -241,13 +253,22 @@ pub fn parse_condition(input: &str) -> IResult<&str, Condition> {
         // Store as array with 2 elements [min, max]
         (input, Value::Array(vec![min_val, max_val]))
     } else if matches!(op, Operator::In | Operator::NotIn) {
-        let (input, _) = char('(').parse(input)?;
-        let (input, _) = multispace0(input)?;
-        let (input, values) =
-            separated_list0((multispace0, char(','), multispace0), parse_value).parse(input)?;
-        let (input, _) = multispace0(input)?;
-        let (input, _) = char(')').parse(input)?;
-        (input, Value::Array(values))
+        parse_in_operand(input)?
+    } else if let Ok((i, raw)) = super::expressions::parse_raw(input) {
+        // Verbatim SQL: created_at > raw!(now() at time zone 'utc')
+        (i, Value::Expr(Box::new(raw)))
+    } else if let Ok((i, quantified)) = parse_quantified_subquery(input) {
+        // id = any (get ...), amount > all (get ...)
+        (i, quantified)
+    } else if op == Operator::Eq
+        && let Ok((i, array)) = parse_any_array(input)
+    {
+        // id = any :ids, id = any (1, 2, 3): one array parameter
+        op = Operator::EqAny;
+        (i, array)
+    } else if let Ok((i, cast @ Expr::Cast { .. })) = parse_json_or_ident(input) {
+        // Casted right-hand side: created_at > '2024-01-01'::date
+        (i, Value::Expr(Box::new(cast)))
     } else if let Ok((i, val)) = parse_value(input) {
         (i, val)
     } else {
@@ -266,7 +287,58 @@ pub fn parse_condition(input: &str) -> IResult<&str, Condition> {
     ))
 }
 
-/// Parse: order by col [asc|desc], col2 [asc|desc]
+/// Parse: any (get ...) | all (get ...) -> Value::AnySubquery / Value::AllSubquery
+fn parse_quantified_subquery(input: &str) -> IResult<&str, Value> {
+    let (input, is_any) = alt((
+        value(true, tag_no_case("any")),
+        value(false, tag_no_case("all")),
+    ))
+    .parse(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char('(').parse(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, subquery) = super::parse_root(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char(')').parse(input)?;
+    let subquery = Box::new(subquery);
+    Ok((
+        input,
+        if is_any {
+            Value::AnySubquery(subquery)
+        } else {
+            Value::AllSubquery(subquery)
+        },
+    ))
+}
+
+/// Parse: any :ids | any $1 | any (1, 2, 3) -> the array operand of `Operator::EqAny`
+fn parse_any_array(input: &str) -> IResult<&str, Value> {
+    let (input, _) = tag_no_case("any").parse(input)?;
+    alt((
+        preceded(multispace0, parse_in_operand),
+        preceded(multispace1, parse_value),
+    ))
+    .parse(input)
+}
+
+/// Parse the right-hand side of `in` / `not in`: either a subquery,
+/// `(get ...)`, or a literal list, `(1, 2, 3)`.
+pub fn parse_in_operand(input: &str) -> IResult<&str, Value> {
+    let (input, _) = char('(').parse(input)?;
+    let (input, _) = multispace0(input)?;
+    if let Ok((input, subquery)) = super::parse_root(input) {
+        let (input, _) = multispace0(input)?;
+        let (input, _) = char(')').parse(input)?;
+        return Ok((input, Value::Subquery(Box::new(subquery))));
+    }
+    let (input, values) =
+        separated_list0((multispace0, char(','), multispace0), parse_value).parse(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char(')').parse(input)?;
+    Ok((input, Value::Array(values)))
+}
+
+/// Parse: order by col [asc|desc] [nulls first|last], col2 ...
 pub fn parse_order_by_clause(input: &str) -> IResult<&str, Vec<Cage>> {
     let (input, _) = tag_no_case("order").parse(input)?;
     let (input, _) = multispace1(input)?;
@@ -279,21 +351,16 @@ pub fn parse_order_by_clause(input: &str) -> IResult<&str, Vec<Cage>> {
     Ok((input, sorts))
 }
 
-/// Parse single sort column: col [asc|desc]
+/// Parse single sort column: col [asc|desc] [nulls first|last]
 pub fn parse_sort_column(input: &str) -> IResult<&str, Cage> {
     let (input, expr) = parse_expression(input)?;
     let (input, _) = multispace0(input)?;
-
-    let (input, order) = opt(alt((
-        value(SortOrder::Desc, tag_no_case("desc")),
-        value(SortOrder::Asc, tag_no_case("asc")),
-    )))
-    .parse(input)?;
+    let (input, order) = parse_sort_order(input)?;
 
     Ok((
         input,
         Cage {
-            kind: CageKind::Sort(order.unwrap_or(SortOrder::Asc)),
+            kind: CageKind::Sort(order),
             conditions: vec![Condition {
                 left: expr,
                 op: Operator::Eq,
@@ -305,16 +372,49 @@ pub fn parse_sort_column(input: &str) -> IResult<&str, Cage> {
     ))
 }
 
-/// Parse: limit N
+/// Parse an optional `asc|desc` followed by an optional `nulls first|last`.
+/// Defaults to `SortOrder::Asc`.
+pub fn parse_sort_order(input: &str) -> IResult<&str, SortOrder> {
+    let (input, desc) = opt(alt((
+        value(true, tag_no_case("desc")),
+        value(false, tag_no_case("asc")),
+    )))
+    .parse(input)?;
+
+    let (input, nulls_first) = opt(preceded(
+        (multispace0, tag_no_case("nulls"), multispace1),
+        alt((
+            value(true, tag_no_case("first")),
+            value(false, tag_no_case("last")),
+        )),
+    ))
+    .parse(input)?;
+
+    let order = match (desc.unwrap_or(false), nulls_first) {
+        (false, None) => SortOrder::Asc,
+        (true, None) => SortOrder::Desc,
+        (false, Some(true)) => SortOrder::AscNullsFirst,
+        (false, Some(false)) => SortOrder::AscNullsLast,
+        (true, Some(true)) => SortOrder::DescNullsFirst,
+        (true, Some(false)) => SortOrder::DescNullsLast,
+    };
+    Ok((input, order))
+}
+
+/// Parse: limit N | limit all
 pub fn parse_limit_clause(input: &str) -> IResult<&str, Cage> {
     let (input, _) = tag_no_case("limit").parse(input)?;
     let (input, _) = multispace1(input)?;
-    let (input, n) = digit1(input)?;
+    let (input, kind) = alt((
+        value(CageKind::LimitAll, tag_no_case("all")),
+        map(digit1, |n: &str| CageKind::Limit(n.parse().unwrap_or(0))),
+    ))
+    .parse(input)?;
 
     Ok((
         input,
         Cage {
-            kind: CageKind::Limit(n.parse().unwrap_or(0)),
+            kind,
             conditions: vec![],
             logical_op: LogicalOp::And,
         },
@@ -337,6 +437,25 @@ pub fn parse_offset_clause(input: &str) -> IResult<&str, Cage> {
     ))
 }
 
+/// Parse: fetch first|next N row|rows only|with ties -> (N, with_ties)
+pub fn parse_fetch_clause(input: &str) -> IResult<&str, (u64, bool)> {
+    let (input, _) = tag_no_case("fetch").parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, _) = alt((tag_no_case("first"), tag_no_case("next"))).parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, n) = digit1(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, _) = alt((tag_no_case("rows"), tag_no_case("row"))).parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, with_ties) = alt((
+        value(false, tag_no_case("only")),
+        value(true, (tag_no_case("with"), multispace1, tag_no_case("ties"))),
+    ))
+    .parse(input)?;
+
+    Ok((input, (n.parse().unwrap_or(0), with_ties)))
+}
+
 /// Parse: DISTINCT ON (col1, col2, ...)
 /// Returns list of column names for DISTINCT ON
 pub fn parse_distinct_on(input: &str) -> IResult<&str, Vec<String>> {
@@ -2,6 +2,7 @@ use super::base::parse_identifier;
 use crate::ast::*;
 use nom::{
     IResult, Parser,
+    branch::alt,
     bytes::complete::tag_no_case,
     character::complete::{char, multispace0, multispace1},
     combinator::{map, opt},
@@ -26,7 +27,7 @@ pub fn parse_with_clause(input: &str) -> IResult<&str, (Vec<CTEDef>, bool)> {
     Ok((input, (ctes, is_recursive)))
 }
 
-/// Parse a single CTE definition: name [(columns)] AS (subquery)
+/// Parse a single CTE definition: name [(columns)] AS [[NOT] MATERIALIZED] (subquery)
 fn parse_cte_definition(input: &str, is_recursive: bool) -> IResult<&str, CTEDef> {
     // CTE name
     let (input, name) = parse_identifier(input)?;
@@ -48,6 +49,17 @@ fn parse_cte_definition(input: &str, is_recursive: bool) -> IResult<&str, CTEDef
     let (input, _) = tag_no_case("as").parse(input)?;
     let (input, _) = multispace0(input)?;
 
+    // Optional materialization hint (PostgreSQL 12+)
+    let (input, materialized) = opt(alt((
+        map(
+            (tag_no_case("not"), multispace1, tag_no_case("materialized")),
+            |_| false,
+        ),
+        map(tag_no_case("materialized"), |_| true),
+    )))
+    .parse(input)?;
+    let (input, _) = multispace0(input)?;
+
     // Subquery in parentheses - extract content and parse recursively
     let (input, cte_body) =
         delimited(char('('), take_until_matching_paren, char(')')).parse(input)?;
@@ -64,6 +76,7 @@ fn parse_cte_definition(input: &str, is_recursive: bool) -> IResult<&str, CTEDef
             base_query: Box::new(base_query),
             recursive_query: None,
             source_table: None,
+            materialized,
         },
     ))
 }
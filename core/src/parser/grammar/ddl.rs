@@ -26,6 +26,7 @@ pub fn parse_create_table<'a>(input: &'a str, table: &str) -> IResult<&'a str, Q
         Qail {
             action: Action::Make,
             table: table.to_string(),
+            schema: None,
             columns,
             joins: vec![],
             cages: vec![],
@@ -51,6 +52,10 @@ pub fn parse_create_table<'a>(input: &'a str, table: &str) -> IResult<&'a str, Q
             overriding: None,
             sample: None,
             only_table: false,
+            if_not_exists: false,
+            restart_identity: false,
+            cascade: false,
+            tags: vec![],
             vector: None,
             score_threshold: None,
             vector_name: None,
@@ -161,6 +166,7 @@ pub fn parse_constraint(input: &str) -> IResult<&str, Constraint> {
 /// Parse CREATE INDEX: index idx_name on table_name col1, col2 [unique]
 pub fn parse_create_index(input: &str) -> IResult<&str, Qail> {
     let (input, _) = tag_no_case("index").parse(input)?;
+    let (input, if_not_exists) = opt(char('?')).parse(input)?;
     let (input, _) = multispace1(input)?;
 
     let (input, index_name) = parse_identifier(input)?;
@@ -183,6 +189,7 @@ pub fn parse_create_index(input: &str) -> IResult<&str, Qail> {
         Qail {
             action: Action::Index,
             table: String::new(),
+            schema: None,
             columns: vec![],
             joins: vec![],
             cages: vec![],
@@ -214,6 +221,10 @@ pub fn parse_create_index(input: &str) -> IResult<&str, Qail> {
             overriding: None,
             sample: None,
             only_table: false,
+            if_not_exists: if_not_exists.is_some(),
+            restart_identity: false,
+            cascade: false,
+            tags: vec![],
             vector: None,
             score_threshold: None,
             vector_name: None,
@@ -229,3 +240,24 @@ pub fn parse_create_index(input: &str) -> IResult<&str, Qail> {
         },
     ))
 }
+
+/// Parse TRUNCATE: truncate events [restart identity] [cascade]
+pub fn parse_truncate(input: &str) -> IResult<&str, Qail> {
+    let (input, _) = tag_no_case("truncate").parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, qualified_table) = parse_identifier(input)?;
+
+    let (input, restart_identity) = opt(preceded(
+        (multispace1, tag_no_case("restart"), multispace1),
+        tag_no_case("identity"),
+    ))
+    .parse(input)?;
+    let (input, cascade) = opt(preceded(multispace1, tag_no_case("cascade"))).parse(input)?;
+
+    let (schema, table) = super::base::split_schema(qualified_table);
+    let mut cmd = Qail::truncate(table);
+    cmd.schema = schema.map(|s| s.to_string());
+    cmd.restart_identity = restart_identity.is_some();
+    cmd.cascade = cascade.is_some();
+    Ok((input, cmd))
+}
@@ -2,9 +2,12 @@ use super::base::{parse_identifier, parse_value};
 use crate::ast::*;
 use nom::{
     IResult, Parser,
-    bytes::complete::tag_no_case,
-    character::complete::{char, multispace0, multispace1},
+    branch::alt,
+    bytes::complete::{tag, tag_no_case},
+    character::complete::{char, multispace0, multispace1, one_of, satisfy},
+    combinator::{not, opt},
     multi::separated_list1,
+    sequence::terminated,
 };
 
 /// Parse: values col = val, col2 = val2 (for SET/UPDATE)
@@ -29,8 +32,11 @@ pub fn parse_insert_values(input: &str) -> IResult<&str, Cage> {
     let (input, _) = tag_no_case("values").parse(input)?;
     let (input, _) = multispace1(input)?;
 
-    let (input, values) =
-        separated_list1((multispace0, char(','), multispace0), parse_value).parse(input)?;
+    let (input, values) = separated_list1(
+        (multispace0, char(','), multispace0),
+        alt((parse_default_value, parse_value)),
+    )
+    .parse(input)?;
 
     let conditions: Vec<Condition> = values
         .into_iter()
@@ -63,7 +69,6 @@ pub fn parse_set_assignments(input: &str) -> IResult<&str, Vec<Condition>> {
 /// Parse single assignment: column = value or column = expression (supports functions and subqueries)
 pub fn parse_assignment(input: &str) -> IResult<&str, Condition> {
     use super::expressions::parse_expression;
-    use nom::branch::alt;
 
     let (input, column) = parse_identifier(input)?;
     let (input, _) = multispace0(input)?;
@@ -72,12 +77,15 @@ pub fn parse_assignment(input: &str) -> IResult<&str, Condition> {
 
     // Try simple value first (booleans, strings, numbers, params), then subquery, then expression
     let (input, value) = alt((
-        // Try simple value parsing first (handles booleans, strings, numbers, params)
-        parse_value,
+        // DEFAULT keyword: use the column default
+        parse_default_value,
+        // Try simple value parsing first (handles booleans, strings, numbers, params),
+        // unless it is only the first operand of an expression like `2 * price`
+        terminated(parse_value, not((multispace0, one_of("+-*/%|:")))),
         // Try parenthesized subquery: (get ...)
         parse_subquery_value,
-        // Fall back to expression and convert to Value::Function
-        nom::combinator::map(parse_expression, |expr| Value::Function(expr.to_string())),
+        // Fall back to an expression: counter + 1, coalesce(a, b), ...
+        nom::combinator::map(parse_expression, |expr| Value::Expr(Box::new(expr))),
     ))
     .parse(input)?;
 
@@ -92,6 +100,13 @@ pub fn parse_assignment(input: &str) -> IResult<&str, Condition> {
     ))
 }
 
+/// Parse the DEFAULT keyword in VALUES / SET -> Value::Default
+fn parse_default_value(input: &str) -> IResult<&str, Value> {
+    let (input, _) = tag_no_case("default").parse(input)?;
+    let (input, _) = not(satisfy(|c: char| c.is_alphanumeric() || c == '_')).parse(input)?;
+    Ok((input, Value::Default))
+}
+
 /// Parse a subquery value: (get ...) -> Value::Subquery
 fn parse_subquery_value(input: &str) -> IResult<&str, Value> {
     let (input, _) = char('(').parse(input)?;
@@ -106,8 +121,8 @@ fn parse_subquery_value(input: &str) -> IResult<&str, Value> {
 /// Syntax:
 /// - `conflict (col1, col2) nothing` -> ON CONFLICT (col1, col2) DO NOTHING
 /// - `conflict (col1) update col2 = val` -> ON CONFLICT (col1) DO UPDATE SET col2 = val
+/// - `conflict (col1) where deleted_at is null nothing` -> ON CONFLICT (col1) WHERE ... DO NOTHING
 pub fn parse_on_conflict(input: &str) -> IResult<&str, OnConflict> {
-    use nom::branch::alt;
 
     let (input, _) = multispace0(input)?;
     let (input, _) = tag_no_case("conflict").parse(input)?;
@@ -121,17 +136,28 @@ pub fn parse_on_conflict(input: &str) -> IResult<&str, OnConflict> {
     let (input, _) = char(')').parse(input)?;
     let (input, _) = multispace0(input)?;
 
+    let (input, target_where) = opt(parse_conflict_target_where).parse(input)?;
+    let (input, _) = multispace0(input)?;
+
     let (input, action) = alt((parse_conflict_nothing, parse_conflict_update)).parse(input)?;
 
     Ok((
         input,
         OnConflict {
             columns: columns.iter().map(|s| s.to_string()).collect(),
+            target_where: target_where.unwrap_or_default(),
             action,
         },
     ))
 }
 
+/// Parse: where cond [and cond ...] (partial-index conflict target)
+fn parse_conflict_target_where(input: &str) -> IResult<&str, Vec<Condition>> {
+    let (input, _) = tag_no_case("where").parse(input)?;
+    let (input, _) = multispace1(input)?;
+    super::clauses::parse_conditions(input)
+}
+
 /// Parse: nothing
 fn parse_conflict_nothing(input: &str) -> IResult<&str, ConflictAction> {
     use nom::combinator::value;
@@ -159,27 +185,31 @@ fn parse_conflict_assignments(input: &str) -> IResult<&str, Vec<(String, Expr)>>
 /// Parse single conflict assignment: column = expression (supports :named_params)
 fn parse_conflict_assignment(input: &str) -> IResult<&str, (String, Expr)> {
     use super::expressions::parse_expression;
-    use nom::branch::alt;
 
     let (input, column) = parse_identifier(input)?;
     let (input, _) = multispace0(input)?;
     let (input, _) = char('=').parse(input)?;
     let (input, _) = multispace0(input)?;
 
-    // Try to parse a value first (handles :named_params, literals, etc.)
+    // Try to parse a value first (handles :named_params, literals, etc.),
+    // unless it starts an expression like `1 + excluded.count`.
     // Then fall back to full expression parsing
+    let value = terminated(parse_value, not((multispace0, one_of("+-*/%|:"))));
     let (input, expr) = alt((
-        nom::combinator::map(parse_value, |v| match v {
+        nom::combinator::map(value, |v| match v {
             Value::NamedParam(name) => Expr::Named(format!(":{}", name)),
             Value::Param(n) => Expr::Named(format!("${}", n)),
-            Value::String(s) => Expr::Named(format!("'{}'", s)),
+            Value::String(s) => Expr::Named(format!("'{}'", s.replace('\'', "''"))),
             Value::Int(n) => Expr::Named(n.to_string()),
             Value::Float(f) => Expr::Named(f.to_string()),
+            Value::Decimal(d) => Expr::Named(d),
             Value::Bool(b) => Expr::Named(b.to_string()),
             Value::Null => Expr::Named("NULL".to_string()),
             Value::Array(_) => Expr::Named("ARRAY".to_string()),
             Value::Function(name) => Expr::Named(name),
             Value::Subquery(_) => Expr::Named("(SUBQUERY)".to_string()),
+            Value::AnySubquery(_) => Expr::Named("ANY (SUBQUERY)".to_string()),
+            Value::AllSubquery(_) => Expr::Named("ALL (SUBQUERY)".to_string()),
             Value::Column(col) => Expr::Named(col),
             Value::Uuid(u) => Expr::Named(format!("'{}'", u)),
             Value::NullUuid => Expr::Named("NULL".to_string()),
@@ -194,6 +224,7 @@ fn parse_conflict_assignment(input: &str) -> IResult<&str, (String, Expr)> {
             Value::Expr(expr) => (*expr).clone(),
             Value::Vector(v) => Expr::Named(format!("[{} floats]", v.len())),
             Value::Json(json) => Expr::Named(format!("'{}'::jsonb", json.replace('\'', "''"))),
+            Value::Default => Expr::Named("DEFAULT".to_string()),
         }),
         // Fall back to full expression parsing
         parse_expression,
@@ -203,10 +234,57 @@ fn parse_conflict_assignment(input: &str) -> IResult<&str, (String, Expr)> {
     Ok((input, (column.to_string(), expr)))
 }
 
+/// Parse: using t1, t2 - auxiliary tables for DELETE ... USING
+pub fn parse_using_clause(input: &str) -> IResult<&str, Vec<String>> {
+    parse_aux_tables(input, "using")
+}
+
+/// Parse: from t1, t2 - auxiliary tables for UPDATE ... FROM
+pub fn parse_update_from_clause(input: &str) -> IResult<&str, Vec<String>> {
+    parse_aux_tables(input, "from")
+}
+
+fn parse_aux_tables<'a>(input: &'a str, keyword: &'static str) -> IResult<&'a str, Vec<String>> {
+    let (input, _) = tag_no_case(keyword).parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, tables) =
+        separated_list1((multispace0, char(','), multispace0), parse_identifier).parse(input)?;
+    Ok((input, tables.into_iter().map(|t| t.to_string()).collect()))
+}
+
+/// Parse: (get ...) as alias - derived table in FROM
+/// Syntax: `get (get orders where status = 'paid') as p where total > 100`
+/// Also used for lateral joins. The subquery must be a `get` or `with`.
+pub fn parse_derived_table(input: &str) -> IResult<&str, (Box<crate::ast::Qail>, &str)> {
+    let (input, _) = char('(').parse(input)?;
+    let (input, _) = multispace0(input)?;
+    let query_start = input;
+    let (input, subquery) = super::parse_root(input)?;
+    if !matches!(subquery.action, crate::ast::Action::Get | crate::ast::Action::With) {
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            query_start,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char(')').parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, _) = tag_no_case("as").parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, alias) = parse_identifier(input)?;
+    Ok((input, (Box::new(subquery), alias)))
+}
+
 /// Parse: from (get ...) - source query for INSERT...SELECT
 /// Syntax: `from (get table fields col1, col2 where ...)`
+/// or `<- get table where ...`, where the query runs to the end of input.
 pub fn parse_source_query(input: &str) -> IResult<&str, Box<crate::ast::Qail>> {
     let (input, _) = multispace0(input)?;
+    if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("<-").parse(input) {
+        let (input, _) = multispace0(input)?;
+        let (input, subquery) = super::parse_root(input)?;
+        return Ok((input, Box::new(subquery)));
+    }
     let (input, _) = tag_no_case("from").parse(input)?;
     let (input, _) = multispace0(input)?;
     let (input, _) = char('(').parse(input)?;
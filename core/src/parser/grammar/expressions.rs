@@ -7,14 +7,15 @@
 //! - `case_when`: CASE WHEN expressions
 //! - `special_funcs`: SUBSTRING, EXTRACT, TRIM with keyword syntax
 
-use super::base::{parse_identifier, parse_value};
+use super::base::{parse_identifier, parse_quoted_identifier, parse_value};
 use crate::ast::*;
 use nom::{
     IResult, Parser,
     branch::alt,
     bytes::complete::{tag, tag_no_case},
-    character::complete::multispace0,
-    combinator::{map, opt},
+    character::complete::{char, digit1, multispace0},
+    combinator::{map, opt, peek},
+    multi::many0,
     sequence::{delimited, preceded},
 };
 
@@ -42,6 +43,7 @@ pub fn parse_expression_with_alias(input: &str) -> IResult<&str, Expr> {
     if let Ok((remaining, _)) = tag_no_case::<_, _, nom::error::Error<&str>>("as").parse(input) {
         let (remaining, _) = nom::character::complete::multispace1(remaining)?;
         let (remaining, alias) = parse_identifier(remaining)?;
+        check_raw_alias(&expr, alias)?;
         expr = set_expr_alias(expr, alias.to_string());
         return Ok((remaining, expr));
     }
@@ -49,6 +51,27 @@ pub fn parse_expression_with_alias(input: &str) -> IResult<&str, Expr> {
     Ok((input, expr))
 }
 
+/// A raw fragment's alias is pasted into its SQL, so it must be a single
+/// plain (`name`) or double-quoted (`"Name"`) identifier.
+pub fn check_raw_alias<'a>(
+    expr: &Expr,
+    alias: &'a str,
+) -> Result<(), nom::Err<nom::error::Error<&'a str>>> {
+    let plain = alias.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && alias.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let quoted = alias.len() > 2
+        && alias.starts_with('"')
+        && alias.ends_with('"')
+        && !alias[1..alias.len() - 1].contains('"');
+    if !matches!(expr, Expr::Raw(_)) || plain || quoted {
+        return Ok(());
+    }
+    Err(nom::Err::Failure(nom::error::Error::new(
+        alias,
+        nom::error::ErrorKind::Verify,
+    )))
+}
+
 /// Set alias on any Expr variant that supports it
 fn set_expr_alias(expr: Expr, alias: String) -> Expr {
     match expr {
@@ -89,6 +112,18 @@ fn set_expr_alias(expr: Expr, alias: String) -> Expr {
             path_segments,
             alias: Some(alias),
         },
+        Expr::Cast {
+            expr, target_type, ..
+        } => Expr::Cast {
+            expr,
+            target_type,
+            alias: Some(alias),
+        },
+        Expr::Subquery { query, .. } => Expr::Subquery {
+            query,
+            alias: Some(alias),
+        },
+        Expr::Raw(sql) => Expr::Raw(format!("{} AS {}", sql, alias)),
         other => other, // Star, Aliased already have alias
     }
 }
@@ -141,19 +176,36 @@ pub fn parse_json_or_ident(input: &str) -> IResult<&str, Expr> {
         atom
     };
 
-    let (input, cast_type) = opt(preceded(tag("::"), parse_identifier)).parse(input)?;
-
-    if let Some(target_type) = cast_type {
+    // Casts may be chained: data->>'n'::text::int
+    while let (remaining, Some(target_type)) =
+        opt(preceded(tag("::"), parse_cast_type)).parse(input)?
+    {
         expr = Expr::Cast {
             expr: Box::new(expr),
             target_type: target_type.to_string(),
             alias: None,
         };
+        input = remaining;
     }
 
     Ok((input, expr))
 }
 
+/// Parse a cast target type: `int`, `numeric(10,2)`, `text[]`, `varchar(255)[]`
+fn parse_cast_type(input: &str) -> IResult<&str, &str> {
+    let (rest, _) = (
+        parse_identifier,
+        opt(delimited(
+            char('('),
+            (digit1, opt(preceded((multispace0, char(','), multispace0), digit1))),
+            char(')'),
+        )),
+        many0(tag("[]")),
+    )
+        .parse(input)?;
+    Ok((rest, &input[..input.len() - rest.len()]))
+}
+
 /// Parse a parenthesized expression: (expr)
 fn parse_grouped_expr(input: &str) -> IResult<&str, Expr> {
     use nom::character::complete::multispace0;
@@ -166,14 +218,60 @@ fn parse_grouped_expr(input: &str) -> IResult<&str, Expr> {
     .parse(input)
 }
 
+/// Parse a scalar subquery: (get orders fields count(*) where user_id = users.id)
+/// Outer-table references inside the subquery are kept as written (correlation).
+fn parse_scalar_subquery(input: &str) -> IResult<&str, Expr> {
+    let (input, _) = char('(').parse(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, query) = super::parse_root(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = char(')').parse(input)?;
+    Ok((
+        input,
+        Expr::Subquery {
+            query: Box::new(query),
+            alias: None,
+        },
+    ))
+}
+
+/// Parse a raw SQL fragment: raw!(vendor_fn(a, 'x)'))
+/// The `!` keeps calls to a user function named `raw` ordinary calls.
+/// The body runs to the matching `)`; parentheses inside '...' strings don't count.
+pub fn parse_raw(input: &str) -> IResult<&str, Expr> {
+    let (rest, _) = tag_no_case("raw!(").parse(input)?;
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string && depth == 0 => {
+                let sql = rest[..i].trim().to_string();
+                return Ok((&rest[i + 1..], Expr::Raw(sql)));
+            }
+            ')' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Char,
+    )))
+}
+
 /// Parse atomic expressions (functions, case, literals, identifiers, wildcards, grouped)
 fn parse_atom(input: &str) -> IResult<&str, Expr> {
     alt((
-        parse_grouped_expr, // Try (expr) first
+        parse_raw,
+        parse_scalar_subquery, // (get ...) before (expr)
+        parse_grouped_expr,
         parse_case,
         parse_special_function,
         parse_function_or_aggregate,
         parse_star,
+        parse_quoted_ident, // "Prénom" is a column here, not a string literal
         parse_literal,
         parse_simple_ident,
     ))
@@ -189,7 +287,7 @@ fn parse_literal(input: &str) -> IResult<&str, Expr> {
     map(parse_value, |v| match v {
         Value::NamedParam(name) => Expr::Named(format!(":{}", name)),
         Value::Param(n) => Expr::Named(format!("${}", n)),
-        Value::String(s) => Expr::Named(format!("'{}'", s)),
+        Value::String(s) => Expr::Named(format!("'{}'", s.replace('\'', "''"))),
         Value::Int(n) => Expr::Named(n.to_string()),
         Value::Float(f) => {
             // Ensure float always has decimal point (100.0 not 100)
@@ -213,6 +311,14 @@ fn parse_literal(input: &str) -> IResult<&str, Expr> {
     .parse(input)
 }
 
+fn parse_quoted_ident(input: &str) -> IResult<&str, Expr> {
+    // `"Sales".total`: a qualified name starting with a quoted part
+    map(preceded(peek(parse_quoted_identifier), parse_identifier), |s| {
+        Expr::Named(s.to_string())
+    })
+    .parse(input)
+}
+
 fn parse_simple_ident(input: &str) -> IResult<&str, Expr> {
     map(parse_identifier, |s| Expr::Named(s.to_string())).parse(input)
 }
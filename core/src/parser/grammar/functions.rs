@@ -5,8 +5,10 @@
 //! - Aggregates: COUNT, SUM, AVG, MIN, MAX
 //! - FILTER (WHERE ...) clause for aggregates
 //! - COUNT(DISTINCT col) syntax
+//! - Ordered aggregates: array_agg(col order by other desc), string_agg(col, ',' order by col)
 
 use super::base::{parse_identifier, parse_operator, parse_value};
+use super::clauses::{parse_in_operand, parse_sort_order};
 use super::expressions::parse_expression;
 use crate::ast::*;
 use nom::{
@@ -15,7 +17,7 @@ use nom::{
     bytes::complete::{tag, tag_no_case},
     character::complete::{char, multispace0, multispace1},
     combinator::{map, opt, peek},
-    multi::separated_list0,
+    multi::{separated_list0, separated_list1},
     sequence::preceded,
 };
 
@@ -33,6 +35,9 @@ pub fn parse_function_or_aggregate(input: &str) -> IResult<&str, Expr> {
     let (input, args) =
         separated_list0((multispace0, char(','), multispace0), parse_function_arg).parse(input)?;
 
+    let (input, order_by) =
+        opt(preceded(multispace0, parse_aggregate_order_by)).parse(input)?;
+
     let (input, _) = multispace0(input)?;
     let (input, _) = char(')').parse(input)?;
     let (input, _) = multispace0(input)?;
@@ -41,6 +46,13 @@ pub fn parse_function_or_aggregate(input: &str) -> IResult<&str, Expr> {
 
     let (input, _) = multispace0(input)?;
     if let Ok((remaining, _)) = tag_no_case::<_, _, nom::error::Error<&str>>("over").parse(input) {
+        // Expr::Window has nowhere to keep an in-call ORDER BY or a FILTER
+        if order_by.is_some() || filter_clause.is_some() {
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )));
+        }
         let (remaining, _) = multispace0(remaining)?;
         let (remaining, _) = char('(').parse(remaining)?;
         let (remaining, _) = multispace0(remaining)?;
@@ -94,33 +106,61 @@ pub fn parse_function_or_aggregate(input: &str) -> IResult<&str, Expr> {
     let alias = alias.map(|s| s.to_string());
 
     let name_lower = name.to_lowercase();
-    match name_lower.as_str() {
-        "count" | "sum" | "avg" | "min" | "max" => {
-            // For aggregates, convert first arg to string representation
-            let col = args
-                .first()
-                .map(|e| e.to_string())
-                .unwrap_or_else(|| "*".to_string());
-            let func = match name_lower.as_str() {
-                "count" => AggregateFunc::Count,
-                "sum" => AggregateFunc::Sum,
-                "avg" => AggregateFunc::Avg,
-                "min" => AggregateFunc::Min,
-                "max" => AggregateFunc::Max,
-                _ => AggregateFunc::Count, // unreachable
+    let func = match name_lower.as_str() {
+        "count" => Some(AggregateFunc::Count),
+        "sum" => Some(AggregateFunc::Sum),
+        "avg" => Some(AggregateFunc::Avg),
+        "min" => Some(AggregateFunc::Min),
+        "max" => Some(AggregateFunc::Max),
+        "array_agg" => Some(AggregateFunc::ArrayAgg),
+        "string_agg" => Some(AggregateFunc::StringAgg),
+        "json_agg" => Some(AggregateFunc::JsonAgg),
+        "jsonb_agg" => Some(AggregateFunc::JsonbAgg),
+        "bool_and" => Some(AggregateFunc::BoolAnd),
+        "bool_or" => Some(AggregateFunc::BoolOr),
+        _ => None,
+    };
+    // string_agg keeps its delimiter; it must be a string literal, which
+    // parse_literal hands back already quoted
+    let delimiter = match (&func, args.get(1)) {
+        (Some(AggregateFunc::StringAgg), Some(Expr::Named(lit)))
+            if lit.len() >= 2 && lit.starts_with('\'') && lit.ends_with('\'') =>
+        {
+            Some(lit[1..lit.len() - 1].replace("''", "'"))
+        }
+        _ => None,
+    };
+    let func = func.filter(|f| *f != AggregateFunc::StringAgg || delimiter.is_some());
+
+    match func {
+        Some(func) => {
+            // A plain column stays in `col`; anything else is kept as an
+            // expression so it is not quoted as an identifier
+            let (col, arg) = match args.into_iter().next() {
+                None | Some(Expr::Star) => ("*".to_string(), None),
+                Some(Expr::Named(name)) if is_column_ref(&name) => (name, None),
+                Some(expr) => (expr.to_string(), Some(Box::new(expr))),
             };
             Ok((
                 input,
                 Expr::Aggregate {
                     col,
+                    arg,
                     func,
                     distinct,
                     filter: filter_clause,
+                    delimiter,
+                    order_by,
                     alias,
                 },
             ))
         }
-        _ => Ok((
+        // ORDER BY inside the parentheses only applies to aggregates
+        None if order_by.is_some() => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+        None => Ok((
             input,
             Expr::FunctionCall {
                 name: name.to_string(),
@@ -169,13 +209,7 @@ fn parse_filter_conditions(input: &str) -> IResult<&str, Vec<Condition>> {
         let (input, value) = if matches!(op, Operator::IsNull | Operator::IsNotNull) {
             (input, Value::Null)
         } else if matches!(op, Operator::In | Operator::NotIn) {
-            let (input, _) = char('(').parse(input)?;
-            let (input, _) = multispace0(input)?;
-            let (input, values) =
-                separated_list0((multispace0, char(','), multispace0), parse_value).parse(input)?;
-            let (input, _) = multispace0(input)?;
-            let (input, _) = char(')').parse(input)?;
-            (input, Value::Array(values))
+            parse_in_operand(input)?
         } else if matches!(op, Operator::Between | Operator::NotBetween) {
             let (input, min_val) = parse_value(input)?;
             let (input, _) = multispace1(input)?;
@@ -258,16 +292,14 @@ fn parse_filter_value(input: &str) -> IResult<&str, Value> {
         }
 
         if paren_depth == 0 && i > 0 {
-            let remaining = &input[i..];
-            if remaining.len() >= 4 {
-                let potential_and = &remaining[..4].to_lowercase();
-                if potential_and.starts_with("and ")
-                    || potential_and.starts_with("and\t")
-                    || potential_and.starts_with("and\n")
-                {
-                    end_pos = i;
-                    break;
-                }
+            // Byte-wise lookahead: `i + 4` may fall inside a multi-byte char
+            let potential_and = input.as_bytes()[i..].get(..4);
+            if let Some([a, n, d, ws]) = potential_and
+                && [a, n, d].map(u8::to_ascii_lowercase) == *b"and"
+                && matches!(*ws, b' ' | b'\t' | b'\n')
+            {
+                end_pos = i;
+                break;
             }
         }
     }
@@ -321,23 +353,45 @@ fn parse_window_order_by(input: &str) -> IResult<&str, Vec<Cage>> {
     Ok((input, order_parts))
 }
 
-/// Parse a single order by item: col [asc|desc]
-fn parse_window_sort_item(input: &str) -> IResult<&str, Cage> {
-    use nom::combinator::value;
+/// Whether a parsed argument names a column rather than a literal or parameter.
+fn is_column_ref(name: &str) -> bool {
+    let starts_like_ident = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '"');
+    starts_like_ident
+        && !["NULL", "TRUE", "FALSE"]
+            .iter()
+            .any(|kw| name.eq_ignore_ascii_case(kw))
+}
 
+/// Parse ORDER BY inside an aggregate call: `order by col [asc|desc] [nulls first|last], ...`
+fn parse_aggregate_order_by(input: &str) -> IResult<&str, Vec<(String, SortOrder)>> {
+    let (input, _) = tag_no_case("order").parse(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, _) = tag_no_case("by").parse(input)?;
+    let (input, _) = multispace1(input)?;
+
+    separated_list1(
+        (multispace0, char(','), multispace0),
+        map(
+            (parse_identifier, multispace0, parse_sort_order),
+            |(col, _, order)| (col.to_string(), order),
+        ),
+    )
+    .parse(input)
+}
+
+/// Parse a single order by item: col [asc|desc] [nulls first|last]
+fn parse_window_sort_item(input: &str) -> IResult<&str, Cage> {
     let (input, col) = parse_identifier(input)?;
     let (input, _) = multispace0(input)?;
-
-    let (input, order) = opt(alt((
-        value(SortOrder::Desc, tag_no_case("desc")),
-        value(SortOrder::Asc, tag_no_case("asc")),
-    )))
-    .parse(input)?;
+    let (input, order) = parse_sort_order(input)?;
 
     Ok((
         input,
         Cage {
-            kind: CageKind::Sort(order.unwrap_or(SortOrder::Asc)),
+            kind: CageKind::Sort(order),
             conditions: vec![Condition {
                 left: Expr::Named(col.to_string()),
                 op: Operator::Eq,
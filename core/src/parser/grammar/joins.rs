@@ -1,4 +1,5 @@
 use super::base::parse_identifier;
+use super::dml::parse_derived_table;
 use super::expressions::parse_expression;
 use crate::ast::*;
 use nom::{
@@ -11,8 +12,25 @@ use nom::{
 };
 
 /// Parse join clause: [left|right|inner] join table [on col = col]
+///
+/// Lateral joins take a correlated subquery:
+/// `lateral join (get orders where orders.user_id = users.id limit 3) as recent`
 pub fn parse_join_clause(input: &str) -> IResult<&str, Join> {
     let (input, kind) = alt((
+        map(
+            (
+                tag_no_case("left"),
+                multispace1,
+                tag_no_case("join"),
+                multispace1,
+                tag_no_case("lateral"),
+            ),
+            |_| JoinKind::Lateral,
+        ),
+        map(
+            (tag_no_case("lateral"), multispace1, tag_no_case("join")),
+            |_| JoinKind::Lateral,
+        ),
         map(
             (tag_no_case("left"), multispace1, tag_no_case("join")),
             |_| JoinKind::Left,
@@ -31,7 +49,15 @@ pub fn parse_join_clause(input: &str) -> IResult<&str, Join> {
     .parse(input)?;
 
     let (input, _) = multispace1(input)?;
-    let (input, table) = parse_identifier(input)?;
+    let (input, (table, subquery)) = if kind == JoinKind::Lateral {
+        alt((
+            map(parse_derived_table, |(query, alias)| (alias, Some(query))),
+            map(parse_identifier, |table| (table, None)),
+        ))
+        .parse(input)?
+    } else {
+        map(parse_identifier, |table| (table, None)).parse(input)?
+    };
     let (input, _) = multispace0(input)?;
 
     // Optional ON clause: either ON TRUE or ON condition
@@ -48,7 +74,9 @@ pub fn parse_join_clause(input: &str) -> IResult<&str, Join> {
             parse_join_condition,
         ))
         .parse(input)?;
-        (input, cond, false)
+        // A lateral subquery without ON is joined ON TRUE
+        let on_true = cond.is_none() && subquery.is_some();
+        (input, cond, on_true)
     };
 
     // Consume trailing whitespace so many0 can find the next JOIN
@@ -61,6 +89,7 @@ pub fn parse_join_clause(input: &str) -> IResult<&str, Join> {
             kind,
             on: on_clause,
             on_true,
+            subquery,
         },
     ))
 }
@@ -17,6 +17,7 @@ use nom::{
     character::complete::{multispace0, multispace1},
     combinator::opt,
     multi::many0,
+    sequence::preceded,
 };
 use self::base::*;
 use self::clauses::*;
@@ -29,6 +30,12 @@ use self::joins::*;
 /// This is the recommended entry point - handles SQL comment stripping.
 pub fn parse(input: &str) -> Result<Qail, String> {
     let cleaned = strip_sql_comments(input);
+    if let Some(position) = find_excessive_nesting(&cleaned) {
+        return Err(format!(
+            "Parse error: nesting deeper than {} levels at position {}",
+            MAX_NESTING_DEPTH, position
+        ));
+    }
     match parse_root(&cleaned) {
         Ok((_, cmd)) => Ok(cmd),
         Err(e) => Err(format!("Parse error: {:?}", e)),
@@ -50,6 +57,11 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
         return Ok((remaining, cmd));
     }
 
+    // TRUNCATE: "truncate table [restart identity] [cascade]"
+    if let Ok((remaining, cmd)) = parse_truncate(input) {
+        return Ok((remaining, cmd));
+    }
+
     // Try WITH clause (CTE) parsing
     let lower_input = input.to_lowercase();
     let (input, ctes) = if lower_input.starts_with("with")
@@ -67,6 +79,12 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
     };
 
     let (input, (action, distinct)) = parse_action(input)?;
+    // "make?" -> CREATE TABLE IF NOT EXISTS
+    let (input, if_not_exists) = if matches!(action, Action::Make) {
+        opt(nom::character::complete::char('?')).parse(input)?
+    } else {
+        (input, None)
+    };
     // v2 syntax only: whitespace separator between action and table
     let (input, _) = multispace1(input)?;
 
@@ -98,18 +116,43 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
         (input, vec![])
     };
 
-    //  Parse table name
-    let (input, table) = parse_identifier(input)?;
+    // GET from a derived table: (get ...) as alias
+    let (input, derived_table) = if matches!(action, Action::Get) {
+        opt(dml::parse_derived_table).parse(input)?
+    } else {
+        (input, None)
+    };
+
+    //  Parse table name (optionally schema-qualified: tenant_42.users)
+    let (input, qualified_table, derived_query) = match derived_table {
+        Some((query, alias)) => (input, alias, Some(query)),
+        None => {
+            let (input, table) = parse_identifier(input)?;
+            (input, table, None)
+        }
+    };
+    let (schema, table) = split_schema(qualified_table);
     let (input, _) = multispace0(input)?;
 
     // For MAKE (CREATE TABLE): parse column definitions
     if matches!(action, Action::Make) {
-        return parse_create_table(input, table);
+        let (input, mut cmd) = parse_create_table(input, table)?;
+        cmd.schema = schema.map(|s| s.to_string());
+        cmd.if_not_exists = if_not_exists.is_some();
+        return Ok((input, cmd));
     }
 
     let (input, joins) = many0(parse_join_clause).parse(input)?;
     let (input, _) = multispace0(input)?;
 
+    // For DEL: "using t1, t2" (DELETE ... USING)
+    let (input, using_tables) = if matches!(action, Action::Del) {
+        opt(dml::parse_using_clause).parse(input)?
+    } else {
+        (input, None)
+    };
+    let (input, _) = multispace0(input)?;
+
     // For SET/UPDATE: parse "values col = val, col2 = val2" before fields
     let (input, set_cages) = if matches!(action, Action::Set) {
         opt(parse_values_clause).parse(input)?
@@ -118,6 +161,14 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
     };
     let (input, _) = multispace0(input)?;
 
+    // For SET: "from t1, t2" after the assignments (UPDATE ... FROM)
+    let (input, from_tables) = if matches!(action, Action::Set) {
+        opt(dml::parse_update_from_clause).parse(input)?
+    } else {
+        (input, None)
+    };
+    let (input, _) = multispace0(input)?;
+
     let (input, columns) = opt(parse_fields_clause).parse(input)?;
     let (input, _) = multispace0(input)?;
 
@@ -156,6 +207,21 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
     let (input, _) = multispace0(input)?;
     let (input, offset_cage) = opt(parse_offset_clause).parse(input)?;
 
+    // fetch first N rows only|with ties: the standard spelling of LIMIT
+    let (input, fetch) = if limit_cage.is_none() {
+        let (rest, fetch) = opt(preceded(multispace0, parse_fetch_clause)).parse(input)?;
+        // WITH TIES extends the last row's peer group, which needs an ORDER BY
+        if matches!(fetch, Some((_, true))) && order_cages.is_none() {
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input.trim_start(),
+                nom::error::ErrorKind::Verify,
+            )));
+        }
+        (rest, fetch)
+    } else {
+        (input, None)
+    };
+
     let mut cages = Vec::new();
 
     // For SET, values come first (as Payload cage)
@@ -186,6 +252,7 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
         Qail {
             action,
             table: table.to_string(),
+            schema: schema.map(|s| s.to_string()),
             columns: columns.unwrap_or_else(|| vec![Expr::Star]),
             joins,
             cages,
@@ -199,18 +266,22 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
             returning: None,
             ctes,
             on_conflict,
-            source_query,
+            source_query: source_query.or(derived_query),
             channel: None,
             payload: None,
             savepoint_name: None,
-            from_tables: vec![],
-            using_tables: vec![],
+            from_tables: from_tables.unwrap_or_default(),
+            using_tables: using_tables.unwrap_or_default(),
             lock_mode: None,
-            fetch: None,
+            fetch,
             default_values: false,
             overriding: None,
             sample: None,
             only_table: false,
+            if_not_exists: false,
+            restart_identity: false,
+            cascade: false,
+            tags: vec![],
             vector: None,
             score_threshold: None,
             vector_name: None,
@@ -227,6 +298,62 @@ pub fn parse_root(input: &str) -> IResult<&str, Qail> {
     ))
 }
 
+/// Maximum nesting (parentheses and `case ... end`) accepted by the parser.
+/// Each level recurses through several combinators, so unbounded input
+/// would overflow the stack instead of returning an error.
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// Byte offset of the first `(` or `case` nested deeper than
+/// [`MAX_NESTING_DEPTH`], ignoring string literals and quoted identifiers.
+pub(crate) fn find_excessive_nesting(input: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        let opens = match c {
+            '\'' | '"' => {
+                quote = Some(c);
+                false
+            }
+            '(' => true,
+            ')' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            // `case` opens a level that `end` closes
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, next)) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    end = j + next.len_utf8();
+                    chars.next();
+                }
+                let word = &input[i..end];
+                if word.eq_ignore_ascii_case("end") {
+                    depth = depth.saturating_sub(1);
+                }
+                word.eq_ignore_ascii_case("case")
+            }
+            _ => false,
+        };
+        if opens {
+            depth += 1;
+            if depth > MAX_NESTING_DEPTH {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
 /// Strip SQL comments from input (both -- line comments and /* */ block comments)
 fn strip_sql_comments(input: &str) -> String {
     let mut result = String::with_capacity(input.len());
@@ -0,0 +1,128 @@
+//! Parsing a whole `.qail` file of commands.
+//!
+//! Statements end at a `;` or a blank line. Neither counts inside
+//! parentheses, string literals, quoted identifiers or comments, so CTE
+//! bodies and subqueries may span lines (and contain blank lines):
+//!
+//! ```text
+//! -- recent buyers
+//! with recent as (
+//!     get orders fields user_id where created_at > :since
+//! )
+//! get users join recent on recent.user_id = users.id
+//!
+//! get products where stock = 0; del sessions where expired = true
+//! ```
+
+use super::parse;
+use crate::ast::Qail;
+use crate::error::{QailError, QailResult, Span};
+use std::ops::Range;
+
+/// Parse every command in `input`, in order.
+///
+/// Each statement is parsed independently, so one syntax error does not
+/// hide the others. Error spans are byte offsets into `input`. Statements
+/// that are empty or only comments are skipped.
+pub fn parse_many(input: &str) -> Vec<QailResult<Qail>> {
+    let (source, ranges) = split_statements(input);
+    ranges
+        .into_iter()
+        .filter(|range| !source[range.clone()].trim().is_empty())
+        .map(|range| parse(&source[range.clone()]).map_err(|e| shift(e, range.start)))
+        .collect()
+}
+
+/// Statement byte ranges of `input`, plus a copy of `input` with comments
+/// replaced by spaces (same length, so the ranges index both).
+fn split_statements(input: &str) -> (String, Vec<Range<usize>>) {
+    let bytes = input.as_bytes();
+    let mut source = bytes.to_vec();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut quote: Option<u8> = None;
+    let mut line_has_content = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            // A doubled quote toggles out and straight back in
+            if b == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+        match b {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&c| c == b'\n')
+                    .map_or(bytes.len(), |n| i + n);
+                source[i..end].fill(b' ');
+                line_has_content = true;
+                i = end;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = input[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |n| i + 2 + n + 2);
+                source[i..end].fill(b' ');
+                line_has_content = true;
+                i = end;
+                continue;
+            }
+            b'\'' | b'"' => quote = Some(b),
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => {
+                ranges.push(start..i);
+                start = i + 1;
+            }
+            b'\n' => {
+                if !line_has_content && depth == 0 {
+                    ranges.push(start..i);
+                    start = i + 1;
+                }
+                line_has_content = false;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        if !b.is_ascii_whitespace() {
+            line_has_content = true;
+        }
+        i += 1;
+    }
+    ranges.push(start..bytes.len());
+
+    // Only whole characters inside comments were replaced, with ASCII spaces
+    let source = String::from_utf8(source).expect("comment blanking keeps UTF-8 valid");
+    (source, ranges)
+}
+
+/// Move a statement-relative parse error to its place in the whole input.
+fn shift(err: QailError, offset: usize) -> QailError {
+    match err {
+        QailError::Parse {
+            position,
+            message,
+            span,
+        } => QailError::Parse {
+            position: position + offset,
+            message,
+            span: span.map(|s| Span::new(s.start + offset, s.end + offset)),
+        },
+        QailError::SyntaxVersionForbidden { position, token } => {
+            QailError::SyntaxVersionForbidden {
+                position: position + offset,
+                token,
+            }
+        }
+        other => other,
+    }
+}
@@ -12,7 +12,9 @@
 //! limit 10
 //! ```
 
+pub mod cache;
 pub mod grammar;
+mod many;
 pub mod query_file;
 pub mod schema;
 
@@ -20,19 +22,95 @@ pub mod schema;
 mod tests;
 
 use crate::ast::*;
-use crate::error::{QailError, QailResult};
+use crate::error::{QailError, QailResult, Span};
+
+pub use cache::parse_cached;
+pub use many::parse_many;
+
+/// Locate the first v1 symbolic token outside string literals.
+/// `::` only counts directly after the action keyword, since v2 uses it for casts.
+fn find_v1_token(input: &str) -> Option<(usize, &str)> {
+    let lower = input.to_ascii_lowercase();
+    for action in ["get::", "set::", "del::", "add::"] {
+        if lower.starts_with(action) {
+            return Some((3, "::"));
+        }
+    }
+
+    let mut in_string = false;
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' => in_string = !in_string,
+            _ if in_string => {}
+            '•' => return Some((i, "•")),
+            '@' if chars
+                .peek()
+                .is_some_and(|&(_, n)| n.is_ascii_alphabetic() || n == '_') =>
+            {
+                return Some((i, "@"));
+            }
+            _ => {}
+        }
+    }
+    None
+}
 
 /// Parse a complete QAIL query string (v2 syntax only).
 /// Uses keyword-based syntax: `get table fields * where col = value`
+///
+/// Parse errors carry a [`Span`](crate::error::Span) (byte offsets into
+/// `input`) around the token where parsing stopped. When the query does not
+/// parse and contains v1 symbolic syntax (`get::`, `•`, `@col`), the error is
+/// [`QailError::SyntaxVersionForbidden`] at the first v1 token instead, so
+/// tooling (LSP, CLI) can report it as a lint.
 pub fn parse(input: &str) -> QailResult<Qail> {
+    let result = parse_v2(input);
+    if let Err(QailError::Parse { .. }) = result {
+        let lead = input.len() - input.trim_start().len();
+        if let Some((offset, token)) = find_v1_token(&input[lead..]) {
+            return Err(QailError::SyntaxVersionForbidden {
+                position: lead + offset,
+                token: token.to_string(),
+            });
+        }
+    }
+    result
+}
+
+fn parse_v2(input: &str) -> QailResult<Qail> {
+    let lead = input.len() - input.trim_start().len();
     let input = input.trim();
 
+    if let Some(offset) = grammar::find_excessive_nesting(input) {
+        return Err(QailError::parse_at(
+            Span::new(lead + offset, lead + offset + 1),
+            format!("Nesting deeper than {} levels", grammar::MAX_NESTING_DEPTH),
+        ));
+    }
+
     match grammar::parse_root(input) {
         Ok(("", cmd)) => Ok(cmd),
-        Ok((remaining, _)) => Err(QailError::parse(
-            input.len() - remaining.len(),
+        Ok((remaining, _)) => Err(QailError::parse_at(
+            token_span(input, remaining, lead),
             format!("Unexpected trailing content: '{}'", remaining),
         )),
-        Err(e) => Err(QailError::parse(0, format!("Parse failed: {:?}", e))),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let span = token_span(input, e.input, lead);
+            let message = match e.input.split_whitespace().next() {
+                Some(token) => format!("Unexpected '{}' ({:?})", token, e.code),
+                None => format!("Unexpected end of input ({:?})", e.code),
+            };
+            Err(QailError::parse_at(span, message))
+        }
+        Err(e) => Err(QailError::parse(lead, format!("Parse failed: {:?}", e))),
     }
 }
+
+/// Span of the whitespace-delimited token at the start of `rest`, a suffix of `input`.
+/// At end of input the span is empty.
+fn token_span(input: &str, rest: &str, lead: usize) -> Span {
+    let start = lead + input.len() - rest.len();
+    let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    Span::new(start, start + len)
+}
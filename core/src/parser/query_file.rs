@@ -20,16 +20,19 @@ use nom::{
     combinator::map,
     multi::{many0, separated_list0},
 };
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Collection of named queries from a queries.qail file
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QueryFile {
     pub queries: Vec<QueryDef>,
 }
 
 /// A named query definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QueryDef {
     /// Query name (function name)
     pub name: String,
@@ -42,14 +45,16 @@ pub struct QueryDef {
 }
 
 /// Query parameter
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QueryParam {
     pub name: String,
     pub typ: String,
 }
 
 /// Return type for queries
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ReturnType {
     /// Single result: -> User
     Single(String),
@@ -77,11 +82,13 @@ impl QueryFile {
     }
 
     /// Export to JSON
+    #[cfg(feature = "serde")]
     pub fn to_json(&self) -> Result<String, String> {
         serde_json::to_string_pretty(self).map_err(|e| format!("JSON serialization failed: {}", e))
     }
 
     /// Import from JSON
+    #[cfg(feature = "serde")]
     pub fn from_json(json: &str) -> Result<Self, String> {
         serde_json::from_str(json).map_err(|e| format!("JSON deserialization failed: {}", e))
     }
@@ -19,49 +19,53 @@ use nom::{
     multi::{many0, separated_list0},
     sequence::preceded,
 };
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Schema containing all table definitions
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Schema {
     /// Schema format version (extracted from `-- qail: version=N` directive)
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub version: Option<u32>,
     pub tables: Vec<TableDef>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TableDef {
     pub name: String,
     pub columns: Vec<ColumnDef>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ColumnDef {
     pub name: String,
-    #[serde(rename = "type", alias = "typ")]
+    #[cfg_attr(feature = "serde", serde(rename = "type", alias = "typ"))]
     pub typ: String,
     /// Type is an array (e.g., text[], uuid[])
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_array: bool,
     /// Type parameters (e.g., varchar(255) -> Some(vec!["255"]), decimal(10,2) -> Some(vec!["10", "2"]))
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub type_params: Option<Vec<String>>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub nullable: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub primary_key: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub unique: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub references: Option<String>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub default_value: Option<String>,
     /// Check constraint expression
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub check: Option<String>,
     /// Is this a serial/auto-increment type
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub is_serial: bool,
 }
 
@@ -101,11 +105,13 @@ impl Schema {
     }
 
     /// Export schema to JSON string (for qail-macros compatibility)
+    #[cfg(feature = "serde")]
     pub fn to_json(&self) -> Result<String, String> {
         serde_json::to_string_pretty(self).map_err(|e| format!("JSON serialization failed: {}", e))
     }
 
     /// Import schema from JSON string
+    #[cfg(feature = "serde")]
     pub fn from_json(json: &str) -> Result<Self, String> {
         serde_json::from_str(json).map_err(|e| format!("JSON deserialization failed: {}", e))
     }
@@ -116,7 +122,10 @@ impl Schema {
             std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
 
         if content.trim().starts_with('{') {
-            Self::from_json(&content)
+            #[cfg(feature = "serde")]
+            return Self::from_json(&content);
+            #[cfg(not(feature = "serde"))]
+            return Err("JSON schema files require the `serde` feature".to_string());
         } else {
             Self::parse(&content)
         }
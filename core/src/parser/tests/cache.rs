@@ -0,0 +1,42 @@
+use crate::parser::cache::*;
+use crate::parser::parse;
+
+#[test]
+fn test_parse_cached_returns_equal_ast() {
+    clear_parse_cache();
+    let query = "get users fields id, email where active = true limit 10";
+
+    let first = parse_cached(query).unwrap();
+    let second = parse_cached(query).unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(first, parse(query).unwrap());
+    assert_eq!(parse_cache_stats(), ParseCacheStats { hits: 1, misses: 1 });
+    assert_eq!(parse_cache_len(), 1);
+}
+
+#[test]
+fn test_parse_cached_skips_errors() {
+    clear_parse_cache();
+    assert!(parse_cached("get").is_err());
+    assert!(parse_cached("get").is_err());
+    assert_eq!(parse_cache_len(), 0);
+    assert_eq!(parse_cache_stats().hits, 0);
+}
+
+#[test]
+fn test_parse_cache_evicts_lru() {
+    clear_parse_cache();
+    set_parse_cache_capacity(2);
+
+    parse_cached("get a").unwrap();
+    parse_cached("get b").unwrap();
+    parse_cached("get c").unwrap();
+    assert_eq!(parse_cache_len(), 2);
+
+    // "get a" was evicted, so this is a miss
+    parse_cached("get a").unwrap();
+    assert_eq!(parse_cache_stats(), ParseCacheStats { hits: 0, misses: 4 });
+
+    set_parse_cache_capacity(DEFAULT_PARSE_CACHE_CAPACITY);
+}
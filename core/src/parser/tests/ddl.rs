@@ -34,6 +34,34 @@ fn test_make_with_default_uuid() {
     }
 }
 
+#[test]
+fn test_make_and_index_if_not_exists() {
+    let cmd = parse("make? users id:uuid:pk").unwrap();
+    assert_eq!(cmd.action, Action::Make);
+    assert_eq!(cmd.table, "users");
+    assert!(cmd.if_not_exists);
+    assert!(!parse("make users id:uuid:pk").unwrap().if_not_exists);
+
+    let cmd = parse("index? idx_email on users email").unwrap();
+    assert_eq!(cmd.action, Action::Index);
+    assert!(cmd.if_not_exists);
+    assert_eq!(cmd.index_def.unwrap().name, "idx_email");
+}
+
+#[test]
+fn test_truncate_options() {
+    let cmd = parse("truncate events").unwrap();
+    assert_eq!(cmd.action, Action::Truncate);
+    assert_eq!(cmd.table, "events");
+    assert!(!cmd.restart_identity && !cmd.cascade);
+
+    let cmd = parse("truncate audit.events restart identity cascade").unwrap();
+    assert_eq!(cmd.schema.as_deref(), Some("audit"));
+    assert_eq!(cmd.table, "events");
+    assert!(cmd.restart_identity && cmd.cascade);
+    assert_eq!(parse(&cmd.to_qail()).unwrap(), cmd);
+}
+
 #[test]
 fn test_make_with_default_numeric() {
     let q = "make stats count:bigint:default=0";
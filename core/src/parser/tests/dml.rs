@@ -41,9 +41,197 @@ fn test_update_multiple_values() {
     );
 }
 
+#[test]
+fn test_set_expression_and_subquery_values() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse("set counters values hits = hits + 1, total = 2 * price where id = $1").unwrap();
+    let payload = &cmd.cages[0];
+    assert_eq!(
+        payload.conditions[0].value,
+        Value::Expr(Box::new(Expr::Binary {
+            left: Box::new(Expr::Named("hits".to_string())),
+            op: BinaryOp::Add,
+            right: Box::new(Expr::Named("1".to_string())),
+            alias: None,
+        }))
+    );
+    assert_eq!(
+        cmd.to_sql(),
+        "UPDATE counters SET hits = (hits + 1), total = (2 * price) WHERE id = $1"
+    );
+
+    let cmd = parse(
+        "set users values total = (get orders fields sum(amount) where user_id = users.id) \
+         where active = true",
+    )
+    .unwrap();
+    assert!(matches!(cmd.cages[0].conditions[0].value, Value::Subquery(_)));
+    assert_eq!(
+        cmd.to_sql(),
+        "UPDATE users SET total = (SELECT SUM(amount) FROM orders WHERE user_id = users.id) \
+         WHERE active = true"
+    );
+}
+
 #[test]
 fn test_delete_with_filter() {
     let cmd = parse("del sessions where user_id = $1 and expired = true").unwrap();
     assert_eq!(cmd.action, Action::Del);
     assert_eq!(cmd.cages[0].conditions.len(), 2);
 }
+
+#[test]
+fn test_add_from_select() {
+    let cmd = parse("add archive from (get orders where created < $1)").unwrap();
+    assert_eq!(cmd.action, Action::Add);
+    let source = cmd.source_query.expect("source query");
+    assert_eq!(source.action, Action::Get);
+    assert_eq!(source.table, "orders");
+    assert_eq!(source.cages[0].conditions[0].value, Value::Param(1));
+}
+
+#[test]
+fn test_add_arrow_select() {
+    use crate::transpiler::ToSql;
+
+    let arrow = parse("add archive <- get orders where created < $1").unwrap();
+    let from = parse("add archive from (get orders where created < $1)").unwrap();
+    assert_eq!(arrow, from);
+    assert_eq!(
+        arrow.to_sql(),
+        "INSERT INTO archive SELECT * FROM orders WHERE created < $1 RETURNING *"
+    );
+}
+
+#[test]
+fn test_get_from_derived_table() {
+    let cmd = parse("get (get orders fields id, total where status = 'paid') as p fields id where total > 100")
+        .unwrap();
+    assert_eq!(cmd.action, Action::Get);
+    assert_eq!(cmd.table, "p");
+    assert_eq!(cmd.columns, vec![Expr::Named("id".to_string())]);
+    assert_eq!(cmd.cages[0].conditions[0].value, Value::Int(100));
+    let derived = cmd.source_query.expect("derived table");
+    assert_eq!(derived.table, "orders");
+    assert_eq!(derived.cages[0].conditions[0].value, Value::String("paid".to_string()));
+}
+
+#[test]
+fn test_lateral_join_subquery() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse(
+        "get users lateral join (get orders fields total where orders.user_id = users.id \
+         order by total desc limit 3) as recent fields users.name, recent.total",
+    )
+    .unwrap();
+    let join = &cmd.joins[0];
+    assert_eq!(join.kind, JoinKind::Lateral);
+    assert_eq!(join.table, "recent");
+    assert!(join.on_true);
+    let sub = join.subquery.as_ref().expect("lateral subquery");
+    assert_eq!(sub.table, "orders");
+    assert_eq!(sub.cages[0].conditions[0].value, Value::Column("users.id".to_string()));
+
+    let sql = cmd.to_sql();
+    let explicit = parse(
+        "get users left join lateral (get orders fields total where orders.user_id = users.id \
+         order by total desc limit 3) as recent on true fields users.name, recent.total",
+    )
+    .unwrap();
+    assert_eq!(explicit.to_sql(), sql);
+}
+
+#[test]
+fn test_derived_table_requires_query() {
+    assert!(parse("get (del users where id = 1) as p").is_err());
+    assert!(parse("get (set users values name = 'x') as p").is_err());
+    assert!(parse("get users lateral join (del orders where id = 1) as recent").is_err());
+    assert!(parse("get users left join lateral (add orders fields id values 1) as r on true").is_err());
+    assert!(parse("get (with t as (get orders) get t) as p").is_ok());
+}
+
+#[test]
+fn test_del_using() {
+    let cmd = parse("del orders using customers where orders.customer_id = customers.id").unwrap();
+    assert_eq!(cmd.action, Action::Del);
+    assert_eq!(cmd.using_tables, vec!["customers".to_string()]);
+    assert_eq!(
+        cmd.cages[0].conditions[0].value,
+        Value::Column("customers.id".to_string())
+    );
+}
+
+#[test]
+fn test_set_from() {
+    let cmd = parse(
+        "set orders values status = 'vip' from customers, tiers where orders.customer_id = customers.id",
+    )
+    .unwrap();
+    assert_eq!(cmd.action, Action::Set);
+    assert_eq!(
+        cmd.from_tables,
+        vec!["customers".to_string(), "tiers".to_string()]
+    );
+    assert_eq!(cmd.cages.len(), 2); // Payload + Filter
+}
+
+#[test]
+fn test_add_default_values() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse("add users fields id, name, email values default, 'Alice', $1").unwrap();
+    let values: Vec<&Value> = cmd.cages[0].conditions.iter().map(|c| &c.value).collect();
+    assert_eq!(
+        values,
+        [&Value::Default, &Value::String("Alice".to_string()), &Value::Param(1)]
+    );
+    assert!(
+        cmd.to_sql()
+            .starts_with("INSERT INTO users (id, name, email) VALUES (DEFAULT, 'Alice', $1)")
+    );
+
+    let cmd = parse("set users values name = default where id = $1").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].value, Value::Default);
+}
+
+#[test]
+fn test_postgres_symbolic_operators() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse("get posts fields id where tags && $1 and meta @> $2").unwrap();
+    let ops: Vec<Operator> = cmd.cages[0].conditions.iter().map(|c| c.op).collect();
+    assert_eq!(ops, [Operator::Overlaps, Operator::Contains]);
+    assert_eq!(cmd.to_sql(), "SELECT id FROM posts WHERE tags && $1 AND meta @> $2");
+
+    let cmd = parse("get items fields id where embedding <-> $1 order by id").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].op, Operator::custom("<->").unwrap());
+    assert_eq!(cmd.to_sql(), "SELECT id FROM items WHERE embedding <-> $1 ORDER BY id ASC");
+
+    // Longest symbol wins; `<=` / `<` still parse as before
+    let cmd = parse("get items where a <=> $1 and b <= 2 and c < 3").unwrap();
+    let ops: Vec<Operator> = cmd.cages[0].conditions.iter().map(|c| c.op).collect();
+    assert_eq!(ops, [Operator::custom("<=>").unwrap(), Operator::Lte, Operator::Lt]);
+}
+
+#[test]
+fn test_custom_operator_allowlist() {
+    assert!(Operator::custom("@@").is_ok());
+    for symbol in ["||", "; drop table users; --", "=", ""] {
+        assert!(
+            matches!(Operator::custom(symbol), Err(crate::error::QailError::InvalidOperator(_))),
+            "{:?} accepted",
+            symbol
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_custom_operator_serde_checks_allowlist() {
+    let op: Operator = serde_json::from_str(r#"{"Custom":"<->"}"#).unwrap();
+    assert_eq!(op, Operator::custom("<->").unwrap());
+    assert_eq!(serde_json::to_string(&op).unwrap(), r#"{"Custom":"<->"}"#);
+    assert!(serde_json::from_str::<Operator>(r#"{"Custom":"1=1 OR"}"#).is_err());
+}
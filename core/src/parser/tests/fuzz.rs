@@ -0,0 +1,188 @@
+//! Robustness tests: `parse` must return `Ok` or `Err` for any input, never panic.
+//!
+//! Inputs are derived deterministically from a seed corpus (truncations,
+//! byte-level mutations, token splices) so failures are reproducible.
+//! `core/fuzz` runs the same property under libFuzzer.
+
+use crate::parser::grammar::MAX_NESTING_DEPTH;
+use crate::parser::parse;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+const CORPUS: &[&str] = &[
+    "get users",
+    "get users fields id, email where active = true order by created_at desc limit 10",
+    "get users fields id order by last_name asc, created_at desc nulls last",
+    "get orders fields count(*) as n, sum(total) group by user_id having count(*) > 5",
+    "get users join orders on users.id = orders.user_id where orders.total > 100",
+    "get users where name ~ 'jo%' and email ~= '100%' or id in (1, 2, 3)",
+    "get users fields data->>'name', tags[1], price::numeric(10, 2)",
+    "get users fields case when age > 18 then 'adult' else 'minor' end as bucket",
+    "get users fields rank() over (partition by dept order by salary desc)",
+    "get distinct on (email) users fields id, email",
+    "with big as materialized (get orders fields id) get big",
+    "with recursive t as (get nodes) get t",
+    "set users values verified = true where id = $1",
+    "add users fields email, name values 'a@b.c', 'Ann' returning id",
+    "add users values email = :email on conflict (email) do nothing",
+    "del sessions where expired_at < now() - interval '1 day'",
+    "make users id uuid primary key, email text unique not null, age int default 0",
+    "make? events id serial, payload jsonb",
+    "index idx_email on users email unique",
+    "alter users add column bio text",
+    "drop users",
+    "begin",
+    "savepoint sp1",
+    "listen orders_channel",
+    "notify orders_channel 'hello'",
+    "get users where name = 'héllo wörld ✓' limit 1",
+    "get users fields id where id = $1 for update skip locked",
+    "get orders fields count(*) filter (where status = 'paid' and total > 10) as paid",
+    "get users where created_at > now() - 24h and id = $1",
+    "get users fields raw!(vendor_fn(id, 'a)b')) as v where t > raw!(now() at time zone 'utc')",
+];
+
+/// Odd inputs that stress slicing and char boundaries.
+const SNIPPETS: &[&str] = &[
+    "", " ", "(", ")", "'", "\"", "[", "]", "::", "->", "->>", "$", "$0", ":",
+    "é", "✓", "•", "🦀", "\u{0}", "\t", "\n", "--", "/*", "*/", ",", "=", "~",
+    "get", "fields", "where", "order by", "limit", "with", "as", "join", "on",
+];
+
+/// xorshift64: tiny deterministic generator, good enough for input mutation.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+fn assert_no_panic(input: &str) {
+    let result = catch_unwind(AssertUnwindSafe(|| parse(input)));
+    assert!(result.is_ok(), "parse panicked on input {:?}", input);
+}
+
+fn mutate(rng: &mut Rng, seed: &str) -> String {
+    let mut s = seed.to_string();
+    for _ in 0..=rng.below(3) {
+        let boundaries: Vec<usize> = (0..=s.len()).filter(|&i| s.is_char_boundary(i)).collect();
+        let at = boundaries[rng.below(boundaries.len())];
+        match rng.below(4) {
+            0 => s.insert_str(at, rng.pick(SNIPPETS)),
+            1 => s.truncate(at),
+            2 => {
+                let end = boundaries[rng.below(boundaries.len())].max(at);
+                s.replace_range(at..end, "");
+            }
+            _ => {
+                let other = rng.pick(CORPUS);
+                let cut = (0..=other.len())
+                    .filter(|&i| other.is_char_boundary(i))
+                    .nth(rng.below(other.chars().count() + 1))
+                    .unwrap_or(other.len());
+                s.insert_str(at, &other[cut..]);
+            }
+        }
+    }
+    s
+}
+
+#[test]
+fn test_parse_corpus_prefixes_never_panic() {
+    for seed in CORPUS {
+        for (i, _) in seed.char_indices().chain([(seed.len(), ' ')]) {
+            assert_no_panic(&seed[..i]);
+            assert_no_panic(&seed[i..]);
+        }
+    }
+}
+
+#[test]
+fn test_parse_mutated_corpus_never_panics() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..20_000 {
+        let seed = rng.pick(CORPUS);
+        assert_no_panic(&mutate(&mut rng, seed));
+    }
+}
+
+#[test]
+fn test_parse_token_soup_never_panics() {
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+    for _ in 0..20_000 {
+        let len = rng.below(8);
+        let input: String = (0..len).map(|_| rng.pick(SNIPPETS)).collect::<Vec<_>>().join(" ");
+        assert_no_panic(&input);
+    }
+}
+
+// ============= Regressions =============
+
+#[test]
+fn test_filter_value_multibyte_char_at_and_lookahead() {
+    // Byte 4 of the lookahead window fell inside '✓'
+    assert_no_panic("get orders fields count(*) filter (where status = x ab✓)");
+    assert_no_panic("get orders fields count(*) filter (where status = xé✓ and b = 1)");
+}
+
+#[test]
+fn test_deep_nesting_is_an_error_not_a_stack_overflow() {
+    let deep = format!("get users where x = {}1{}", "(".repeat(10_000), ")".repeat(10_000));
+    let err = parse(&deep).unwrap_err();
+    assert!(err.to_string().contains("Nesting deeper than"), "{}", err);
+    let span = err.span().unwrap();
+    assert_eq!(span.start, "get users where x = ".len() + MAX_NESTING_DEPTH);
+
+    // Parentheses inside string literals don't count
+    let quoted = format!("get users where x = '{}'", "(".repeat(10_000));
+    assert!(parse(&quoted).is_ok());
+
+    // The limit itself is accepted by the depth check
+    let at_limit = format!(
+        "get users fields {}1{}",
+        "(".repeat(MAX_NESTING_DEPTH),
+        ")".repeat(MAX_NESTING_DEPTH)
+    );
+    assert_no_panic(&at_limit);
+    assert!(!matches!(parse(&at_limit), Err(e) if e.to_string().contains("Nesting")));
+}
+
+#[test]
+fn test_deep_case_nesting_is_an_error_not_a_stack_overflow() {
+    let nested = |depth: usize| {
+        format!(
+            "get users fields {}1{}",
+            "case when a = 1 then ".repeat(depth),
+            " else 0 end".repeat(depth)
+        )
+    };
+    let err = parse(&nested(10_000)).unwrap_err();
+    assert!(err.to_string().contains("Nesting deeper than"), "{}", err);
+    let span = err.span().unwrap();
+    assert_eq!(
+        span.start,
+        "get users fields ".len() + "case when a = 1 then ".len() * MAX_NESTING_DEPTH
+    );
+
+    // `case` in strings and quoted identifiers doesn't count; `cases` is a column
+    let words = format!(
+        "get users fields \"{}\" where x = 'case' and cases = 1",
+        "case ".repeat(100)
+    );
+    assert!(!matches!(parse(&words), Err(e) if e.to_string().contains("Nesting")));
+
+    let at_limit = nested(MAX_NESTING_DEPTH);
+    assert_no_panic(&at_limit);
+    assert!(!matches!(parse(&at_limit), Err(e) if e.to_string().contains("Nesting")));
+}
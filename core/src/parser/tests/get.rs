@@ -57,6 +57,22 @@ fn test_v2_get_with_limit() {
     assert_eq!(limit_cage.unwrap().kind, CageKind::Limit(10));
 }
 
+#[test]
+fn test_v2_limit_all_and_fetch_first() {
+    let cmd = parse("get users limit all offset 20").unwrap();
+    assert_eq!(cmd.cages[0].kind, CageKind::LimitAll);
+
+    let cmd = parse("get scores order by points desc fetch first 5 rows with ties").unwrap();
+    assert_eq!(cmd.fetch, Some((5, true)));
+    let cmd = parse("get scores offset 10 fetch next 1 row only").unwrap();
+    assert_eq!(cmd.fetch, Some((1, false)));
+
+    // WITH TIES needs an ORDER BY to know which rows tie
+    assert!(parse("get scores fetch first 5 rows with ties").is_err());
+    // LIMIT and FETCH FIRST are alternatives
+    assert!(parse("get scores limit 5 fetch first 5 rows only").is_err());
+}
+
 #[test]
 fn test_v2_get_with_offset() {
     let cmd = parse("get users fields * offset 20").unwrap();
@@ -135,6 +151,23 @@ fn test_v2_fuzzy_match() {
     );
 }
 
+#[test]
+fn test_v2_regex_match() {
+    let cmd = parse("get users fields id where name ~r '^A.*z$'").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].op, Operator::Regex);
+    assert_eq!(
+        cmd.cages[0].conditions[0].value,
+        Value::String("^A.*z$".to_string())
+    );
+
+    let cmd = parse("get users fields id where name ~* '^a'").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].op, Operator::RegexI);
+
+    // `~r` only counts as an operator when not followed by an identifier
+    let cmd = parse("get users fields id where name ~rank").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].op, Operator::Fuzzy);
+}
+
 #[test]
 fn test_v2_param_in_filter() {
     let cmd = parse("get users fields id where email = $1").unwrap();
@@ -142,6 +175,29 @@ fn test_v2_param_in_filter() {
     assert_eq!(cmd.cages[0].conditions[0].value, Value::Param(1));
 }
 
+#[test]
+fn test_v2_decimal_filter_value() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse("get products fields id where price = 19.99n").unwrap();
+    assert_eq!(
+        cmd.cages[0].conditions[0].value,
+        Value::Decimal("19.99".to_string())
+    );
+    assert_eq!(cmd.to_sql(), "SELECT id FROM products WHERE price = 19.99");
+
+    // Digits beyond f64 precision survive untouched
+    let cmd = parse("get ledger fields id where amount = -12345678901234567.000000001n").unwrap();
+    assert_eq!(
+        cmd.cages[0].conditions[0].value,
+        Value::Decimal("-12345678901234567.000000001".to_string())
+    );
+
+    // Without the suffix it stays a float
+    let cmd = parse("get products fields id where price = 19.99").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].value, Value::Float(19.99));
+}
+
 #[test]
 fn test_v2_multiple_conditions() {
     let cmd = parse("get users fields * where active = true and role = \"admin\"").unwrap();
@@ -166,3 +222,166 @@ fn test_v2_full_query() {
     assert_eq!(cmd.columns.len(), 3);
     assert!(!cmd.cages.is_empty());
 }
+
+#[test]
+fn test_v2_multi_column_sort() {
+    let cmd = parse(
+        "get users fields id order by last_name asc, created_at desc nulls last, score nulls first",
+    )
+    .unwrap();
+    let sorts: Vec<(&Expr, &CageKind)> = cmd
+        .cages
+        .iter()
+        .filter(|c| matches!(c.kind, CageKind::Sort(_)))
+        .map(|c| (&c.conditions[0].left, &c.kind))
+        .collect();
+    assert_eq!(
+        sorts,
+        vec![
+            (&Expr::Named("last_name".to_string()), &CageKind::Sort(SortOrder::Asc)),
+            (&Expr::Named("created_at".to_string()), &CageKind::Sort(SortOrder::DescNullsLast)),
+            (&Expr::Named("score".to_string()), &CageKind::Sort(SortOrder::AscNullsFirst)),
+        ]
+    );
+}
+
+#[test]
+fn test_v2_multi_column_sort_sql_order() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse("get users fields id order by b desc, a, c asc nulls last limit 5").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM users ORDER BY b DESC, a ASC, c ASC NULLS LAST LIMIT 5"
+    );
+}
+
+#[test]
+fn test_v2_cte_materialization_hints() {
+    let cmd = parse("with big as materialized (get orders fields id) get big").unwrap();
+    assert_eq!(cmd.ctes[0].materialized, Some(true));
+
+    let cmd = parse("with big as not materialized (get orders fields id) get big").unwrap();
+    assert_eq!(cmd.ctes[0].materialized, Some(false));
+
+    let cmd = parse("with big as (get orders fields id) get big").unwrap();
+    assert_eq!(cmd.ctes[0].materialized, None);
+}
+
+#[test]
+fn test_v2_scalar_subquery_column() {
+    let cmd = parse(
+        "get users fields id, (get orders fields count(*) where user_id = users.id) as order_count",
+    )
+    .unwrap();
+    assert!(matches!(
+        &cmd.columns[1],
+        Expr::Subquery { alias: Some(a), .. } if a == "order_count"
+    ));
+    assert_eq!(parse(&cmd.to_qail()).unwrap(), cmd);
+
+    // A parenthesized expression is still a grouped expression
+    let cmd = parse("get t fields (a + b) as s").unwrap();
+    assert!(matches!(&cmd.columns[0], Expr::Binary { .. }));
+}
+
+#[test]
+fn test_v2_raw_fragment() {
+    let cmd = parse(
+        "get users fields id, raw!(vendor_fn(id, 'a)b')) as v where created_at > raw!(now() at time zone 'utc')",
+    )
+    .unwrap();
+    assert_eq!(cmd.columns[1], Expr::Raw("vendor_fn(id, 'a)b') AS v".to_string()));
+    assert_eq!(
+        cmd.cages[0].conditions[0].value,
+        Value::Expr(Box::new(Expr::Raw("now() at time zone 'utc'".to_string())))
+    );
+    assert_eq!(parse(&cmd.to_qail()).unwrap(), cmd);
+
+    // Only `raw!(` starts a fragment
+    let cmd = parse("get raw_events fields raw_payload").unwrap();
+    assert_eq!(cmd.columns[0], Expr::Named("raw_payload".to_string()));
+    let cmd = parse("get users fields raw(id)").unwrap();
+    assert!(matches!(&cmd.columns[0], Expr::FunctionCall { name, .. } if name == "raw"));
+    assert!(parse("get users fields raw!(unterminated").is_err());
+
+    // Aliases are pasted into the fragment, so they must be one identifier
+    let cmd = parse("get users fields raw!(x) as \"Total\"").unwrap();
+    assert_eq!(cmd.columns[0], Expr::Raw("x AS \"Total\"".to_string()));
+    assert!(parse("get users fields raw!(x) as a.b").is_err());
+    assert!(parse("get users fields raw!(x) as \"a\"\"b\"").is_err());
+}
+
+#[test]
+fn test_v2_aggregate_filter() {
+    let cmd = parse("get orders fields region, sum(total) filter (where status = $1) as paid").unwrap();
+    let Expr::Aggregate { func, filter: Some(filter), alias, .. } = &cmd.columns[1] else {
+        panic!("expected filtered aggregate, got {:?}", cmd.columns[1]);
+    };
+    assert_eq!(*func, AggregateFunc::Sum);
+    assert_eq!(alias.as_deref(), Some("paid"));
+    assert_eq!(filter[0].value, Value::Param(1));
+    assert_eq!(parse(&cmd.to_qail()).unwrap(), cmd);
+
+    // FILTER conditions are ANDed; OR has no representation
+    assert!(parse("get orders fields count(*) filter (where a = 1 or b = 2)").is_err());
+}
+
+#[test]
+fn test_v2_ordered_aggregates() {
+    let cmd = parse("get orders fields array_agg(id order by created_at desc, id) as ids").unwrap();
+    let Expr::Aggregate { func, order_by: Some(order), alias, .. } = &cmd.columns[0] else {
+        panic!("expected ordered aggregate, got {:?}", cmd.columns[0]);
+    };
+    assert_eq!(*func, AggregateFunc::ArrayAgg);
+    assert_eq!(
+        order,
+        &vec![
+            ("created_at".to_string(), SortOrder::Desc),
+            ("id".to_string(), SortOrder::Asc),
+        ]
+    );
+    assert_eq!(alias.as_deref(), Some("ids"));
+
+    let cmd = parse("get users fields string_agg(name, ',' order by name)").unwrap();
+    let Expr::Aggregate { func, delimiter, order_by, .. } = &cmd.columns[0] else {
+        panic!("expected string_agg aggregate, got {:?}", cmd.columns[0]);
+    };
+    assert_eq!(*func, AggregateFunc::StringAgg);
+    assert_eq!(delimiter.as_deref(), Some(","));
+    assert_eq!(order_by, &Some(vec![("name".to_string(), SortOrder::Asc)]));
+
+    // ORDER BY inside the parentheses is only for aggregates
+    assert!(parse("get users fields lower(name order by name)").is_err());
+}
+
+#[test]
+fn test_v2_unicode_quoted_identifiers() {
+    use crate::transpiler::ToSql;
+
+    let cmd = parse(r#"get "Café Menü" fields "Prénom", größe where "Prénom" = 'Zoë' order by größe"#)
+        .unwrap();
+    assert_eq!(cmd.table, "\"Café Menü\"");
+    assert_eq!(
+        cmd.columns,
+        vec![Expr::Named("\"Prénom\"".to_string()), Expr::Named("größe".to_string())]
+    );
+    assert_eq!(
+        cmd.to_sql(),
+        r#"SELECT "Prénom", "größe" FROM "Café Menü" WHERE "Prénom" = 'Zoë' ORDER BY "größe" ASC"#
+    );
+
+    // Quoted names keep their case and are never split on `.`
+    let cmd = parse(r#"get "Users" fields "FirstName", "a.b", "Sales".total"#).unwrap();
+    assert_eq!(cmd.to_sql(), r#"SELECT "FirstName", "a.b", "Sales".total FROM "Users""#);
+    assert_eq!(
+        cmd.to_sql_with_dialect(crate::transpiler::Dialect::MySQL),
+        "SELECT `FirstName`, `a.b`, `Sales`.`total` FROM `Users`"
+    );
+    let cmd = parse(r#"get "My Schema"."Users""#).unwrap();
+    assert_eq!(cmd.to_sql(), r#"SELECT * FROM "My Schema"."Users""#);
+
+    // Double quotes on the right-hand side are still string values
+    let cmd = parse(r#"get users where role = "admin""#).unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].value, Value::String("admin".to_string()));
+}
@@ -0,0 +1,70 @@
+use crate::ast::{Action, CageKind};
+use crate::error::line_col;
+use crate::parser::parse_many;
+
+#[test]
+fn test_parse_many_with_multiline_cte() {
+    let file = "\
+-- users who ordered this week
+with recent as (
+    get orders
+    fields user_id
+
+    where created_at > :since
+)
+get users
+join recent on recent.user_id = users.id
+fields id, email
+
+get products fields id, name
+where note = 'restock; soon'
+limit 10
+
+/* cleanup
+   job */
+del sessions where expired = true
+";
+    let cmds: Vec<_> = parse_many(file)
+        .into_iter()
+        .map(|r| r.expect("statement should parse"))
+        .collect();
+    assert_eq!(cmds.len(), 3);
+
+    assert_eq!(cmds[0].table, "users");
+    assert_eq!(cmds[0].ctes.len(), 1);
+    assert_eq!(cmds[0].ctes[0].name, "recent");
+    assert_eq!(cmds[0].ctes[0].base_query.table, "orders");
+    assert_eq!(cmds[0].joins.len(), 1);
+
+    assert_eq!(cmds[1].table, "products");
+    assert!(
+        cmds[1]
+            .cages
+            .iter()
+            .any(|c| matches!(c.kind, CageKind::Limit(10)))
+    );
+
+    assert_eq!(cmds[2].action, Action::Del);
+    assert_eq!(cmds[2].table, "sessions");
+}
+
+#[test]
+fn test_parse_many_semicolons_and_errors() {
+    let file = "get a fields id; get b fields id;\n\n;\nget c fields id limit abc\n\nget d";
+    let results = parse_many(file);
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap().table, "a");
+    assert_eq!(results[1].as_ref().unwrap().table, "b");
+    assert_eq!(results[3].as_ref().unwrap().table, "d");
+
+    // The error span points into the whole file, not the statement
+    let span = results[2].as_ref().unwrap_err().span().unwrap();
+    assert_eq!(&file[span.start..span.end], "limit");
+    assert_eq!(line_col(file, span.start), (3, 16));
+}
+
+#[test]
+fn test_parse_many_empty_input() {
+    assert!(parse_many("").is_empty());
+    assert!(parse_many("\n\n  -- nothing here\n;\n").is_empty());
+}
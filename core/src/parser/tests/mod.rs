@@ -1,7 +1,12 @@
+mod cache;
 mod ddl;
 mod dml;
+mod fuzz;
 mod get;
 mod index;
 mod joins;
+mod many;
+mod spans;
 mod tokens;
 mod txn;
+mod v1;
@@ -0,0 +1,40 @@
+use crate::error::{QailError, Span, line_col};
+use crate::parser::parse;
+
+fn span_of(q: &str) -> Span {
+    match parse(q) {
+        Err(e @ QailError::Parse { .. }) => e.span().expect("parse error should carry a span"),
+        other => panic!("expected parse error for {:?}, got {:?}", q, other),
+    }
+}
+
+#[test]
+fn test_span_points_at_unknown_action() {
+    let q = "gte users fields id";
+    let span = span_of(q);
+    assert_eq!(span, Span::new(0, 3));
+    assert_eq!(&q[span.start..span.end], "gte");
+}
+
+#[test]
+fn test_span_points_at_unparsed_clause() {
+    let q = "get users fields id limit abc";
+    let span = span_of(q);
+    assert_eq!(&q[span.start..span.end], "limit");
+}
+
+#[test]
+fn test_span_points_at_trailing_token() {
+    let q = "get users fields id limit 5 extra";
+    let span = span_of(q);
+    assert_eq!(span, Span::new(28, 33));
+    assert_eq!(&q[span.start..span.end], "extra");
+}
+
+#[test]
+fn test_span_is_relative_to_untrimmed_input() {
+    let q = "\n  get users\n  fields id\n  bogus";
+    let span = span_of(q);
+    assert_eq!(&q[span.start..span.end], "bogus");
+    assert_eq!(line_col(q, span.start), (3, 2));
+}
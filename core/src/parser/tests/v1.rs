@@ -0,0 +1,51 @@
+use crate::error::QailError;
+use crate::parser::{parse, parse_many};
+
+#[test]
+fn test_v2_query_with_symbols_still_parses() {
+    // `::` casts, `@>` and `@@` are v2
+    let q = "get users fields id, total::text where name = 'a@b.com' and tags @> $1 and body @@ $2";
+    assert!(parse(q).is_ok());
+}
+
+#[test]
+fn test_v1_action_rejected_as_forbidden() {
+    match parse("get::users:'id'email[active=true]") {
+        Err(QailError::SyntaxVersionForbidden { position, token }) => {
+            assert_eq!(position, 3);
+            assert_eq!(token, "::");
+        }
+        other => panic!("expected SyntaxVersionForbidden, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_v1_symbols_rejected_as_forbidden() {
+    assert!(matches!(
+        parse("get users fields id•email"),
+        Err(QailError::SyntaxVersionForbidden { position: 19, .. })
+    ));
+    assert!(matches!(
+        parse("get users fields @id"),
+        Err(QailError::SyntaxVersionForbidden { position: 17, .. })
+    ));
+    // Other syntax errors are still ordinary parse errors
+    assert!(matches!(parse("get users fields"), Err(QailError::Parse { .. })));
+}
+
+#[test]
+fn test_v1_position_counts_leading_whitespace() {
+    assert!(matches!(
+        parse("\n  get::users"),
+        Err(QailError::SyntaxVersionForbidden { position: 6, .. })
+    ));
+    assert!(matches!(
+        parse("  get users fields @id"),
+        Err(QailError::SyntaxVersionForbidden { position: 19, .. })
+    ));
+    // parse_many reports it against the whole input
+    assert!(matches!(
+        parse_many("get users;\nget users fields @id")[1],
+        Err(QailError::SyntaxVersionForbidden { position: 28, .. })
+    ));
+}
@@ -21,27 +21,31 @@
 //! ```
 
 use crate::validator::Validator;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Schema {
     pub tables: Vec<TableDef>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TableDef {
     pub name: String,
     pub columns: Vec<ColumnDef>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ColumnDef {
     pub name: String,
-    #[serde(rename = "type", alias = "typ")]
+    #[cfg_attr(feature = "serde", serde(rename = "type", alias = "typ"))]
     pub typ: String,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub nullable: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub primary_key: bool,
 }
 
@@ -67,6 +71,7 @@ impl Schema {
     }
 
     /// Load schema from JSON string.
+    #[cfg(feature = "serde")]
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
@@ -151,7 +156,10 @@ impl Schema {
 
         // Detect format: .json -> JSON, else -> QAIL schema
         if path.extension().map(|e| e == "json").unwrap_or(false) {
-            Self::from_json(&content).map_err(|e| e.to_string())
+            #[cfg(feature = "serde")]
+            return Self::from_json(&content).map_err(|e| e.to_string());
+            #[cfg(not(feature = "serde"))]
+            return Err("JSON schema files require the `serde` feature".to_string());
         } else {
             Self::from_qail_schema(&content)
         }
@@ -206,6 +214,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_schema_from_json() {
         let json = r#"{
             "tables": [{
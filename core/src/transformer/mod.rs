@@ -10,13 +10,20 @@
 //! ```text
 //! SQL String → Parser → Statement AST → Pattern Matcher → PatternData → Target Emitter → QAIL Code
 //! ```
+//!
+//! [`renumber_params`] rewrites `$n` placeholders after composing QAIL ASTs.
+//! [`expand_star`] replaces `*` with a table's columns from a [`Schema`](crate::schema::Schema).
 
 mod traits;
 mod patterns;
 mod clauses;
 mod registry;
+mod params;
+mod star;
 
 pub use traits::*;
 pub use patterns::*;
 pub use clauses::*;
 pub use registry::*;
+pub use params::renumber_params;
+pub use star::expand_star;
@@ -0,0 +1,266 @@
+//! Positional parameter renumbering for composed ASTs
+//!
+//! Each `Qail` node (the command, CTEs, subqueries, set operations) is a
+//! separate parameter scope: `$1` in a subquery and `$1` in the outer query
+//! are different values once the two are composed. Within one scope a
+//! repeated `$n` keeps referring to a single value.
+
+use crate::ast::{Cage, CageKind, Condition, ConflictAction, Expr, Qail, Value};
+use std::collections::HashMap;
+
+/// Rewrite every `$n` placeholder in `cmd` to a fresh contiguous sequence.
+///
+/// Placeholders are numbered in the order their clauses are emitted (CTEs,
+/// columns, FROM subquery, joins, SET/VALUES, WHERE, HAVING, ..., set operations), each
+/// scope getting its own numbers. Returns the rewritten command and the
+/// `(old, new)` pairs in `new` order, so `mapping[i].1 == i + 1`.
+///
+/// ```ignore
+/// let outer = parse("get users where org_id = $1")?;
+/// let inner = parse("get orders fields user_id where total > $1")?;
+/// let cmd = outer.filter("id", Operator::Eq, Value::AnySubquery(Box::new(inner)));
+/// let (cmd, mapping) = renumber_params(cmd);
+/// assert_eq!(mapping, vec![(1, 1), (1, 2)]); // inner $1 is now $2
+/// ```
+pub fn renumber_params(mut cmd: Qail) -> (Qail, Vec<(usize, usize)>) {
+    let mut renumberer = Renumberer::default();
+    renumberer.qail(&mut cmd);
+    (cmd, renumberer.mapping)
+}
+
+#[derive(Default)]
+struct Renumberer {
+    mapping: Vec<(usize, usize)>,
+}
+
+type Scope = HashMap<usize, usize>;
+
+impl Renumberer {
+    fn param(&mut self, scope: &mut Scope, old: usize) -> usize {
+        *scope.entry(old).or_insert_with(|| {
+            let new = self.mapping.len() + 1;
+            self.mapping.push((old, new));
+            new
+        })
+    }
+
+    fn qail(&mut self, cmd: &mut Qail) {
+        for cte in &mut cmd.ctes {
+            self.qail(&mut cte.base_query);
+            if let Some(recursive) = &mut cte.recursive_query {
+                self.qail(recursive);
+            }
+        }
+
+        let scope = &mut Scope::new();
+
+        for expr in cmd.distinct_on.iter_mut().chain(&mut cmd.columns) {
+            self.expr(scope, expr);
+        }
+        // Derived table in FROM, INSERT ... SELECT source or view definition
+        if let Some(source) = &mut cmd.source_query {
+            self.qail(source);
+        }
+        for join in &mut cmd.joins {
+            if let Some(query) = &mut join.subquery {
+                self.qail(query);
+            }
+            for cond in join.on.iter_mut().flatten() {
+                self.condition(scope, cond);
+            }
+        }
+
+        // SET / VALUES come before WHERE in the emitted SQL
+        let (payload, rest): (Vec<&mut Cage>, Vec<&mut Cage>) = cmd
+            .cages
+            .iter_mut()
+            .partition(|c| matches!(c.kind, CageKind::Payload));
+        let (filters, rest): (Vec<&mut Cage>, Vec<&mut Cage>) = rest
+            .into_iter()
+            .partition(|c| matches!(c.kind, CageKind::Filter));
+        for cage in payload {
+            self.cage(scope, cage);
+        }
+        for cage in filters {
+            self.cage(scope, cage);
+        }
+        for cond in &mut cmd.having {
+            self.condition(scope, cond);
+        }
+        for cage in rest {
+            self.cage(scope, cage);
+        }
+
+        if let Some(conflict) = &mut cmd.on_conflict {
+            for cond in &mut conflict.target_where {
+                self.condition(scope, cond);
+            }
+            if let ConflictAction::DoUpdate { assignments } = &mut conflict.action {
+                for (_, expr) in assignments {
+                    self.expr(scope, expr);
+                }
+            }
+        }
+        for expr in cmd.returning.iter_mut().flatten() {
+            self.expr(scope, expr);
+        }
+
+        for (_, other) in &mut cmd.set_ops {
+            self.qail(other);
+        }
+    }
+
+    fn cage(&mut self, scope: &mut Scope, cage: &mut Cage) {
+        for cond in &mut cage.conditions {
+            self.condition(scope, cond);
+        }
+    }
+
+    fn condition(&mut self, scope: &mut Scope, cond: &mut Condition) {
+        self.expr(scope, &mut cond.left);
+        self.value(scope, &mut cond.value);
+    }
+
+    fn value(&mut self, scope: &mut Scope, value: &mut Value) {
+        match value {
+            Value::Param(n) => *n = self.param(scope, *n),
+            Value::Array(items) => {
+                for item in items {
+                    self.value(scope, item);
+                }
+            }
+            Value::Subquery(query) | Value::AnySubquery(query) | Value::AllSubquery(query) => {
+                self.qail(query)
+            }
+            Value::Expr(expr) => self.expr(scope, expr),
+            _ => {}
+        }
+    }
+
+    fn expr(&mut self, scope: &mut Scope, expr: &mut Expr) {
+        match expr {
+            // The parser keeps `$n` in expression position as a name
+            Expr::Named(name) => {
+                if let Some(old) = name.strip_prefix('$').and_then(|n| n.parse().ok()) {
+                    *name = format!("${}", self.param(scope, old));
+                }
+            }
+            Expr::Literal(value) => self.value(scope, value),
+            Expr::Aggregate { arg, filter, .. } => {
+                if let Some(arg) = arg {
+                    self.expr(scope, arg);
+                }
+                for cond in filter.iter_mut().flatten() {
+                    self.condition(scope, cond);
+                }
+            }
+            Expr::Cast { expr, .. }
+            | Expr::Mod { col: expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::FieldAccess { expr, .. } => self.expr(scope, expr),
+            Expr::Window { params, order, .. } => {
+                for param in params {
+                    self.expr(scope, param);
+                }
+                for cage in order {
+                    self.cage(scope, cage);
+                }
+            }
+            Expr::Case { when_clauses, else_value, .. } => {
+                for (cond, then) in when_clauses {
+                    self.condition(scope, cond);
+                    self.expr(scope, then);
+                }
+                if let Some(else_value) = else_value {
+                    self.expr(scope, else_value);
+                }
+            }
+            Expr::FunctionCall { args, .. }
+            | Expr::ArrayConstructor { elements: args, .. }
+            | Expr::RowConstructor { elements: args, .. } => {
+                for arg in args {
+                    self.expr(scope, arg);
+                }
+            }
+            Expr::SpecialFunction { args, .. } => {
+                for (_, arg) in args {
+                    self.expr(scope, arg);
+                }
+            }
+            Expr::Binary { left, right, .. } => {
+                self.expr(scope, left);
+                self.expr(scope, right);
+            }
+            Expr::Subscript { expr, index, .. } => {
+                self.expr(scope, expr);
+                self.expr(scope, index);
+            }
+            Expr::Subquery { query, .. } | Expr::Exists { query, .. } => self.qail(query),
+            // Raw SQL is opaque: any `$n` inside it is left as written
+            Expr::Star
+            | Expr::Aliased { .. }
+            | Expr::Def { .. }
+            | Expr::JsonAccess { .. }
+            | Expr::Raw(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Operator;
+    use crate::parser::parse;
+    use crate::transpiler::ToSql;
+
+    #[test]
+    fn test_composed_queries_get_distinct_params() {
+        let inner = parse("get orders fields user_id where total > $1").unwrap();
+        let outer = parse("get users where org_id = $1")
+            .unwrap()
+            .filter("id", Operator::Eq, Value::AnySubquery(Box::new(inner)));
+
+        let (cmd, mapping) = renumber_params(outer);
+        assert_eq!(mapping, vec![(1, 1), (1, 2)]);
+
+        let sql = cmd.to_sql();
+        assert!(sql.contains("org_id = $1"), "{}", sql);
+        assert!(sql.contains("total > $2"), "{}", sql);
+    }
+
+    #[test]
+    fn test_repeated_param_in_one_scope_is_kept_shared() {
+        let cmd = parse("get users fields id where a = $2 or b = $2 and c = $1").unwrap();
+        let (cmd, mapping) = renumber_params(cmd);
+        assert_eq!(mapping, vec![(2, 1), (1, 2)]);
+
+        let sql = cmd.to_sql();
+        assert!(sql.contains("a = $1") && sql.contains("b = $1"), "{}", sql);
+        assert!(sql.contains("c = $2"), "{}", sql);
+    }
+
+    #[test]
+    fn test_derived_table_params_come_before_outer_filter() {
+        let cmd = parse("get (get orders where status = $1) as p where total > $1").unwrap();
+        let (cmd, mapping) = renumber_params(cmd);
+        assert_eq!(mapping, vec![(1, 1), (1, 2)]);
+        assert_eq!(
+            cmd.to_sql(),
+            "SELECT * FROM (SELECT * FROM orders WHERE status = $1) AS p WHERE total > $2"
+        );
+    }
+
+    #[test]
+    fn test_cte_params_come_first() {
+        let cte = parse("get orders fields user_id where status = $1").unwrap();
+        let cmd = parse("get active where user_id = $1 limit 10")
+            .unwrap()
+            .with("active", cte);
+
+        let (cmd, mapping) = renumber_params(cmd);
+        assert_eq!(mapping, vec![(1, 1), (1, 2)]);
+        let sql = cmd.to_sql();
+        assert!(sql.contains("status = $1"), "{}", sql);
+        assert!(sql.contains("user_id = $2"), "{}", sql);
+    }
+}
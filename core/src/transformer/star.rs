@@ -0,0 +1,204 @@
+//! `SELECT *` expansion against a known schema
+//!
+//! Listing the columns explicitly keeps the output shape fixed when columns
+//! are later added to a table, and tells typed row mapping exactly which
+//! columns arrive in which position.
+
+use crate::ast::{Action, Expr, Qail};
+use crate::schema::{Schema, TableDef};
+
+/// Replace `*` in the select list of `cmd` with the columns of its table,
+/// in definition order.
+///
+/// A `get` with no columns counts as `*`. With joins, `*` becomes the
+/// qualified columns of every table in FROM order (`users.id, ...,
+/// orders.id, ...`), matching what PostgreSQL returns. CTE bodies, set
+/// operation members and `INSERT ... SELECT` sources are expanded too.
+/// A `*` over any table missing from `schema` (a view the schema does not
+/// describe), or over a CTE, derived table or lateral subquery that shadows
+/// a schema table, is left as written.
+///
+/// ```ignore
+/// let schema = Schema { tables: vec![TableDef::new("users").pk("id", "uuid").column("email", "text")] };
+/// let cmd = expand_star(parse("get users")?, &schema);
+/// assert_eq!(cmd.to_sql(), "SELECT id, email FROM users");
+/// ```
+pub fn expand_star(mut cmd: Qail, schema: &Schema) -> Qail {
+    expand(&mut cmd, schema, &[]);
+    cmd
+}
+
+/// `ctes` holds the CTE names visible from `cmd`, which hide schema tables.
+fn expand(cmd: &mut Qail, schema: &Schema, ctes: &[String]) {
+    let mut scope = ctes.to_vec();
+    for cte in &mut cmd.ctes {
+        // A CTE sees the ones before it, and itself only when recursive
+        if cte.recursive {
+            scope.push(cte.name.clone());
+        }
+        expand(&mut cte.base_query, schema, &scope);
+        if let Some(recursive) = &mut cte.recursive_query {
+            expand(recursive, schema, &scope);
+        }
+        if !cte.recursive {
+            scope.push(cte.name.clone());
+        }
+    }
+    if let Some(source) = &mut cmd.source_query {
+        expand(source, schema, &scope);
+    }
+    for query in cmd.joins.iter_mut().filter_map(|j| j.subquery.as_mut()) {
+        expand(query, schema, &scope);
+    }
+    for (_, other) in &mut cmd.set_ops {
+        expand(other, schema, &scope);
+    }
+
+    if !matches!(cmd.action, Action::Get) {
+        return;
+    }
+    let implicit = cmd.columns.is_empty();
+    if !implicit && !cmd.columns.iter().any(|c| matches!(c, Expr::Star)) {
+        return;
+    }
+
+    // A `get` with a source query reads a derived table aliased as `table`;
+    // a join with a subquery is a lateral alias
+    let from = cmd.source_query.is_none().then_some(cmd.table.as_str());
+    let tables: Option<Vec<&TableDef>> = std::iter::once(from)
+        .chain(cmd.joins.iter().map(|j| j.subquery.is_none().then_some(j.table.as_str())))
+        .map(|name| {
+            let name = name.filter(|name| !scope.iter().any(|cte| cte == name))?;
+            schema.tables.iter().find(|t| t.name == name)
+        })
+        .collect();
+    let Some(tables) = tables else {
+        return;
+    };
+
+    let qualify = tables.len() > 1;
+    let star_columns: Vec<Expr> = tables
+        .iter()
+        .flat_map(|table| {
+            table.columns.iter().map(move |col| {
+                Expr::Named(if qualify {
+                    format!("{}.{}", table.name, col.name)
+                } else {
+                    col.name.clone()
+                })
+            })
+        })
+        .collect();
+
+    if implicit {
+        cmd.columns = star_columns;
+        return;
+    }
+    cmd.columns = std::mem::take(&mut cmd.columns)
+        .into_iter()
+        .flat_map(|c| match c {
+            Expr::Star => star_columns.clone(),
+            other => vec![other],
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use crate::transpiler::ToSql;
+
+    fn schema() -> Schema {
+        Schema {
+            tables: vec![
+                TableDef::new("users")
+                    .pk("id", "uuid")
+                    .column("email", "text")
+                    .column("created_at", "timestamptz"),
+                TableDef::new("orders")
+                    .pk("id", "uuid")
+                    .column("user_id", "uuid"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_expand_star_in_definition_order() {
+        let cmd = expand_star(parse("get users fields * where id = $1").unwrap(), &schema());
+        assert_eq!(
+            cmd.columns,
+            vec![
+                Expr::Named("id".to_string()),
+                Expr::Named("email".to_string()),
+                Expr::Named("created_at".to_string()),
+            ]
+        );
+        assert_eq!(
+            cmd.to_sql(),
+            "SELECT id, email, created_at FROM users WHERE id = $1"
+        );
+
+        // No field list means `*` too
+        let cmd = expand_star(parse("get users").unwrap(), &schema());
+        assert_eq!(cmd.to_sql(), "SELECT id, email, created_at FROM users");
+    }
+
+    #[test]
+    fn test_expand_star_with_join_and_unknown_tables() {
+        let cmd = parse("get users join orders on orders.user_id = users.id").unwrap();
+        let sql = expand_star(cmd, &schema()).to_sql();
+        assert!(
+            sql.starts_with(
+                "SELECT users.id, users.email, users.created_at, orders.id, orders.user_id FROM"
+            ),
+            "{}",
+            sql
+        );
+
+        let recent = parse("get orders fields *").unwrap();
+        let cmd = parse("get recent").unwrap().with("recent", recent);
+        let original = cmd.columns.clone();
+        let cmd = expand_star(cmd, &schema());
+        // `recent` is not in the schema; only the CTE body is expanded
+        assert_eq!(cmd.columns, original);
+        assert_eq!(
+            cmd.ctes[0].base_query.columns,
+            vec![
+                Expr::Named("id".to_string()),
+                Expr::Named("user_id".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_star_skips_shadowing_aliases() {
+        // The outer `users` is the CTE, the inner one the schema table
+        let cmd = parse("with users as (get users fields id) get users").unwrap();
+        let cmd = expand_star(cmd, &schema());
+        assert!(cmd.columns.iter().all(|c| matches!(c, Expr::Star)), "{:?}", cmd.columns);
+        assert_eq!(cmd.ctes[0].base_query.columns, vec![Expr::Named("id".to_string())]);
+
+        // A CTE defined inside a nested query still hides the table there
+        let inner = parse("get orders").unwrap().with("orders", parse("get users fields id").unwrap());
+        let cmd = parse("get orders").unwrap().with("recent", inner);
+        let cmd = expand_star(cmd, &schema());
+        assert_eq!(cmd.columns, vec![Expr::Named("id".to_string()), Expr::Named("user_id".to_string())]);
+        let inner = &cmd.ctes[0].base_query;
+        assert!(inner.columns.iter().all(|c| matches!(c, Expr::Star)), "{:?}", inner.columns);
+
+        // Derived table aliased as a schema table
+        let cmd = parse("get (get orders fields id) as users").unwrap();
+        let cmd = expand_star(cmd, &schema());
+        assert!(cmd.columns.iter().all(|c| matches!(c, Expr::Star)), "{:?}", cmd.columns);
+        assert_eq!(
+            cmd.source_query.as_ref().unwrap().columns,
+            vec![Expr::Named("id".to_string())]
+        );
+
+        // Lateral subquery aliased as a schema table
+        let cmd = parse("get users lateral join (get orders fields id limit 1) as orders").unwrap();
+        let cmd = expand_star(cmd, &schema());
+        assert!(cmd.columns.iter().all(|c| matches!(c, Expr::Star)), "{:?}", cmd.columns);
+    }
+}
@@ -0,0 +1,148 @@
+//! Memoized transpilation.
+//!
+//! The gateway and long-lived handlers transpile the same command again and
+//! again. [`CachedQail`] owns a `Qail` and keeps the SQL of its last
+//! `to_sql` call, so repeated calls on an unmodified command return the
+//! stored string instead of walking the AST.
+//!
+//! Builders take `Qail` by value, so the cache cannot live on `Qail` itself.
+//! Every way of changing the wrapped command (`get_mut`, `update`) drops the
+//! cached SQL.
+//!
+//! ```
+//! use qail_core::ast::Qail;
+//! use qail_core::transpiler::{CachedQail, ToSql};
+//!
+//! let cmd = CachedQail::new(Qail::get("users").columns(["id"]));
+//! assert_eq!(cmd.to_sql(), cmd.to_sql());
+//! assert_eq!(cmd.stats().hits, 1);
+//! ```
+
+use super::traits::quoting_policy;
+use super::{Dialect, QuotingPolicy, ToSql};
+use crate::ast::Qail;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+/// Hit/miss counters for one [`CachedQail`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SqlCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct SqlCache {
+    /// Dialect, quoting policy and SQL of the last transpile, `None` once invalidated
+    sql: Option<(Dialect, QuotingPolicy, String)>,
+    stats: SqlCacheStats,
+}
+
+/// A `Qail` that remembers its last transpiled SQL.
+#[derive(Debug)]
+pub struct CachedQail {
+    cmd: Qail,
+    cache: Mutex<SqlCache>,
+}
+
+impl CachedQail {
+    pub fn new(cmd: Qail) -> Self {
+        Self {
+            cmd,
+            cache: Mutex::new(SqlCache::default()),
+        }
+    }
+
+    /// The wrapped command.
+    pub fn get(&self) -> &Qail {
+        &self.cmd
+    }
+
+    /// Mutable access to the wrapped command. Drops the cached SQL.
+    pub fn get_mut(&mut self) -> &mut Qail {
+        self.invalidate();
+        &mut self.cmd
+    }
+
+    /// Apply a by-value builder chain to the wrapped command.
+    ///
+    /// ```
+    /// use qail_core::ast::Qail;
+    /// use qail_core::transpiler::{CachedQail, ToSql};
+    ///
+    /// let mut cmd = CachedQail::new(Qail::get("users"));
+    /// cmd.update(|q| q.limit(10));
+    /// assert_eq!(cmd.to_sql(), "SELECT * FROM users LIMIT 10");
+    /// ```
+    pub fn update(&mut self, f: impl FnOnce(Qail) -> Qail) {
+        let cmd = std::mem::take(&mut self.cmd);
+        self.cmd = f(cmd);
+        self.invalidate();
+    }
+
+    /// Unwrap the command, discarding the cached SQL.
+    pub fn into_inner(self) -> Qail {
+        self.cmd
+    }
+
+    /// Hit/miss counters since this wrapper was created.
+    pub fn stats(&self) -> SqlCacheStats {
+        self.lock().stats
+    }
+
+    fn invalidate(&mut self) {
+        self.cache.get_mut().unwrap_or_else(|e| e.into_inner()).sql = None;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SqlCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ToSql for CachedQail {
+    /// Cached SQL for `dialect`, transpiling only on the first call or after
+    /// the dialect or this thread's quoting policy changed.
+    fn to_sql_with_dialect(&self, dialect: Dialect) -> String {
+        let policy = quoting_policy();
+        let mut cache = self.lock();
+        if let Some((cached_dialect, cached_policy, sql)) = &cache.sql
+            && *cached_dialect == dialect
+            && *cached_policy == policy
+        {
+            let sql = sql.clone();
+            cache.stats.hits += 1;
+            return sql;
+        }
+        cache.stats.misses += 1;
+        let sql = self.cmd.to_sql_with_dialect(dialect);
+        cache.sql = Some((dialect, policy, sql.clone()));
+        sql
+    }
+}
+
+impl Deref for CachedQail {
+    type Target = Qail;
+
+    fn deref(&self) -> &Qail {
+        &self.cmd
+    }
+}
+
+impl From<Qail> for CachedQail {
+    fn from(cmd: Qail) -> Self {
+        Self::new(cmd)
+    }
+}
+
+impl Clone for CachedQail {
+    fn clone(&self) -> Self {
+        let cache = self.lock();
+        Self {
+            cmd: self.cmd.clone(),
+            cache: Mutex::new(SqlCache {
+                sql: cache.sql.clone(),
+                stats: SqlCacheStats::default(),
+            }),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use super::ToSql;
 use super::traits::SqlGenerator;
+use super::{ToSql, replace_named_params};
 use crate::ast::*;
 
 /// Context for parameterized query building.
@@ -36,6 +36,72 @@ impl ParamContext {
     }
 }
 
+/// A literal value in the dialect's syntax. Strings go through
+/// `string_literal`, so dialects with backslash escapes (MySQL) stay quoted.
+fn value_literal(value: &Value, generator: &dyn SqlGenerator) -> String {
+    match value {
+        Value::String(s) => generator.string_literal(s),
+        Value::Bool(b) => generator.bool_literal(*b),
+        Value::Array(vals) => {
+            let vals: Vec<String> = vals.iter().map(|v| value_literal(v, generator)).collect();
+            format!("({})", vals.join(", "))
+        }
+        v => v.to_string(),
+    }
+}
+
+/// A nested query in the generator's dialect.
+fn subquery_sql(cmd: &Qail, generator: &dyn SqlGenerator) -> String {
+    cmd.to_sql_with_dialect(generator.dialect())
+}
+
+/// A nested query whose `:name` parameters are numbered in the outer `params`.
+fn subquery_sql_parameterized(
+    cmd: &Qail,
+    generator: &dyn SqlGenerator,
+    params: &mut ParamContext,
+) -> String {
+    replace_named_params(&subquery_sql(cmd, generator), |name| {
+        params.add_named_param(name, generator)
+    })
+}
+
+/// `'%value%'` for a literal LIKE/ILIKE operand.
+fn fuzzy_literal(value: &Value, generator: &dyn SqlGenerator) -> String {
+    match value {
+        Value::String(s) => generator.string_literal(&format!("%{}%", s)),
+        Value::Param(n) => {
+            let p = generator.placeholder(*n);
+            generator.string_concat(&["'%'", &p, "'%'"])
+        }
+        v => generator.string_literal(&format!("%{}%", v)),
+    }
+}
+
+/// `(a, b)`, the left side of a row comparison.
+fn row_columns(elements: &[Expr], generator: &dyn SqlGenerator, context: Option<&Qail>) -> String {
+    let cols: Vec<String> = elements
+        .iter()
+        .map(|expr| match (expr, context) {
+            (Expr::Named(name), Some(cmd)) => resolve_col_syntax(name, cmd, generator),
+            (Expr::Named(name), None) => generator.quote_identifier(name),
+            (expr, _) => expr.to_string(),
+        })
+        .collect();
+    format!("({})", cols.join(", "))
+}
+
+/// `col IN (a, b, c)` / `col NOT IN (...)`. SQL has no empty list, so an
+/// empty `IN` is always false and an empty `NOT IN` always true.
+fn in_list(col: &str, not: &str, items: impl IntoIterator<Item = String>) -> String {
+    let items: Vec<String> = items.into_iter().collect();
+    match (items.is_empty(), not.is_empty()) {
+        (true, true) => "FALSE".to_string(),
+        (true, false) => "TRUE".to_string(),
+        _ => format!("{} {}IN ({})", col, not, items.join(", ")),
+    }
+}
+
 /// Heuristic:
 /// 1. Split by '.'
 /// 2. If single part -> quote_identifier
@@ -74,21 +140,74 @@ fn resolve_col_syntax(col: &str, cmd: &Qail, generator: &dyn SqlGenerator) -> St
         }
     }
 
+    // USING / FROM auxiliary tables (DELETE ... USING, UPDATE ... FROM)
+    if cmd.using_tables.iter().chain(&cmd.from_tables).any(|t| t == first) {
+        return format!(
+            "{}.{}",
+            generator.quote_identifier(first),
+            generator.quote_identifier(parts[1])
+        );
+    }
+
     // Default: treated as JSON access on the first part
     let col_name = parts[0];
     let path = &parts[1..];
     generator.json_access(col_name, path)
 }
 
-#[allow(clippy::borrowed_box)]
+/// Wrap a bound value in `%...%` with its LIKE wildcards escaped server-side.
+fn escaped_like_param(generator: &dyn SqlGenerator, placeholder: &str) -> String {
+    let lit = |s: &str| generator.string_literal(s);
+    let escaped = format!(
+        "replace(replace(replace({}, {}, {}), {}, {}), {}, {})",
+        placeholder,
+        lit("\\"),
+        lit("\\\\"),
+        lit("%"),
+        lit("\\%"),
+        lit("_"),
+        lit("\\_")
+    );
+    generator.string_concat(&["'%'", &escaped, "'%'"])
+}
+
+/// `ESCAPE '\'` clause matching [`escape_like`], in the dialect's literal syntax.
+fn like_escape_clause(generator: &dyn SqlGenerator) -> String {
+    format!("ESCAPE {}", generator.string_literal("\\"))
+}
+
+/// `target ILIKE '%value%' ESCAPE '\'` with `%`, `_` and `\` in `value`
+/// matched literally (`~=`).
+fn escaped_fuzzy(target: &str, value: &Value, generator: &dyn SqlGenerator) -> String {
+    let val = match value {
+        Value::String(s) => generator.string_literal(&format!("%{}%", escape_like(s))),
+        Value::Param(n) => escaped_like_param(generator, &generator.placeholder(*n)),
+        Value::NamedParam(_) => escaped_like_param(generator, &value.to_string()),
+        v => generator.string_literal(&format!("%{}%", escape_like(&v.to_string()))),
+    };
+    format!(
+        "{} {} {} {}",
+        target,
+        generator.fuzzy_operator(),
+        val,
+        like_escape_clause(generator)
+    )
+}
+
+/// Stand-in for a PostgreSQL-only operator in another dialect, so the
+/// statement fails instead of meaning something else.
+fn custom_operator_error(op: CustomOp) -> String {
+    format!("/* ERROR: operator {} is PostgreSQL-only */", op.as_str())
+}
+
 pub trait ConditionToSql {
-    fn to_sql(&self, generator: &Box<dyn SqlGenerator>, context: Option<&Qail>) -> String;
-    fn to_value_sql(&self, generator: &Box<dyn SqlGenerator>) -> String;
+    fn to_sql(&self, generator: &dyn SqlGenerator, context: Option<&Qail>) -> String;
+    fn to_value_sql(&self, generator: &dyn SqlGenerator) -> String;
 
     /// Convert condition to SQL with parameterized values.
     fn to_sql_parameterized(
         &self,
-        generator: &Box<dyn SqlGenerator>,
+        generator: &dyn SqlGenerator,
         context: Option<&Qail>,
         params: &mut ParamContext,
     ) -> String;
@@ -96,17 +215,20 @@ pub trait ConditionToSql {
 
 impl ConditionToSql for Condition {
     /// Convert condition to SQL string.
-    fn to_sql(&self, generator: &Box<dyn SqlGenerator>, context: Option<&Qail>) -> String {
+    fn to_sql(&self, generator: &dyn SqlGenerator, context: Option<&Qail>) -> String {
         let col = match &self.left {
             Expr::Named(name) => {
                 if name.starts_with('{') && name.ends_with('}') {
                     name[1..name.len() - 1].to_string()
                 } else if let Some(cmd) = context {
-                    resolve_col_syntax(name, cmd, generator.as_ref())
+                    resolve_col_syntax(name, cmd, generator)
                 } else {
                     generator.quote_identifier(name)
                 }
             }
+            Expr::RowConstructor { elements, .. } => {
+                row_columns(elements, generator, context)
+            }
             Expr::JsonAccess {
                 column,
                 path_segments,
@@ -134,17 +256,12 @@ impl ConditionToSql for Condition {
                 Operator::Gte => format!("_el >= {}", self.to_value_sql(generator)),
                 Operator::Lt => format!("_el < {}", self.to_value_sql(generator)),
                 Operator::Lte => format!("_el <= {}", self.to_value_sql(generator)),
-                Operator::Fuzzy => {
-                    let val = match &self.value {
-                        Value::String(s) => format!("'%{}%'", s),
-                        Value::Param(n) => {
-                            let p = generator.placeholder(*n);
-                            generator.string_concat(&["'%'", &p, "'%'"])
-                        }
-                        v => format!("'%{}%'", v),
-                    };
-                    format!("_el {} {}", generator.fuzzy_operator(), val)
-                }
+                Operator::Fuzzy => format!(
+                    "_el {} {}",
+                    generator.fuzzy_operator(),
+                    fuzzy_literal(&self.value, generator)
+                ),
+                Operator::FuzzyEscaped => escaped_fuzzy("_el", &self.value, generator),
                 _ => format!("_el = {}", self.to_value_sql(generator)),
             };
             return format!(
@@ -154,31 +271,53 @@ impl ConditionToSql for Condition {
         }
 
         // Normal conditions
-        // Simple binary operators use sql_symbol() for unified handling
+        // Simple binary operators use binary_operator() for unified handling
         if self.op.is_simple_binary() {
             return format!(
                 "{} {} {}",
                 col,
-                self.op.sql_symbol(),
+                generator.binary_operator(&self.op),
                 self.to_value_sql(generator)
             );
         }
 
         // Special operators that need custom handling
         match self.op {
-            Operator::Fuzzy => {
-                let val = match &self.value {
-                    Value::String(s) => format!("'%{}%'", s),
-                    Value::Param(n) => {
-                        let p = generator.placeholder(*n);
-                        generator.string_concat(&["'%'", &p, "'%'"])
+            Operator::Fuzzy => format!(
+                "{} {} {}",
+                col,
+                generator.fuzzy_operator(),
+                fuzzy_literal(&self.value, generator)
+            ),
+            Operator::FuzzyEscaped => escaped_fuzzy(&col, &self.value, generator),
+            Operator::In | Operator::NotIn => {
+                let not = if self.op == Operator::NotIn { "NOT " } else { "" };
+                match &self.value {
+                    Value::Subquery(cmd) => {
+                        format!("{} {}IN ({})", col, not, subquery_sql(cmd, generator))
                     }
-                    v => format!("'%{}%'", v),
-                };
-                format!("{} {} {}", col, generator.fuzzy_operator(), val)
+                    Value::Array(vals) => in_list(
+                        &col,
+                        not,
+                        vals.iter().map(|v| value_literal(v, generator)),
+                    ),
+                    // A single array value: col = ANY($1)
+                    v if not.is_empty() => generator.in_array(&col, &value_literal(v, generator)),
+                    v => generator.not_in_array(&col, &value_literal(v, generator)),
+                }
             }
-            Operator::In => generator.in_array(&col, &format!("{}", self.value)),
-            Operator::NotIn => generator.not_in_array(&col, &format!("{}", self.value)),
+            Operator::EqAny => match &self.value {
+                Value::Array(vals) if generator.supports_arrays() && !vals.is_empty() => {
+                    let vals: Vec<String> = vals.iter().map(|v| value_literal(v, generator)).collect();
+                    generator.in_array(&col, &format!("ARRAY[{}]", vals.join(", ")))
+                }
+                // No array type (or an untyped empty `ARRAY[]`): the same
+                // test as a plain IN list, so an empty list is FALSE
+                Value::Array(vals) => {
+                    in_list(&col, "", vals.iter().map(|v| value_literal(v, generator)))
+                }
+                _ => generator.in_array(&col, &self.to_value_sql(generator)),
+            },
             Operator::IsNull => format!("{} IS NULL", col),
             Operator::IsNotNull => format!("{} IS NOT NULL", col),
             Operator::Contains => generator.json_contains(&col, &self.to_value_sql(generator)),
@@ -205,7 +344,12 @@ impl ConditionToSql for Condition {
                 if let Value::Array(vals) = &self.value
                     && vals.len() >= 2
                 {
-                    return format!("{} BETWEEN {} AND {}", col, vals[0], vals[1]);
+                    return format!(
+                        "{} BETWEEN {} AND {}",
+                        col,
+                        value_literal(&vals[0], generator),
+                        value_literal(&vals[1], generator)
+                    );
                 }
                 format!("{} BETWEEN {}", col, self.value)
             }
@@ -213,53 +357,55 @@ impl ConditionToSql for Condition {
                 if let Value::Array(vals) = &self.value
                     && vals.len() >= 2
                 {
-                    return format!("{} NOT BETWEEN {} AND {}", col, vals[0], vals[1]);
+                    return format!(
+                        "{} NOT BETWEEN {} AND {}",
+                        col,
+                        value_literal(&vals[0], generator),
+                        value_literal(&vals[1], generator)
+                    );
                 }
                 format!("{} NOT BETWEEN {}", col, self.value)
             }
             Operator::Exists => {
                 // EXISTS takes subquery, col is ignored
                 if let Value::Subquery(cmd) = &self.value {
-                    let subquery_sql = cmd.to_sql();
-                    format!("EXISTS ({})", subquery_sql)
+                    format!("EXISTS ({})", subquery_sql(cmd, generator))
                 } else {
                     format!("EXISTS ({})", self.value)
                 }
             }
             Operator::NotExists => {
                 if let Value::Subquery(cmd) = &self.value {
-                    let subquery_sql = cmd.to_sql();
-                    format!("NOT EXISTS ({})", subquery_sql)
+                    format!("NOT EXISTS ({})", subquery_sql(cmd, generator))
                 } else {
                     format!("NOT EXISTS ({})", self.value)
                 }
             }
+            Operator::Custom(op) if !generator.supports_custom_operators() => {
+                custom_operator_error(op)
+            }
             // Simple binary operators are handled above by is_simple_binary()
             _ => format!(
                 "{} {} {}",
                 col,
-                self.op.sql_symbol(),
+                generator.binary_operator(&self.op),
                 self.to_value_sql(generator)
             ),
         }
     }
 
-    fn to_value_sql(&self, generator: &Box<dyn SqlGenerator>) -> String {
+    fn to_value_sql(&self, generator: &dyn SqlGenerator) -> String {
         match &self.value {
             Value::Param(n) => generator.placeholder(*n),
-            Value::String(s) => format!("'{}'", s.replace('\'', "''")),
-            Value::Bool(b) => generator.bool_literal(*b),
-            Value::Subquery(cmd) => {
-                // Use ToSql trait to generate subquery SQL
-                use crate::transpiler::ToSql;
-                format!("({})", cmd.to_sql())
-            }
+            Value::Subquery(cmd) => format!("({})", subquery_sql(cmd, generator)),
+            Value::AnySubquery(cmd) => format!("ANY ({})", subquery_sql(cmd, generator)),
+            Value::AllSubquery(cmd) => format!("ALL ({})", subquery_sql(cmd, generator)),
             Value::Column(col) => {
                 // Determine if it's "table"."col" or just "col"
                 // Use resolve_col_syntax logic? Or simply quote?
                 // Usually Join ON RHS is just an identifier, but transpiler logic in resolve_col_syntax
                 // requires a Qail context which we don't have here efficiently (we have context: Option<&Qail> in other methods but strictly to_value_sql signature is fixed?).
-                // Wait, to_value_sql signature is: fn to_value_sql(&self, generator: &Box<dyn SqlGenerator>) -> String
+                // Wait, to_value_sql signature is: fn to_value_sql(&self, generator: &dyn SqlGenerator) -> String
                 // We don't have context here.
                 // However, we can use a basic split check or just quote full string.
                 // If col is "users.id", generator.quote_identifier("users.id") might quote the whole thing which is wrong for Postgres ("users.id" vs "users"."id").
@@ -275,13 +421,13 @@ impl ConditionToSql for Condition {
                     generator.quote_identifier(col)
                 }
             }
-            v => v.to_string(),
+            v => value_literal(v, generator),
         }
     }
 
     fn to_sql_parameterized(
         &self,
-        generator: &Box<dyn SqlGenerator>,
+        generator: &dyn SqlGenerator,
         context: Option<&Qail>,
         params: &mut ParamContext,
     ) -> String {
@@ -290,11 +436,14 @@ impl ConditionToSql for Condition {
                 if name.starts_with('{') && name.ends_with('}') {
                     name[1..name.len() - 1].to_string()
                 } else if let Some(cmd) = context {
-                    resolve_col_syntax(name, cmd, generator.as_ref())
+                    resolve_col_syntax(name, cmd, generator)
                 } else {
                     generator.quote_identifier(name)
                 }
             }
+            Expr::RowConstructor { elements, .. } => {
+                row_columns(elements, generator, context)
+            }
             Expr::JsonAccess {
                 column,
                 path_segments,
@@ -318,12 +467,35 @@ impl ConditionToSql for Condition {
         let value_placeholder = |v: &Value, p: &mut ParamContext| -> String {
             match v {
                 Value::Param(n) => generator.placeholder(*n), // Already a placeholder
-                Value::NamedParam(name) => p.add_named_param(name.clone(), generator.as_ref()),
+                Value::NamedParam(name) => p.add_named_param(name.clone(), generator),
                 Value::Null => "NULL".to_string(),
-                other => p.add_param(other.clone(), generator.as_ref()),
+                Value::Default => "DEFAULT".to_string(),
+                Value::Subquery(cmd) => {
+                    format!("({})", subquery_sql_parameterized(cmd, generator, p))
+                }
+                Value::AnySubquery(cmd) => {
+                    format!("ANY ({})", subquery_sql_parameterized(cmd, generator, p))
+                }
+                Value::AllSubquery(cmd) => {
+                    format!("ALL ({})", subquery_sql_parameterized(cmd, generator, p))
+                }
+                other => p.add_param(other.clone(), generator),
             }
         };
 
+        // Row comparison: (a, b) > ($1, $2)
+        if let (Expr::RowConstructor { .. }, Value::Array(vals)) = (&self.left, &self.value)
+            && self.op.is_simple_binary()
+        {
+            let vals: Vec<String> = vals.iter().map(|v| value_placeholder(v, params)).collect();
+            return format!(
+                "{} {} ({})",
+                col,
+                generator.binary_operator(&self.op),
+                vals.join(", ")
+            );
+        }
+
         match self.op {
             Operator::Eq => {
                 // Raw conditions ({...}, op=Eq, value=Null) are now handled at col resolution
@@ -341,12 +513,55 @@ impl ConditionToSql for Condition {
                 let placeholder = value_placeholder(&self.value, params);
                 format!("{} {} {}", col, generator.fuzzy_operator(), placeholder)
             }
+            Operator::FuzzyEscaped => {
+                // Literal values are escaped before binding; placeholders are escaped in SQL
+                let val = match &self.value {
+                    Value::String(s) => params.add_param(
+                        Value::String(format!("%{}%", escape_like(s))),
+                        generator,
+                    ),
+                    v => escaped_like_param(generator, &value_placeholder(v, params)),
+                };
+                format!(
+                    "{} {} {} {}",
+                    col,
+                    generator.fuzzy_operator(),
+                    val,
+                    like_escape_clause(generator)
+                )
+            }
             Operator::IsNull => format!("{} IS NULL", col),
             Operator::IsNotNull => format!("{} IS NOT NULL", col),
-            Operator::In => generator.in_array(&col, &value_placeholder(&self.value, params)),
-            Operator::NotIn => {
-                generator.not_in_array(&col, &value_placeholder(&self.value, params))
+            Operator::In | Operator::NotIn => {
+                let not = if self.op == Operator::NotIn { "NOT " } else { "" };
+                match &self.value {
+                    Value::Subquery(cmd) => format!(
+                        "{} {}IN ({})",
+                        col,
+                        not,
+                        subquery_sql_parameterized(cmd, generator, params)
+                    ),
+                    // One bind parameter per list element
+                    Value::Array(vals) => in_list(
+                        &col,
+                        not,
+                        vals.iter().map(|v| value_placeholder(v, params)),
+                    ),
+                    v if not.is_empty() => {
+                        generator.in_array(&col, &value_placeholder(v, params))
+                    }
+                    v => generator.not_in_array(&col, &value_placeholder(v, params)),
+                }
             }
+            // Without an array type, one bind parameter per element as for IN;
+            // an empty literal list is FALSE rather than an untyped `ARRAY[]`
+            Operator::EqAny => match &self.value {
+                Value::Array(vals) if !generator.supports_arrays() || vals.is_empty() => {
+                    in_list(&col, "", vals.iter().map(|v| value_placeholder(v, params)))
+                }
+                // The whole list is one bind parameter
+                v => generator.in_array(&col, &value_placeholder(v, params)),
+            },
             Operator::Contains => {
                 generator.json_contains(&col, &value_placeholder(&self.value, params))
             }
@@ -373,7 +588,12 @@ impl ConditionToSql for Condition {
                 if let Value::Array(vals) = &self.value
                     && vals.len() >= 2
                 {
-                    return format!("{} BETWEEN {} AND {}", col, vals[0], vals[1]);
+                    return format!(
+                        "{} BETWEEN {} AND {}",
+                        col,
+                        value_literal(&vals[0], generator),
+                        value_literal(&vals[1], generator)
+                    );
                 }
                 format!("{} BETWEEN {}", col, self.value)
             }
@@ -381,31 +601,40 @@ impl ConditionToSql for Condition {
                 if let Value::Array(vals) = &self.value
                     && vals.len() >= 2
                 {
-                    return format!("{} NOT BETWEEN {} AND {}", col, vals[0], vals[1]);
+                    return format!(
+                        "{} NOT BETWEEN {} AND {}",
+                        col,
+                        value_literal(&vals[0], generator),
+                        value_literal(&vals[1], generator)
+                    );
                 }
                 format!("{} NOT BETWEEN {}", col, self.value)
             }
             Operator::Exists => {
                 if let Value::Subquery(cmd) = &self.value {
-                    let subquery_sql = cmd.to_sql();
-                    format!("EXISTS ({})", subquery_sql)
+                    format!("EXISTS ({})", subquery_sql_parameterized(cmd, generator, params))
                 } else {
                     format!("EXISTS ({})", self.value)
                 }
             }
             Operator::NotExists => {
                 if let Value::Subquery(cmd) = &self.value {
-                    let subquery_sql = cmd.to_sql();
-                    format!("NOT EXISTS ({})", subquery_sql)
+                    format!(
+                        "NOT EXISTS ({})",
+                        subquery_sql_parameterized(cmd, generator, params)
+                    )
                 } else {
                     format!("NOT EXISTS ({})", self.value)
                 }
             }
-            // Simple operators (Ne, Gt, Gte, Lt, Lte, Like, NotLike, ILike, NotILike) use sql_symbol()
+            Operator::Custom(op) if !generator.supports_custom_operators() => {
+                custom_operator_error(op)
+            }
+            // Simple operators (Ne, Gt, Gte, Lt, Lte, Like, NotLike, ILike, NotILike) use binary_operator()
             _ => format!(
                 "{} {} {}",
                 col,
-                self.op.sql_symbol(),
+                generator.binary_operator(&self.op),
                 value_placeholder(&self.value, params)
             ),
         }
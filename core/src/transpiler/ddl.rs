@@ -7,7 +7,10 @@ pub fn build_create_table(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
     let mut sql = String::new();
     sql.push_str("CREATE TABLE ");
-    sql.push_str(&generator.quote_identifier(&cmd.table));
+    if cmd.if_not_exists {
+        sql.push_str("IF NOT EXISTS ");
+    }
+    sql.push_str(&generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table));
     sql.push_str(" (\n");
 
     let mut defs = Vec::new();
@@ -53,7 +56,7 @@ pub fn build_create_table(cmd: &Qail, dialect: Dialect) -> String {
                         " CHECK ({} IN ({}))",
                         generator.quote_identifier(name),
                         vals.iter()
-                            .map(|v| format!("'{}'", v))
+                            .map(|v| format!("'{}'", v.replace('\'', "''")))
                             .collect::<Vec<_>>()
                             .join(", ")
                     ));
@@ -98,7 +101,7 @@ pub fn build_create_table(cmd: &Qail, dialect: Dialect) -> String {
                 if let Constraint::Comment(text) = c {
                     comments.push(format!(
                         "COMMENT ON COLUMN {}.{} IS '{}'",
-                        generator.quote_identifier(&cmd.table),
+                        generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table),
                         generator.quote_identifier(name),
                         text.replace('\'', "''")
                     ));
@@ -118,7 +121,7 @@ pub fn build_create_table(cmd: &Qail, dialect: Dialect) -> String {
 pub fn build_alter_table(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
     let mut stmts = Vec::new();
-    let table_name = generator.quote_identifier(&cmd.table);
+    let table_name = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
     for col in &cmd.columns {
         match col {
@@ -184,6 +187,7 @@ pub fn build_create_index(cmd: &Qail, dialect: Dialect) -> String {
     match &cmd.index_def {
         Some(idx) => {
             let unique = if idx.unique { "UNIQUE " } else { "" };
+            let if_not_exists = if cmd.if_not_exists { "IF NOT EXISTS " } else { "" };
             let cols = idx
                 .columns
                 .iter()
@@ -191,8 +195,9 @@ pub fn build_create_index(cmd: &Qail, dialect: Dialect) -> String {
                 .collect::<Vec<_>>()
                 .join(", ");
             format!(
-                "CREATE {}INDEX {} ON {} ({})",
+                "CREATE {}INDEX {}{} ON {} ({})",
                 unique,
+                if_not_exists,
                 generator.quote_identifier(&idx.name),
                 generator.quote_identifier(&idx.table),
                 cols
@@ -221,7 +226,7 @@ fn map_type(t: &str) -> &str {
 // Stub
 pub fn build_alter_column(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
-    let table = generator.quote_identifier(&cmd.table);
+    let table = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
     // Identified columns (target column)
     let cols: Vec<String> = cmd
@@ -280,7 +285,7 @@ pub fn build_alter_column(cmd: &Qail, dialect: Dialect) -> String {
 /// Generate ALTER TABLE ADD COLUMN SQL (for migrations).
 pub fn build_alter_add_column(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
-    let table = generator.quote_identifier(&cmd.table);
+    let table = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
     let mut parts = Vec::new();
 
@@ -323,7 +328,7 @@ pub fn build_alter_add_column(cmd: &Qail, dialect: Dialect) -> String {
 /// Generate ALTER TABLE DROP COLUMN SQL (for migrations).
 pub fn build_alter_drop_column(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
-    let table = generator.quote_identifier(&cmd.table);
+    let table = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
     let mut parts = Vec::new();
 
@@ -344,7 +349,7 @@ pub fn build_alter_drop_column(cmd: &Qail, dialect: Dialect) -> String {
 /// Generate ALTER TABLE ALTER COLUMN TYPE SQL (for migrations).
 pub fn build_alter_column_type(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
-    let table = generator.quote_identifier(&cmd.table);
+    let table = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
     let mut parts = Vec::new();
 
@@ -1,11 +1,28 @@
+use crate::transpiler::sql::mysql::MySqlGenerator;
 use crate::transpiler::sql::postgres::PostgresGenerator;
 use crate::transpiler::sql::sqlite::SqliteGenerator;
+use crate::transpiler::sql::sqlserver::SqlServerGenerator;
 use crate::transpiler::traits::SqlGenerator;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Dialect {
     Postgres,
     SQLite,
+    MySQL,
+    SqlServer,
+}
+
+/// How named parameters (`:name`) appear in parameterized SQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// `$1`, `$2`, ... (PostgreSQL). A repeated name reuses its number.
+    Dollar,
+    /// `?` (MySQL, SQLite). Positional, so a repeated name is bound again.
+    Question,
+    /// `@p1`, `@p2`, ... (SQL Server). A repeated name reuses its number.
+    AtP,
+    /// `:name` left as written, for drivers that bind by name.
+    Named,
 }
 
 impl Default for Dialect {
@@ -19,6 +36,17 @@ impl Dialect {
         match self {
             Dialect::Postgres => Box::new(PostgresGenerator),
             Dialect::SQLite => Box::new(SqliteGenerator),
+            Dialect::MySQL => Box::new(MySqlGenerator),
+            Dialect::SqlServer => Box::new(SqlServerGenerator),
+        }
+    }
+
+    /// Placeholder style the dialect's drivers expect.
+    pub fn placeholder_style(&self) -> PlaceholderStyle {
+        match self {
+            Dialect::Postgres => PlaceholderStyle::Dollar,
+            Dialect::SQLite | Dialect::MySQL => PlaceholderStyle::Question,
+            Dialect::SqlServer => PlaceholderStyle::AtP,
         }
     }
 }
@@ -63,7 +63,11 @@ pub fn build_single_cte(cte: &CTEDef, dialect: Dialect) -> String {
         sql.push(')');
     }
 
-    sql.push_str(" AS (");
+    sql.push_str(match cte.materialized {
+        Some(true) => " AS MATERIALIZED (",
+        Some(false) => " AS NOT MATERIALIZED (",
+        None => " AS (",
+    });
 
     // Base query - check if it's raw SQL passthrough
     // Raw SQL is stored when table contains SQL keywords and columns are empty/star
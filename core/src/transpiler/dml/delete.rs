@@ -1,7 +1,6 @@
 //! DELETE SQL generation.
 
 use crate::ast::*;
-use crate::transpiler::conditions::ConditionToSql;
 use crate::transpiler::dialect::Dialect;
 
 pub fn build_delete(cmd: &Qail, dialect: Dialect) -> String {
@@ -11,7 +10,7 @@ pub fn build_delete(cmd: &Qail, dialect: Dialect) -> String {
     } else {
         String::from("DELETE FROM ")
     };
-    sql.push_str(&generator.quote_identifier(&cmd.table));
+    sql.push_str(&generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table));
 
     // USING clause (multi-table delete)
     if !cmd.using_tables.is_empty() {
@@ -19,7 +18,7 @@ pub fn build_delete(cmd: &Qail, dialect: Dialect) -> String {
         sql.push_str(
             &cmd.using_tables
                 .iter()
-                .map(|t| generator.quote_identifier(t))
+                .map(|t| generator.table_ref(cmd.schema_for(t), t))
                 .collect::<Vec<_>>()
                 .join(", "),
         );
@@ -29,10 +28,10 @@ pub fn build_delete(cmd: &Qail, dialect: Dialect) -> String {
     let mut where_clauses: Vec<String> = Vec::new();
 
     for cage in &cmd.cages {
-        if let CageKind::Filter = cage.kind {
-            for cond in &cage.conditions {
-                where_clauses.push(cond.to_sql(&generator, Some(cmd)));
-            }
+        if let CageKind::Filter = cage.kind
+            && !cage.conditions.is_empty()
+        {
+            where_clauses.push(super::filter_group_sql(cage, generator.as_ref(), cmd));
         }
     }
 
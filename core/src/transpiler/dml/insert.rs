@@ -1,17 +1,25 @@
 //! INSERT SQL generation.
 
 use crate::ast::*;
-use crate::transpiler::SqlGenerator;
+use crate::transpiler::{ReturningStyle, SqlGenerator};
 use crate::transpiler::conditions::ConditionToSql;
 use crate::transpiler::dialect::Dialect;
 
 pub fn build_insert(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
     let mut sql = String::from("INSERT INTO ");
-    sql.push_str(&generator.quote_identifier(&cmd.table));
+    sql.push_str(&generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table));
 
     // For ADD queries, we use columns and first cage contains values
-    let cols: Vec<String> = cmd.columns.iter().map(|c| c.to_string()).collect();
+    let cols: Vec<String> = cmd
+        .columns
+        .iter()
+        .filter(|c| !matches!(c, Expr::Star))
+        .map(|c| match c {
+            Expr::Named(name) => generator.quote_identifier(name),
+            other => other.to_string(),
+        })
+        .collect();
 
     if !cols.is_empty() {
         sql.push_str(" (");
@@ -19,6 +27,20 @@ pub fn build_insert(cmd: &Qail, dialect: Dialect) -> String {
         sql.push(')');
     }
 
+    // SQL Server: OUTPUT sits between the column list and the row source
+    if generator.returning_style() == ReturningStyle::Output {
+        match output_clause(cmd.returning.as_deref(), generator.as_ref()) {
+            Ok(Some(output)) => sql.push_str(&output),
+            Ok(None) => {}
+            Err(expr) => {
+                return format!(
+                    "/* ERROR: SQL Server OUTPUT takes plain columns (INSERTED.col [AS alias]), not `{}` */",
+                    expr
+                );
+            }
+        }
+    }
+
     // OVERRIDING clause for GENERATED columns
     if let Some(ref overriding) = cmd.overriding {
         match overriding {
@@ -37,10 +59,16 @@ pub fn build_insert(cmd: &Qail, dialect: Dialect) -> String {
         sql.push_str(&source_query.to_sql_with_dialect(dialect));
     } else if let Some(cage) = cmd.cages.first() {
         // Traditional INSERT with VALUES
+        if dialect == Dialect::SQLite
+            && cage.conditions.iter().any(|c| matches!(c.value, Value::Default))
+        {
+            return "/* ERROR: SQLite has no DEFAULT keyword in VALUES; leave the column out */"
+                .to_string();
+        }
         let values: Vec<String> = cage
             .conditions
             .iter()
-            .map(|c| c.to_value_sql(&generator))
+            .map(|c| c.to_value_sql(generator.as_ref()))
             .collect();
 
         if !values.is_empty() {
@@ -52,17 +80,36 @@ pub fn build_insert(cmd: &Qail, dialect: Dialect) -> String {
 
     // ON CONFLICT clause
     if let Some(on_conflict) = &cmd.on_conflict {
-        sql.push_str(&build_on_conflict(
-            on_conflict,
-            &dialect,
-            generator.as_ref(),
-        ));
+        if dialect == Dialect::SqlServer {
+            // Same limitation as `put`
+            return "/* ERROR: Upsert for SQL Server needs MERGE, which is not supported yet */"
+                .to_string();
+        }
+        if on_conflict.columns.is_empty() && !on_conflict.target_where.is_empty() {
+            // The predicate narrows an index on the conflict columns
+            return "/* ERROR: a conflict WHERE needs conflict columns */".to_string();
+        }
+        if dialect == Dialect::MySQL && !on_conflict.target_where.is_empty() {
+            // No partial unique indexes, so the predicate can't be honored
+            return "/* ERROR: MySQL has no partial-index conflict target; drop the conflict WHERE */"
+                .to_string();
+        }
+        sql.push_str(&build_on_conflict(on_conflict, &dialect, generator.as_ref(), cmd));
     }
 
-    match &cmd.returning {
-        None => sql.push_str(" RETURNING *"), // Default: return all
-        Some(cols) if cols.is_empty() => {}   // Explicitly no RETURNING
-        Some(cols) => {
+    match (&cmd.returning, generator.returning_style()) {
+        (_, ReturningStyle::Output) => {}
+        // Dropping the implicit default is harmless; dropping requested columns is not
+        (Some(cols), ReturningStyle::Unsupported) if !cols.is_empty() => {
+            return format!(
+                "/* ERROR: {:?} has no INSERT ... RETURNING; read the row back with a SELECT */",
+                dialect
+            );
+        }
+        (_, ReturningStyle::Unsupported) => {}
+        (None, _) => sql.push_str(" RETURNING *"), // Default: return all
+        (Some(cols), _) if cols.is_empty() => {}   // Explicitly no RETURNING
+        (Some(cols), _) => {
             let col_strs: Vec<String> = cols.iter().map(|e| e.to_string()).collect();
             sql.push_str(" RETURNING ");
             sql.push_str(&col_strs.join(", "));
@@ -72,26 +119,72 @@ pub fn build_insert(cmd: &Qail, dialect: Dialect) -> String {
     sql
 }
 
+/// SQL Server `OUTPUT INSERTED.cols` for the requested returning columns
+/// (`None` means all, like the default `RETURNING *`). Every column is
+/// read from the `INSERTED` pseudo-table; any other expression is returned
+/// as the error, since it would reference the target table directly.
+fn output_clause(
+    returning: Option<&[Expr]>,
+    generator: &dyn SqlGenerator,
+) -> Result<Option<String>, String> {
+    let cols = match returning {
+        None => return Ok(Some(" OUTPUT INSERTED.*".to_string())),
+        Some([]) => return Ok(None),
+        Some(cols) => cols,
+    };
+    let cols = cols
+        .iter()
+        .map(|col| match col {
+            Expr::Star => Ok("INSERTED.*".to_string()),
+            Expr::Named(name) => Ok(format!("INSERTED.{}", generator.quote_identifier(name))),
+            Expr::Aliased { name, alias } => Ok(format!(
+                "INSERTED.{} AS {}",
+                generator.quote_identifier(name),
+                generator.quote_identifier(alias)
+            )),
+            other => Err(other.to_string()),
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    Ok(Some(format!(" OUTPUT {}", cols.join(", "))))
+}
+
 /// Build ON CONFLICT clause (Standard SQL / Postgres / SQLite style)
 fn build_on_conflict(
     on_conflict: &OnConflict,
     _dialect: &Dialect,
     generator: &dyn SqlGenerator,
+    cmd: &Qail,
 ) -> String {
     // Both Postgres and SQLite support ON CONFLICT
-    build_on_conflict_postgres(on_conflict, generator)
+    build_on_conflict_postgres(on_conflict, generator, cmd)
 }
 
-/// PostgreSQL/SQLite style: ON CONFLICT (cols) DO UPDATE SET ... or DO NOTHING
-fn build_on_conflict_postgres(on_conflict: &OnConflict, generator: &dyn SqlGenerator) -> String {
-    let mut sql = String::from(" ON CONFLICT (");
-    let cols: Vec<String> = on_conflict
-        .columns
-        .iter()
-        .map(|c| generator.quote_identifier(c))
-        .collect();
-    sql.push_str(&cols.join(", "));
-    sql.push(')');
+/// PostgreSQL/SQLite style: ON CONFLICT (cols) [WHERE ...] DO UPDATE SET ... or DO NOTHING
+fn build_on_conflict_postgres(
+    on_conflict: &OnConflict,
+    generator: &dyn SqlGenerator,
+    cmd: &Qail,
+) -> String {
+    let mut sql = String::from(" ON CONFLICT");
+    // No target: any constraint violation (only valid with DO NOTHING)
+    if !on_conflict.columns.is_empty() {
+        let cols: Vec<String> = on_conflict
+            .columns
+            .iter()
+            .map(|c| generator.quote_identifier(c))
+            .collect();
+        sql.push_str(&format!(" ({})", cols.join(", ")));
+    }
+
+    if !on_conflict.target_where.is_empty() {
+        let conds: Vec<String> = on_conflict
+            .target_where
+            .iter()
+            .map(|c| c.to_sql(generator, Some(cmd)))
+            .collect();
+        sql.push_str(" WHERE ");
+        sql.push_str(&conds.join(" AND "));
+    }
 
     match &on_conflict.action {
         ConflictAction::DoNothing => {
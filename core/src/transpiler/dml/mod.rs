@@ -11,3 +11,27 @@ pub mod select;
 pub mod update;
 pub mod upsert;
 pub mod window;
+
+use crate::ast::{Cage, LogicalOp, Qail};
+use crate::transpiler::conditions::ConditionToSql;
+use crate::transpiler::traits::SqlGenerator;
+
+/// WHERE group for one filter cage: conditions joined by the cage's logical
+/// operator. OR groups are parenthesized so groups can be ANDed together.
+pub(crate) fn filter_group_sql(cage: &Cage, generator: &dyn SqlGenerator, cmd: &Qail) -> String {
+    let joiner = match cage.logical_op {
+        LogicalOp::And => " AND ",
+        LogicalOp::Or => " OR ",
+    };
+    let group = cage
+        .conditions
+        .iter()
+        .map(|c| c.to_sql(generator, Some(cmd)))
+        .collect::<Vec<_>>()
+        .join(joiner);
+    if cage.logical_op == LogicalOp::Or && cage.conditions.len() > 1 {
+        format!("({})", group)
+    } else {
+        group
+    }
+}
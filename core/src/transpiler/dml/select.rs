@@ -3,6 +3,7 @@
 use crate::ast::*;
 use crate::transpiler::conditions::ConditionToSql;
 use crate::transpiler::dialect::Dialect;
+use crate::transpiler::ToSql;
 
 pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
@@ -28,7 +29,7 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
         let exprs: Vec<String> = cmd
             .distinct_on
             .iter()
-            .map(|e| render_expr_for_orderby(e, &generator, cmd))
+            .map(|e| render_expr_for_orderby(e, generator.as_ref(), cmd))
             .collect();
         format!("{}SELECT DISTINCT ON ({}) ", cte_prefix, exprs.join(", "))
     } else if cmd.distinct {
@@ -55,14 +56,14 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
                         for (cond, val) in when_clauses {
                             case_sql.push_str(&format!(
                                 " WHEN {} THEN {}",
-                                cond.to_sql(&generator, Some(cmd)),
-                                render_expr_for_orderby(val, &generator, cmd)
+                                cond.to_sql(generator.as_ref(), Some(cmd)),
+                                render_expr_for_orderby(val, generator.as_ref(), cmd)
                             ));
                         }
                         if let Some(e) = else_value {
                             case_sql.push_str(&format!(
                                 " ELSE {}",
-                                render_expr_for_orderby(e, &generator, cmd)
+                                render_expr_for_orderby(e, generator.as_ref(), cmd)
                             ));
                         }
                         case_sql.push_str(" END");
@@ -188,22 +189,43 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
                     }
                     Expr::Aggregate {
                         col,
+                        arg,
                         func,
                         distinct,
                         filter,
+                        delimiter,
+                        order_by,
                         alias,
                     } => {
                         // Render aggregate function: COUNT(*), COUNT(DISTINCT col), SUM(col), etc.
-                        let col_expr = if col == "*" {
+                        let col_expr = if let Some(arg) = arg {
+                            // Expressions are never quoted as identifiers
+                            arg.to_string()
+                        } else if col == "*" {
                             "*".to_string()
                         } else {
                             generator.quote_identifier(col)
                         };
-                        let mut expr = if *distinct {
-                            format!("{}(DISTINCT {})", func, col_expr)
+                        let mut args = if *distinct {
+                            format!("DISTINCT {}", col_expr)
                         } else {
-                            format!("{}({})", func, col_expr)
+                            col_expr
                         };
+                        if let Some(sep) = delimiter {
+                            args.push_str(&format!(", {}", generator.string_literal(sep)));
+                        }
+                        if let Some(order) = order_by
+                            && !order.is_empty()
+                        {
+                            let items: Vec<String> = order
+                                .iter()
+                                .map(|(c, o)| {
+                                    generator.order_by_item(&generator.quote_identifier(c), *o)
+                                })
+                                .collect();
+                            args.push_str(&format!(" ORDER BY {}", items.join(", ")));
+                        }
+                        let mut expr = format!("{}({})", func, args);
 
                         if let Some(conditions) = filter
                             && !conditions.is_empty()
@@ -279,23 +301,8 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
                                         return String::new();
                                     };
                                     match &cage.kind {
-                                        CageKind::Sort(SortOrder::Asc) => {
-                                            format!("{} ASC", col_str)
-                                        }
-                                        CageKind::Sort(SortOrder::Desc) => {
-                                            format!("{} DESC", col_str)
-                                        }
-                                        CageKind::Sort(SortOrder::AscNullsFirst) => {
-                                            format!("{} ASC NULLS FIRST", col_str)
-                                        }
-                                        CageKind::Sort(SortOrder::AscNullsLast) => {
-                                            format!("{} ASC NULLS LAST", col_str)
-                                        }
-                                        CageKind::Sort(SortOrder::DescNullsFirst) => {
-                                            format!("{} DESC NULLS FIRST", col_str)
-                                        }
-                                        CageKind::Sort(SortOrder::DescNullsLast) => {
-                                            format!("{} DESC NULLS LAST", col_str)
+                                        CageKind::Sort(order) => {
+                                            generator.order_by_item(&col_str, *order)
                                         }
                                         _ => String::new(),
                                     }
@@ -334,6 +341,14 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
                             generator.quote_identifier(name)
                         )
                     }
+                    Expr::Subquery { query, alias } => {
+                        let sub = format!("({})", query.to_sql_with_dialect(dialect));
+                        if let Some(a) = alias {
+                            format!("{} AS {}", sub, generator.quote_identifier(a))
+                        } else {
+                            sub
+                        }
+                    }
                     _ => c.to_string(), // Fallback for complex cols if any remaining
                 }
             })
@@ -347,7 +362,15 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
     } else {
         sql.push_str(" FROM ");
     }
-    sql.push_str(&generator.quote_identifier(&cmd.table));
+    if let Some(derived) = &cmd.source_query {
+        // Derived table: (SELECT ...) AS alias
+        sql.push('(');
+        sql.push_str(&derived.to_sql_with_dialect(dialect));
+        sql.push_str(") AS ");
+        sql.push_str(&generator.quote_identifier(&cmd.table));
+    } else {
+        sql.push_str(&generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table));
+    }
 
     // TABLESAMPLE - check new sample field first, then legacy CageKind::Sample
     if let Some((method, percent, seed)) = &cmd.sample {
@@ -379,42 +402,57 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
             JoinKind::Inner => ("INNER", true),
             JoinKind::Left => ("LEFT", true),
             JoinKind::Right => ("RIGHT", true),
-            JoinKind::Lateral => ("LATERAL", true),
+            JoinKind::Lateral => ("LEFT", true),
             JoinKind::Full => ("FULL OUTER", true),
             JoinKind::Cross => ("CROSS", false),
         };
         // Join: target.source_singular_id = source.id
         let source_singular = cmd.table.trim_end_matches('s');
 
-        let target_table = generator.quote_identifier(&join.table);
+        let target_table = match &join.subquery {
+            // Correlated subquery: (SELECT ...) AS alias
+            Some(query) => format!(
+                "({}) AS {}",
+                query.to_sql_with_dialect(dialect),
+                generator.quote_identifier(&join.table)
+            ),
+            None => generator.table_ref(cmd.schema_for(&join.table), &join.table),
+        };
+        let join_kw = if join.kind == JoinKind::Lateral {
+            "JOIN LATERAL"
+        } else {
+            "JOIN"
+        };
         let source_fk = format!("{}_id", source_singular);
-        let source_table = generator.quote_identifier(&cmd.table);
+        let source_table = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
         if let Some(on_conds) = &join.on {
             let on_sql: Vec<String> = on_conds
                 .iter()
-                .map(|c| c.to_sql(&generator, Some(cmd)))
+                .map(|c| c.to_sql(generator.as_ref(), Some(cmd)))
                 .collect();
             sql.push_str(&format!(
-                " {} JOIN {} ON {}",
+                " {} {} {} ON {}",
                 kind,
+                join_kw,
                 target_table,
                 on_sql.join(" AND ")
             ));
-        } else if join.on_true {
-            // Explicit ON TRUE (unconditional join, used for CTE joins)
-            sql.push_str(&format!(" {} JOIN {} ON TRUE", kind, target_table));
+        } else if join.on_true || join.subquery.is_some() {
+            // Explicit ON TRUE (unconditional join, used for CTE and lateral joins)
+            sql.push_str(&format!(" {} {} {} ON TRUE", kind, join_kw, target_table));
         } else if needs_on {
             sql.push_str(&format!(
-                " {} JOIN {} ON {}.{} = {}.id",
+                " {} {} {} ON {}.{} = {}.id",
                 kind,
+                join_kw,
                 target_table,
                 target_table,
                 generator.quote_identifier(&source_fk),
                 source_table
             ));
         } else {
-            sql.push_str(&format!(" {} JOIN {}", kind, target_table));
+            sql.push_str(&format!(" {} {} {}", kind, join_kw, target_table));
         }
     }
 
@@ -460,46 +498,30 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
     let mut where_groups: Vec<String> = Vec::new();
     let mut order_by_clauses: Vec<String> = Vec::new();
     let mut limit: Option<usize> = None;
+    let mut limit_all = false;
     let mut offset: Option<usize> = None;
 
     for cage in &cmd.cages {
         match &cage.kind {
             CageKind::Filter => {
                 if !cage.conditions.is_empty() {
-                    let joiner = match cage.logical_op {
-                        LogicalOp::And => " AND ",
-                        LogicalOp::Or => " OR ",
-                    };
-                    let conditions: Vec<String> = cage
-                        .conditions
-                        .iter()
-                        .map(|c| c.to_sql(&generator, Some(cmd)))
-                        .collect();
-                    let group = conditions.join(joiner);
-                    // Wrap OR groups in parentheses for correct precedence
-                    if cage.logical_op == LogicalOp::Or && cage.conditions.len() > 1 {
-                        where_groups.push(format!("({})", group));
-                    } else {
-                        where_groups.push(group);
-                    }
+                    where_groups.push(super::filter_group_sql(cage, generator.as_ref(), cmd));
                 }
             }
             CageKind::Sort(order) => {
                 if let Some(cond) = cage.conditions.first() {
-                    let dir = match order {
-                        SortOrder::Asc => "ASC",
-                        SortOrder::Desc => "DESC",
-                        SortOrder::AscNullsFirst => "ASC NULLS FIRST",
-                        SortOrder::AscNullsLast => "ASC NULLS LAST",
-                        SortOrder::DescNullsFirst => "DESC NULLS FIRST",
-                        SortOrder::DescNullsLast => "DESC NULLS LAST",
-                    };
-                    let col_sql = render_expr_for_orderby(&cond.left, &generator, cmd);
-                    order_by_clauses.push(format!("{} {}", col_sql, dir));
+                    let col_sql = render_expr_for_orderby(&cond.left, generator.as_ref(), cmd);
+                    order_by_clauses.push(generator.order_by_item(&col_sql, *order));
                 }
             }
+            // The last limit wins, so `limit_all` can override a default limit
             CageKind::Limit(n) => {
                 limit = Some(*n);
+                limit_all = false;
+            }
+            CageKind::LimitAll => {
+                limit = None;
+                limit_all = true;
             }
             CageKind::Offset(n) => {
                 offset = Some(*n);
@@ -548,7 +570,7 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
         let having_conds: Vec<String> = cmd
             .having
             .iter()
-            .map(|c| c.to_sql(&generator, Some(cmd)))
+            .map(|c| c.to_sql(generator.as_ref(), Some(cmd)))
             .collect();
         sql.push_str(" HAVING ");
         sql.push_str(&having_conds.join(" AND "));
@@ -568,14 +590,41 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
             let qualify_conds: Vec<String> = cage
                 .conditions
                 .iter()
-                .map(|c| c.to_sql(&generator, Some(cmd)))
+                .map(|c| c.to_sql(generator.as_ref(), Some(cmd)))
                 .collect();
             sql.push_str(" QUALIFY ");
             sql.push_str(&qualify_conds.join(" AND "));
         }
     }
 
-    sql.push_str(&generator.limit_offset(limit, offset));
+    // FETCH clause (SQL standard alternative to LIMIT)
+    let paging = if let Some((count, with_ties)) = cmd.fetch {
+        if with_ties && order_by_clauses.is_empty() {
+            // WITH TIES extends the last row's peer group, which needs an ORDER BY
+            return "/* ERROR: FETCH FIRST ... WITH TIES requires an ORDER BY */".to_string();
+        }
+        if limit.is_some() || limit_all {
+            return "/* ERROR: FETCH FIRST cannot be combined with LIMIT */".to_string();
+        }
+        match generator.fetch_first(count, with_ties, offset) {
+            Some(paging) => paging,
+            None => {
+                return format!(
+                    "/* ERROR: FETCH FIRST ... WITH TIES is not supported by {:?} */",
+                    generator.dialect()
+                );
+            }
+        }
+    } else if limit_all {
+        generator.limit_all(offset)
+    } else {
+        generator.limit_offset(limit, offset)
+    };
+    if !paging.is_empty() && order_by_clauses.is_empty() && generator.paging_requires_order_by() {
+        // Any ORDER BY will do when the caller did not ask for one
+        sql.push_str(" ORDER BY (SELECT NULL)");
+    }
+    sql.push_str(&paging);
 
     // SET OPERATIONS (UNION, INTERSECT, EXCEPT)
     for (set_op, other_cmd) in &cmd.set_ops {
@@ -588,15 +637,6 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
         sql.push_str(&format!(" {} {}", op_str, build_select(other_cmd, dialect)));
     }
 
-    // FETCH clause (SQL standard alternative to LIMIT)
-    if let Some((count, with_ties)) = cmd.fetch {
-        if with_ties {
-            sql.push_str(&format!(" FETCH FIRST {} ROWS WITH TIES", count));
-        } else {
-            sql.push_str(&format!(" FETCH FIRST {} ROWS ONLY", count));
-        }
-    }
-
     // FOR UPDATE/SHARE (row locking)
     if let Some(lock) = &cmd.lock_mode {
         match lock {
@@ -612,10 +652,9 @@ pub fn build_select(cmd: &Qail, dialect: Dialect) -> String {
 
 /// Render an expression for ORDER BY (and potentially other contexts).
 /// Handles CASE, Binary, FunctionCall, SpecialFunction, and Named expressions.
-#[allow(clippy::borrowed_box)]
 fn render_expr_for_orderby(
     expr: &Expr,
-    generator: &Box<dyn crate::transpiler::SqlGenerator>,
+    generator: &dyn crate::transpiler::SqlGenerator,
     cmd: &Qail,
 ) -> String {
     match expr {
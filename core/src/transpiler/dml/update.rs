@@ -11,7 +11,7 @@ pub fn build_update(cmd: &Qail, dialect: Dialect) -> String {
     } else {
         String::from("UPDATE ")
     };
-    sql.push_str(&generator.quote_identifier(&cmd.table));
+    sql.push_str(&generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table));
 
     let mut set_clauses: Vec<String> = Vec::new();
     let mut where_clauses: Vec<String> = Vec::new();
@@ -21,17 +21,18 @@ pub fn build_update(cmd: &Qail, dialect: Dialect) -> String {
             // V2 syntax: Payload cage contains SET values
             CageKind::Payload => {
                 for cond in &cage.conditions {
+                    if dialect == Dialect::SQLite && matches!(cond.value, Value::Default) {
+                        return "/* ERROR: SQLite has no DEFAULT keyword in SET */".to_string();
+                    }
                     let col_sql = match &cond.left {
                         Expr::Named(name) => generator.quote_identifier(name),
                         expr => expr.to_string(),
                     };
-                    set_clauses.push(format!("{} = {}", col_sql, cond.to_value_sql(&generator)));
+                    set_clauses.push(format!("{} = {}", col_sql, cond.to_value_sql(generator.as_ref())));
                 }
             }
-            CageKind::Filter => {
-                for cond in &cage.conditions {
-                    where_clauses.push(cond.to_sql(&generator, Some(cmd)));
-                }
+            CageKind::Filter if !cage.conditions.is_empty() => {
+                where_clauses.push(super::filter_group_sql(cage, generator.as_ref(), cmd));
             }
             _ => {}
         }
@@ -49,7 +50,7 @@ pub fn build_update(cmd: &Qail, dialect: Dialect) -> String {
         sql.push_str(
             &cmd.from_tables
                 .iter()
-                .map(|t| generator.quote_identifier(t))
+                .map(|t| generator.table_ref(cmd.schema_for(t), t))
                 .collect::<Vec<_>>()
                 .join(", "),
         );
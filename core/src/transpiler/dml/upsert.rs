@@ -7,7 +7,7 @@ use crate::transpiler::dialect::Dialect;
 /// Supports Postgres ON CONFLICT, MySQL ON DUPLICATE KEY, and Oracle/SQL Server MERGE.
 pub fn build_upsert(cmd: &Qail, dialect: Dialect) -> String {
     let generator = dialect.generator();
-    let table = generator.quote_identifier(&cmd.table);
+    let table = generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table);
 
     // 1. Identify PK (Conflict Target) from command columns (put::table:pk)
     let pk_cols: Vec<String> = cmd
@@ -33,7 +33,7 @@ pub fn build_upsert(cmd: &Qail, dialect: Dialect) -> String {
                         Expr::Named(name) => name.clone(),
                         expr => expr.to_string(),
                     },
-                    c.to_value_sql(&generator),
+                    c.to_value_sql(generator.as_ref()),
                 )
             })
             .unzip()
@@ -93,6 +93,33 @@ pub fn build_upsert(cmd: &Qail, dialect: Dialect) -> String {
             // Postgres supports RETURNING on upsert (SQLite depends on version, but usually fine in simple cases or ignored)
             sql.push_str(" RETURNING *");
         }
+        Dialect::SqlServer => {
+            return "/* ERROR: Upsert for SQL Server needs MERGE, which is not supported yet */"
+                .to_string();
+        }
+        Dialect::MySQL => {
+            // The conflict target is implied by the table's unique keys
+            let update_cols: Vec<&String> = data_cols
+                .iter()
+                .filter(|c| !pk_cols.contains(c))
+                .collect();
+            let update_cols = if update_cols.is_empty() {
+                pk_cols.iter().collect()
+            } else {
+                update_cols
+            };
+            sql.push_str(" ON DUPLICATE KEY UPDATE ");
+            sql.push_str(
+                &update_cols
+                    .iter()
+                    .map(|c| {
+                        let quoted = generator.quote_identifier(c);
+                        format!("{} = VALUES({})", quoted, quoted)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
     }
 
     sql
@@ -59,19 +59,8 @@ pub fn build_window(cmd: &Qail, dialect: Dialect) -> String {
                                 };
 
                                 match &cage.kind {
-                                    CageKind::Sort(SortOrder::Asc) => format!("{} ASC", col_str),
-                                    CageKind::Sort(SortOrder::Desc) => format!("{} DESC", col_str),
-                                    CageKind::Sort(SortOrder::AscNullsFirst) => {
-                                        format!("{} ASC NULLS FIRST", col_str)
-                                    }
-                                    CageKind::Sort(SortOrder::AscNullsLast) => {
-                                        format!("{} ASC NULLS LAST", col_str)
-                                    }
-                                    CageKind::Sort(SortOrder::DescNullsFirst) => {
-                                        format!("{} DESC NULLS FIRST", col_str)
-                                    }
-                                    CageKind::Sort(SortOrder::DescNullsLast) => {
-                                        format!("{} DESC NULLS LAST", col_str)
+                                    CageKind::Sort(order) => {
+                                        generator.order_by_item(&col_str, *order)
                                     }
                                     _ => String::new(),
                                 }
@@ -118,13 +107,13 @@ pub fn build_window(cmd: &Qail, dialect: Dialect) -> String {
 
     sql.push_str(&cols.join(", "));
     sql.push_str(" FROM ");
-    sql.push_str(&generator.quote_identifier(&cmd.table));
+    sql.push_str(&generator.table_ref(cmd.schema_for(&cmd.table), &cmd.table));
 
     let mut where_clauses: Vec<String> = Vec::new();
     for cage in &cmd.cages {
         if let CageKind::Filter = cage.kind {
             for cond in &cage.conditions {
-                where_clauses.push(cond.to_sql(&generator, Some(cmd)));
+                where_clauses.push(cond.to_sql(generator.as_ref(), Some(cmd)));
             }
         }
     }
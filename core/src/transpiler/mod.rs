@@ -1,6 +1,7 @@
 //! SQL Transpiler for QAIL AST.
 //!
 
+pub mod cached;
 pub mod conditions;
 pub mod ddl;
 pub mod dialect;
@@ -18,10 +19,12 @@ pub use nosql::qdrant::ToQdrant;
 mod tests;
 
 use crate::ast::*;
+pub use cached::{CachedQail, SqlCacheStats};
 pub use conditions::ConditionToSql;
-pub use dialect::Dialect;
-pub use traits::SqlGenerator;
+pub use dialect::{Dialect, PlaceholderStyle};
+pub use traits::{ReturningStyle, SqlGenerator};
 pub use traits::escape_identifier;
+pub use traits::{QuotingPolicy, with_quoting_policy};
 
 /// Result of transpilation with extracted parameters.
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -30,7 +33,8 @@ pub struct TranspileResult {
     pub sql: String,
     /// The extracted parameter values in order
     pub params: Vec<Value>,
-    /// Names of named parameters in order they appear (for :name → $n mapping)
+    /// Names of named parameters in placeholder order (for :name → $n mapping).
+    /// With `PlaceholderStyle::Question` a name repeats once per `?`.
     pub named_params: Vec<String>,
 }
 
@@ -60,8 +64,18 @@ pub trait ToSqlParameterized {
     fn to_sql_parameterized(&self) -> TranspileResult {
         self.to_sql_parameterized_with_dialect(Dialect::default())
     }
-    /// Convert to SQL with extracted parameters for specific dialect.
+    /// Convert to SQL with extracted parameters for specific dialect,
+    /// using the dialect's [`PlaceholderStyle`].
     fn to_sql_parameterized_with_dialect(&self, dialect: Dialect) -> TranspileResult;
+    /// Convert to SQL with extracted parameters and an explicit placeholder style.
+    /// Implementations that do not override this keep the dialect's own style.
+    fn to_sql_parameterized_with_style(
+        &self,
+        dialect: Dialect,
+        _style: PlaceholderStyle,
+    ) -> TranspileResult {
+        self.to_sql_parameterized_with_dialect(dialect)
+    }
 }
 
 /// Trait for converting AST nodes to SQL.
@@ -72,11 +86,16 @@ pub trait ToSql {
     }
     /// Convert this node to a SQL string with specific dialect.
     fn to_sql_with_dialect(&self, dialect: Dialect) -> String;
+    /// Convert this node to a SQL string with an explicit identifier quoting policy.
+    /// The policy only changes PostgreSQL output; other dialects always quote.
+    fn to_sql_with_quoting(&self, dialect: Dialect, policy: QuotingPolicy) -> String {
+        with_quoting_policy(policy, || self.to_sql_with_dialect(dialect))
+    }
 }
 
 impl ToSql for Qail {
     fn to_sql_with_dialect(&self, dialect: Dialect) -> String {
-        match self.action {
+        let sql = match self.action {
             Action::Get => dml::select::build_select(self, dialect),
             Action::Set => dml::update::build_update(self, dialect),
             Action::Del => dml::delete::build_delete(self, dialect),
@@ -96,14 +115,23 @@ impl ToSql for Qail {
             Action::TxnCommit => "COMMIT;".to_string(),
             Action::TxnRollback => "ROLLBACK;".to_string(),
             Action::Put => dml::upsert::build_upsert(self, dialect),
-            Action::Drop => format!("DROP TABLE {}", self.table),
+            Action::Drop => format!("DROP TABLE {}", qualified_table(self, dialect)),
             Action::DropCol | Action::RenameCol => ddl::build_alter_column(self, dialect),
             // JSON features
             Action::JsonTable => dml::json_table::build_json_table(self, dialect),
             // COPY protocol (AST-native in qail-pg, generates SELECT for fallback)
             Action::Export => dml::select::build_select(self, dialect),
             // TRUNCATE TABLE
-            Action::Truncate => format!("TRUNCATE TABLE {}", self.table),
+            Action::Truncate => {
+                let mut sql = format!("TRUNCATE TABLE {}", qualified_table(self, dialect));
+                if self.restart_identity {
+                    sql.push_str(" RESTART IDENTITY");
+                }
+                if self.cascade {
+                    sql.push_str(" CASCADE");
+                }
+                sql
+            }
             // EXPLAIN - wrap SELECT query
             Action::Explain => format!("EXPLAIN {}", dml::select::build_select(self, dialect)),
             // EXPLAIN ANALYZE - execute and analyze query
@@ -112,7 +140,10 @@ impl ToSql for Qail {
                 dml::select::build_select(self, dialect)
             ),
             // LOCK TABLE
-            Action::Lock => format!("LOCK TABLE {} IN ACCESS EXCLUSIVE MODE", self.table),
+            Action::Lock => format!(
+                "LOCK TABLE {} IN ACCESS EXCLUSIVE MODE",
+                qualified_table(self, dialect)
+            ),
             // CREATE MATERIALIZED VIEW - uses source_query for the view definition
             Action::CreateMaterializedView => {
                 if let Some(source) = &self.source_query {
@@ -144,7 +175,7 @@ impl ToSql for Qail {
             Action::Notify => {
                 if let Some(ch) = &self.channel {
                     if let Some(msg) = &self.payload {
-                        format!("NOTIFY {}, '{}'", ch, msg)
+                        format!("NOTIFY {}, '{}'", ch, msg.replace('\'', "''"))
                     } else {
                         format!("NOTIFY {}", ch)
                     }
@@ -264,60 +295,104 @@ impl ToSql for Qail {
                     self.action
                 )
             }
+        };
+        match self.tag_comment() {
+            Some(comment) => format!("{} {}", comment, sql),
+            None => sql,
+        }
+    }
+}
+
+/// Replace each `:name` in `sql` with `bind(name)`. `::` casts are kept.
+pub(crate) fn replace_named_params(sql: &str, mut bind: impl FnMut(String) -> String) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':'
+            && let Some(&next) = chars.peek()
+        {
+            if next == ':' {
+                result.push(':');
+                result.push(chars.next().unwrap());
+                continue;
+            }
+            if next.is_ascii_alphabetic() || next == '_' {
+                let mut param_name = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_ascii_alphanumeric() || ch == '_' {
+                        param_name.push(chars.next().unwrap());
+                    } else {
+                        break;
+                    }
+                }
+                result.push_str(&bind(param_name));
+                continue;
+            }
         }
+        result.push(c);
+    }
+    result
+}
+
+/// Table name for statements that take a bare table, schema-qualified when set.
+fn qualified_table(cmd: &Qail, dialect: Dialect) -> String {
+    match cmd.schema_for(&cmd.table) {
+        Some(schema) => dialect.generator().table_ref(Some(schema), &cmd.table),
+        None => cmd.table.clone(),
     }
 }
 
 impl ToSqlParameterized for Qail {
     fn to_sql_parameterized_with_dialect(&self, dialect: Dialect) -> TranspileResult {
+        self.to_sql_parameterized_with_style(dialect, dialect.placeholder_style())
+    }
+
+    fn to_sql_parameterized_with_style(
+        &self,
+        dialect: Dialect,
+        style: PlaceholderStyle,
+    ) -> TranspileResult {
         // Use the full ToSql implementation which handles CTEs, JOINs, etc.
         // Then post-process to extract named parameters for binding
         let full_sql = self.to_sql_with_dialect(dialect);
 
-        // and replace them with positional parameters ($1, $2, etc.)
+        // and replace them with placeholders in the requested style
         let mut named_params: Vec<String> = Vec::new();
         let mut seen_params: std::collections::HashMap<String, usize> =
             std::collections::HashMap::new();
         let mut result = String::with_capacity(full_sql.len());
-        let mut chars = full_sql.chars().peekable();
+        // Copy the leading tag comment verbatim: tags like `app:checkout` are not params
+        let body = match self.tag_comment() {
+            Some(comment) if full_sql.starts_with(&comment) => {
+                result.push_str(&comment);
+                &full_sql[comment.len()..]
+            }
+            _ => full_sql.as_str(),
+        };
         let mut param_index = 1;
+        result.push_str(&replace_named_params(body, |param_name| {
+            if style == PlaceholderStyle::Question {
+                // Positional: every occurrence is its own bind slot
+                named_params.push(param_name);
+                return "?".to_string();
+            }
 
-        while let Some(c) = chars.next() {
-            if c == ':'
-                && let Some(&next) = chars.peek()
-            {
-                if next == ':' {
-                    result.push(':');
-                    result.push(chars.next().unwrap());
-                    continue;
-                }
-                if next.is_ascii_alphabetic() || next == '_' {
-                    let mut param_name = String::new();
-                    while let Some(&ch) = chars.peek() {
-                        if ch.is_ascii_alphanumeric() || ch == '_' {
-                            param_name.push(chars.next().unwrap());
-                        } else {
-                            break;
-                        }
-                    }
-
-                    let idx = if let Some(&existing) = seen_params.get(&param_name) {
-                        existing
-                    } else {
-                        let idx = param_index;
-                        seen_params.insert(param_name.clone(), idx);
-                        named_params.push(param_name);
-                        param_index += 1;
-                        idx
-                    };
+            let idx = if let Some(&existing) = seen_params.get(&param_name) {
+                existing
+            } else {
+                let idx = param_index;
+                seen_params.insert(param_name.clone(), idx);
+                named_params.push(param_name);
+                param_index += 1;
+                idx
+            };
 
-                    result.push('$');
-                    result.push_str(&idx.to_string());
-                    continue;
-                }
+            match style {
+                PlaceholderStyle::Named => format!(":{}", named_params[idx - 1]),
+                PlaceholderStyle::AtP => format!("@p{}", idx),
+                PlaceholderStyle::Dollar | PlaceholderStyle::Question => format!("${}", idx),
             }
-            result.push(c);
-        }
+        }));
 
         TranspileResult {
             sql: result,
@@ -1,4 +1,5 @@
 use crate::ast::*;
+use crate::ast::values::is_decimal_literal;
 
 pub trait ToDynamo {
     fn to_dynamo(&self) -> String;
@@ -272,6 +273,7 @@ fn value_to_dynamo(v: &Value) -> String {
         Value::String(s) => format!("{{ \"S\": \"{}\" }}", s),
         Value::Int(n) => format!("{{ \"N\": \"{}\" }}", n),
         Value::Float(n) => format!("{{ \"N\": \"{}\" }}", n),
+        Value::Decimal(d) if is_decimal_literal(d) => format!("{{ \"N\": \"{}\" }}", d),
         Value::Bool(b) => format!("{{ \"BOOL\": {} }}", b),
         Value::Null => "{ \"NULL\": true }".to_string(),
         _ => "{ \"S\": \"unknown\" }".to_string(),
@@ -1,4 +1,5 @@
 use crate::ast::*;
+use crate::ast::values::is_decimal_literal;
 
 pub trait ToMongo {
     fn to_mongo(&self) -> String;
@@ -287,6 +288,7 @@ fn value_to_json(v: &Value) -> String {
         Value::String(s) => format!("\"{}\"", s),
         Value::Int(n) => n.to_string(),
         Value::Float(n) => n.to_string(),
+        Value::Decimal(d) if is_decimal_literal(d) => d.clone(),
         Value::Bool(b) => b.to_string(),
         Value::Null => "null".to_string(),
         Value::Param(i) => format!("\"$param{}\"", i),
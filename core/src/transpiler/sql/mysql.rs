@@ -0,0 +1,101 @@
+use super::super::Dialect;
+use super::super::traits::{ReturningStyle, SqlGenerator, identifier_parts};
+use crate::ast::{Operator, SortOrder};
+
+pub struct MySqlGenerator;
+
+impl SqlGenerator for MySqlGenerator {
+    fn quote_identifier(&self, id: &str) -> String {
+        identifier_parts(id)
+            .into_iter()
+            .map(|(part, quoted)| {
+                if part == "*" && !quoted {
+                    part.to_string()
+                } else {
+                    format!("`{}`", part.replace('`', "``"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn fuzzy_operator(&self) -> &str {
+        "LIKE"
+    }
+
+    fn bool_literal(&self, val: bool) -> String {
+        if val {
+            "TRUE".to_string()
+        } else {
+            "FALSE".to_string()
+        }
+    }
+
+    fn string_concat(&self, parts: &[&str]) -> String {
+        format!("CONCAT({})", parts.join(", "))
+    }
+
+    /// Backslash is an escape character in MySQL string literals.
+    fn string_literal(&self, s: &str) -> String {
+        format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''"))
+    }
+
+    fn dialect(&self) -> Dialect {
+        Dialect::MySQL
+    }
+
+    /// MySQL has no `FETCH FIRST`; without ties it is a plain `LIMIT`.
+    fn fetch_first(&self, count: u64, with_ties: bool, offset: Option<usize>) -> Option<String> {
+        if with_ties {
+            return None;
+        }
+        Some(self.limit_offset(Some(count as usize), offset))
+    }
+
+    fn limit_offset(&self, limit: Option<usize>, offset: Option<usize>) -> String {
+        let mut sql = String::new();
+        match (limit, offset) {
+            (Some(n), _) => sql.push_str(&format!(" LIMIT {}", n)),
+            // MySQL has no OFFSET without LIMIT; use the documented max row count
+            (None, Some(_)) => sql.push_str(" LIMIT 18446744073709551615"),
+            (None, None) => {}
+        }
+        if let Some(n) = offset {
+            sql.push_str(&format!(" OFFSET {}", n));
+        }
+        sql
+    }
+
+    /// MySQL has no `ILIKE` or `~`: `LIKE` and `REGEXP` are already
+    /// case-insensitive under the default collations, so case-sensitive
+    /// `~` compares against a `BINARY` pattern.
+    fn binary_operator(&self, op: &Operator) -> &'static str {
+        match op {
+            Operator::ILike => "LIKE",
+            Operator::NotILike => "NOT LIKE",
+            Operator::Regex => "REGEXP BINARY",
+            Operator::RegexI => "REGEXP",
+            other => other.sql_symbol(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Unsupported
+    }
+
+    /// MySQL has no `NULLS FIRST` / `NULLS LAST`. NULLs sort first ascending
+    /// and last descending, so only the opposite placements need rewriting,
+    /// using `ISNULL(expr)` (1 for NULL) as a leading sort key.
+    fn order_by_item(&self, expr: &str, order: SortOrder) -> String {
+        match order {
+            SortOrder::Asc | SortOrder::AscNullsFirst => format!("{} ASC", expr),
+            SortOrder::Desc | SortOrder::DescNullsLast => format!("{} DESC", expr),
+            SortOrder::AscNullsLast => format!("ISNULL({}), {} ASC", expr, expr),
+            SortOrder::DescNullsFirst => format!("ISNULL({}) DESC, {} DESC", expr, expr),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::transpiler::escape_identifier;
-use crate::transpiler::traits::SqlGenerator;
+use crate::transpiler::traits::{QuotingPolicy, SqlGenerator, quote_identifier_always, quoting_policy};
 
 pub struct PostgresGenerator;
 
@@ -17,7 +17,10 @@ impl PostgresGenerator {
 
 impl SqlGenerator for PostgresGenerator {
     fn quote_identifier(&self, name: &str) -> String {
-        escape_identifier(name)
+        match quoting_policy() {
+            QuotingPolicy::Auto => escape_identifier(name),
+            QuotingPolicy::Always => quote_identifier_always(name),
+        }
     }
 
     fn placeholder(&self, index: usize) -> String {
@@ -51,6 +54,22 @@ impl SqlGenerator for PostgresGenerator {
         sql
     }
 
+    fn supports_arrays(&self) -> bool {
+        true
+    }
+
+    fn supports_custom_operators(&self) -> bool {
+        true
+    }
+
+    fn limit_all(&self, offset: Option<usize>) -> String {
+        let mut sql = " LIMIT ALL".to_string();
+        if let Some(n) = offset {
+            sql.push_str(&format!(" OFFSET {}", n));
+        }
+        sql
+    }
+
     fn json_access(&self, col: &str, path: &[&str]) -> String {
         let mut sql = self.quote_identifier(col);
 
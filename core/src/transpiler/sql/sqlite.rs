@@ -1,10 +1,18 @@
-use super::super::traits::SqlGenerator;
+use super::super::Dialect;
+use super::super::traits::{SqlGenerator, identifier_parts};
 
 pub struct SqliteGenerator;
 
 impl SqlGenerator for SqliteGenerator {
     fn quote_identifier(&self, id: &str) -> String {
-        format!("\"{}\"", id)
+        if !id.contains('"') {
+            return format!("\"{}\"", id);
+        }
+        identifier_parts(id)
+            .into_iter()
+            .map(|(part, _)| format!("\"{}\"", part))
+            .collect::<Vec<_>>()
+            .join(".")
     }
 
     fn placeholder(&self, _index: usize) -> String {
@@ -27,6 +35,18 @@ impl SqlGenerator for SqliteGenerator {
         parts.join(" || ")
     }
 
+    fn dialect(&self) -> Dialect {
+        Dialect::SQLite
+    }
+
+    /// SQLite has no `FETCH FIRST`; without ties it is a plain `LIMIT`.
+    fn fetch_first(&self, count: u64, with_ties: bool, offset: Option<usize>) -> Option<String> {
+        if with_ties {
+            return None;
+        }
+        Some(self.limit_offset(Some(count as usize), offset))
+    }
+
     fn limit_offset(&self, limit: Option<usize>, offset: Option<usize>) -> String {
         let mut sql = String::new();
         if let Some(n) = limit {
@@ -37,4 +57,13 @@ impl SqlGenerator for SqliteGenerator {
         }
         sql
     }
+
+    /// SQLite spells "no limit" as a negative limit.
+    fn limit_all(&self, offset: Option<usize>) -> String {
+        let mut sql = " LIMIT -1".to_string();
+        if let Some(n) = offset {
+            sql.push_str(&format!(" OFFSET {}", n));
+        }
+        sql
+    }
 }
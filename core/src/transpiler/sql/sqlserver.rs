@@ -0,0 +1,100 @@
+use super::super::Dialect;
+use super::super::traits::{ReturningStyle, SqlGenerator, identifier_parts};
+use crate::ast::{Operator, SortOrder};
+
+pub struct SqlServerGenerator;
+
+impl SqlGenerator for SqlServerGenerator {
+    fn quote_identifier(&self, id: &str) -> String {
+        identifier_parts(id)
+            .into_iter()
+            .map(|(part, quoted)| {
+                if part == "*" && !quoted {
+                    part.to_string()
+                } else {
+                    format!("[{}]", part.replace(']', "]]"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("@p{}", index)
+    }
+
+    fn fuzzy_operator(&self) -> &str {
+        "LIKE"
+    }
+
+    fn bool_literal(&self, val: bool) -> String {
+        if val {
+            "1".to_string()
+        } else {
+            "0".to_string()
+        }
+    }
+
+    fn string_concat(&self, parts: &[&str]) -> String {
+        format!("CONCAT({})", parts.join(", "))
+    }
+
+    /// T-SQL has no `LIMIT`; `OFFSET ... FETCH` needs an `ORDER BY`.
+    fn dialect(&self) -> Dialect {
+        Dialect::SqlServer
+    }
+
+    /// `FETCH` needs an `OFFSET` here, and only `TOP` can keep ties.
+    fn fetch_first(&self, count: u64, with_ties: bool, offset: Option<usize>) -> Option<String> {
+        if with_ties {
+            return None;
+        }
+        Some(self.limit_offset(Some(count as usize), offset))
+    }
+
+    fn limit_offset(&self, limit: Option<usize>, offset: Option<usize>) -> String {
+        match (limit, offset) {
+            (None, None) => String::new(),
+            (limit, offset) => {
+                let mut sql = format!(" OFFSET {} ROWS", offset.unwrap_or(0));
+                if let Some(n) = limit {
+                    sql.push_str(&format!(" FETCH NEXT {} ROWS ONLY", n));
+                }
+                sql
+            }
+        }
+    }
+
+    fn paging_requires_order_by(&self) -> bool {
+        true
+    }
+
+    /// No `ILIKE` or regex operators: `LIKE` is case-insensitive under
+    /// the default collations.
+    fn binary_operator(&self, op: &Operator) -> &'static str {
+        match op {
+            Operator::ILike => "LIKE",
+            Operator::NotILike => "NOT LIKE",
+            other => other.sql_symbol(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Output
+    }
+
+    /// No `NULLS FIRST` / `NULLS LAST`; NULLs sort first ascending, so the
+    /// opposite placements get a leading `CASE` sort key.
+    fn order_by_item(&self, expr: &str, order: SortOrder) -> String {
+        match order {
+            SortOrder::Asc | SortOrder::AscNullsFirst => format!("{} ASC", expr),
+            SortOrder::Desc | SortOrder::DescNullsLast => format!("{} DESC", expr),
+            SortOrder::AscNullsLast => {
+                format!("CASE WHEN {} IS NULL THEN 1 ELSE 0 END, {} ASC", expr, expr)
+            }
+            SortOrder::DescNullsFirst => {
+                format!("CASE WHEN {} IS NULL THEN 0 ELSE 1 END, {} DESC", expr, expr)
+            }
+        }
+    }
+}
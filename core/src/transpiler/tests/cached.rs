@@ -0,0 +1,60 @@
+use crate::ast::{Operator, Qail};
+use crate::transpiler::{CachedQail, Dialect, QuotingPolicy, SqlCacheStats, ToSql, with_quoting_policy};
+
+#[test]
+fn test_cached_to_sql_hits_on_unmodified_command() {
+    let cmd = CachedQail::new(Qail::get("users").columns(["id"]).filter("active", Operator::Eq, true));
+
+    let first = cmd.to_sql();
+    let second = cmd.to_sql();
+
+    assert_eq!(first, second);
+    assert_eq!(first, cmd.get().to_sql());
+    assert_eq!(cmd.stats(), SqlCacheStats { hits: 1, misses: 1 });
+}
+
+#[test]
+fn test_cached_to_sql_invalidated_by_mutation() {
+    let mut cmd = CachedQail::from(Qail::get("users").columns(["id"]));
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users");
+
+    cmd.update(|q| q.limit(5));
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users LIMIT 5");
+
+    cmd.get_mut().table = "orders".to_string();
+    assert_eq!(cmd.to_sql(), "SELECT id FROM orders LIMIT 5");
+    assert_eq!(cmd.to_sql(), "SELECT id FROM orders LIMIT 5");
+
+    assert_eq!(cmd.stats(), SqlCacheStats { hits: 1, misses: 3 });
+}
+
+#[test]
+fn test_cached_to_sql_keyed_by_dialect() {
+    let cmd = CachedQail::new(Qail::get("users").limit(1));
+
+    cmd.to_sql_with_dialect(Dialect::Postgres);
+    cmd.to_sql_with_dialect(Dialect::Postgres);
+    assert_eq!(cmd.stats(), SqlCacheStats { hits: 1, misses: 1 });
+
+    // Only the last dialect is kept
+    let other = cmd.to_sql_with_dialect(Dialect::MySQL);
+    assert_eq!(other, cmd.get().to_sql_with_dialect(Dialect::MySQL));
+    cmd.to_sql_with_dialect(Dialect::Postgres);
+    assert_eq!(cmd.stats(), SqlCacheStats { hits: 1, misses: 3 });
+}
+
+#[test]
+fn test_cached_to_sql_keyed_by_quoting_policy() {
+    let cmd = CachedQail::new(Qail::get("users").columns(["id"]));
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users");
+
+    // A scoped policy must not be served the SQL cached under another one
+    let quoted = with_quoting_policy(QuotingPolicy::Always, || cmd.to_sql());
+    assert_eq!(quoted, "SELECT \"id\" FROM \"users\"");
+    assert_eq!(
+        cmd.to_sql_with_quoting(Dialect::Postgres, QuotingPolicy::Always),
+        quoted
+    );
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users");
+    assert_eq!(cmd.stats(), SqlCacheStats { hits: 1, misses: 3 });
+}
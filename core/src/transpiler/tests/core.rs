@@ -68,6 +68,21 @@ fn test_fuzzy_match() {
     );
 }
 
+#[test]
+fn test_match_modes() {
+    let fuzzy = parse("get users fields id where name ~ 'foo'").unwrap();
+    assert_eq!(
+        fuzzy.to_sql(),
+        "SELECT id FROM users WHERE name ILIKE '%foo%'"
+    );
+
+    let regex = parse("get users fields id where name ~r '^A.*z$'").unwrap();
+    assert_eq!(regex.to_sql(), "SELECT id FROM users WHERE name ~ '^A.*z$'");
+
+    let regex_i = parse("get users fields id where name ~* '^a.*z$'").unwrap();
+    assert_eq!(regex_i.to_sql(), "SELECT id FROM users WHERE name ~* '^a.*z$'");
+}
+
 // OR conditions - using manual Qail construction
 #[test]
 fn test_or_conditions() {
@@ -111,6 +126,15 @@ fn test_array_unnest() {
     });
     let sql = cmd.to_sql();
     assert!(sql.contains("EXISTS (SELECT 1 FROM unnest(tags)"));
+
+    // `~=` matches `%` and `_` literally inside the unnest too
+    cmd.cages[0].conditions[0].op = Operator::FuzzyEscaped;
+    cmd.cages[0].conditions[0].value = Value::String("100%".to_string());
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT * FROM users WHERE EXISTS (SELECT 1 FROM unnest(tags) _el \
+         WHERE _el ILIKE '%100\\%%' ESCAPE '\\')"
+    );
 }
 
 #[test]
@@ -122,6 +146,7 @@ fn test_left_join() {
         kind: JoinKind::Left,
         on: None,
         on_true: false,
+        subquery: None,
     });
     let sql = cmd.to_sql();
     assert!(sql.contains("LEFT JOIN"));
@@ -137,6 +162,7 @@ fn test_right_join() {
         kind: JoinKind::Right,
         on: None,
         on_true: false,
+        subquery: None,
     });
     let sql = cmd.to_sql();
     assert!(sql.contains("RIGHT JOIN"));
@@ -202,3 +228,29 @@ fn test_parameterized_sql() {
     assert_eq!(result.named_params[0], "name");
     assert_eq!(result.named_params[1], "age");
 }
+
+#[test]
+fn test_string_literal_quotes_escaped() {
+    use crate::ast::{Operator, Qail, Value};
+
+    let cmd = Qail::get("users").filter("name", Operator::Eq, "O'Brien");
+    assert_eq!(cmd.to_sql(), "SELECT * FROM users WHERE name = 'O''Brien'");
+
+    let cmd = Qail::get("users").filter("name", Operator::Fuzzy, "O'Brien");
+    assert!(cmd.to_sql().contains("'%O''Brien%'"), "{}", cmd.to_sql());
+
+    let cmd = Qail::get("users").filter(
+        "name",
+        Operator::In,
+        Value::Array(vec!["O'Brien".into(), "Smith".into()]),
+    );
+    assert!(cmd.to_sql().contains("'O''Brien', 'Smith'"), "{}", cmd.to_sql());
+
+    // Doubled quotes parse back to the original value
+    let cmd = parse("get users where name = 'O''Brien'").unwrap();
+    assert_eq!(
+        cmd.cages[0].conditions[0].value,
+        Value::String("O'Brien".to_string())
+    );
+    assert_eq!(cmd.to_sql(), "SELECT * FROM users WHERE name = 'O''Brien'");
+}
@@ -1,7 +1,7 @@
 //! SQL Dialect tests.
 
 use crate::parser::parse;
-use crate::transpiler::{Dialect, ToSql};
+use crate::transpiler::{Dialect, QuotingPolicy, ToSql, ToSqlParameterized};
 
 #[test]
 fn test_sqlite_dialect() {
@@ -17,3 +17,243 @@ fn test_sqlite_dialect() {
         "SELECT * FROM \"users\" WHERE \"name\" LIKE '%' || ? || '%'"
     );
 }
+
+#[test]
+fn test_quoting_policy_always() {
+    let cmd = parse("get user fields order").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_quoting(Dialect::Postgres, QuotingPolicy::Always),
+        "SELECT \"order\" FROM \"user\""
+    );
+
+    let cmd = parse("get users fields id, users.email where active = true").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_quoting(Dialect::Postgres, QuotingPolicy::Always),
+        "SELECT \"id\", \"users\".\"email\" FROM \"users\" WHERE \"active\" = true"
+    );
+    // Policy is scoped to the call
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id, users.email FROM users WHERE active = true"
+    );
+}
+
+#[test]
+fn test_quoting_policy_auto_reserved_words() {
+    let cmd = parse("get user fields id, order").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_quoting(Dialect::Postgres, QuotingPolicy::Auto),
+        "SELECT id, \"order\" FROM \"user\""
+    );
+
+    let cmd = parse("add users fields id, order values 1, 'x'").unwrap();
+    assert!(cmd.to_sql().starts_with("INSERT INTO users (id, \"order\")"));
+}
+
+#[test]
+fn test_quoting_policy_is_postgres_only() {
+    // The other dialects quote every identifier whatever the policy
+    let cmd = parse("get users fields id, order").unwrap();
+    for (dialect, sql) in [
+        (Dialect::MySQL, "SELECT `id`, `order` FROM `users`"),
+        (Dialect::SQLite, "SELECT \"id\", \"order\" FROM \"users\""),
+        (Dialect::SqlServer, "SELECT [id], [order] FROM [users]"),
+    ] {
+        assert_eq!(cmd.to_sql_with_quoting(dialect, QuotingPolicy::Auto), sql);
+        assert_eq!(cmd.to_sql_with_quoting(dialect, QuotingPolicy::Always), sql);
+    }
+}
+
+#[test]
+fn test_nulls_ordering_postgres_vs_mysql() {
+    let cmd = parse("get users fields id order by score desc nulls first, name asc nulls last").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM users ORDER BY score DESC NULLS FIRST, name ASC NULLS LAST"
+    );
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` ORDER BY ISNULL(`score`) DESC, `score` DESC, ISNULL(`name`), `name` ASC"
+    );
+
+    // Placements matching MySQL's defaults need no rewrite
+    let cmd = parse("get users fields id order by score asc nulls first, name desc nulls last").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` ORDER BY `score` ASC, `name` DESC"
+    );
+}
+
+#[test]
+fn test_mysql_dialect() {
+    let cmd = parse("get users fields * where name ~ $1 and active = true offset 20").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT * FROM `users` WHERE `name` LIKE CONCAT('%', ?, '%') AND `active` = TRUE LIMIT 18446744073709551615 OFFSET 20"
+    );
+}
+
+#[test]
+fn test_mysql_limit_offset_ilike_and_returning() {
+    let cmd = parse("get users fields * limit 10 offset 20").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT * FROM `users` LIMIT 10 OFFSET 20"
+    );
+
+    let cmd = parse("get users fields id where email ilike 'x%' and name not ilike $1 and bio ~* 'rust'")
+        .unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` WHERE `email` LIKE 'x%' AND `name` NOT LIKE ? AND `bio` REGEXP 'rust'"
+    );
+    assert!(cmd.to_sql().contains("email ILIKE 'x%' AND name NOT ILIKE $1"));
+
+    // Case-sensitive regex must not fall back to the case-insensitive REGEXP
+    let cmd = parse("get users fields id where bio ~r 'Rust'").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` WHERE `bio` REGEXP BINARY 'Rust'"
+    );
+
+    // MySQL has no RETURNING
+    let cmd = parse("add users fields id, active values 1, true").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "INSERT INTO `users` (`id`, `active`) VALUES (1, TRUE)"
+    );
+}
+
+#[test]
+fn test_mysql_string_literals_escape_backslash() {
+    use crate::ast::{Operator, Qail};
+
+    // `\'` must not end the literal: MySQL reads a backslash as an escape
+    let evil = "\\' OR 1=1 -- ";
+    let cmd = Qail::get("users").columns(["id"]).filter("name", Operator::Eq, evil);
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` WHERE `name` = '\\\\'' OR 1=1 -- '"
+    );
+    let cmd = Qail::get("users").columns(["id"]).filter("name", Operator::Fuzzy, evil);
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` WHERE `name` LIKE '%\\\\'' OR 1=1 -- %'"
+    );
+    let cmd = Qail::get("users").columns(["id"]).filter("name", Operator::Like, evil);
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` WHERE `name` LIKE '\\\\'' OR 1=1 -- '"
+    );
+
+    // Postgres keeps standard literals
+    let cmd = Qail::get("users").columns(["id"]).filter("name", Operator::Eq, evil);
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users WHERE name = '\\'' OR 1=1 -- '");
+}
+
+#[test]
+fn test_returning_vs_output_per_dialect() {
+    use crate::ast::{Expr, Qail};
+
+    let cmd = Qail::add("users")
+        .columns(["email", "name"])
+        .values(["a@b.c", "Al"])
+        .returning(["id", "created_at"]);
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::Postgres),
+        "INSERT INTO users (email, name) VALUES ('a@b.c', 'Al') RETURNING id, created_at"
+    );
+    // OUTPUT goes before VALUES, not at the end
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer),
+        "INSERT INTO [users] ([email], [name]) OUTPUT INSERTED.[id], INSERTED.[created_at] \
+         VALUES ('a@b.c', 'Al')"
+    );
+    assert!(
+        cmd.to_sql_with_dialect(Dialect::MySQL)
+            .starts_with("/* ERROR: MySQL has no INSERT ... RETURNING")
+    );
+
+    // The implicit default returns everything where the dialect can
+    let cmd = parse("add users fields email values 'a@b.c'").unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer),
+        "INSERT INTO [users] ([email]) OUTPUT INSERTED.* VALUES ('a@b.c')"
+    );
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "INSERT INTO `users` (`email`) VALUES ('a@b.c')"
+    );
+    let cmd = cmd.returning(Vec::<&str>::new());
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer),
+        "INSERT INTO [users] ([email]) VALUES ('a@b.c')"
+    );
+
+    // Aliases are read from INSERTED too; other expressions can't be
+    let mut cmd = cmd;
+    cmd.returning = Some(vec![Expr::Aliased {
+        name: "id".to_string(),
+        alias: "new_id".to_string(),
+    }]);
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer),
+        "INSERT INTO [users] ([email]) OUTPUT INSERTED.[id] AS [new_id] VALUES ('a@b.c')"
+    );
+    cmd.returning = Some(vec![Expr::Cast {
+        expr: Box::new(Expr::Named("id".to_string())),
+        target_type: "text".to_string(),
+        alias: None,
+    }]);
+    assert!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer)
+            .starts_with("/* ERROR: SQL Server OUTPUT takes plain columns")
+    );
+}
+
+#[test]
+fn test_sqlserver_dialect() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse("get users fields id where active = true and name ilike $1 order by id limit 10 offset 20")
+        .unwrap();
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer),
+        "SELECT [id] FROM [users] WHERE [active] = 1 AND [name] LIKE @p1 ORDER BY [id] ASC \
+         OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+    );
+
+    // OFFSET ... FETCH is only valid after an ORDER BY
+    let cmd = parse("get users fields id limit 10").unwrap();
+    let sql = cmd.to_sql_with_dialect(Dialect::SqlServer);
+    assert_eq!(
+        sql,
+        "SELECT [id] FROM [users] ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY"
+    );
+    super::oracle::assert_valid_sql(&sql, Dialect::SqlServer);
+
+    let cmd = parse("add users fields email values 'a' conflict (email) nothing").unwrap();
+    assert!(cmd.to_sql_with_dialect(Dialect::SqlServer).starts_with("/* ERROR"));
+
+    let cmd = parse("get users fields id where id = :id and parent = :id").unwrap();
+    let result = cmd.to_sql_parameterized_with_dialect(Dialect::SqlServer);
+    assert_eq!(
+        result.sql,
+        "SELECT [id] FROM [users] WHERE [id] = @p1 AND [parent] = @p1"
+    );
+    assert_eq!(result.named_params, vec!["id"]);
+}
+
+#[test]
+fn test_sqlite_rejects_default_keyword() {
+    let cmd = parse("add users fields id, name values default, 'Alice'").unwrap();
+    assert!(cmd.to_sql_with_dialect(Dialect::SQLite).starts_with("/* ERROR"));
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "INSERT INTO `users` (`id`, `name`) VALUES (DEFAULT, 'Alice')"
+    );
+
+    let cmd = parse("set users values name = default where id = $1").unwrap();
+    assert!(cmd.to_sql_with_dialect(Dialect::SQLite).starts_with("/* ERROR"));
+    assert!(cmd.to_sql_parameterized_with_dialect(Dialect::SQLite).sql.starts_with("/* ERROR"));
+}
@@ -3,6 +3,7 @@
 use crate::ast::*;
 use crate::parser::parse;
 use crate::transpiler::{Dialect, ToSql};
+use super::oracle::assert_valid_sql;
 
 // ============= DDL Tests =============
 
@@ -21,6 +22,33 @@ fn test_index_sql_unique() {
     assert!(sql.contains("CREATE UNIQUE INDEX"));
 }
 
+#[test]
+fn test_index_sql_if_not_exists() {
+    let cmd = parse("index? idx_email on users email unique").unwrap();
+    let sql = cmd.to_sql();
+    assert!(sql.contains("CREATE UNIQUE INDEX IF NOT EXISTS idx_email ON users"));
+}
+
+#[test]
+fn test_make_sql_if_not_exists() {
+    let cmd = parse("make? users id:uuid:pk").unwrap();
+    let sql = cmd.to_sql();
+    assert!(sql.starts_with("CREATE TABLE IF NOT EXISTS users ("));
+
+    let built = Qail {
+        action: Action::Make,
+        table: "users".to_string(),
+        columns: vec![Expr::Def {
+            name: "id".to_string(),
+            data_type: "uuid".to_string(),
+            constraints: vec![Constraint::PrimaryKey],
+        }],
+        ..Default::default()
+    }
+    .if_not_exists();
+    assert_eq!(built.to_sql(), sql);
+}
+
 #[test]
 fn test_composite_pk_sql() {
     // make order_items order_id:uuid, item_id:uuid primary key(order_id, item_id)
@@ -98,6 +126,34 @@ fn test_upsert_postgres() {
     assert!(sql.contains("RETURNING *"));
 }
 
+#[test]
+fn test_upsert_mysql() {
+    let mut cmd = Qail::put("users");
+    cmd.columns.push(Expr::Named("id".to_string()));
+    cmd.cages.push(Cage {
+        kind: CageKind::Payload,
+        conditions: vec![
+            Condition {
+                left: Expr::Named("id".to_string()),
+                op: Operator::Eq,
+                value: Value::Int(1),
+                is_array_unnest: false,
+            },
+            Condition {
+                left: Expr::Named("name".to_string()),
+                op: Operator::Eq,
+                value: Value::String("John".to_string()),
+                is_array_unnest: false,
+            },
+        ],
+        logical_op: LogicalOp::And,
+    });
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "INSERT INTO `users` (`id`, `name`) VALUES (1, 'John') ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+    );
+}
+
 // ============= JSON Tests =============
 
 #[test]
@@ -116,6 +172,7 @@ fn test_json_access() {
     });
     let sql = cmd.to_sql_with_dialect(Dialect::Postgres);
     assert!(sql.contains(r#"meta->>'theme' = 'dark'"#));
+    assert_valid_sql(&sql, Dialect::Postgres);
 }
 
 #[test]
@@ -133,6 +190,7 @@ fn test_json_contains() {
     });
     let sql = cmd.to_sql_with_dialect(Dialect::Postgres);
     assert!(sql.contains(r#"@> '{"theme": "dark"}'"#));
+    assert_valid_sql(&sql, Dialect::Postgres);
 }
 
 #[test]
@@ -214,10 +272,31 @@ fn test_lateral_join() {
         kind: JoinKind::Lateral,
         on: None,
         on_true: false,
+        subquery: None,
     });
 
     let sql = cmd.to_sql_with_dialect(Dialect::Postgres);
-    assert!(sql.contains("LATERAL JOIN"));
+    assert!(sql.contains("LEFT JOIN LATERAL orders"));
+}
+
+#[test]
+fn test_lateral_subquery_top_n_per_group() {
+    // Latest 3 orders per user
+    let latest = Qail::get("orders")
+        .columns(["id", "total"])
+        .filter("orders.user_id", Operator::Eq, Value::Column("users.id".into()))
+        .order_desc("created_at")
+        .limit(3);
+    let cmd = Qail::get("users")
+        .columns(["users.id", "latest.id", "latest.total"])
+        .left_join_lateral(latest, "latest");
+
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::Postgres),
+        "SELECT users.id, latest.id, latest.total FROM users \
+         LEFT JOIN LATERAL (SELECT id, total FROM orders WHERE orders.user_id = users.id \
+         ORDER BY created_at DESC LIMIT 3) AS latest ON TRUE"
+    );
 }
 
 // ============= SQL/JSON Standard Functions (Postgres 17+) =============
@@ -375,9 +454,12 @@ fn test_having_clause() {
     cmd.columns.push(Expr::Named("customer_id".to_string()));
     cmd.columns.push(Expr::Aggregate {
         col: "total".to_string(),
+        arg: None,
         func: AggregateFunc::Sum,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     });
     cmd.having.push(Condition {
@@ -402,9 +484,12 @@ fn test_group_by_rollup() {
     cmd.columns.push(Expr::Named("year".to_string()));
     cmd.columns.push(Expr::Aggregate {
         col: "amount".to_string(),
+        arg: None,
         func: AggregateFunc::Sum,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     });
     cmd.group_by_mode = GroupByMode::Rollup;
@@ -421,9 +506,12 @@ fn test_group_by_cube() {
     cmd.columns.push(Expr::Named("product".to_string()));
     cmd.columns.push(Expr::Aggregate {
         col: "amount".to_string(),
+        arg: None,
         func: AggregateFunc::Sum,
         distinct: false,
         filter: None,
+        delimiter: None,
+        order_by: None,
         alias: None,
     });
     cmd.group_by_mode = GroupByMode::Cube;
@@ -443,6 +531,7 @@ fn test_aggregate_filter() {
     // COUNT(*) FILTER (WHERE direction = 'outbound')
     cmd.columns.push(Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(vec![Condition {
@@ -451,6 +540,8 @@ fn test_aggregate_filter() {
             value: Value::String("outbound".to_string()),
             is_array_unnest: false,
         }]),
+        delimiter: None,
+        order_by: None,
         alias: Some("sent_count".to_string()),
     });
 
@@ -461,6 +552,92 @@ fn test_aggregate_filter() {
     assert!(sql.contains("direction"));
 }
 
+#[test]
+fn test_parsed_aggregate_filter() {
+    let cmd = parse("get orders fields count(*) filter (where status = 'active') as active_count")
+        .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT COUNT(*) FILTER (WHERE status = 'active') AS active_count FROM orders"
+    );
+
+    // Non-aggregate columns become the GROUP BY
+    let cmd = parse(
+        "get orders fields region, sum(total) filter (where status = 'paid' and total > 10) as paid_total",
+    )
+    .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT region, SUM(total) FILTER (WHERE status = 'paid' AND total > 10) AS paid_total \
+         FROM orders GROUP BY region"
+    );
+}
+
+#[test]
+fn test_ordered_aggregates() {
+    use crate::ast::builders::{self, aggregates::string_agg, array_agg, col};
+
+    let cmd = parse("get orders fields user_id, array_agg(id order by created_at desc) as ids")
+        .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT user_id, ARRAY_AGG(id ORDER BY created_at DESC) AS ids FROM orders GROUP BY user_id"
+    );
+
+    let cmd = parse("get users fields string_agg(name, ', ' order by name desc nulls last) as names")
+        .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT STRING_AGG(name, ', ' ORDER BY name DESC NULLS LAST) AS names FROM users"
+    );
+
+    // Builders produce the same expressions
+    let built = Qail::get("users").column_expr(
+        string_agg("name", ", ")
+            .order_by("name", SortOrder::DescNullsLast)
+            .alias("names"),
+    );
+    assert_eq!(built.to_sql(), cmd.to_sql());
+    let built = Qail::get("orders")
+        .column_expr(array_agg("id").order_by("created_at", SortOrder::Desc).alias("ids"));
+    assert_eq!(
+        built.to_sql(),
+        "SELECT ARRAY_AGG(id ORDER BY created_at DESC) AS ids FROM orders"
+    );
+
+    // Expression arguments are not quoted as identifiers
+    let cmd = parse("get users fields array_agg(lower(name)), json_agg(json_build_object('a', a))")
+        .unwrap();
+    let sql = cmd.to_sql();
+    assert_eq!(
+        sql,
+        "SELECT ARRAY_AGG(LOWER(name)), JSON_AGG(JSON_BUILD_OBJECT('a', a)) FROM users"
+    );
+    assert_valid_sql(&sql, Dialect::Postgres);
+    let built = Qail::get("users").column_expr(
+        string_agg(crate::ast::builders::func("lower", vec![Expr::Named("name".into())]), ",")
+            .build(),
+    );
+    assert_eq!(built.to_sql(), "SELECT STRING_AGG(LOWER(name), ',') FROM users");
+
+    // The root builder is the same aggregate
+    let root = Qail::get("users")
+        .column_expr(builders::string_agg(col("name"), ", ").alias("names"));
+    assert_eq!(root.to_sql(), "SELECT STRING_AGG(name, ', ') AS names FROM users");
+
+    // The delimiter is a string literal in the target dialect
+    let cmd = Qail::get("users").column_expr(string_agg("name", "\\' OR 1=1 -- ").build());
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT STRING_AGG(`name`, '\\\\'' OR 1=1 -- ') FROM `users`"
+    );
+
+    // A window call has nowhere to keep an in-call ORDER BY or FILTER
+    assert!(parse("get orders fields array_agg(id order by created_at desc) over (partition by user_id)").is_err());
+    assert!(parse("get orders fields count(*) filter (where paid) over (partition by user_id)").is_err());
+    assert!(parse("get orders fields array_agg(id) over (partition by user_id order by created_at desc)").is_ok());
+}
+
 // ============= RECURSIVE CTEs =============
 
 #[test]
@@ -500,6 +677,7 @@ fn test_recursive_cte() {
         base_query: Box::new(base),
         recursive_query: Some(Box::new(recursive)),
         source_table: Some("employees".to_string()),
+        materialized: None,
     }];
     cmd.action = Action::With;
 
@@ -511,6 +689,65 @@ fn test_recursive_cte() {
     assert!(sql.contains("UNION ALL"));
 }
 
+#[test]
+fn test_cte_materialization_hints() {
+    let inner = || Qail::get("orders").columns(["id", "total"]);
+
+    let sql = Qail::get("big")
+        .with("big", inner())
+        .materialized(true)
+        .to_sql();
+    assert!(sql.contains("big(id, total) AS MATERIALIZED (SELECT"), "{}", sql);
+
+    let sql = Qail::get("big")
+        .with("big", inner())
+        .materialized(false)
+        .to_sql();
+    assert!(sql.contains("big(id, total) AS NOT MATERIALIZED (SELECT"), "{}", sql);
+
+    let sql = Qail::get("big").with("big", inner()).to_sql();
+    assert!(sql.contains("big(id, total) AS (SELECT"), "{}", sql);
+    assert!(!sql.contains("MATERIALIZED"), "{}", sql);
+}
+
+#[test]
+fn test_correlated_scalar_subquery_column() {
+    let cmd = parse(
+        "get users fields id, (get orders fields count(*) where user_id = users.id) as order_count",
+    )
+    .unwrap();
+    let Expr::Subquery { query, alias } = &cmd.columns[1] else {
+        panic!("expected scalar subquery column, got {:?}", cmd.columns[1]);
+    };
+    assert_eq!(alias.as_deref(), Some("order_count"));
+    assert_eq!(query.table, "orders");
+
+    let sql = cmd.to_sql();
+    assert!(
+        sql.contains("(SELECT COUNT(*) FROM orders WHERE user_id = users.id) AS order_count"),
+        "{}",
+        sql
+    );
+    assert!(sql.ends_with("FROM users"), "{}", sql);
+}
+
+#[test]
+fn test_raw_fragment_is_verbatim() {
+    let cmd = Qail::get("users")
+        .column("id")
+        .column_expr(Qail::raw_fragment("vendor_hash(email, 'sha1') AS h"))
+        .filter(
+            "created_at",
+            Operator::Gt,
+            Value::Expr(Box::new(Qail::raw_fragment("now() at time zone 'utc'"))),
+        );
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id, vendor_hash(email, 'sha1') AS h FROM users WHERE created_at > now() at time zone 'utc'"
+    );
+    assert_eq!(cmd.complexity().raw, 2);
+}
+
 // ============= v0.8.6: Custom JOINs & DISTINCT ON =============
 
 #[test]
@@ -527,6 +764,7 @@ fn test_custom_join_on() {
             is_array_unnest: false,
         }]),
         on_true: false,
+        subquery: None,
     });
     let sql = cmd.to_sql();
     // Identifiers are unquoted if safe in Postgres dialect implementation used
@@ -558,6 +796,7 @@ fn test_custom_join_multiple_conditions() {
             },
         ]),
         on_true: false,
+        subquery: None,
     });
     let sql = cmd.to_sql();
     assert!(
@@ -587,3 +826,801 @@ fn test_distinct_on() {
         sql
     );
 }
+
+// ============= Schema-Qualified Tables =============
+
+#[test]
+fn test_schema_qualified_select() {
+    let cmd = Qail::get("users").schema("tenant_42").columns(["id", "email"]);
+    let sql = cmd.to_sql();
+    assert_eq!(sql, "SELECT id, email FROM tenant_42.users");
+}
+
+#[test]
+fn test_schema_qualified_join() {
+    let cmd = Qail::get("users")
+        .schema("tenant_42")
+        .columns(["users.id", "orders.total"])
+        .join(JoinKind::Inner, "orders", "users.id", "orders.user_id");
+    let sql = cmd.to_sql();
+    assert!(
+        sql.contains("FROM tenant_42.users INNER JOIN tenant_42.orders ON users.id = orders.user_id"),
+        "SQL was: {}",
+        sql
+    );
+}
+
+#[test]
+fn test_schema_reserved_word_quoted() {
+    let cmd = Qail::get("users").schema("order");
+    assert_eq!(cmd.to_sql(), "SELECT * FROM \"order\".users");
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SQLite),
+        "SELECT * FROM \"order\".\"users\""
+    );
+}
+
+#[test]
+fn test_schema_skips_cte_references() {
+    let cmd = Qail::get("active_users")
+        .schema("tenant_42")
+        .with("active_users", Qail::get("users"));
+    let sql = cmd.to_sql();
+    assert!(sql.contains("FROM active_users"), "SQL was: {}", sql);
+}
+
+#[test]
+fn test_schema_qualified_mutations() {
+    let del = Qail::del("sessions").schema("tenant_42").where_eq("id", 1);
+    assert!(del.to_sql().starts_with("DELETE FROM tenant_42.sessions"));
+
+    let upd = Qail::set("users").schema("tenant_42").set_value("active", false);
+    assert!(upd.to_sql().starts_with("UPDATE tenant_42.users SET"));
+
+    let ins = Qail::add("users").schema("tenant_42").columns(["email"]).values(["a@b.c"]);
+    assert!(ins.to_sql().starts_with("INSERT INTO tenant_42.users"));
+
+    let trunc = Qail::truncate("logs").schema("tenant_42");
+    assert_eq!(trunc.to_sql(), "TRUNCATE TABLE tenant_42.logs");
+}
+
+#[test]
+fn test_truncate_sql() {
+    assert_eq!(Qail::truncate("events").to_sql(), "TRUNCATE TABLE events");
+
+    let cmd = Qail::truncate("events").restart_identity().cascade();
+    assert_eq!(cmd.to_sql(), "TRUNCATE TABLE events RESTART IDENTITY CASCADE");
+
+    let cmd = parse("truncate events cascade").unwrap();
+    assert_eq!(cmd.to_sql(), "TRUNCATE TABLE events CASCADE");
+}
+
+#[test]
+fn test_schema_qualified_parse() {
+    let cmd = parse("get tenant_42.users fields id where users.active = true").unwrap();
+    assert_eq!(cmd.schema.as_deref(), Some("tenant_42"));
+    assert_eq!(cmd.table, "users");
+    let sql = cmd.to_sql();
+    assert!(
+        sql.contains("FROM tenant_42.users WHERE users.active = true"),
+        "SQL was: {}",
+        sql
+    );
+}
+
+// ============= Type Casts (::type) =============
+
+#[test]
+fn test_cast_in_select() {
+    let cmd = parse("get orders fields amount::numeric, id::text, data::jsonb").unwrap();
+    assert!(matches!(
+        &cmd.columns[0],
+        Expr::Cast { target_type, .. } if target_type == "numeric"
+    ));
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT amount::numeric, id::text, data::jsonb FROM orders"
+    );
+}
+
+#[test]
+fn test_cast_with_alias() {
+    let cmd = parse("get orders fields amount::numeric(10,2) as amt, tags::text[]").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT amount::numeric(10,2) AS amt, tags::text[] FROM orders"
+    );
+}
+
+#[test]
+fn test_cast_in_filter() {
+    let cmd = parse("get orders fields id where price::int > 100").unwrap();
+    assert_eq!(cmd.to_sql(), "SELECT id FROM orders WHERE price::int > 100");
+
+    let cmd = parse("get events fields id where created_at > '2024-01-01'::date").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM events WHERE created_at > '2024-01-01'::date"
+    );
+}
+
+#[test]
+fn test_cast_chained() {
+    let cmd = parse("get orders fields data->>'n'::text::int as n").unwrap();
+    assert_eq!(cmd.to_sql(), "SELECT data->>'n'::text::int AS n FROM orders");
+}
+
+// ============= DELETE ... USING / UPDATE ... FROM =============
+
+#[test]
+fn test_delete_using_join() {
+    let cmd = Qail::del("orders")
+        .using("customers")
+        .filter("customers.banned", Operator::Eq, true);
+    assert_eq!(
+        cmd.to_sql(),
+        "DELETE FROM orders USING customers WHERE customers.banned = true"
+    );
+
+    let cmd = parse(
+        "del orders using customers where orders.customer_id = customers.id and customers.banned = true",
+    )
+    .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "DELETE FROM orders USING customers WHERE orders.customer_id = customers.id AND customers.banned = true"
+    );
+}
+
+#[test]
+fn test_update_from_join() {
+    let cmd = parse(
+        "set orders values status = 'vip' from customers where orders.customer_id = customers.id and customers.tier = 'gold'",
+    )
+    .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "UPDATE orders SET status = 'vip' FROM customers WHERE orders.customer_id = customers.id AND customers.tier = 'gold'"
+    );
+}
+
+// ============= INSERT ... SELECT =============
+
+#[test]
+fn test_insert_select_filtered() {
+    let cmd = parse("add archive from (get orders where created < $1)").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "INSERT INTO archive SELECT * FROM orders WHERE created < $1 RETURNING *"
+    );
+
+    let cmd = Qail::add("archive")
+        .columns(["id", "total"])
+        .from_select(
+            Qail::get("orders")
+                .columns(["id", "total"])
+                .filter("status", Operator::Eq, "closed"),
+        );
+    assert_eq!(
+        cmd.to_sql(),
+        "INSERT INTO archive (id, total) SELECT id, total FROM orders WHERE status = 'closed' RETURNING *"
+    );
+}
+
+#[test]
+fn test_insert_select_param_numbering() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse(
+        "add archive fields id from (get orders fields id where created < :cutoff and status = :status)",
+    )
+    .unwrap();
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "INSERT INTO archive (id) SELECT id FROM orders WHERE created < $1 AND status = $2 RETURNING *"
+    );
+    assert_eq!(result.named_params, vec!["cutoff", "status"]);
+}
+
+#[test]
+fn test_any_subquery() {
+    let cmd = parse("get users fields id where id = any (get allowed fields user_id)").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM users WHERE id = ANY (SELECT user_id FROM allowed)"
+    );
+}
+
+#[test]
+fn test_all_subquery() {
+    let cmd = parse(
+        "get orders fields id where total > all (get orders fields total where status = 'refunded')",
+    )
+    .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM orders WHERE total > ALL (SELECT total FROM orders WHERE status = 'refunded')"
+    );
+}
+
+#[test]
+fn test_any_subquery_param_numbering() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse(
+        "get users fields id where org = :org and id = any (get allowed fields user_id where role = :role)",
+    )
+    .unwrap();
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "SELECT id FROM users WHERE org = $1 AND id = ANY (SELECT user_id FROM allowed WHERE role = $2)"
+    );
+    assert_eq!(result.named_params, vec!["org", "role"]);
+
+    // Condition-level: the subquery continues the outer numbering, in the outer dialect
+    use crate::transpiler::conditions::{ConditionToSql, ParamContext};
+    let mut params = ParamContext::new();
+    let generator = Dialect::Postgres.generator();
+    let conds = &cmd.cages[0].conditions;
+    let sql: Vec<String> = conds
+        .iter()
+        .map(|c| c.to_sql_parameterized(generator.as_ref(), None, &mut params))
+        .collect();
+    assert_eq!(
+        sql,
+        vec!["org = $1", "id = ANY (SELECT user_id FROM allowed WHERE role = $2)"]
+    );
+    assert_eq!(params.named_params, vec!["org", "role"]);
+
+    let mut params = ParamContext::new();
+    let generator = Dialect::MySQL.generator();
+    assert_eq!(
+        conds[1].to_sql_parameterized(generator.as_ref(), None, &mut params),
+        "`id` = ANY (SELECT `user_id` FROM `allowed` WHERE `role` = ?)"
+    );
+    assert_eq!(
+        conds[1].to_sql(generator.as_ref(), None),
+        "`id` = ANY (SELECT `user_id` FROM `allowed` WHERE `role` = :role)"
+    );
+}
+
+#[test]
+fn test_limit_all_and_fetch_first() {
+    use crate::ast::SetOp;
+    use crate::transpiler::{Dialect, ToSql};
+
+    // LIMIT ALL overrides a default limit
+    let cmd = Qail::get("users").limit(100).limit_all().offset(20);
+    assert_eq!(cmd.to_sql(), "SELECT * FROM users LIMIT ALL OFFSET 20");
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SQLite),
+        "SELECT * FROM \"users\" LIMIT -1 OFFSET 20"
+    );
+
+    let cmd = parse("get scores fields name order by points desc fetch first 5 rows with ties").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT name FROM scores ORDER BY points DESC FETCH FIRST 5 ROWS WITH TIES"
+    );
+
+    // The builder can skip the ORDER BY the parser insists on
+    assert_eq!(
+        Qail::get("scores").fetch_with_ties(5).to_sql(),
+        "/* ERROR: FETCH FIRST ... WITH TIES requires an ORDER BY */"
+    );
+
+    // FETCH belongs to the first query, before any set operation
+    let mut cmd = Qail::get("a").columns(["id"]).fetch_first(3).offset(1);
+    cmd.set_ops.push((SetOp::Union, Box::new(Qail::get("b").columns(["id"]))));
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM a OFFSET 1 ROWS FETCH FIRST 3 ROWS ONLY UNION SELECT id FROM b"
+    );
+
+    // Dialects without FETCH FIRST page with their own syntax
+    let cmd = Qail::get("scores").columns(["name"]).order_desc("points").fetch_first(5);
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `name` FROM `scores` ORDER BY `points` DESC LIMIT 5"
+    );
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SQLite),
+        "SELECT \"name\" FROM \"scores\" ORDER BY \"points\" DESC LIMIT 5"
+    );
+    assert_eq!(
+        Qail::get("scores").columns(["name"]).fetch_first(5).to_sql_with_dialect(Dialect::SqlServer),
+        "SELECT [name] FROM [scores] ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 5 ROWS ONLY"
+    );
+
+    // Only Postgres keeps ties
+    let cmd = Qail::get("scores").order_desc("points").fetch_with_ties(5);
+    for dialect in [Dialect::MySQL, Dialect::SQLite, Dialect::SqlServer] {
+        assert_eq!(
+            cmd.to_sql_with_dialect(dialect),
+            format!("/* ERROR: FETCH FIRST ... WITH TIES is not supported by {:?} */", dialect)
+        );
+    }
+
+    assert_eq!(
+        Qail::get("scores").limit(10).fetch_first(5).to_sql(),
+        "/* ERROR: FETCH FIRST cannot be combined with LIMIT */"
+    );
+}
+
+#[test]
+fn test_eq_any_array() {
+    use crate::ast::Operator;
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse("get users fields id where id = any (1, 2, 3) and org = any :orgs").unwrap();
+    assert_eq!(cmd.cages[0].conditions[0].op, Operator::EqAny);
+    assert_eq!(cmd.cages[0].conditions[1].op, Operator::EqAny);
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM users WHERE id = ANY(ARRAY[1, 2, 3]) AND org = ANY(:orgs)"
+    );
+
+    // Literal lists stay inline; a named list is one placeholder
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "SELECT id FROM users WHERE id = ANY(ARRAY[1, 2, 3]) AND org = ANY($1)"
+    );
+    assert_eq!(result.named_params, vec!["orgs"]);
+
+    // No arrays outside PostgreSQL: literal lists become IN lists
+    let cmd = parse("get users fields id where id = any (1, 2, 3)").unwrap();
+    for dialect in [Dialect::MySQL, Dialect::SQLite] {
+        let sql = cmd.to_sql_with_dialect(dialect);
+        assert!(sql.ends_with(" IN (1, 2, 3)"), "{}", sql);
+        assert_valid_sql(&sql, dialect);
+        let result = cmd.to_sql_parameterized_with_dialect(dialect);
+        assert!(result.sql.contains(" IN ("), "{}", result.sql);
+        assert!(!result.sql.contains("ANY"), "{}", result.sql);
+    }
+
+    // PostgreSQL rejects an untyped `ARRAY[]`; an empty list matches nothing
+    let cmd = Qail::get("users")
+        .columns(["id"])
+        .filter_cond(crate::ast::builders::eq_any("id", Vec::<i64>::new()));
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users WHERE FALSE");
+    assert_eq!(
+        cmd.to_sql_parameterized().sql,
+        "SELECT id FROM users WHERE FALSE"
+    );
+    assert_valid_sql(&cmd.to_sql(), Dialect::Postgres);
+}
+
+#[test]
+fn test_in_list_and_in_subquery() {
+    let cmd = parse("get users fields id where id in (1, 2, 3)").unwrap();
+    assert!(matches!(cmd.cages[0].conditions[0].value, Value::Array(_)));
+    assert_eq!(cmd.to_sql(), "SELECT id FROM users WHERE id IN (1, 2, 3)");
+
+    let cmd = parse("get users fields id where id not in (get bans fields user_id)").unwrap();
+    assert!(matches!(cmd.cages[0].conditions[0].value, Value::Subquery(_)));
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM bans)"
+    );
+
+    // List items and subqueries use the outer dialect
+    let evil = Value::String("\\' OR 1=1 -- ".to_string());
+    let bans = Qail::get("bans")
+        .columns(["user_id"])
+        .filter("reason", crate::ast::Operator::Eq, evil.clone());
+    let cmd = Qail::get("users")
+        .columns(["id"])
+        .filter("org", crate::ast::Operator::In, Value::Array(vec![evil]))
+        .filter("id", crate::ast::Operator::In, Value::Subquery(Box::new(bans)));
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `id` FROM `users` WHERE `org` IN ('\\\\'' OR 1=1 -- ') AND `id` IN \
+         (SELECT `user_id` FROM `bans` WHERE `reason` = '\\\\'' OR 1=1 -- ')"
+    );
+    let cmd = Qail::get("users").columns(["id"]).filter(
+        "id",
+        crate::ast::Operator::In,
+        Value::Subquery(Box::new(Qail::get("bans").columns(["user_id"]).limit(5))),
+    );
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::SqlServer),
+        "SELECT [id] FROM [users] WHERE [id] IN \
+         (SELECT [user_id] FROM [bans] ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 5 ROWS ONLY)"
+    );
+}
+
+#[test]
+fn test_in_list_param_numbering() {
+    use crate::transpiler::conditions::{ConditionToSql, ParamContext};
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse(
+        "get users fields id where org = :org and id in (:a, :b, 7) and role = :role \
+         and team in (get teams fields id where lead = :lead)",
+    )
+    .unwrap();
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "SELECT id FROM users WHERE org = $1 AND id IN ($2, $3, 7) AND role = $4 \
+         AND team IN (SELECT id FROM teams WHERE lead = $5)"
+    );
+    assert_eq!(result.named_params, vec!["org", "a", "b", "role", "lead"]);
+
+    // Condition-level parameterization binds each list element separately
+    let generator = Dialect::Postgres.generator();
+    let mut params = ParamContext::new();
+    let conds = &parse("get users where org = 'acme' and id not in (1, 2, 3) and role = 'admin'")
+        .unwrap()
+        .cages[0]
+        .conditions;
+    let sql: Vec<String> = conds
+        .iter()
+        .map(|c| c.to_sql_parameterized(generator.as_ref(), None, &mut params))
+        .collect();
+    assert_eq!(sql, vec!["org = $1", "id NOT IN ($2, $3, $4)", "role = $5"]);
+    assert_eq!(params.params[1..4], [Value::Int(1), Value::Int(2), Value::Int(3)]);
+
+    // Named parameters inside a subquery continue the outer numbering
+    let conds = &parse("get users where team in (get teams fields id where lead = :lead)")
+        .unwrap()
+        .cages[0]
+        .conditions;
+    assert_eq!(
+        conds[0].to_sql_parameterized(generator.as_ref(), None, &mut params),
+        "team IN (SELECT id FROM teams WHERE lead = $6)"
+    );
+    assert_eq!(params.named_params, vec!["lead"]);
+}
+
+#[test]
+fn test_placeholder_style_per_dialect() {
+    use crate::transpiler::{PlaceholderStyle, ToSqlParameterized};
+
+    let cmd =
+        parse("get users fields id where org = :org and role = :role and owner = :org").unwrap();
+
+    let pg = cmd.to_sql_parameterized_with_dialect(Dialect::Postgres);
+    assert_eq!(
+        pg.sql,
+        "SELECT id FROM users WHERE org = $1 AND role = $2 AND owner = $1"
+    );
+    assert_eq!(pg.named_params, vec!["org", "role"]);
+
+    let mysql = cmd.to_sql_parameterized_with_dialect(Dialect::MySQL);
+    assert_eq!(
+        mysql.sql,
+        "SELECT `id` FROM `users` WHERE `org` = ? AND `role` = ? AND `owner` = ?"
+    );
+    assert_eq!(mysql.named_params, vec!["org", "role", "org"]);
+
+    let named = cmd.to_sql_parameterized_with_style(Dialect::SQLite, PlaceholderStyle::Named);
+    assert_eq!(
+        named.sql,
+        r#"SELECT "id" FROM "users" WHERE "org" = :org AND "role" = :role AND "owner" = :org"#
+    );
+    assert_eq!(named.named_params, vec!["org", "role"]);
+}
+
+#[test]
+fn test_parameterized_impl_without_style_override() {
+    use crate::transpiler::{PlaceholderStyle, ToSqlParameterized, TranspileResult};
+
+    // An implementor written before placeholder styles existed
+    struct Fixed;
+    impl ToSqlParameterized for Fixed {
+        fn to_sql_parameterized_with_dialect(&self, _dialect: Dialect) -> TranspileResult {
+            TranspileResult::sql_only("SELECT 1")
+        }
+    }
+
+    let result = Fixed.to_sql_parameterized_with_style(Dialect::SQLite, PlaceholderStyle::Named);
+    assert_eq!(result.sql, "SELECT 1");
+}
+
+#[test]
+fn test_keyset_after() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = Qail::get("posts")
+        .columns(["id", "title"])
+        .keyset_after(("created", "id"), ("2024-05-01 10:00:00", 42))
+        .unwrap()
+        .limit(20);
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id, title FROM posts WHERE (created, id) > ('2024-05-01 10:00:00', 42) \
+         ORDER BY created ASC, id ASC LIMIT 20"
+    );
+
+    let cmd = Qail::get("posts")
+        .filter("author", Operator::Eq, Value::NamedParam("author".into()))
+        .keyset_after(
+            ("created", "id"),
+            (Value::NamedParam("created".into()), Value::NamedParam("id".into())),
+        )
+        .unwrap()
+        .limit(20);
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "SELECT * FROM posts WHERE author = $1 AND (created, id) > ($2, $3) \
+         ORDER BY created ASC, id ASC LIMIT 20"
+    );
+    assert_eq!(result.named_params, vec!["author", "created", "id"]);
+}
+
+#[test]
+fn test_keyset_after_arity_mismatch() {
+    let err = Qail::get("posts")
+        .keyset_after(("created", "id"), (1,))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid value: keyset_after: 2 columns but 1 values"
+    );
+}
+
+#[test]
+fn test_on_conflict_partial_index_target() {
+    let cmd = Qail::add("users")
+        .columns(["email", "name"])
+        .values(["a@b.c", "Al"])
+        .on_conflict_update(&["email"], &[("name", Expr::Named("EXCLUDED.name".into()))])
+        .on_conflict_where("deleted_at", Operator::IsNull, Value::Null)
+        .returning(Vec::<&str>::new());
+    assert_eq!(
+        cmd.to_sql(),
+        "INSERT INTO users (email, name) VALUES ('a@b.c', 'Al') \
+         ON CONFLICT (email) WHERE deleted_at IS NULL DO UPDATE SET name = EXCLUDED.name"
+    );
+
+    let parsed = parse(
+        "add users fields email values 'a@b.c' \
+         conflict (email) where deleted_at is null and tenant = 3 nothing",
+    )
+    .unwrap();
+    let conflict = parsed.on_conflict.as_ref().unwrap();
+    assert_eq!(conflict.target_where.len(), 2);
+    assert!(parsed.to_sql().contains(
+        "ON CONFLICT (email) WHERE deleted_at IS NULL AND tenant = 3 DO NOTHING"
+    ));
+
+    // MySQL can't express the predicate, so it must not be dropped silently
+    assert!(
+        cmd.to_sql_with_dialect(Dialect::MySQL)
+            .starts_with("/* ERROR: MySQL has no partial-index conflict target")
+    );
+
+    // The predicate narrows the conflict columns' index; without them there is nothing to narrow
+    let cmd = Qail::add("users")
+        .columns(["email"])
+        .values(["a@b.c"])
+        .on_conflict_where("deleted_at", Operator::IsNull, Value::Null);
+    assert_eq!(cmd.to_sql(), "/* ERROR: a conflict WHERE needs conflict columns */");
+
+    // No target at all is a plain `ON CONFLICT DO NOTHING`
+    let cmd = Qail::add("users")
+        .columns(["email"])
+        .values(["a@b.c"])
+        .on_conflict_nothing::<&str>(&[])
+        .returning(Vec::<&str>::new());
+    assert_eq!(
+        cmd.to_sql(),
+        "INSERT INTO users (email) VALUES ('a@b.c') ON CONFLICT DO NOTHING"
+    );
+}
+
+#[test]
+fn test_on_conflict_accumulating_update() {
+    let cmd = parse(
+        "add counters fields id, count values 1, 5 \
+         conflict (id) update count = counters.count + excluded.count, hits = 1 + counters.hits",
+    )
+    .unwrap();
+    let sql = cmd.to_sql();
+    assert!(
+        sql.contains(
+            "ON CONFLICT (id) DO UPDATE SET count = (counters.count + excluded.count), \
+             hits = (1 + counters.hits)"
+        ),
+        "{}",
+        sql
+    );
+
+    let built = Qail::add("counters")
+        .columns(["id", "count"])
+        .values([1, 5])
+        .on_conflict_update(
+            &["id"],
+            &[(
+                "count",
+                Expr::Binary {
+                    left: Box::new(Expr::Named("counters.count".into())),
+                    op: BinaryOp::Add,
+                    right: Box::new(Expr::Named("EXCLUDED.count".into())),
+                    alias: None,
+                },
+            )],
+        )
+        .returning(Vec::<&str>::new());
+    assert_eq!(
+        built.to_sql(),
+        "INSERT INTO counters (id, count) VALUES (1, 5) \
+         ON CONFLICT (id) DO UPDATE SET count = (counters.count + EXCLUDED.count)"
+    );
+}
+
+#[test]
+fn test_derived_table_with_outer_filter() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse(
+        "get (get orders fields user_id, sum(total) as total where status = 'paid') as p \
+         fields user_id where total > 100",
+    )
+    .unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT user_id FROM (SELECT user_id, SUM(total) AS total FROM orders WHERE status = 'paid' GROUP BY user_id) AS p \
+         WHERE total > 100"
+    );
+    assert_eq!(
+        cmd.to_sql_with_dialect(Dialect::MySQL),
+        "SELECT `user_id` FROM (SELECT `user_id`, SUM(`total`) AS `total` FROM `orders` WHERE `status` = 'paid' GROUP BY `user_id`) AS `p` \
+         WHERE `total` > 100"
+    );
+
+    // Named params are numbered in SQL order: the derived table comes first
+    let paid = Qail::get("orders").filter("status", Operator::Eq, Value::NamedParam("status".into()));
+    let cmd = Qail::get_from(paid, "p").filter("total", Operator::Gt, Value::NamedParam("min".into()));
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "SELECT * FROM (SELECT * FROM orders WHERE status = $1) AS p WHERE total > $2"
+    );
+    assert_eq!(result.named_params, vec!["status", "min"]);
+}
+
+#[test]
+fn test_fuzzy_escaped_literal() {
+    let cmd = parse("get products fields id where label ~= '100%_off'").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id FROM products WHERE label ILIKE '%100\\%\\_off%' ESCAPE '\\'"
+    );
+    // Plain `~` keeps treating % as a wildcard
+    let cmd = parse("get products fields id where label ~ '100%'").unwrap();
+    assert_eq!(cmd.to_sql(), "SELECT id FROM products WHERE label ILIKE '%100%%'");
+
+    // MySQL treats backslash as an escape character inside literals
+    let cmd = parse("get products fields id where label ~= '100%_off'").unwrap();
+    let sql = cmd.to_sql_with_dialect(Dialect::MySQL);
+    assert_eq!(
+        sql,
+        "SELECT `id` FROM `products` WHERE `label` LIKE '%100\\\\%\\\\_off%' ESCAPE '\\\\'"
+    );
+    assert_valid_sql(&sql, Dialect::MySQL);
+    let sql = parse("get products fields id where label ~= $1")
+        .unwrap()
+        .to_sql_with_dialect(Dialect::MySQL);
+    assert!(sql.ends_with(" ESCAPE '\\\\'"), "{}", sql);
+    assert_valid_sql(&sql, Dialect::MySQL);
+}
+
+#[test]
+fn test_fuzzy_escaped_parameterized() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = parse("get products fields id where label ~= :q").unwrap();
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "SELECT id FROM products WHERE label ILIKE '%' || replace(replace(replace($1, '\\', '\\\\'), '%', '\\%'), '_', '\\_') || '%' ESCAPE '\\'"
+    );
+    assert_eq!(result.named_params, vec!["q"]);
+}
+
+#[test]
+fn test_tag_leading_comment() {
+    let cmd = Qail::get("orders")
+        .column("id")
+        .tag("app:checkout")
+        .tag("route:/pay");
+    assert_eq!(cmd.to_sql(), "/* app:checkout route:/pay */ SELECT id FROM orders");
+
+    let cmd = Qail::del("sessions").tag("job:cleanup");
+    assert_eq!(cmd.to_sql(), "/* job:cleanup */ DELETE FROM sessions");
+
+    // Untagged SQL is unchanged
+    assert_eq!(Qail::get("orders").column("id").to_sql(), "SELECT id FROM orders");
+}
+
+#[test]
+fn test_tag_parameterized_and_escaped() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let mut cmd = parse("get orders fields id where user_id = :uid").unwrap();
+    cmd = cmd.tag("app:checkout");
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(
+        result.sql,
+        "/* app:checkout */ SELECT id FROM orders WHERE user_id = $1"
+    );
+    assert_eq!(result.named_params, vec!["uid"]);
+
+    // Only the tag comment is skipped, not a `/*` inside a string literal
+    let cmd = parse("get pages fields id where path = '/*' and id = :id").unwrap();
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(result.sql, "SELECT id FROM pages WHERE path = '/*' AND id = $1");
+    assert_eq!(result.named_params, vec!["id"]);
+
+    // A tag cannot close the comment early
+    let cmd = Qail::get("orders").column("id").tag("x */ DROP TABLE orders; /*");
+    assert_eq!(
+        cmd.to_sql(),
+        "/* x * / DROP TABLE orders; / * */ SELECT id FROM orders"
+    );
+}
+
+#[test]
+fn test_array_overlap_and_vector_distance_operators() {
+    use crate::transpiler::ToSqlParameterized;
+
+    let cmd = Qail::get("posts")
+        .columns(["id"])
+        .filter("tags", Operator::Overlaps, Value::Param(1));
+    let sql = cmd.to_sql();
+    assert_eq!(sql, "SELECT id FROM posts WHERE tags && $1");
+    assert_valid_sql(&sql, Dialect::Postgres);
+
+    let distance = Operator::custom("<->").unwrap();
+    let cmd = Qail::get("items")
+        .columns(["id"])
+        .filter("embedding", distance, Value::Param(1));
+    assert_eq!(cmd.to_sql(), "SELECT id FROM items WHERE embedding <-> $1");
+    let result = cmd.to_sql_parameterized();
+    assert_eq!(result.sql, "SELECT id FROM items WHERE embedding <-> $1");
+
+    let cmd = parse("get docs fields id where body @@ $1").unwrap();
+    let sql = cmd.to_sql();
+    assert_eq!(sql, "SELECT id FROM docs WHERE body @@ $1");
+    assert_valid_sql(&sql, Dialect::Postgres);
+
+    // `<=>` is null-safe equality in MySQL: never pass it through
+    let cmd = parse("get items fields id where embedding <=> $1").unwrap();
+    for dialect in [Dialect::MySQL, Dialect::SQLite, Dialect::SqlServer] {
+        let sql = cmd.to_sql_with_dialect(dialect);
+        assert!(sql.contains("/* ERROR: operator <=> is PostgreSQL-only */"), "{}", sql);
+        let result = cmd.to_sql_parameterized_with_dialect(dialect);
+        assert!(!result.sql.contains("embedding <=>"), "{}", result.sql);
+    }
+}
+
+#[test]
+fn test_decimal_text_is_never_spliced_into_sql() {
+    let cmd = Qail::get("orders").filter("total", Operator::Gt, Value::decimal("19.99").unwrap());
+    assert_eq!(cmd.to_sql(), "SELECT * FROM orders WHERE total > 19.99");
+
+    for text in ["1; DROP TABLE t", "1e", ".", "", "NaN"] {
+        assert!(Value::decimal(text).is_err(), "{:?} accepted", text);
+    }
+    assert!(Value::decimal("-1.5e+10").is_ok());
+
+    // Unchecked text is quoted and cast instead
+    let cmd = Qail::get("orders").filter("total", Operator::Gt, Value::Decimal("1; DROP TABLE t".into()));
+    let sql = cmd.to_sql();
+    assert_eq!(sql, "SELECT * FROM orders WHERE total > '1; DROP TABLE t'::numeric");
+    assert_valid_sql(&sql, Dialect::Postgres);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_decimal_serde_checks_literal() {
+    let value: Value = serde_json::from_str(r#"{"Decimal":"19.99"}"#).unwrap();
+    assert_eq!(value, Value::Decimal("19.99".into()));
+    assert!(serde_json::from_str::<Value>(r#"{"Decimal":"1; DROP TABLE t"}"#).is_err());
+}
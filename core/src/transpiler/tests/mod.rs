@@ -5,8 +5,12 @@
 //! - `dialects`: SQL dialect-specific tests (MySQL, SQLite, Oracle, etc.)
 //! - `nosql`: NoSQL transpiler tests (MongoDB, Redis, DynamoDB, etc.)
 //! - `features`: DDL, Upsert, JSON operations, advanced features
+//! - `cached`: Memoized transpilation (`CachedQail`)
+//! - `oracle`: Generated SQL checked with `sqlparser` (`assert_valid_sql`)
 
+mod cached;
 mod core;
 mod dialects;
 mod features;
 mod nosql;
+mod oracle;
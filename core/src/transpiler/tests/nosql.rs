@@ -54,6 +54,7 @@ fn test_mongo_join() {
         kind: JoinKind::Left,
         on: None,
         on_true: false,
+        subquery: None,
     });
 
     let mongo = cmd.to_mongo();
@@ -0,0 +1,129 @@
+//! SQL oracle: generated SQL must parse with `sqlparser`.
+//!
+//! Substring assertions only check the parts a test looks at; running the
+//! whole statement through an independent parser catches malformed output
+//! around them (unquoted JSON keys, stray keywords, unbalanced parens).
+
+use crate::ast::*;
+use crate::parser::parse;
+use crate::transpiler::{Dialect, ToSql};
+use sqlparser::dialect::{MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+
+/// Panic with the SQL and parser error unless `sql` parses as `dialect`.
+pub(super) fn assert_valid_sql(sql: &str, dialect: Dialect) {
+    let parsed = match dialect {
+        Dialect::Postgres => Parser::parse_sql(&PostgreSqlDialect {}, sql),
+        Dialect::SQLite => Parser::parse_sql(&SQLiteDialect {}, sql),
+        Dialect::MySQL => Parser::parse_sql(&MySqlDialect {}, sql),
+        Dialect::SqlServer => Parser::parse_sql(&MsSqlDialect {}, sql),
+    };
+    if let Err(e) = parsed {
+        panic!("invalid {:?} SQL: {}\n  {}", dialect, e, sql);
+    }
+}
+
+/// Transpile a QAIL query for `dialect` and check the result parses.
+fn assert_valid_qail(query: &str, dialect: Dialect) {
+    let cmd = parse(query).unwrap_or_else(|e| panic!("{}: {:?}", query, e));
+    assert_valid_sql(&cmd.to_sql_with_dialect(dialect), dialect);
+}
+
+#[test]
+fn test_oracle_select() {
+    for query in [
+        "get users",
+        "get users fields id, email where active = true and age >= 18 order by created_at desc limit 10",
+        "get users fields id where name ~ 'ali' or email ~ 'ali'",
+        "get users fields id where id in (1, 2, 3) and org not in (get bans fields org)",
+        "get users fields metadata->>'theme' as theme, data->'a'->0->>'b'",
+        "get users fields id where metadata->>'theme' = 'dark'",
+        "get orders fields status, count(*) filter (where total > 100) as big",
+        "get distinct users fields country limit 10 offset 20",
+        "get users inner join orders on users.id = orders.user_id fields users.id, orders.total",
+    ] {
+        assert_valid_qail(query, Dialect::Postgres);
+    }
+}
+
+#[test]
+fn test_oracle_select_builders() {
+    let cmd = Qail::get("users")
+        .columns(["id", "name"])
+        .filter("age", Operator::Gt, 18)
+        .filter("role", Operator::In, Value::Array(vec!["admin".into(), "owner".into()]))
+        .order_by("name", SortOrder::Asc)
+        .limit(5);
+    assert_valid_sql(&cmd.to_sql(), Dialect::Postgres);
+
+    let last_order = Qail::get("orders")
+        .columns(["total"])
+        .filter("user_id", Operator::Eq, Value::Column("u.id".into()))
+        .limit(1);
+    let lateral = Qail::get("users")
+        .columns(["id"])
+        .left_join_lateral(last_order, "o");
+    assert_valid_sql(&lateral.to_sql(), Dialect::Postgres);
+}
+
+#[test]
+fn test_oracle_insert() {
+    for query in [
+        "add users fields name, email values 'alice', 'a@example.com'",
+        "add users fields name values 'O''Brien'",
+    ] {
+        assert_valid_qail(query, Dialect::Postgres);
+    }
+
+    let cmd = Qail::add("users")
+        .set_value("name", "alice")
+        .set_value("age", 30)
+        .returning(["id"]);
+    assert_valid_sql(&cmd.to_sql(), Dialect::Postgres);
+}
+
+#[test]
+fn test_oracle_update_delete() {
+    for query in [
+        "set users values active = false where id = 1",
+        "del users where id = 1",
+    ] {
+        assert_valid_qail(query, Dialect::Postgres);
+    }
+
+    let update = Qail::set("users")
+        .set_value("active", false)
+        .filter("last_login", Operator::Lt, "2024-01-01")
+        .or_filter("banned", Operator::Eq, true);
+    assert_valid_sql(&update.to_sql(), Dialect::Postgres);
+}
+
+#[test]
+fn test_oracle_cte() {
+    let active = Qail::get("users")
+        .columns(["id", "name"])
+        .filter("active", Operator::Eq, true);
+    let cmd = Qail::get("active_users").with("active_users", active).columns(["name"]);
+    assert_valid_sql(&cmd.to_sql(), Dialect::Postgres);
+
+    assert_valid_qail("with big as materialized (get orders fields id) get big", Dialect::Postgres);
+}
+
+#[test]
+fn test_oracle_dialects() {
+    let cmd = Qail::get("users")
+        .columns(["id", "name"])
+        .filter("active", Operator::Eq, true)
+        .order_by("name", SortOrder::Desc)
+        .limit(10);
+    for dialect in [Dialect::Postgres, Dialect::SQLite, Dialect::MySQL, Dialect::SqlServer] {
+        assert_valid_sql(&cmd.to_sql_with_dialect(dialect), dialect);
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid Postgres SQL")]
+fn test_oracle_rejects_invalid_sql() {
+    // The shape of the old unquoted JSON key output
+    assert_valid_sql("SELECT data->>theme FROM users WHERE", Dialect::Postgres);
+}
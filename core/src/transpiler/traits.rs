@@ -1,5 +1,8 @@
 //! Transpiler traits and utilities.
 
+use super::Dialect;
+use crate::ast::{Operator, SortOrder};
+
 /// SQL reserved words that must be quoted when used as identifiers.
 pub const RESERVED_WORDS: &[&str] = &[
     "order",
@@ -55,7 +58,21 @@ pub const RESERVED_WORDS: &[&str] = &[
 
 /// Escape an identifier if it's a reserved word or contains special chars.
 /// Handles dotted identifiers (e.g., `table.column`) by quoting each part.
+/// Parts written as `"..."` in the source are always quoted and never split.
 pub fn escape_identifier(name: &str) -> String {
+    if name.contains('"') {
+        return identifier_parts(name)
+            .into_iter()
+            .map(|(part, quoted)| {
+                if quoted {
+                    format!("\"{}\"", part)
+                } else {
+                    escape_single_identifier(part)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(".");
+    }
     if name.contains('.') {
         return name
             .split('.')
@@ -66,11 +83,36 @@ pub fn escape_identifier(name: &str) -> String {
     escape_single_identifier(name)
 }
 
+/// Split a possibly qualified identifier on the dots outside double quotes.
+/// Each part comes back without its quotes, flagged `true` if it was quoted:
+/// `"My Schema".users` -> `[("My Schema", true), ("users", false)]`.
+pub fn identifier_parts(name: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    let mut rest = name;
+    loop {
+        if let Some(quoted) = rest.strip_prefix('"')
+            && let Some(end) = quoted.find('"')
+        {
+            parts.push((&quoted[..end], true));
+            rest = &quoted[end + 1..];
+        } else {
+            let end = rest.find('.').unwrap_or(rest.len());
+            parts.push((&rest[..end], false));
+            rest = &rest[end..];
+        }
+        match rest.strip_prefix('.') {
+            Some(next) => rest = next,
+            None => return parts,
+        }
+    }
+}
+
 /// Escape a single identifier part (no dots).
 fn escape_single_identifier(name: &str) -> String {
     let lower = name.to_lowercase();
     let needs_escaping = RESERVED_WORDS.contains(&lower.as_str())
-        || name.chars().any(|c| !c.is_alphanumeric() && c != '_')
+        // Non-ASCII letters are quoted: Postgres only case-folds ASCII
+        || name.chars().any(|c| !c.is_ascii_alphanumeric() && c != '_')
         || name.chars().next().map(|c| c.is_numeric()).unwrap_or(false);
 
     if needs_escaping {
@@ -80,6 +122,70 @@ fn escape_single_identifier(name: &str) -> String {
     }
 }
 
+/// How the Postgres generator quotes identifiers.
+///
+/// Only PostgreSQL has an unquoted form to choose: the MySQL, SQLite and
+/// SQL Server generators quote every identifier under either policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuotingPolicy {
+    /// Quote only reserved words and names with special characters.
+    #[default]
+    Auto,
+    /// Quote every identifier (`SELECT "id" FROM "users"`).
+    Always,
+}
+
+thread_local! {
+    static QUOTING_POLICY: std::cell::Cell<QuotingPolicy> =
+        const { std::cell::Cell::new(QuotingPolicy::Auto) };
+}
+
+/// The quoting policy in effect for SQL generated on this thread.
+pub fn quoting_policy() -> QuotingPolicy {
+    QUOTING_POLICY.with(|p| p.get())
+}
+
+/// Run `f` with `policy` in effect, restoring the previous policy afterwards.
+/// Nested subqueries generated inside `f` use the same policy. Only the
+/// Postgres generator reads it; see [`QuotingPolicy`].
+pub fn with_quoting_policy<T>(policy: QuotingPolicy, f: impl FnOnce() -> T) -> T {
+    struct Restore(QuotingPolicy);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUOTING_POLICY.with(|p| p.set(self.0));
+        }
+    }
+
+    let _restore = Restore(QUOTING_POLICY.with(|p| p.replace(policy)));
+    f()
+}
+
+/// Quote an identifier unconditionally, quoting each part of dotted names.
+pub fn quote_identifier_always(name: &str) -> String {
+    identifier_parts(name)
+        .into_iter()
+        .map(|(part, quoted)| {
+            if part == "*" && !quoted {
+                part.to_string()
+            } else {
+                format!("\"{}\"", part.replace('"', "\"\""))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// How a dialect returns the rows written by an `INSERT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturningStyle {
+    /// Trailing `RETURNING cols` (PostgreSQL, SQLite).
+    Returning,
+    /// `OUTPUT INSERTED.cols` ahead of `VALUES` / `SELECT` (SQL Server).
+    Output,
+    /// No way to return written rows (MySQL).
+    Unsupported,
+}
+
 /// Trait for dialect-specific SQL generation.
 pub trait SqlGenerator {
     /// Quote an identifier (table or column name).
@@ -93,6 +199,70 @@ pub trait SqlGenerator {
     /// Generate string concatenation expression (e.g. 'a' || 'b' vs CONCAT('a', 'b')).
     fn string_concat(&self, parts: &[&str]) -> String;
     fn limit_offset(&self, limit: Option<usize>, offset: Option<usize>) -> String;
+    /// The dialect this generator writes, for nested queries (`IN (SELECT ...)`).
+    /// Default: Postgres.
+    fn dialect(&self) -> Dialect {
+        Dialect::Postgres
+    }
+    /// Whether `limit_offset` output is only valid after an `ORDER BY`
+    /// (SQL Server's `OFFSET ... FETCH`). Default: no.
+    fn paging_requires_order_by(&self) -> bool {
+        false
+    }
+    /// An explicit "no limit". Default: no `LIMIT` clause at all.
+    fn limit_all(&self, offset: Option<usize>) -> String {
+        self.limit_offset(None, offset)
+    }
+    /// `FETCH FIRST count ROWS` paging, including the offset, or `None`
+    /// when the dialect cannot keep ties. Default: the SQL-standard form.
+    fn fetch_first(&self, count: u64, with_ties: bool, offset: Option<usize>) -> Option<String> {
+        let mut sql = String::new();
+        if let Some(n) = offset {
+            sql.push_str(&format!(" OFFSET {} ROWS", n));
+        }
+        let ties = if with_ties { "WITH TIES" } else { "ONLY" };
+        sql.push_str(&format!(" FETCH FIRST {} ROWS {}", count, ties));
+        Some(sql)
+    }
+    /// Spell a simple binary operator (`ILIKE`, `~`, ...).
+    /// Default: the Postgres form from `Operator::sql_symbol()`.
+    fn binary_operator(&self, op: &Operator) -> &'static str {
+        op.sql_symbol()
+    }
+    /// Quote `s` as a string literal. Default: double the single quotes.
+    fn string_literal(&self, s: &str) -> String {
+        format!("'{}'", s.replace('\'', "''"))
+    }
+    /// Whether the dialect has array values (`ARRAY[...]`, `= ANY($1)`).
+    /// Default: no, lists become `IN (...)`.
+    fn supports_arrays(&self) -> bool {
+        false
+    }
+    /// Whether `Operator::Custom` symbols mean what they do in PostgreSQL.
+    /// Default: no (MySQL's `<=>` is null-safe equality, not a distance).
+    fn supports_custom_operators(&self) -> bool {
+        false
+    }
+    /// How `INSERT` returns written rows. Default: `RETURNING`.
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Returning
+    }
+    /// Whether `INSERT` can return written rows at all.
+    fn supports_returning(&self) -> bool {
+        self.returning_style() != ReturningStyle::Unsupported
+    }
+    /// Generate a table reference, optionally qualified with a schema.
+    /// Schema and table are quoted independently (`"order"."users"`).
+    fn table_ref(&self, schema: Option<&str>, table: &str) -> String {
+        match schema {
+            Some(schema) => format!(
+                "{}.{}",
+                self.quote_identifier(schema),
+                self.quote_identifier(table)
+            ),
+            None => self.quote_identifier(table),
+        }
+    }
     /// Generate JSON access syntax.
     /// path components are the keys to traverse.
     /// Default implementation returns "col"."key1"."key2" (Standard SQL composite).
@@ -140,4 +310,18 @@ pub trait SqlGenerator {
     fn not_in_array(&self, col: &str, value: &str) -> String {
         format!("{} != ALL({})", col, value)
     }
+
+    /// Generate one ORDER BY item for an already-rendered expression.
+    /// Default: standard `NULLS FIRST` / `NULLS LAST` modifiers.
+    fn order_by_item(&self, expr: &str, order: SortOrder) -> String {
+        let dir = match order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+            SortOrder::AscNullsFirst => "ASC NULLS FIRST",
+            SortOrder::AscNullsLast => "ASC NULLS LAST",
+            SortOrder::DescNullsFirst => "DESC NULLS FIRST",
+            SortOrder::DescNullsLast => "DESC NULLS LAST",
+        };
+        format!("{} {}", expr, dir)
+    }
 }
@@ -138,6 +138,7 @@ impl ColumnValue<uuid::Uuid> for uuid::Uuid {}
 impl ColumnValue<uuid::Uuid> for &uuid::Uuid {}
 
 // JSON accepts many types
+#[cfg(feature = "serde")]
 impl<T> ColumnValue<serde_json::Value> for T {}
 
 // DateTime
@@ -32,6 +32,11 @@ pub enum ValidationError {
         operator: String,
         reason: String,
     },
+    /// Unqualified column that exists in more than one joined table
+    AmbiguousColumn {
+        column: String,
+        tables: Vec<String>,
+    },
 }
 
 impl std::fmt::Display for ValidationError {
@@ -82,6 +87,21 @@ impl std::fmt::Display for ValidationError {
                     operator, column, reason
                 )
             }
+            ValidationError::AmbiguousColumn { column, tables } => match tables.first() {
+                Some(first) => write!(
+                    f,
+                    "Column '{}' is ambiguous: it exists in {}. Qualify it as '{}.{}'.",
+                    column,
+                    tables.join(", "),
+                    first,
+                    column
+                ),
+                None => write!(
+                    f,
+                    "Column '{}' is ambiguous. Qualify it with its table name.",
+                    column
+                ),
+            },
         }
     }
 }
@@ -194,11 +214,45 @@ impl Validator {
         }
     }
 
+    /// Check an unqualified column against every table in scope.
+    ///
+    /// With a single table this is [`validate_column`](Self::validate_column).
+    /// With joins, a column found in more than one table is ambiguous, and
+    /// one found in none is reported against the first (FROM) table.
+    fn validate_in_scope(&self, tables: &[&str], column: &str) -> Result<(), ValidationError> {
+        if tables.len() < 2 || column == "*" || column.contains('.') {
+            return self.validate_column(tables[0], column);
+        }
+
+        let owners: Vec<String> = tables
+            .iter()
+            .filter(|t| {
+                self.columns
+                    .get(**t)
+                    .is_some_and(|cols| cols.iter().any(|c| c == column))
+            })
+            .map(|t| t.to_string())
+            .collect();
+
+        match owners.len() {
+            0 if tables.iter().all(|t| self.columns.contains_key(*t)) => {
+                self.validate_column(tables[0], column)
+            }
+            // Unknown tables (CTEs, views) may provide it
+            0 | 1 => Ok(()),
+            _ => Err(ValidationError::AmbiguousColumn {
+                column: column.to_string(),
+                tables: owners,
+            }),
+        }
+    }
+
     /// Extract column name from an Expr for validation.
     fn extract_column_name(expr: &Expr) -> Option<String> {
         match expr {
             Expr::Named(name) => Some(name.clone()),
             Expr::Aliased { name, .. } => Some(name.clone()),
+            Expr::Aggregate { arg: Some(arg), .. } => Self::extract_column_name(arg),
             Expr::Aggregate { col, .. } => Some(col.clone()),
             Expr::Cast { expr, .. } => Self::extract_column_name(expr),
             Expr::JsonAccess { column, .. } => Some(column.clone()),
@@ -233,7 +287,7 @@ impl Validator {
         }
         
         // Param and NamedParam are runtime values - can't validate at compile time
-        if matches!(value, Value::Param(_) | Value::NamedParam(_) | Value::Function(_) | Value::Subquery(_) | Value::Expr(_)) {
+        if matches!(value, Value::Param(_) | Value::NamedParam(_) | Value::Function(_) | Value::Subquery(_) | Value::AnySubquery(_) | Value::AllSubquery(_) | Value::Expr(_)) {
             return Ok(());
         }
         
@@ -242,6 +296,7 @@ impl Validator {
             Value::Bool(_) => "BOOLEAN",
             Value::Int(_) => "INT",
             Value::Float(_) => "FLOAT",
+            Value::Decimal(_) => "NUMERIC",
             Value::String(_) => "TEXT",
             Value::Uuid(_) => "UUID",
             Value::Array(_) => "ARRAY",
@@ -286,7 +341,7 @@ impl Validator {
         
         // Float family
         let float_types = ["FLOAT", "FLOAT4", "FLOAT8", "DOUBLE", "DECIMAL", "NUMERIC", "REAL"];
-        if float_types.contains(&expected.as_str()) && (value_type == "FLOAT" || value_type == "INT") {
+        if float_types.contains(&expected.as_str()) && (value_type == "FLOAT" || value_type == "NUMERIC" || value_type == "INT") {
             return true;
         }
         
@@ -328,6 +383,10 @@ impl Validator {
     /// Validate an entire Qail against the schema.
     pub fn validate_command(&self, cmd: &Qail) -> ValidationResult {
         let mut errors = Vec::new();
+        // FROM table first, then joined tables in join order
+        let tables: Vec<&str> = std::iter::once(cmd.table.as_str())
+            .chain(cmd.joins.iter().map(|j| j.table.as_str()))
+            .collect();
 
         if let Err(e) = self.validate_table(&cmd.table) {
             errors.push(e);
@@ -335,7 +394,7 @@ impl Validator {
 
         for col in &cmd.columns {
             if let Some(name) = Self::extract_column_name(col)
-                && let Err(e) = self.validate_column(&cmd.table, &name)
+                && let Err(e) = self.validate_in_scope(&tables, &name)
             {
                 errors.push(e);
             }
@@ -357,7 +416,7 @@ impl Validator {
                             }
                         }
                     } else {
-                        if let Err(e) = self.validate_column(&cmd.table, &name) {
+                        if let Err(e) = self.validate_in_scope(&tables, &name) {
                             errors.push(e);
                         }
                         // Type validation for unqualified column
@@ -369,23 +428,34 @@ impl Validator {
             }
         }
 
-        for join in &cmd.joins {
+        for (i, join) in cmd.joins.iter().enumerate() {
 
-            if let Err(e) = self.validate_table(&join.table) {
+            // A lateral subquery's `table` is its alias
+            if join.subquery.is_none()
+                && let Err(e) = self.validate_table(&join.table)
+            {
                 errors.push(e);
             }
 
 
             if let Some(conditions) = &join.on {
                 for cond in conditions {
-                    if let Some(name) = Self::extract_column_name(&cond.left)
-                        && name.contains('.')
-                    {
-                        let parts: Vec<&str> = name.split('.').collect();
-                        if parts.len() == 2
-                            && let Err(e) = self.validate_column(parts[0], parts[1])
-                        {
-                            errors.push(e);
+                    if let Some(name) = Self::extract_column_name(&cond.left) {
+                        if name.contains('.') {
+                            let parts: Vec<&str> = name.split('.').collect();
+                            if parts.len() == 2
+                                && let Err(e) = self.validate_column(parts[0], parts[1])
+                            {
+                                errors.push(e);
+                            }
+                        } else {
+                            // Only tables joined so far are visible in ON
+                            let visible = &tables[..i + 2];
+                            if let Err(e @ ValidationError::AmbiguousColumn { .. }) =
+                                self.validate_in_scope(visible, &name)
+                            {
+                                errors.push(e);
+                            }
                         }
                     }
                     // Also check right side if it's a column reference
@@ -527,6 +597,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ambiguous_column_in_join() {
+        let mut v = Validator::new();
+        v.add_table("users", &["id", "email"]);
+        v.add_table("orders", &["id", "user_id", "total"]);
+
+        let joined = || {
+            Qail::get("users").join(
+                crate::ast::JoinKind::Inner,
+                "orders",
+                "orders.user_id",
+                "users.id",
+            )
+        };
+
+        // `id` is in both tables
+        let errors = v
+            .validate_command(&joined().columns(["id", "total"]))
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ValidationError::AmbiguousColumn {
+                column: "id".to_string(),
+                tables: vec!["users".to_string(), "orders".to_string()],
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Column 'id' is ambiguous: it exists in users, orders. Qualify it as 'users.id'."
+        );
+        // Built by hand with no tables: still displays
+        let err = ValidationError::AmbiguousColumn {
+            column: "id".to_string(),
+            tables: vec![],
+        };
+        assert_eq!(
+            err.to_string(),
+            "Column 'id' is ambiguous. Qualify it with its table name."
+        );
+
+        // Qualified, or unique to one table: fine
+        let cmd = joined()
+            .columns(["users.id", "email", "total"])
+            .filter("orders.id", crate::ast::Operator::Eq, 7);
+        assert!(v.validate_command(&cmd).is_ok());
+
+        let cmd = joined().filter("id", crate::ast::Operator::Eq, 7);
+        assert!(matches!(
+            v.validate_command(&cmd).unwrap_err()[..],
+            [ValidationError::AmbiguousColumn { .. }]
+        ));
+    }
+
     #[test]
     fn test_error_display() {
         let err = ValidationError::TableNotFound {
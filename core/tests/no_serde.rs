@@ -0,0 +1,21 @@
+//! Compiled only by `cargo test -p qail-core --no-default-features`:
+//! the AST must stay usable without the `serde` feature.
+#![cfg(not(feature = "serde"))]
+
+use qail_core::ast::{Operator, Qail};
+use qail_core::parse;
+use qail_core::transpiler::ToSql;
+
+#[test]
+fn test_parse_and_transpile_without_serde() {
+    let cmd = parse("get users fields id, email where active = true limit 5").unwrap();
+    assert_eq!(
+        cmd.to_sql(),
+        "SELECT id, email FROM users WHERE active = true LIMIT 5"
+    );
+
+    let built = Qail::get("users")
+        .columns(["id"])
+        .filter("id", Operator::Eq, 1);
+    assert_eq!(built.clone(), built);
+}
@@ -37,6 +37,7 @@ char* qail_transpile_with_dialect(const char* qail, const char* dialect);
 
 /**
  * Parse QAIL string and return AST as JSON.
+ * Only exported when qail-ffi is built with the `json` feature (default).
  * 
  * @param qail  QAIL query string (UTF-8)
  * @return      JSON string (caller must free with qail_free), or NULL on error
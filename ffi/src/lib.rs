@@ -138,6 +138,8 @@ pub extern "C" fn qail_transpile_with_dialect(
 /// Parse QAIL and return AST as JSON string.
 /// Returns NULL on error.
 /// Caller must free the returned string with qail_free().
+/// Only built with the `json` feature (on by default).
+#[cfg(feature = "json")]
 #[unsafe(no_mangle)]
 pub extern "C" fn qail_parse_json(qail: *const c_char) -> *mut c_char {
     clear_error();
@@ -320,7 +322,13 @@ pub extern "C" fn qail_encode_get(
     }
 
     // Encode to wire bytes
-    let (wire_bytes, _) = AstEncoder::encode_cmd(&cmd);
+    let (wire_bytes, _) = match AstEncoder::encode_cmd(&cmd) {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            set_error(e.to_string());
+            return -4;
+        }
+    };
     let bytes_vec = wire_bytes.to_vec();
     let len = bytes_vec.len();
 
@@ -428,7 +436,13 @@ pub extern "C" fn qail_encode_batch_get(
     }
 
     // Encode batch
-    let wire_bytes = AstEncoder::encode_batch(&cmds);
+    let wire_bytes = match AstEncoder::encode_batch(&cmds) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_error(e.to_string());
+            return -4;
+        }
+    };
     let bytes_vec = wire_bytes.to_vec();
     let len = bytes_vec.len();
 
@@ -516,7 +530,13 @@ pub extern "C" fn qail_encode_uniform_batch(
     let cmds: Vec<_> = (0..count).map(|_| base_cmd.clone()).collect();
 
     // Encode batch
-    let wire_bytes = AstEncoder::encode_batch(&cmds);
+    let wire_bytes = match AstEncoder::encode_batch(&cmds) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            set_error(e.to_string());
+            return -3;
+        }
+    };
     let bytes_vec = wire_bytes.to_vec();
     let len = bytes_vec.len();
 
@@ -1,6 +1,8 @@
 //! Gateway configuration
 
+use qail_core::ast::Action;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Main gateway configuration
@@ -33,11 +35,57 @@ pub struct GatewayConfig {
     /// Cache TTL in seconds
     #[serde(default = "default_cache_ttl")]
     pub cache_ttl_seconds: u64,
+    
+    /// Replay mutation responses for retried `Idempotency-Key`s
+    #[serde(default = "default_true")]
+    pub idempotency_enabled: bool,
+    
+    /// Maximum idempotency keys kept in memory
+    #[serde(default = "default_idempotency_max")]
+    pub idempotency_max_entries: usize,
+    
+    /// How long an idempotency key is remembered, in seconds
+    #[serde(default = "default_idempotency_ttl")]
+    pub idempotency_ttl_seconds: u64,
+    
+    /// Actions the gateway will execute (None = all), e.g. `[Get]` for read-only
+    #[serde(default)]
+    pub allowed_actions: Option<Vec<Action>>,
+    
+    /// Per-client (user id) overrides of `allowed_actions`
+    #[serde(default)]
+    pub client_allowed_actions: HashMap<String, Vec<Action>>,
+    
+    /// Maximum subquery / CTE nesting per query (None = unlimited)
+    #[serde(default = "default_max_query_depth")]
+    pub max_query_depth: Option<usize>,
+    
+    /// Maximum joins per query, subqueries included (None = unlimited)
+    #[serde(default = "default_max_query_joins")]
+    pub max_query_joins: Option<usize>,
+    
+    /// Maximum AST nodes per query (None = unlimited)
+    #[serde(default = "default_max_query_nodes")]
+    pub max_query_nodes: Option<usize>,
+    
+    /// Connections opened at startup and kept idle in the pool
+    /// (None = the database URL's `min_connections`, default 1)
+    #[serde(default)]
+    pub min_idle: Option<usize>,
+    
+    /// Transformed columns: `"table.column"` → registered transform name
+    #[serde(default)]
+    pub column_transforms: HashMap<String, String>,
 }
 
 fn default_true() -> bool { true }
 fn default_cache_max() -> usize { 1000 }
 fn default_cache_ttl() -> u64 { 60 }
+fn default_idempotency_max() -> usize { 10_000 }
+fn default_idempotency_ttl() -> u64 { 24 * 60 * 60 }
+fn default_max_query_depth() -> Option<usize> { Some(8) }
+fn default_max_query_joins() -> Option<usize> { Some(16) }
+fn default_max_query_nodes() -> Option<usize> { Some(5000) }
 
 impl Default for GatewayConfig {
     fn default() -> Self {
@@ -50,6 +98,16 @@ impl Default for GatewayConfig {
             cache_enabled: true,
             cache_max_entries: 1000,
             cache_ttl_seconds: 60,
+            idempotency_enabled: true,
+            idempotency_max_entries: default_idempotency_max(),
+            idempotency_ttl_seconds: default_idempotency_ttl(),
+            allowed_actions: None,
+            client_allowed_actions: HashMap::new(),
+            max_query_depth: default_max_query_depth(),
+            max_query_joins: default_max_query_joins(),
+            max_query_nodes: default_max_query_nodes(),
+            min_idle: None,
+            column_transforms: HashMap::new(),
         }
     }
 }
@@ -63,6 +121,32 @@ impl GatewayConfig {
             ttl: Duration::from_secs(self.cache_ttl_seconds),
         }
     }
+    
+    /// Get idempotency key configuration
+    pub fn idempotency_config(&self) -> crate::idempotency::IdempotencyConfig {
+        crate::idempotency::IdempotencyConfig {
+            enabled: self.idempotency_enabled,
+            max_entries: self.idempotency_max_entries,
+            ttl: Duration::from_secs(self.idempotency_ttl_seconds),
+        }
+    }
+    
+    /// Get action allowlist configuration
+    pub fn action_policy(&self) -> crate::policy::ActionPolicy {
+        crate::policy::ActionPolicy {
+            allowed: self.allowed_actions.clone(),
+            per_client: self.client_allowed_actions.clone(),
+        }
+    }
+    
+    /// Get query complexity limits
+    pub fn complexity_limits(&self) -> crate::policy::ComplexityLimits {
+        crate::policy::ComplexityLimits {
+            max_depth: self.max_query_depth,
+            max_joins: self.max_query_joins,
+            max_nodes: self.max_query_nodes,
+        }
+    }
 }
 
 impl GatewayConfig {
@@ -103,8 +187,57 @@ impl GatewayConfigBuilder {
         self
     }
     
+    /// Restrict the gateway to the given actions
+    pub fn allowed_actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        self.config.allowed_actions = Some(actions.into_iter().collect());
+        self
+    }
+    
+    /// Override the allowed actions for a single client (user id)
+    pub fn client_allowed_actions(
+        mut self,
+        client: impl Into<String>,
+        actions: impl IntoIterator<Item = Action>,
+    ) -> Self {
+        self.config
+            .client_allowed_actions
+            .insert(client.into(), actions.into_iter().collect());
+        self
+    }
+    
+    /// Cap subquery / CTE nesting, join count and AST size (None = unlimited)
+    pub fn complexity_limits(
+        mut self,
+        max_depth: Option<usize>,
+        max_joins: Option<usize>,
+        max_nodes: Option<usize>,
+    ) -> Self {
+        self.config.max_query_depth = max_depth;
+        self.config.max_query_joins = max_joins;
+        self.config.max_query_nodes = max_nodes;
+        self
+    }
+    
+    /// Pre-open `n` pool connections at startup
+    pub fn min_idle(mut self, n: usize) -> Self {
+        self.config.min_idle = Some(n);
+        self
+    }
+    
+    /// Apply the registered transform `name` to `column` (`"table.column"`)
+    pub fn column_transform(mut self, column: impl Into<String>, name: impl Into<String>) -> Self {
+        self.config.column_transforms.insert(column.into(), name.into());
+        self
+    }
+    
     /// Build the configuration
     pub fn build(self) -> GatewayConfig {
         self.config
     }
 }
+
+impl From<GatewayConfig> for GatewayConfigBuilder {
+    fn from(config: GatewayConfig) -> Self {
+        Self { config }
+    }
+}
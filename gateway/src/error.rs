@@ -29,10 +29,18 @@ pub enum GatewayError {
     #[error("Access denied: {0}")]
     AccessDenied(String),
     
+    /// Action not permitted by the gateway's action allowlist
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    
     /// Query validation error
     #[error("Invalid query: {0}")]
     InvalidQuery(String),
     
+    /// Query exceeds the configured depth / join / size limits
+    #[error("Query too complex: {0}")]
+    TooComplex(String),
+    
     /// Internal server error
     #[error("Internal error: {0}")]
     Internal(#[from] anyhow::Error),
@@ -48,8 +56,19 @@ impl GatewayError {
             Self::Database(_) => 503,
             Self::Auth(_) => 401,
             Self::AccessDenied(_) => 403,
+            Self::Forbidden(_) => 403,
             Self::InvalidQuery(_) => 400,
+            Self::TooComplex(_) => 400,
             Self::Internal(_) => 500,
         }
     }
+    
+    /// Machine-readable code for error responses from query rejections
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TooComplex(_) => "TOO_COMPLEX",
+            Self::InvalidQuery(_) => "INVALID_QUERY",
+            _ => "POLICY_DENIED",
+        }
+    }
 }
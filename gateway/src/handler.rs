@@ -3,15 +3,22 @@
 //! Handles incoming requests and executes QAIL queries.
 
 use axum::{
-    body::Bytes,
+    body::{Body, Bytes},
     extract::State,
-    http::{HeaderMap, StatusCode},
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use serde::{Deserialize, Serialize};
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::future::Future;
 use std::sync::Arc;
 
-use crate::auth::extract_auth_from_headers;
+use crate::auth::{extract_auth_from_headers, AuthContext};
+use crate::idempotency::{caller_scope, IdempotencyError, IDEMPOTENCY_KEY_HEADER};
+use crate::metrics::{record_batch, record_query_error, table_label, QueryTimer};
+use crate::transform::{ColumnTransforms, TransformError};
 use crate::GatewayState;
 
 /// Health check response
@@ -41,7 +48,7 @@ pub struct BatchRequest {
 fn default_true() -> bool { true }
 
 /// Batch query response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BatchResponse {
     pub results: Vec<BatchQueryResult>,
     pub total: usize,
@@ -49,7 +56,7 @@ pub struct BatchResponse {
 }
 
 /// Result for a single query in a batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BatchQueryResult {
     pub index: usize,
     pub success: bool,
@@ -61,6 +68,12 @@ pub struct BatchQueryResult {
     pub error: Option<String>,
 }
 
+/// Schema describe response: the tables (and their columns) the client may read
+#[derive(Debug, Serialize)]
+pub struct DescribeResponse {
+    pub tables: Vec<crate::schema::TableSchema>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
@@ -79,6 +92,19 @@ pub async fn health_check(
     })
 }
 
+/// Describe the loaded schema, limited to tables the caller may query
+pub async fn describe_schema(
+    State(state): State<Arc<GatewayState>>,
+    headers: HeaderMap,
+) -> Json<DescribeResponse> {
+    let auth = extract_auth_from_headers(&headers);
+    tracing::info!("Describe schema (user: {})", auth.user_id);
+    
+    Json(DescribeResponse {
+        tables: state.schema_validator.describe(&state.policy_engine, &auth),
+    })
+}
+
 pub async fn execute_query(
     State(state): State<Arc<GatewayState>>,
     headers: HeaderMap,
@@ -123,12 +149,12 @@ pub async fn execute_query(
             StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::FORBIDDEN),
             Json(ErrorResponse {
                 error: e.to_string(),
-                code: "POLICY_DENIED".to_string(),
+                code: e.code().to_string(),
             }),
         ));
     }
     
-    execute_qail_cmd(&state, &cmd).await
+    execute_idempotent(&state, &headers, &auth, &cmd).await
 }
 
 /// Execute a QAIL query (BINARY format)
@@ -177,12 +203,133 @@ pub async fn execute_query_binary(
             StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::FORBIDDEN),
             Json(ErrorResponse {
                 error: e.to_string(),
-                code: "POLICY_DENIED".to_string(),
+                code: e.code().to_string(),
             }),
         ));
     }
     
-    execute_qail_cmd(&state, &cmd).await
+    execute_idempotent(&state, &headers, &auth, &cmd).await
+}
+
+/// Run a mutation at most once per `Idempotency-Key` header
+/// 
+/// A retry with the same key (from the same caller) gets the stored response
+/// instead of executing again. Reads and requests without a key go straight
+/// to [`execute_qail_cmd`].
+async fn execute_idempotent(
+    state: &Arc<GatewayState>,
+    headers: &HeaderMap,
+    auth: &AuthContext,
+    cmd: &qail_core::ast::Qail,
+) -> Result<Json<QueryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    use qail_core::ast::Action;
+    
+    let key = match idempotency_key(headers) {
+        Some(key) if !matches!(cmd.action, Action::Get) && state.idempotency.is_enabled() => key,
+        _ => return execute_qail_cmd(state, cmd).await,
+    };
+    
+    let scope = caller_scope(auth, headers);
+    run_idempotent(state, scope.as_deref(), key, format!("{:?}", cmd), || async {
+        execute_qail_cmd(state, cmd).await.map(|Json(response)| response)
+    })
+    .await
+    .map(Json)
+}
+
+/// The trimmed `Idempotency-Key` header, if present and not empty
+pub(crate) fn idempotency_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+}
+
+/// Run `run` at most once per caller `scope` and `key`, replaying the
+/// stored response on retries
+/// 
+/// `request` fingerprints what is being run, to reject a key reused for a
+/// different request. Callers without a scope (no credentials at all) are
+/// rejected rather than sharing one key space.
+pub(crate) async fn run_idempotent<T, F, Fut>(
+    state: &GatewayState,
+    scope: Option<&str>,
+    key: &str,
+    request: String,
+    run: F,
+) -> Result<T, (StatusCode, Json<ErrorResponse>)>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, (StatusCode, Json<ErrorResponse>)>>,
+{
+    let Some(scope) = scope else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Idempotency keys require an authenticated caller or an Authorization header".to_string(),
+                code: "IDEMPOTENCY_KEY_ANONYMOUS".to_string(),
+            }),
+        ));
+    };
+    
+    // Keys are per caller, so one client cannot replay another's response
+    let scoped_key = format!("{}:{}", scope, key);
+    
+    let json = state
+        .idempotency
+        .execute(&scoped_key, &request, || async {
+            let response = run().await?;
+            serde_json::to_string(&response).map_err(|e| internal_error(e.to_string()))
+        })
+        .await
+        .map_err(|e| match e {
+            IdempotencyError::KeyReused => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ErrorResponse {
+                    error: format!("Idempotency key '{}' was already used for a different request", key),
+                    code: "IDEMPOTENCY_KEY_REUSED".to_string(),
+                }),
+            ),
+            IdempotencyError::Failed(e) => e,
+        })?;
+    
+    serde_json::from_str(&json).map_err(|e| internal_error(e.to_string()))
+}
+
+fn internal_error(error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error,
+            code: "INTERNAL_ERROR".to_string(),
+        }),
+    )
+}
+
+fn transform_error(e: TransformError) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("{}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: e.to_string(),
+            code: "TRANSFORM_ERROR".to_string(),
+        }),
+    )
+}
+
+/// Encode the values written to transformed columns, cloning only if needed
+fn encode_transformed<'a>(
+    transforms: &ColumnTransforms,
+    cmd: &'a qail_core::ast::Qail,
+) -> Result<Cow<'a, qail_core::ast::Qail>, TransformError> {
+    if transforms.is_empty() {
+        return Ok(Cow::Borrowed(cmd));
+    }
+    let mut cmd = cmd.clone();
+    transforms.encode_payload(&mut cmd)?;
+    Ok(Cow::Owned(cmd))
 }
 
 /// Common query execution logic
@@ -192,6 +339,8 @@ async fn execute_qail_cmd(
 ) -> Result<Json<QueryResponse>, (StatusCode, Json<ErrorResponse>)> {
     use qail_core::ast::Action;
     
+    let cmd = encode_transformed(&state.transforms, cmd).map_err(transform_error)?;
+    let cmd = cmd.as_ref();
     let table = &cmd.table;
     let is_read_query = matches!(cmd.action, Action::Get);
     
@@ -221,8 +370,13 @@ async fn execute_qail_cmd(
         )
     })?;
     
-    let rows = conn.fetch_all_uncached(cmd).await.map_err(|e| {
+    let timer = QueryTimer::new(table_label(&state.schema_validator, table), &cmd.action.to_string());
+    let result = conn.fetch_all_uncached(cmd).await;
+    timer.finish(result.is_ok());
+    
+    let rows = result.map_err(|e| {
         tracing::error!("Query error: {}", e);
+        record_query_error(e.code());
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -235,8 +389,9 @@ async fn execute_qail_cmd(
     // Convert rows to JSON
     let json_rows: Vec<serde_json::Value> = rows
         .iter()
-        .map(row_to_json)
-        .collect();
+        .map(|row| transformed_row_to_json(row, &state.transforms))
+        .collect::<Result<_, _>>()
+        .map_err(transform_error)?;
     
     let count = json_rows.len();
     
@@ -261,45 +416,81 @@ async fn execute_qail_cmd(
 }
 
 pub fn row_to_json(row: &qail_pg::PgRow) -> serde_json::Value {
-    let column_names: Vec<String> = if let Some(ref info) = row.column_info {
-        let mut pairs: Vec<_> = info.name_to_index.iter().collect();
-        pairs.sort_by_key(|(_, idx)| *idx);
-        pairs.into_iter().map(|(name, _)| name.clone()).collect()
-    } else {
-        (0..row.columns.len()).map(|i| format!("col_{}", i)).collect()
+    let mut obj = serde_json::Map::new();
+    
+    for (i, col_name) in column_names(row).into_iter().enumerate() {
+        let value = match row.get_string(i) {
+            Some(s) => text_to_json(s),
+            None => serde_json::Value::Null,
+        };
+        obj.insert(col_name, value);
+    }
+    
+    serde_json::Value::Object(obj)
+}
+
+/// [`row_to_json`], decoding fields read from transformed columns
+///
+/// Fields are matched by the source column in the row description, so an
+/// aliased or joined transformed column is decoded as well.
+pub fn transformed_row_to_json(
+    row: &qail_pg::PgRow,
+    transforms: &ColumnTransforms,
+) -> Result<serde_json::Value, TransformError> {
+    let Some(info) = row.column_info.as_deref().filter(|_| !transforms.is_empty()) else {
+        return Ok(row_to_json(row));
     };
     
     let mut obj = serde_json::Map::new();
     
-    for (i, col_name) in column_names.into_iter().enumerate() {
-        let value = if let Some(s) = row.get_string(i) {
-            if (s.starts_with('{') && s.ends_with('}')) || (s.starts_with('[') && s.ends_with(']')) {
-                serde_json::from_str(&s).unwrap_or(serde_json::Value::String(s))
-            } else {
-                if let Ok(n) = s.parse::<i64>() {
-                    serde_json::Value::Number(n.into())
-                } else if let Ok(f) = s.parse::<f64>() {
-                    if let Some(n) = serde_json::Number::from_f64(f) {
-                        serde_json::Value::Number(n)
-                    } else {
-                        serde_json::Value::String(s)
-                    }
-                } else if s == "t" || s == "true" {
-                    serde_json::Value::Bool(true)
-                } else if s == "f" || s == "false" {
-                    serde_json::Value::Bool(false)
-                } else {
-                    serde_json::Value::String(s)
-                }
-            }
-        } else {
-            serde_json::Value::Null
+    for (i, col_name) in column_names(row).into_iter().enumerate() {
+        let raw = row.columns.get(i).and_then(|c| c.as_deref());
+        let source = info.table_oids.get(i).zip(info.column_attrs.get(i));
+        let decoded = raw.zip(source).and_then(|(raw, (&table_oid, &column_attr))| {
+            transforms.decode_field(table_oid, column_attr, raw)
+        });
+        let value = match decoded {
+            Some(decoded) => text_to_json(String::from_utf8_lossy(&decoded?).into_owned()),
+            None => match row.get_string(i) {
+                Some(s) => text_to_json(s),
+                None => serde_json::Value::Null,
+            },
         };
-        
         obj.insert(col_name, value);
     }
     
-    serde_json::Value::Object(obj)
+    Ok(serde_json::Value::Object(obj))
+}
+
+fn column_names(row: &qail_pg::PgRow) -> Vec<String> {
+    if let Some(ref info) = row.column_info {
+        let mut pairs: Vec<_> = info.name_to_index.iter().collect();
+        pairs.sort_by_key(|(_, idx)| *idx);
+        pairs.into_iter().map(|(name, _)| name.clone()).collect()
+    } else {
+        (0..row.columns.len()).map(|i| format!("col_{}", i)).collect()
+    }
+}
+
+/// Convert a text-format column value to the closest JSON type
+fn text_to_json(s: String) -> serde_json::Value {
+    if (s.starts_with('{') && s.ends_with('}')) || (s.starts_with('[') && s.ends_with(']')) {
+        serde_json::from_str(&s).unwrap_or(serde_json::Value::String(s))
+    } else if let Ok(n) = s.parse::<i64>() {
+        serde_json::Value::Number(n.into())
+    } else if let Ok(f) = s.parse::<f64>() {
+        if let Some(n) = serde_json::Number::from_f64(f) {
+            serde_json::Value::Number(n)
+        } else {
+            serde_json::Value::String(s)
+        }
+    } else if s == "t" || s == "true" {
+        serde_json::Value::Bool(true)
+    } else if s == "f" || s == "false" {
+        serde_json::Value::Bool(false)
+    } else {
+        serde_json::Value::String(s)
+    }
 }
 
 pub async fn execute_batch(
@@ -320,6 +511,34 @@ pub async fn execute_batch(
     let auth = extract_auth_from_headers(&headers);
     tracing::info!("Executing batch of {} queries (user: {})", request.queries.len(), auth.user_id);
     
+    // A keyed batch with any mutation runs at most once, as a whole
+    let has_mutation = || {
+        request.queries.iter().any(|q| {
+            qail_core::parser::parse(q.trim())
+                .is_ok_and(|cmd| !matches!(cmd.action, qail_core::ast::Action::Get))
+        })
+    };
+    match idempotency_key(&headers) {
+        Some(key) if state.idempotency.is_enabled() && has_mutation() => {
+            let scope = caller_scope(&auth, &headers);
+            let fingerprint = format!("{:?}", (&request.queries, request.transaction));
+            run_idempotent(&state, scope.as_deref(), key, fingerprint, || {
+                run_batch(&state, &auth, &request)
+            })
+            .await
+            .map(Json)
+        }
+        _ => run_batch(&state, &auth, &request).await.map(Json),
+    }
+}
+
+/// Execute every query of a batch on one pooled connection
+async fn run_batch(
+    state: &GatewayState,
+    auth: &AuthContext,
+    request: &BatchRequest,
+) -> Result<BatchResponse, (StatusCode, Json<ErrorResponse>)> {
+    let started = std::time::Instant::now();
     let mut results = Vec::with_capacity(request.queries.len());
     let mut success_count = 0;
     
@@ -354,7 +573,18 @@ pub async fn execute_batch(
         };
         
         // Apply policies
-        if let Err(e) = state.policy_engine.apply_policies(&auth, &mut cmd) {
+        if let Err(e) = state.policy_engine.apply_policies(auth, &mut cmd) {
+            results.push(BatchQueryResult {
+                index,
+                success: false,
+                rows: None,
+                count: None,
+                error: Some(e.to_string()),
+            });
+            continue;
+        }
+        
+        if let Err(e) = state.transforms.encode_payload(&mut cmd) {
             results.push(BatchQueryResult {
                 index,
                 success: false,
@@ -366,9 +596,23 @@ pub async fn execute_batch(
         }
         
         // Execute query
-        match conn.fetch_all_uncached(&cmd).await {
-            Ok(rows) => {
-                let json_rows: Vec<serde_json::Value> = rows.iter().map(row_to_json).collect();
+        let timer = QueryTimer::new(
+            table_label(&state.schema_validator, &cmd.table),
+            &cmd.action.to_string(),
+        );
+        let result = conn.fetch_all_uncached(&cmd).await;
+        timer.finish(result.is_ok());
+        let result = result.map_err(|e| {
+            record_query_error(e.code());
+            format!("Query error: {}", e)
+        }).and_then(|rows| {
+            rows.iter()
+                .map(|row| transformed_row_to_json(row, &state.transforms))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.to_string())
+        });
+        match result {
+            Ok(json_rows) => {
                 let count = json_rows.len();
                 
                 results.push(BatchQueryResult {
@@ -380,23 +624,230 @@ pub async fn execute_batch(
                 });
                 success_count += 1;
             }
-            Err(e) => {
+            Err(error) => {
                 results.push(BatchQueryResult {
                     index,
                     success: false,
                     rows: None,
                     count: None,
-                    error: Some(format!("Query error: {}", e)),
+                    error: Some(error),
                 });
             }
         }
     }
     
     let total = results.len();
+    record_batch(total, success_count, started.elapsed().as_secs_f64() * 1000.0);
     
-    Ok(Json(BatchResponse {
+    Ok(BatchResponse {
         results,
         total,
         success: success_count,
-    }))
+    })
+}
+
+/// Rows fetched per cursor round-trip when streaming
+const STREAM_BATCH_SIZE: usize = 500;
+
+/// Execute a QAIL `get` query and stream the rows as NDJSON
+///
+/// Rows are read through a server-side cursor and written one JSON object
+/// per line as each batch arrives, so memory stays bounded by the batch size.
+/// A failure after the response has started ends the stream with a final
+/// `{"error": ..., "code": "QUERY_ERROR"}` line.
+pub async fn execute_query_stream(
+    State(state): State<Arc<GatewayState>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    use qail_core::ast::Action;
+    
+    let query_text = body.trim();
+    
+    if query_text.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Empty query".to_string(),
+                code: "EMPTY_QUERY".to_string(),
+            }),
+        ));
+    }
+    
+    let auth = extract_auth_from_headers(&headers);
+    
+    tracing::info!("Streaming text query: {} (user: {})", query_text, auth.user_id);
+    
+    let mut cmd = qail_core::parser::parse(query_text).map_err(|e| {
+        tracing::warn!("Parse error: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Parse error: {}", e),
+                code: "PARSE_ERROR".to_string(),
+            }),
+        )
+    })?;
+    
+    if !matches!(cmd.action, Action::Get) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Only get queries can be streamed".to_string(),
+                code: "STREAM_UNSUPPORTED".to_string(),
+            }),
+        ));
+    }
+    
+    // Apply row-level security policies
+    if let Err(e) = state.policy_engine.apply_policies(&auth, &mut cmd) {
+        tracing::warn!("Policy error: {}", e);
+        return Err((
+            StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::FORBIDDEN),
+            Json(ErrorResponse {
+                error: e.to_string(),
+                code: e.code().to_string(),
+            }),
+        ));
+    }
+    
+    // Acquire up front so pool exhaustion is still a proper 503
+    let conn = state.pool.acquire().await.map_err(|e| {
+        tracing::error!("Pool error: {}", e);
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: "Database connection failed".to_string(),
+                code: "CONNECTION_ERROR".to_string(),
+            }),
+        )
+    })?;
+    
+    let (tx, rx) = tokio::sync::mpsc::channel(STREAM_BATCH_SIZE);
+    let transforms = state.transforms.clone();
+    // Covers the whole stream, until the last row is sent
+    let timer = QueryTimer::new(
+        table_label(&state.schema_validator, &cmd.table),
+        &cmd.action.to_string(),
+    );
+    tokio::spawn(async move {
+        let result = stream_cursor_rows(conn, &cmd, &transforms, &tx).await;
+        timer.finish(result.is_ok());
+        if let Err(e) = result {
+            tracing::error!("Stream query error: {}", e);
+            record_query_error(e.code());
+            let _ = tx.send(Err(format!("Query failed: {}", e))).await;
+        }
+    });
+    
+    let rows = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|row| (row, rx))
+    });
+    
+    Ok(ndjson_response(rows))
+}
+
+/// Drain a cursor over `cmd` into `tx`, one JSON row at a time
+///
+/// Stops early (without error) once the receiving side is dropped, e.g.
+/// when the client disconnects.
+async fn stream_cursor_rows(
+    mut conn: qail_pg::PooledConnection,
+    cmd: &qail_core::ast::Qail,
+    transforms: &ColumnTransforms,
+    tx: &tokio::sync::mpsc::Sender<Result<serde_json::Value, String>>,
+) -> qail_pg::PgResult<()> {
+    let (sql, params) = qail_pg::protocol::AstEncoder::encode_cmd_sql(cmd)?;
+    
+    conn.begin_transaction().await?;
+    
+    let result = async {
+        conn.declare_cursor("qail_stream", &sql, &params).await?;
+        while let Some(rows) = conn.fetch_cursor("qail_stream", STREAM_BATCH_SIZE).await? {
+            for row in &rows {
+                let row = transformed_row_to_json(row, transforms).map_err(|e| e.to_string());
+                if tx.send(row).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+    
+    // Ending the transaction also closes the cursor
+    match result {
+        Ok(()) => conn.commit().await,
+        Err(e) => {
+            let _ = conn.rollback().await;
+            Err(e)
+        }
+    }
+}
+
+/// Build an `application/x-ndjson` response that writes each row as it arrives
+///
+/// An `Err` item is written as an error line and terminates the body.
+pub fn ndjson_response<S>(rows: S) -> Response
+where
+    S: Stream<Item = Result<serde_json::Value, String>> + Send + 'static,
+{
+    let lines = rows
+        .scan(false, |failed, row| {
+            if *failed {
+                return futures::future::ready(None);
+            }
+            let value = row.unwrap_or_else(|error| {
+                *failed = true;
+                serde_json::json!({ "error": error, "code": "QUERY_ERROR" })
+            });
+            let mut line = serde_json::to_vec(&value).unwrap_or_default();
+            line.push(b'\n');
+            futures::future::ready(Some(Ok::<_, Infallible>(Bytes::from(line))))
+        });
+    
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    
+    #[tokio::test]
+    async fn test_ndjson_rows_written_before_source_completes() {
+        // Mock slow source: rows arrive only when the test sends them
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let rows = futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|row| (row, rx))
+        });
+        
+        let response = ndjson_response(rows);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/x-ndjson");
+        let mut body = response.into_body().into_data_stream();
+        
+        tx.send(Ok(serde_json::json!({"id": 1}))).await.unwrap();
+        let first = tokio::time::timeout(Duration::from_secs(1), body.next())
+            .await
+            .expect("first row should be written while the query is still running")
+            .unwrap()
+            .unwrap();
+        assert_eq!(&first[..], b"{\"id\":1}\n");
+        
+        tx.send(Err("Query failed: boom".to_string())).await.unwrap();
+        tx.send(Ok(serde_json::json!({"id": 2}))).await.unwrap();
+        drop(tx);
+        
+        let error = body.next().await.unwrap().unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&error).unwrap();
+        assert_eq!(error["code"], "QUERY_ERROR");
+        assert_eq!(error["error"], "Query failed: boom");
+        
+        // Nothing follows the error line
+        assert!(body.next().await.is_none());
+    }
 }
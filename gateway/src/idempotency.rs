@@ -0,0 +1,297 @@
+//! Idempotency Module
+//!
+//! Deduplicates retried mutations. A client that sends an `Idempotency-Key`
+//! header with a mutation gets the stored response back when it retries
+//! with the same key, instead of the mutation running a second time.
+//!
+//! Keys live in memory for a TTL. Concurrent requests with the same key wait
+//! for the first one; a failed execution is not stored, so it can be retried.
+//! Keys are scoped per caller (see [`caller_scope`]), so one client can never
+//! replay another's response.
+
+use crate::auth::AuthContext;
+use axum::http::HeaderMap;
+use dashmap::DashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+/// Request header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Namespace for a caller's idempotency keys
+///
+/// The user id when the caller is authenticated, otherwise a hash of the
+/// `Authorization` header (the raw token is not kept). `None` for callers
+/// with no credentials at all, who would otherwise share one scope.
+pub fn caller_scope(auth: &AuthContext, headers: &HeaderMap) -> Option<String> {
+    if auth.is_authenticated() {
+        return Some(format!("user:{}", auth.user_id));
+    }
+    let token = headers.get("authorization")?.as_bytes();
+    let mut hasher = DefaultHasher::new();
+    token.hash(&mut hasher);
+    Some(format!("token:{:016x}", hasher.finish()))
+}
+
+/// Idempotency configuration
+#[derive(Debug, Clone)]
+pub struct IdempotencyConfig {
+    pub max_entries: usize,
+    pub ttl: Duration,
+    pub enabled: bool,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            ttl: Duration::from_secs(24 * 60 * 60),
+            enabled: true,
+        }
+    }
+}
+
+/// Why a keyed request was not executed
+#[derive(Debug)]
+pub enum IdempotencyError<E> {
+    /// The key was already used for a different request
+    KeyReused,
+    /// The execution itself failed (nothing was stored)
+    Failed(E),
+}
+
+/// Stored outcome of one keyed mutation
+struct IdempotencyEntry {
+    /// Hash of the request the key was first used with
+    fingerprint: u64,
+    /// Serialized JSON response, set once the mutation succeeded
+    result: Arc<OnceCell<String>>,
+    /// When the key was first seen
+    created_at: Instant,
+}
+
+/// Thread-safe store of idempotency keys with TTL
+pub struct IdempotencyStore {
+    entries: DashMap<String, IdempotencyEntry>,
+    config: IdempotencyConfig,
+}
+
+impl IdempotencyStore {
+    pub fn new(config: IdempotencyConfig) -> Self {
+        Self {
+            entries: DashMap::new(),
+            config,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn fingerprint(request: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        request.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Run `execute` at most once per `key` within the TTL.
+    ///
+    /// `request` identifies what is being executed; reusing a key for a
+    /// different request is rejected with [`IdempotencyError::KeyReused`].
+    pub async fn execute<F, Fut, E>(
+        &self,
+        key: &str,
+        request: &str,
+        execute: F,
+    ) -> Result<String, IdempotencyError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String, E>>,
+    {
+        if !self.config.enabled {
+            return execute().await.map_err(IdempotencyError::Failed);
+        }
+
+        if !self.entries.contains_key(key) && self.entries.len() >= self.config.max_entries {
+            self.evict_expired();
+
+            if self.entries.len() >= self.config.max_entries {
+                tracing::warn!("Idempotency store full, executing key '{}' without dedup", key);
+                return execute().await.map_err(IdempotencyError::Failed);
+            }
+        }
+
+        let fingerprint = Self::fingerprint(request);
+        let result = {
+            let mut entry = self
+                .entries
+                .entry(key.to_string())
+                .or_insert_with(|| Self::new_entry(fingerprint));
+            if entry.created_at.elapsed() >= self.config.ttl {
+                *entry = Self::new_entry(fingerprint);
+            }
+            if entry.fingerprint != fingerprint {
+                return Err(IdempotencyError::KeyReused);
+            }
+            Arc::clone(&entry.result)
+        };
+
+        if let Some(stored) = result.get() {
+            tracing::debug!("Idempotency REPLAY for key '{}'", key);
+            return Ok(stored.clone());
+        }
+
+        result
+            .get_or_try_init(execute)
+            .await
+            .cloned()
+            .map_err(IdempotencyError::Failed)
+    }
+
+    fn new_entry(fingerprint: u64) -> IdempotencyEntry {
+        IdempotencyEntry {
+            fingerprint,
+            result: Arc::new(OnceCell::new()),
+            created_at: Instant::now(),
+        }
+    }
+
+    fn evict_expired(&self) {
+        let now = Instant::now();
+        let ttl = self.config.ttl;
+
+        self.entries
+            .retain(|_, entry| now.duration_since(entry.created_at) < ttl);
+    }
+
+    /// Number of keys currently stored
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn insert(
+        store: &IdempotencyStore,
+        executions: &AtomicUsize,
+        key: &str,
+        request: &str,
+    ) -> Result<String, IdempotencyError<String>> {
+        store
+            .execute(key, request, || async {
+                let n = executions.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(format!(r#"{{"rows":[{{"id":{}}}],"count":1}}"#, n))
+            })
+            .await
+    }
+
+    #[test]
+    fn test_caller_scope() {
+        let user = AuthContext {
+            user_id: "user-1".to_string(),
+            ..AuthContext::anonymous()
+        };
+        assert_eq!(caller_scope(&user, &HeaderMap::new()).as_deref(), Some("user:user-1"));
+
+        // Anonymous callers are told apart by their credentials
+        let anonymous = AuthContext::anonymous();
+        assert_eq!(caller_scope(&anonymous, &HeaderMap::new()), None);
+
+        let token = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("authorization", value.parse().unwrap());
+            caller_scope(&anonymous, &headers).unwrap()
+        };
+        assert!(token("Bearer a").starts_with("token:"));
+        assert!(!token("Bearer a").contains("Bearer"));
+        assert_eq!(token("Bearer a"), token("Bearer a"));
+        assert_ne!(token("Bearer a"), token("Bearer b"));
+    }
+
+    #[tokio::test]
+    async fn test_same_key_executes_once() {
+        let store = IdempotencyStore::new(IdempotencyConfig::default());
+        let executions = AtomicUsize::new(0);
+        let request = "add users fields name values 'alice'";
+
+        let first = insert(&store, &executions, "user-1:abc", request).await.unwrap();
+        let retry = insert(&store, &executions, "user-1:abc", request).await.unwrap();
+
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(first, retry);
+
+        // A different key is a different mutation
+        insert(&store, &executions, "user-1:def", request).await.unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_retries_execute_once() {
+        let store = IdempotencyStore::new(IdempotencyConfig::default());
+        let executions = AtomicUsize::new(0);
+        let request = "add users fields name values 'alice'";
+
+        let (a, b) = tokio::join!(
+            insert(&store, &executions, "k", request),
+            insert(&store, &executions, "k", request),
+        );
+
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(a.unwrap(), b.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_key_reused_for_other_request() {
+        let store = IdempotencyStore::new(IdempotencyConfig::default());
+        let executions = AtomicUsize::new(0);
+
+        insert(&store, &executions, "k", "add users fields name values 'alice'")
+            .await
+            .unwrap();
+        let reused = insert(&store, &executions, "k", "add users fields name values 'bob'").await;
+
+        assert!(matches!(reused, Err(IdempotencyError::KeyReused)));
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failure_is_not_stored() {
+        let store = IdempotencyStore::new(IdempotencyConfig::default());
+        let request = "add users fields name values 'alice'";
+
+        let failed = store
+            .execute("k", request, || async { Err::<String, _>("deadlock".to_string()) })
+            .await;
+        assert!(matches!(failed, Err(IdempotencyError::Failed(_))));
+
+        let executions = AtomicUsize::new(0);
+        insert(&store, &executions, "k", request).await.unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_key_executes_again() {
+        let store = IdempotencyStore::new(IdempotencyConfig {
+            ttl: Duration::ZERO,
+            ..Default::default()
+        });
+        let executions = AtomicUsize::new(0);
+        let request = "add users fields name values 'alice'";
+
+        insert(&store, &executions, "k", request).await.unwrap();
+        insert(&store, &executions, "k", request).await.unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+}
@@ -36,12 +36,14 @@ pub mod cache;
 pub mod config;
 pub mod error;
 pub mod handler;
+pub mod idempotency;
 pub mod metrics;
 pub mod middleware;
 pub mod policy;
 pub mod router;
 pub mod schema;
 pub mod server;
+pub mod transform;
 pub mod ws;
 
 pub use config::GatewayConfig;
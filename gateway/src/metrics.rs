@@ -2,28 +2,103 @@
 //!
 //! Exposes gateway metrics for monitoring.
 
-use axum::{extract::State, response::IntoResponse};
+use axum::{extract::State, http::header, response::IntoResponse, Json};
 use metrics::{counter, gauge, histogram};
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
-use std::sync::Arc;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use serde::Serialize;
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
 
+use crate::schema::SchemaValidator;
+use crate::GatewayState;
+
+/// Query latency histogram buckets (milliseconds)
+const QUERY_DURATION_BUCKETS_MS: &[f64] = &[
+    1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+];
+
+/// `table` label for tables the schema does not declare
+pub const OTHER_TABLE_LABEL: &str = "other";
+
+static PROMETHEUS: OnceLock<PrometheusHandle> = OnceLock::new();
+
 /// Initialize Prometheus metrics recorder
+///
+/// The first call installs the global recorder; later calls return the same
+/// handle. If the application already installed another recorder, the
+/// returned handle renders nothing.
 pub fn init_metrics() -> PrometheusHandle {
-    PrometheusBuilder::new()
-        .install_recorder()
-        .expect("Failed to install Prometheus recorder")
+    PROMETHEUS
+        .get_or_init(|| {
+            let builder = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full("qail_query_duration_ms".to_string()),
+                    QUERY_DURATION_BUCKETS_MS,
+                )
+                .expect("histogram buckets are non-empty");
+            match builder.install_recorder() {
+                Ok(handle) => handle,
+                Err(e) => {
+                    tracing::warn!("Prometheus recorder not installed: {}", e);
+                    PrometheusBuilder::new().build_recorder().handle()
+                }
+            }
+        })
+        .clone()
 }
 
 /// Metrics handler - returns Prometheus format metrics
 pub async fn metrics_handler(
-    State(handle): State<Arc<PrometheusHandle>>,
+    State(state): State<Arc<GatewayState>>,
 ) -> impl IntoResponse {
-    handle.render()
+    let stats = state.pool.stats().await;
+    record_pool_stats(stats.active, stats.idle, stats.max_size);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        init_metrics().render(),
+    )
+}
+
+/// Connection pool statistics
+#[derive(Debug, Serialize)]
+pub struct PoolStatsResponse {
+    pub active: usize,
+    pub idle: usize,
+    pub pending: usize,
+    pub max_size: usize,
+    pub total_created: usize,
+}
+
+/// Pool stats handler - returns pool statistics as JSON and refreshes the pool gauges
+pub async fn pool_stats_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Json<PoolStatsResponse> {
+    let stats = state.pool.stats().await;
+    record_pool_stats(stats.active, stats.idle, stats.max_size);
+    Json(PoolStatsResponse {
+        active: stats.active,
+        idle: stats.idle,
+        pending: stats.pending,
+        max_size: stats.max_size,
+        total_created: stats.total_created,
+    })
 }
 
 // Metric recording helpers
 
+/// `table` label for a query on `table`.
+///
+/// The name comes from client input, so only tables declared in the schema
+/// get their own label; anything else is [`OTHER_TABLE_LABEL`]. This keeps
+/// the number of label values bounded.
+pub fn table_label<'a>(schema: &SchemaValidator, table: &'a str) -> &'a str {
+    if schema.table_exists(table) {
+        table
+    } else {
+        OTHER_TABLE_LABEL
+    }
+}
+
 /// Record a query execution
 pub fn record_query(table: &str, action: &str, duration_ms: f64, success: bool) {
     let labels = [
@@ -36,11 +111,19 @@ pub fn record_query(table: &str, action: &str, duration_ms: f64, success: bool)
     histogram!("qail_query_duration_ms", &labels).record(duration_ms);
 }
 
+/// Record a failed query by SQLSTATE (`unknown` for non-server errors)
+pub fn record_query_error(sqlstate: Option<&str>) {
+    let labels = [("sqlstate", sqlstate.unwrap_or("unknown").to_string())];
+    counter!("qail_query_errors_total", &labels).increment(1);
+}
+
 /// Record pool stats
 pub fn record_pool_stats(active: usize, idle: usize, max: usize) {
     gauge!("qail_pool_active_connections").set(active as f64);
     gauge!("qail_pool_idle_connections").set(idle as f64);
     gauge!("qail_pool_max_connections").set(max as f64);
+    let utilization = if max == 0 { 0.0 } else { active as f64 / max as f64 };
+    gauge!("qail_pool_utilization").set(utilization);
 }
 
 /// Record WebSocket connections
@@ -81,3 +164,40 @@ impl QueryTimer {
         record_query(&self.table, &self.action, duration_ms, success);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_render_contains_recorded_metrics() {
+        let handle = init_metrics();
+        record_query("users", "GET", 3.0, true);
+        record_query_error(Some("42P01"));
+        record_pool_stats(1, 2, 4);
+        
+        let text = handle.render();
+        assert!(text.contains("qail_queries_total{"), "{}", text);
+        assert!(text.contains("qail_query_duration_ms_bucket{"), "{}", text);
+        assert!(text.contains("qail_query_errors_total{sqlstate=\"42P01\"} 1"), "{}", text);
+        assert!(text.contains("qail_pool_utilization 0.25"), "{}", text);
+        
+        // Idempotent: same recorder, same data
+        assert!(init_metrics().render().contains("qail_query_errors_total"));
+    }
+    
+    #[test]
+    fn test_table_label_clamped_to_schema() {
+        use crate::schema::TableSchema;
+        
+        let mut schema = SchemaValidator::new();
+        schema.add_table(TableSchema {
+            name: "users".to_string(),
+            columns: Vec::new(),
+        });
+        
+        assert_eq!(table_label(&schema, "users"), "users");
+        assert_eq!(table_label(&schema, "users_8f3a1c"), OTHER_TABLE_LABEL);
+        assert_eq!(table_label(&SchemaValidator::new(), "users"), OTHER_TABLE_LABEL);
+    }
+}
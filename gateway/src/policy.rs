@@ -6,7 +6,9 @@
 use crate::auth::AuthContext;
 use crate::error::GatewayError;
 use qail_core::ast::{Action, Cage, CageKind, Condition, Expr, LogicalOp, Operator, Qail, Value};
+use qail_core::parser::grammar::base::split_schema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 
 /// Policy configuration loaded from YAML
@@ -50,10 +52,99 @@ impl OperationType {
     }
 }
 
+/// Gateway-wide allowlist of actions, with per-client overrides.
+///
+/// Checked before any table policy, so a read-only gateway rejects
+/// `set`/`add`/`del`/`make` outright.
+#[derive(Debug, Clone, Default)]
+pub struct ActionPolicy {
+    /// Allowed actions for all clients (None = all)
+    pub allowed: Option<Vec<Action>>,
+    /// Per-client (user id) allowlists that replace `allowed`
+    pub per_client: HashMap<String, Vec<Action>>,
+}
+
+impl ActionPolicy {
+    /// Only `get` queries are executed
+    pub fn read_only() -> Self {
+        Self {
+            allowed: Some(vec![Action::Get]),
+            per_client: HashMap::new(),
+        }
+    }
+    
+    /// Reject `action` unless the client's allowlist (or the global one)
+    /// contains it. No allowlist at all permits every action.
+    pub fn check(&self, auth: &AuthContext, action: Action) -> Result<(), GatewayError> {
+        let allowed = match self.per_client.get(&auth.user_id) {
+            Some(actions) => actions,
+            None => match &self.allowed {
+                Some(actions) => actions,
+                None => return Ok(()),
+            },
+        };
+        
+        if allowed.contains(&action) {
+            Ok(())
+        } else {
+            Err(GatewayError::Forbidden(format!(
+                "Action {:?} is not allowed for client '{}'",
+                action, auth.user_id
+            )))
+        }
+    }
+}
+
+/// Caps on query shape, checked on the client's AST before policy filters
+/// are injected. `None` disables a cap.
+#[derive(Debug, Clone, Default)]
+pub struct ComplexityLimits {
+    /// Maximum subquery / CTE nesting (a flat query is 0)
+    pub max_depth: Option<usize>,
+    /// Maximum joins across the whole query
+    pub max_joins: Option<usize>,
+    /// Maximum AST node count
+    pub max_nodes: Option<usize>,
+}
+
+impl ComplexityLimits {
+    pub fn check(&self, cmd: &Qail) -> Result<(), GatewayError> {
+        let complexity = cmd.complexity();
+        // Raw SQL is opaque to these checks and to policy filters
+        if complexity.raw > 0 {
+            return Err(GatewayError::InvalidQuery(
+                "raw SQL fragments are not accepted".to_string(),
+            ));
+        }
+        
+        let checks = [
+            ("nesting depth", complexity.depth, self.max_depth),
+            ("joins", complexity.joins, self.max_joins),
+            ("nodes", complexity.nodes, self.max_nodes),
+        ];
+        
+        for (what, value, max) in checks {
+            match max {
+                Some(max) if value > max => {
+                    return Err(GatewayError::TooComplex(format!(
+                        "{} {} exceeds the limit of {}",
+                        what, value, max
+                    )));
+                }
+                _ => {}
+            }
+        }
+        
+        Ok(())
+    }
+}
+
 /// Policy engine that evaluates access control and injects filters
 #[derive(Debug, Default)]
 pub struct PolicyEngine {
     policies: Vec<PolicyDef>,
+    action_policy: ActionPolicy,
+    complexity_limits: ComplexityLimits,
 }
 
 impl PolicyEngine {
@@ -85,31 +176,25 @@ impl PolicyEngine {
         self.policies.push(policy);
     }
     
+    pub fn set_action_policy(&mut self, action_policy: ActionPolicy) {
+        self.action_policy = action_policy;
+    }
+    
+    pub fn set_complexity_limits(&mut self, limits: ComplexityLimits) {
+        self.complexity_limits = limits;
+    }
+    
     pub fn apply_policies(&self, auth: &AuthContext, cmd: &mut Qail) -> Result<(), GatewayError> {
-        let op = OperationType::from_action(cmd.action);
+        self.check_operation(auth, &cmd.table, cmd.action)?;
+        self.complexity_limits.check(cmd)?;
         
         let mut filters_to_inject: Vec<(String, String)> = Vec::new();
         
         for policy in &self.policies {
-            if policy.table != "*" && policy.table != cmd.table {
+            if !Self::policy_matches(policy, auth, &cmd.table) {
                 continue;
             }
             
-            if let Some(ref required_role) = policy.role {
-                if &auth.role != required_role {
-                    continue;
-                }
-            }
-            
-            if let Some(operation) = op {
-                if !policy.operations.is_empty() && !policy.operations.contains(&operation) {
-                    return Err(GatewayError::AccessDenied(format!(
-                        "Operation {:?} not allowed on table '{}' by policy '{}'",
-                        operation, cmd.table, policy.name
-                    )));
-                }
-            }
-            
             if let Some(ref filter_template) = policy.filter {
                 let filter = self.expand_filter(filter_template, auth);
                 filters_to_inject.push((policy.name.clone(), filter));
@@ -124,6 +209,48 @@ impl PolicyEngine {
         Ok(())
     }
     
+    /// Whether `auth` may run `get` on `table` under the same rules as
+    /// [`apply_policies`](Self::apply_policies). Used to filter schema describes.
+    pub fn can_read(&self, auth: &AuthContext, table: &str) -> bool {
+        self.check_operation(auth, table, Action::Get).is_ok()
+    }
+    
+    /// Action allowlist plus per-table operation restrictions (no filter injection).
+    fn check_operation(&self, auth: &AuthContext, table: &str, action: Action) -> Result<(), GatewayError> {
+        self.action_policy.check(auth, action)?;
+        
+        let Some(operation) = OperationType::from_action(action) else {
+            return Ok(());
+        };
+        
+        for policy in &self.policies {
+            if Self::policy_matches(policy, auth, table)
+                && !policy.operations.is_empty()
+                && !policy.operations.contains(&operation)
+            {
+                return Err(GatewayError::AccessDenied(format!(
+                    "Operation {:?} not allowed on table '{}' by policy '{}'",
+                    operation, table, policy.name
+                )));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// A policy applies when its table (or `*`) and role (if any) match.
+    fn policy_matches(policy: &PolicyDef, auth: &AuthContext, table: &str) -> bool {
+        Self::table_matches(&policy.table, table)
+            && policy.role.as_ref().is_none_or(|role| &auth.role == role)
+    }
+
+    /// Compare table names the way PostgreSQL resolves them, so `"orders"`
+    /// and `ORDERS` hit the `orders` policy. Schema qualifiers are ignored
+    /// on both sides: a policy follows its table into every schema.
+    fn table_matches(policy_table: &str, table: &str) -> bool {
+        policy_table == "*" || normalize_table(policy_table) == normalize_table(table)
+    }
+    
     /// Expand filter template with auth context values
     fn expand_filter(&self, template: &str, auth: &AuthContext) -> String {
         let mut result = template.to_string();
@@ -206,7 +333,7 @@ impl PolicyEngine {
         }
         
         for policy in &self.policies {
-            if policy.table != "*" && policy.table != table {
+            if !Self::table_matches(&policy.table, table) {
                 continue;
             }
             
@@ -232,6 +359,16 @@ impl PolicyEngine {
     }
 }
 
+/// The unqualified table name as PostgreSQL sees it: quotes stripped,
+/// unquoted names lowercased.
+fn normalize_table(name: &str) -> String {
+    let (_, table) = split_schema(name);
+    match table.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(quoted) => quoted.to_string(),
+        None => table.to_lowercase(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +426,117 @@ mod tests {
         assert_eq!(condition.left, Expr::Named("user_id".to_string()));
         assert_eq!(condition.value, Value::String("user456".to_string()));
     }
+    
+    #[test]
+    fn test_policy_matches_quoted_and_qualified_tables() {
+        let mut engine = PolicyEngine::new();
+        engine.add_policy(PolicyDef {
+            name: "tenant_isolation".to_string(),
+            table: "orders".to_string(),
+            filter: Some("user_id = $user_id".to_string()),
+            role: None,
+            operations: vec![OperationType::Read],
+        });
+        let auth = AuthContext {
+            user_id: "user456".to_string(),
+            role: "user".to_string(),
+            tenant_id: None,
+            claims: std::collections::HashMap::new(),
+        };
+        
+        for query in [
+            "get \"orders\" fields id",
+            "get ORDERS fields id",
+            "get public.orders fields id",
+            "get \"public\".\"orders\" fields id",
+        ] {
+            let mut cmd = qail_core::parser::parse(query).unwrap();
+            engine.apply_policies(&auth, &mut cmd).unwrap();
+            assert_eq!(cmd.cages.len(), 1, "{} bypassed the policy", query);
+            assert!(engine.check_access(&auth, &cmd.table, Action::Get).is_ok());
+            assert!(engine.check_access(&auth, &cmd.table, Action::Del).is_err());
+        }
+        
+        // A quoted name keeps its case, so it is a different table
+        let mut cmd = qail_core::parser::parse("get \"Orders\" fields id").unwrap();
+        engine.apply_policies(&auth, &mut cmd).unwrap();
+        assert!(cmd.cages.is_empty());
+    }
+    
+    #[test]
+    fn test_read_only_rejects_delete() {
+        let mut engine = PolicyEngine::new();
+        engine.set_action_policy(ActionPolicy::read_only());
+        let auth = AuthContext::anonymous();
+        
+        let mut cmd = Qail::del("orders");
+        let err = engine.apply_policies(&auth, &mut cmd).unwrap_err();
+        assert!(matches!(err, GatewayError::Forbidden(_)));
+        assert_eq!(err.status_code(), 403);
+        
+        let mut cmd = Qail::get("orders");
+        assert!(engine.apply_policies(&auth, &mut cmd).is_ok());
+    }
+    
+    #[test]
+    fn test_client_override_allows_delete() {
+        let mut action_policy = ActionPolicy::read_only();
+        action_policy
+            .per_client
+            .insert("admin".to_string(), vec![Action::Get, Action::Del]);
+        let mut engine = PolicyEngine::new();
+        engine.set_action_policy(action_policy);
+        
+        let admin = AuthContext {
+            user_id: "admin".to_string(),
+            role: "admin".to_string(),
+            tenant_id: None,
+            claims: std::collections::HashMap::new(),
+        };
+        let mut cmd = Qail::del("orders");
+        assert!(engine.apply_policies(&admin, &mut cmd).is_ok());
+        
+        let mut cmd = Qail::del("orders");
+        assert!(engine.apply_policies(&AuthContext::anonymous(), &mut cmd).is_err());
+    }
+    
+    #[test]
+    fn test_complexity_limit_rejects_nested_subqueries() {
+        let mut engine = PolicyEngine::new();
+        engine.set_complexity_limits(ComplexityLimits {
+            max_depth: Some(2),
+            ..Default::default()
+        });
+        let auth = AuthContext::anonymous();
+        
+        let mut cmd = qail_core::parser::parse(
+            "get users where id = any (get orders fields user_id \
+             where product_id = any (get products fields id \
+             where vendor_id = any (get vendors fields id)))",
+        )
+        .unwrap();
+        assert_eq!(cmd.complexity().depth, 3);
+        
+        let err = engine.apply_policies(&auth, &mut cmd).unwrap_err();
+        assert!(matches!(err, GatewayError::TooComplex(_)));
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(err.code(), "TOO_COMPLEX");
+        assert!(err.to_string().contains("nesting depth 3 exceeds the limit of 2"), "{}", err);
+        
+        let mut cmd = qail_core::parser::parse(
+            "get users where id = any (get orders fields user_id where total > 10)",
+        )
+        .unwrap();
+        assert!(engine.apply_policies(&auth, &mut cmd).is_ok());
+    }
+    
+    #[test]
+    fn test_raw_fragments_rejected() {
+        let engine = PolicyEngine::new();
+        let auth = AuthContext::anonymous();
+        
+        let mut cmd = qail_core::parser::parse("get users where id = raw!(1 or true)").unwrap();
+        let err = engine.apply_policies(&auth, &mut cmd).unwrap_err();
+        assert!(matches!(err, GatewayError::InvalidQuery(_)));
+    }
 }
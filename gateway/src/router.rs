@@ -12,7 +12,11 @@ use tower_http::{
     trace::TraceLayer,
 };
 
-use crate::handler::{execute_batch, execute_query, execute_query_binary, health_check};
+use crate::handler::{
+    describe_schema, execute_batch, execute_query, execute_query_binary, execute_query_stream,
+    health_check,
+};
+use crate::metrics::{metrics_handler, pool_stats_handler};
 use crate::ws::ws_handler;
 use crate::GatewayState;
 
@@ -33,6 +37,12 @@ pub fn create_router(state: Arc<GatewayState>) -> Router {
         .route("/qail", post(execute_query))
         .route("/qail/binary", post(execute_query_binary))
         .route("/qail/batch", post(execute_batch))
+        .route("/qail/stream", post(execute_query_stream))
+        // Schema reflection
+        .route("/qail/describe", get(describe_schema))
+        // Metrics (no auth, like /health)
+        .route("/metrics", get(metrics_handler))
+        .route("/metrics/pool", get(pool_stats_handler))
         // WebSocket
         .route("/ws", get(ws_handler))
         // Middleware layers
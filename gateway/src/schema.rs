@@ -2,7 +2,9 @@
 //!
 //! Loads table schemas and validates queries against them.
 
+use crate::auth::AuthContext;
 use crate::error::GatewayError;
+use crate::policy::PolicyEngine;
 use qail_core::ast::{Action, Qail};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -75,6 +77,19 @@ impl SchemaValidator {
         })
     }
     
+    /// Tables `auth` may read, sorted by name. Tables the policy engine
+    /// would reject for a `get` are left out entirely.
+    pub fn describe(&self, policy_engine: &PolicyEngine, auth: &AuthContext) -> Vec<TableSchema> {
+        let mut tables: Vec<TableSchema> = self
+            .tables
+            .values()
+            .filter(|t| policy_engine.can_read(auth, &t.name))
+            .cloned()
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        tables
+    }
+    
     pub fn validate(&self, cmd: &Qail) -> Result<(), GatewayError> {
         if self.tables.is_empty() {
             return Ok(());
@@ -140,4 +155,44 @@ mod tests {
         let cmd = Qail::get("nonexistent");
         assert!(validator.validate(&cmd).is_err());
     }
+    
+    #[test]
+    fn test_describe_omits_unreadable_tables() {
+        use crate::policy::{OperationType, PolicyDef};
+        
+        let mut validator = SchemaValidator::new();
+        for name in ["users", "audit_log", "orders"] {
+            validator.add_table(TableSchema {
+                name: name.to_string(),
+                columns: vec![
+                    ColumnDef { name: "id".to_string(), col_type: "int".to_string(), nullable: false, primary_key: true },
+                ],
+            });
+        }
+        
+        let mut engine = PolicyEngine::new();
+        // Only writes allowed on audit_log for regular users
+        engine.add_policy(PolicyDef {
+            name: "audit_write_only".to_string(),
+            table: "audit_log".to_string(),
+            filter: None,
+            role: Some("user".to_string()),
+            operations: vec![OperationType::Create],
+        });
+        
+        let user = AuthContext {
+            user_id: "u1".to_string(),
+            role: "user".to_string(),
+            tenant_id: None,
+            claims: HashMap::new(),
+        };
+        let names: Vec<String> = validator.describe(&engine, &user).into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["orders", "users"]);
+        
+        // The policy is role-scoped, so other roles still see audit_log
+        let admin = AuthContext { role: "admin".to_string(), ..user };
+        let described = validator.describe(&engine, &admin);
+        assert_eq!(described.len(), 3);
+        assert_eq!(described[0].columns[0].col_type, "int");
+    }
 }
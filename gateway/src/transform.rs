@@ -0,0 +1,471 @@
+//! Column Transform Module
+//!
+//! Field-level protection for selected columns. A [`ColumnTransform`] is
+//! applied to the values written to a configured `(table, column)` pair
+//! (e.g. encrypt) and to the bytes read back from it (e.g. decrypt), so
+//! clients only ever see plaintext while the database only stores the
+//! transformed form.
+//!
+//! Transforms are registered by name in code and bound to columns through
+//! [`GatewayConfig::column_transforms`](crate::config::GatewayConfig):
+//!
+//! ```rust,ignore
+//! let gateway = Gateway::builder()
+//!     .database("postgres://localhost/mydb")
+//!     .transform("aes", MyAesTransform::new(key))
+//!     .column_transform("users.ssn", "aes")
+//!     .build_and_init()
+//!     .await?;
+//! ```
+//!
+//! Transformed values are written as bytea hex text (`\x...`), so the column
+//! should be `bytea`. Only literal values can be written to a transformed
+//! column. Filters are not transformed: a `where` on a transformed column
+//! compares against the stored bytes.
+
+use qail_core::ast::{CageKind, ConflictAction, Expr, OnConflict, Qail, Value};
+use qail_pg::PgConnection;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::error::GatewayError;
+
+/// Reversible transform of a column's bytes
+pub trait ColumnTransform: Send + Sync {
+    /// Plaintext → stored bytes (on insert / update)
+    fn encode(&self, plain: &[u8]) -> Result<Vec<u8>, String>;
+
+    /// Stored bytes → plaintext (on read)
+    fn decode(&self, stored: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// A transform failed for one column
+#[derive(Debug, Error)]
+#[error("Transform failed for {table}.{column}: {message}")]
+pub struct TransformError {
+    pub table: String,
+    pub column: String,
+    pub message: String,
+}
+
+/// Registered transforms and the columns they apply to
+#[derive(Clone, Default)]
+pub struct ColumnTransforms {
+    /// Transform implementations by name
+    registered: HashMap<String, Arc<dyn ColumnTransform>>,
+    /// Resolved `(table, column)` bindings
+    columns: HashMap<(String, String), Arc<dyn ColumnTransform>>,
+    /// `(table OID, attribute number)` of each bound column, see [`resolve`](Self::resolve)
+    sources: HashMap<(u32, i16), (String, String)>,
+}
+
+impl fmt::Debug for ColumnTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut registered: Vec<_> = self.registered.keys().collect();
+        registered.sort();
+        let mut columns: Vec<_> = self
+            .columns
+            .keys()
+            .map(|(table, column)| format!("{}.{}", table, column))
+            .collect();
+        columns.sort();
+        f.debug_struct("ColumnTransforms")
+            .field("registered", &registered)
+            .field("columns", &columns)
+            .finish()
+    }
+}
+
+impl ColumnTransforms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a transform under `name`
+    pub fn register(&mut self, name: impl Into<String>, transform: impl ColumnTransform + 'static) {
+        self.registered.insert(name.into(), Arc::new(transform));
+    }
+
+    /// Bind columns to registered transforms
+    ///
+    /// `bindings` maps `"table.column"` to a transform name, as in
+    /// `GatewayConfig::column_transforms`.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Config` for a malformed column or an
+    /// unregistered transform name.
+    pub fn bind(&mut self, bindings: &HashMap<String, String>) -> Result<(), GatewayError> {
+        for (column, name) in bindings {
+            let (table, col) = column
+                .split_once('.')
+                .filter(|(t, c)| !t.is_empty() && !c.is_empty())
+                .ok_or_else(|| {
+                    GatewayError::Config(format!(
+                        "Invalid transformed column '{}', expected 'table.column'",
+                        column
+                    ))
+                })?;
+            let transform = self.registered.get(name).ok_or_else(|| {
+                GatewayError::Config(format!(
+                    "Unknown transform '{}' for column '{}'",
+                    name, column
+                ))
+            })?;
+            self.columns
+                .insert((table.to_string(), col.to_string()), Arc::clone(transform));
+        }
+        Ok(())
+    }
+
+    /// Whether no column is transformed
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// The transform bound to `table.column`, if any
+    pub fn get(&self, table: &str, column: &str) -> Option<&dyn ColumnTransform> {
+        if self.columns.is_empty() {
+            return None;
+        }
+        self.columns
+            .get(&(table.to_string(), column.to_string()))
+            .map(|t| t.as_ref())
+    }
+
+    /// Look up the `(table OID, attribute number)` of every bound column
+    ///
+    /// Rows are decoded by the source column PostgreSQL reports for each
+    /// field, so aliased and joined columns are decoded too.
+    ///
+    /// # Errors
+    /// Returns `GatewayError::Config` if a bound column does not exist.
+    pub async fn resolve(&mut self, conn: &mut PgConnection) -> Result<(), GatewayError> {
+        const SQL: &str = "SELECT attrelid::int8, attnum FROM pg_attribute \
+                           WHERE attrelid = to_regclass($1) AND attname = $2 AND NOT attisdropped";
+
+        let mut sources = HashMap::with_capacity(self.columns.len());
+        for (table, column) in self.columns.keys() {
+            let params = [Some(table.as_bytes().to_vec()), Some(column.as_bytes().to_vec())];
+            let rows = conn
+                .query_cached(SQL, &params)
+                .await
+                .map_err(|e| GatewayError::Database(e.to_string()))?;
+            let source = rows.first().and_then(|row| {
+                let text = |i: usize| {
+                    row.get(i)?
+                        .as_deref()
+                        .and_then(|v| std::str::from_utf8(v).ok())
+                };
+                Some((text(0)?.parse::<u32>().ok()?, text(1)?.parse::<i16>().ok()?))
+            });
+            let source = source.ok_or_else(|| {
+                GatewayError::Config(format!(
+                    "Transformed column '{}.{}' does not exist",
+                    table, column
+                ))
+            })?;
+            sources.insert(source, (table.clone(), column.clone()));
+        }
+        self.sources = sources;
+        Ok(())
+    }
+
+    /// Encode the payload values written to transformed columns of `cmd.table`
+    ///
+    /// Literal values are encoded and replaced by bytea hex text; NULL and
+    /// DEFAULT are left untouched.
+    ///
+    /// # Errors
+    /// Any other write to a transformed column (params, expressions,
+    /// subqueries, an `add ... from (get ...)` source, or an ON CONFLICT
+    /// assignment that is not a literal) is rejected, since the value would
+    /// reach the column untransformed.
+    pub fn encode_payload(&self, cmd: &mut Qail) -> Result<(), TransformError> {
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+
+        let table = cmd.table.clone();
+        let error = |column: &str, message: &str| TransformError {
+            table: table.clone(),
+            column: column.to_string(),
+            message: message.to_string(),
+        };
+
+        if cmd.source_query.is_some() {
+            let protected = self.columns.keys().find(|(t, c)| {
+                *t == table
+                    && (cmd.columns.is_empty()
+                        || cmd.columns.iter().any(|e| matches!(e, Expr::Named(n) if n == c)))
+            });
+            if let Some((_, column)) = protected {
+                return Err(error(column, "cannot insert from a query into a transformed column"));
+            }
+        }
+
+        let columns = cmd.columns.clone();
+        for cage in cmd.cages.iter_mut().filter(|c| matches!(c.kind, CageKind::Payload)) {
+            for cond in &mut cage.conditions {
+                let Some(column) = payload_column(&cond.left, &columns) else {
+                    continue;
+                };
+                let Some(transform) = self.get(&table, column) else {
+                    continue;
+                };
+                if let Some(stored) = encode_value(transform, &cond.value)
+                    .map_err(|message| error(column, &message))?
+                {
+                    cond.value = stored;
+                }
+            }
+        }
+
+        if let Some(OnConflict { action: ConflictAction::DoUpdate { assignments }, .. }) =
+            &mut cmd.on_conflict
+        {
+            for (column, expr) in assignments {
+                let Some(transform) = self.get(&table, column) else {
+                    continue;
+                };
+                // `excluded.col` is the proposed row, already encoded above
+                let is_excluded = |n: &str| {
+                    n.split_once('.')
+                        .is_some_and(|(t, c)| t.eq_ignore_ascii_case("excluded") && c == column)
+                };
+                if matches!(expr, Expr::Named(n) if is_excluded(n)) {
+                    continue;
+                }
+                let Expr::Literal(value) = expr else {
+                    return Err(error(column, "only literal values can be written to a transformed column"));
+                };
+                if let Some(stored) = encode_value(transform, value)
+                    .map_err(|message| error(column, &message))?
+                {
+                    *value = stored;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode the raw text-format bytes of a result field
+    ///
+    /// `table_oid` and `column_attr` are the field's source column from the
+    /// row description. Returns `None` when that column is not transformed.
+    pub fn decode_field(
+        &self,
+        table_oid: u32,
+        column_attr: i16,
+        raw: &[u8],
+    ) -> Option<Result<Vec<u8>, TransformError>> {
+        let (table, column) = self.sources.get(&(table_oid, column_attr))?;
+        self.decode_column(table, column, raw)
+    }
+
+    /// Decode the raw text-format bytes read from `table.column`
+    ///
+    /// Returns `None` when the column is not transformed.
+    pub fn decode_column(
+        &self,
+        table: &str,
+        column: &str,
+        raw: &[u8],
+    ) -> Option<Result<Vec<u8>, TransformError>> {
+        let transform = self.get(table, column)?;
+        let error = |message: String| TransformError {
+            table: table.to_string(),
+            column: column.to_string(),
+            message,
+        };
+        let stored = match raw.strip_prefix(b"\\x") {
+            Some(hex) => match from_hex(hex) {
+                Some(bytes) => bytes,
+                None => return Some(Err(error("invalid bytea hex".to_string()))),
+            },
+            None => raw.to_vec(),
+        };
+        Some(transform.decode(&stored).map_err(error))
+    }
+}
+
+/// Encode a literal written to a transformed column
+///
+/// Returns `Ok(None)` for NULL and DEFAULT, which are stored as-is.
+fn encode_value(transform: &dyn ColumnTransform, value: &Value) -> Result<Option<Value>, String> {
+    let plain = match value {
+        Value::Null | Value::NullUuid | Value::Default => return Ok(None),
+        Value::String(s) | Value::Decimal(s) | Value::Timestamp(s) | Value::Json(s) => {
+            s.as_bytes().to_vec()
+        }
+        Value::Bytes(b) => b.clone(),
+        Value::Bool(_) | Value::Int(_) | Value::Float(_) | Value::Uuid(_) => value.to_string().into_bytes(),
+        _ => return Err("only literal values can be written to a transformed column".to_string()),
+    };
+    let stored = transform.encode(&plain)?;
+    Ok(Some(Value::String(to_bytea_hex(&stored))))
+}
+
+/// Column a payload condition writes to
+///
+/// `add ... fields a, b values ...` stores positional `$n` placeholders on
+/// the left and the names in `cmd.columns`; `set`/`set_value` name the column
+/// directly.
+fn payload_column<'a>(left: &'a Expr, columns: &'a [Expr]) -> Option<&'a str> {
+    let Expr::Named(name) = left else {
+        return None;
+    };
+    match name.strip_prefix('$').and_then(|n| n.parse::<usize>().ok()) {
+        Some(position) => match columns.get(position.checked_sub(1)?) {
+            Some(Expr::Named(column)) => Some(column),
+            _ => None,
+        },
+        None => Some(name),
+    }
+}
+
+/// Format bytes as PostgreSQL bytea hex input (`\x0a1b...`)
+fn to_bytea_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("\\x");
+    for b in bytes {
+        out.push_str(&format!("{:02x}", b));
+    }
+    out
+}
+
+fn from_hex(hex: &[u8]) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    hex.chunks(2)
+        .map(|pair| {
+            let s = std::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(s, 16).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// XOR with a fixed key: its own inverse
+    pub(crate) struct Xor(pub u8);
+
+    impl ColumnTransform for Xor {
+        fn encode(&self, plain: &[u8]) -> Result<Vec<u8>, String> {
+            Ok(plain.iter().map(|b| b ^ self.0).collect())
+        }
+
+        fn decode(&self, stored: &[u8]) -> Result<Vec<u8>, String> {
+            self.encode(stored)
+        }
+    }
+
+    fn transforms() -> ColumnTransforms {
+        let mut transforms = ColumnTransforms::new();
+        transforms.register("xor", Xor(0x5a));
+        let bindings = HashMap::from([("users.ssn".to_string(), "xor".to_string())]);
+        transforms.bind(&bindings).unwrap();
+        transforms
+    }
+
+    #[test]
+    fn test_encode_insert_and_update_payloads() {
+        let transforms = transforms();
+
+        let mut add = qail_core::parser::parse("add users fields name, ssn values 'bob', 'AB'").unwrap();
+        transforms.encode_payload(&mut add).unwrap();
+        let values: Vec<_> = add.cages[0].conditions.iter().map(|c| c.value.clone()).collect();
+        // 'A' ^ 0x5a = 0x1b, 'B' ^ 0x5a = 0x18
+        assert_eq!(values, vec![Value::String("bob".into()), Value::String("\\x1b18".into())]);
+
+        let mut set = Qail::set("users").set_value("ssn", "AB").set_value("name", Value::Null);
+        transforms.encode_payload(&mut set).unwrap();
+        assert_eq!(set.cages[0].conditions[0].value, Value::String("\\x1b18".into()));
+        assert_eq!(set.cages[0].conditions[1].value, Value::Null);
+
+        // Other tables are untouched
+        let mut other = Qail::set("accounts").set_value("ssn", "AB");
+        transforms.encode_payload(&mut other).unwrap();
+        assert_eq!(other.cages[0].conditions[0].value, Value::String("AB".into()));
+    }
+
+    #[test]
+    fn test_encode_rejects_non_literal_writes() {
+        let transforms = transforms();
+        let rejected = |mut cmd: Qail| {
+            let err = transforms.encode_payload(&mut cmd).unwrap_err();
+            assert_eq!((err.table.as_str(), err.column.as_str()), ("users", "ssn"));
+        };
+
+        rejected(Qail::set("users").set_value("ssn", Value::Param(1)));
+        rejected(Qail::set("users").set_value("ssn", Value::NamedParam("ssn".into())));
+        rejected(Qail::set("users").set_value("ssn", Value::Column("name".into())));
+        rejected(Qail::set("users").set_value("ssn", Value::Function("md5(name)".into())));
+        rejected(qail_core::parser::parse("add users fields name, ssn values 'bob', $1").unwrap());
+
+        let mut from_query = Qail::add("users").columns(["name", "ssn"]);
+        from_query.source_query = Some(Box::new(Qail::get("staging").columns(["name", "ssn"])));
+        rejected(from_query);
+
+        rejected(
+            Qail::add("users")
+                .set_value("ssn", "AB")
+                .on_conflict_update(&["id"], &[("ssn", Expr::Named("name".into()))]),
+        );
+
+        // Literal and EXCLUDED upsert assignments are fine
+        let mut upsert = Qail::add("users").set_value("ssn", "AB").on_conflict_update(
+            &["id"],
+            &[
+                ("ssn", Expr::Named("EXCLUDED.ssn".into())),
+                ("name", Expr::Named("EXCLUDED.name".into())),
+            ],
+        );
+        transforms.encode_payload(&mut upsert).unwrap();
+        let mut upsert = Qail::add("users")
+            .set_value("ssn", "AB")
+            .on_conflict_update(&["id"], &[("ssn", Expr::Literal(Value::String("AB".into())))]);
+        transforms.encode_payload(&mut upsert).unwrap();
+        let Some(OnConflict { action: ConflictAction::DoUpdate { assignments }, .. }) = upsert.on_conflict else {
+            panic!("expected DO UPDATE");
+        };
+        assert_eq!(assignments[0].1, Expr::Literal(Value::String("\\x1b18".into())));
+    }
+
+    #[test]
+    fn test_decode_field_by_source_column() {
+        let mut transforms = transforms();
+        transforms.sources.insert((16384, 3), ("users".to_string(), "ssn".to_string()));
+
+        assert_eq!(transforms.decode_field(16384, 3, b"\\x1b18").unwrap().unwrap(), b"AB");
+        assert!(transforms.decode_field(16384, 2, b"bob").is_none());
+        // Computed fields have no source column
+        assert!(transforms.decode_field(0, 0, b"\\x1b18").is_none());
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let transforms = transforms();
+
+        let decoded = transforms.decode_column("users", "ssn", b"\\x1b18").unwrap().unwrap();
+        assert_eq!(decoded, b"AB");
+        assert!(transforms.decode_column("users", "name", b"bob").is_none());
+        assert!(transforms.decode_column("users", "ssn", b"\\x1").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_bind_rejects_unknown_transform() {
+        let mut transforms = ColumnTransforms::new();
+        transforms.register("xor", Xor(1));
+
+        let unknown = HashMap::from([("users.ssn".to_string(), "aes".to_string())]);
+        assert!(matches!(transforms.bind(&unknown), Err(GatewayError::Config(_))));
+
+        let malformed = HashMap::from([("ssn".to_string(), "xor".to_string())]);
+        assert!(matches!(transforms.bind(&malformed), Err(GatewayError::Config(_))));
+    }
+}
@@ -15,7 +15,12 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use crate::auth::extract_auth_from_headers;
+use crate::auth::{extract_auth_from_headers, AuthContext};
+use crate::handler::{run_idempotent, ErrorResponse, QueryResponse};
+use crate::idempotency::caller_scope;
+use crate::metrics::{record_query_error, record_ws_connection, table_label, QueryTimer};
+use crate::policy::PolicyEngine;
+use crate::transform::ColumnTransforms;
 use crate::GatewayState;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,8 +32,14 @@ pub enum WsClientMessage {
     #[serde(rename = "unsubscribe")]
     Unsubscribe { channel: String },
     
+    /// `idempotency_key` makes a mutation run at most once, like the
+    /// `Idempotency-Key` header on `/qail`
     #[serde(rename = "query")]
-    Query { qail: String },
+    Query {
+        qail: String,
+        #[serde(default)]
+        idempotency_key: Option<String>,
+    },
     
     #[serde(rename = "ping")]
     Ping,
@@ -68,12 +79,20 @@ pub async fn ws_handler(
     headers: HeaderMap,
 ) -> impl IntoResponse {
     let auth = extract_auth_from_headers(&headers);
+    let scope = caller_scope(&auth, &headers);
     tracing::info!("WebSocket connection from user: {}", auth.user_id);
     
-    ws.on_upgrade(move |socket| handle_socket(socket, state, auth.user_id))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, auth, scope))
 }
 
-async fn handle_socket(socket: WebSocket, state: Arc<GatewayState>, user_id: String) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: Arc<GatewayState>,
+    auth: AuthContext,
+    scope: Option<String>,
+) {
+    let user_id = auth.user_id.clone();
+    record_ws_connection(true);
     let (mut sender, mut receiver) = socket.split();
     
     let (tx, mut rx) = mpsc::channel::<WsServerMessage>(32);
@@ -106,7 +125,8 @@ async fn handle_socket(socket: WebSocket, state: Arc<GatewayState>, user_id: Str
                             client_msg, 
                             &state, 
                             &tx, 
-                            &user_id,
+                            &auth,
+                            scope.as_deref(),
                             &mut subscribed_channels,
                         ).await;
                     }
@@ -136,6 +156,7 @@ async fn handle_socket(socket: WebSocket, state: Arc<GatewayState>, user_id: Str
     }
     
     send_task.abort();
+    record_ws_connection(false);
     tracing::info!("WebSocket disconnected: {}", user_id);
 }
 
@@ -144,9 +165,11 @@ async fn handle_client_message(
     msg: WsClientMessage,
     state: &Arc<GatewayState>,
     tx: &mpsc::Sender<WsServerMessage>,
-    user_id: &str,
+    auth: &AuthContext,
+    scope: Option<&str>,
     subscribed_channels: &mut Vec<String>,
 ) {
+    let user_id = auth.user_id.as_str();
     match msg {
         WsClientMessage::Subscribe { channel } => {
             tracing::debug!("User {} subscribing to channel: {}", user_id, channel);
@@ -187,35 +210,38 @@ async fn handle_client_message(
             }
         }
         
-        WsClientMessage::Query { qail } => {
+        WsClientMessage::Query { qail, idempotency_key } => {
             tracing::debug!("User {} executing query: {}", user_id, qail);
             
-            match qail_core::parser::parse(&qail) {
-                Ok(cmd) => {
-                    if let Ok(mut conn) = state.pool.acquire().await {
-                        match conn.fetch_all_uncached(&cmd).await {
-                            Ok(rows) => {
-                                let json_rows: Vec<serde_json::Value> = rows
-                                    .iter()
-                                    .map(crate::handler::row_to_json)
-                                    .collect();
-                                let count = json_rows.len();
-                                let _ = tx.send(WsServerMessage::Result { rows: json_rows, count }).await;
-                            }
-                            Err(e) => {
-                                let _ = tx.send(WsServerMessage::Error {
-                                    message: format!("Query failed: {}", e),
-                                }).await;
-                            }
-                        }
-                    }
+            let cmd = match prepare_query(&qail, &state.policy_engine, &state.transforms, auth) {
+                Ok(cmd) => cmd,
+                Err(message) => {
+                    let _ = tx.send(WsServerMessage::Error { message }).await;
+                    return;
                 }
-                Err(e) => {
-                    let _ = tx.send(WsServerMessage::Error {
-                        message: format!("Parse error: {}", e),
-                    }).await;
+            };
+            
+            let key = idempotency_key
+                .as_deref()
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .filter(|_| {
+                    !matches!(cmd.action, qail_core::ast::Action::Get) && state.idempotency.is_enabled()
+                });
+            let result = match key {
+                Some(key) => {
+                    run_idempotent(state, scope, key, format!("{:?}", cmd), || run_query(state, &cmd)).await
                 }
-            }
+                None => run_query(state, &cmd).await,
+            };
+            let msg = match result {
+                Ok(response) => WsServerMessage::Result {
+                    rows: response.rows,
+                    count: response.count,
+                },
+                Err((_, axum::Json(e))) => WsServerMessage::Error { message: e.error },
+            };
+            let _ = tx.send(msg).await;
         }
         
         WsClientMessage::Ping => {
@@ -223,3 +249,135 @@ async fn handle_client_message(
         }
     }
 }
+
+/// Execute a prepared WebSocket query on a pooled connection
+async fn run_query(
+    state: &GatewayState,
+    cmd: &qail_core::ast::Qail,
+) -> Result<QueryResponse, (axum::http::StatusCode, axum::Json<ErrorResponse>)> {
+    let error = |message: String| {
+        (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            axum::Json(ErrorResponse {
+                error: message,
+                code: "QUERY_ERROR".to_string(),
+            }),
+        )
+    };
+    
+    let mut conn = state
+        .pool
+        .acquire()
+        .await
+        .map_err(|e| error(format!("Query failed: {}", e)))?;
+    let timer = QueryTimer::new(
+        table_label(&state.schema_validator, &cmd.table),
+        &cmd.action.to_string(),
+    );
+    let rows = conn.fetch_all_uncached(cmd).await;
+    timer.finish(rows.is_ok());
+    let rows = rows.map_err(|e| {
+        record_query_error(e.code());
+        error(format!("Query failed: {}", e))
+    })?;
+    let rows = rows
+        .iter()
+        .map(|row| crate::handler::transformed_row_to_json(row, &state.transforms))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            error(e.to_string())
+        })?;
+    Ok(QueryResponse {
+        count: rows.len(),
+        rows,
+    })
+}
+
+/// Parse a WebSocket query, apply row-level security policies and encode
+/// transformed column values, the same way the HTTP text endpoint does.
+fn prepare_query(
+    qail: &str,
+    policy_engine: &PolicyEngine,
+    transforms: &ColumnTransforms,
+    auth: &AuthContext,
+) -> Result<qail_core::ast::Qail, String> {
+    let mut cmd = qail_core::parser::parse(qail).map_err(|e| format!("Parse error: {}", e))?;
+    policy_engine.apply_policies(auth, &mut cmd).map_err(|e| {
+        tracing::warn!("Policy error: {}", e);
+        e.to_string()
+    })?;
+    transforms.encode_payload(&mut cmd).map_err(|e| {
+        tracing::error!("{}", e);
+        e.to_string()
+    })?;
+    Ok(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{ActionPolicy, OperationType, PolicyDef};
+    use qail_core::ast::Value;
+    
+    #[test]
+    fn test_query_message_idempotency_key() {
+        let msg: WsClientMessage =
+            serde_json::from_str(r#"{"type":"query","qail":"del orders","idempotency_key":"k1"}"#).unwrap();
+        assert!(matches!(
+            msg,
+            WsClientMessage::Query { idempotency_key: Some(ref k), .. } if k == "k1"
+        ));
+        
+        // The key is optional
+        let msg: WsClientMessage = serde_json::from_str(r#"{"type":"query","qail":"get orders"}"#).unwrap();
+        assert!(matches!(msg, WsClientMessage::Query { idempotency_key: None, .. }));
+    }
+    
+    #[test]
+    fn test_query_applies_policies() {
+        let mut engine = PolicyEngine::new();
+        engine.add_policy(PolicyDef {
+            name: "own_orders".to_string(),
+            table: "orders".to_string(),
+            filter: Some("user_id = $user_id".to_string()),
+            role: None,
+            operations: vec![OperationType::Read],
+        });
+        let auth = AuthContext {
+            user_id: "user456".to_string(),
+            ..AuthContext::anonymous()
+        };
+        
+        let cmd = prepare_query("get orders fields id", &engine, &ColumnTransforms::new(), &auth).unwrap();
+        assert_eq!(cmd.cages.len(), 1);
+        assert_eq!(cmd.cages[0].conditions[0].value, Value::String("user456".to_string()));
+    }
+    
+    #[test]
+    fn test_query_rejected_by_policy() {
+        let mut engine = PolicyEngine::new();
+        engine.set_action_policy(ActionPolicy::read_only());
+        
+        let err = prepare_query("del orders", &engine, &ColumnTransforms::new(), &AuthContext::anonymous()).unwrap_err();
+        assert!(err.starts_with("Forbidden"), "{}", err);
+        assert!(prepare_query("get orders", &engine, &ColumnTransforms::new(), &AuthContext::anonymous()).is_ok());
+    }
+    
+    #[test]
+    fn test_query_encodes_transformed_columns() {
+        let mut transforms = ColumnTransforms::new();
+        transforms.register("xor", crate::transform::tests::Xor(0x5a));
+        let bindings = std::collections::HashMap::from([("users.ssn".to_string(), "xor".to_string())]);
+        transforms.bind(&bindings).unwrap();
+        
+        let cmd = prepare_query(
+            "add users fields ssn values 'AB'",
+            &PolicyEngine::new(),
+            &transforms,
+            &AuthContext::anonymous(),
+        )
+        .unwrap();
+        assert_eq!(cmd.cages[0].conditions[0].value, Value::String("\\x1b18".to_string()));
+    }
+}
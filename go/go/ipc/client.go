@@ -34,10 +34,23 @@ type Request struct {
 type Query struct {
 	Table   string   `json:"table"`
 	Columns []string `json:"columns"`
-	Filter  string   `json:"filter,omitempty"`
+	Filter  *Filter  `json:"filter,omitempty"`
 	Limit   int64    `json:"limit,omitempty"`
 }
 
+// Filter is a structured WHERE clause: conditions joined by Logic ("And" or "Or")
+type Filter struct {
+	Logic      string      `json:"logic,omitempty"`
+	Conditions []Condition `json:"conditions"`
+}
+
+// Condition compares a column using a qail operator name ("Eq", "Gt", "In", "IsNull", ...)
+type Condition struct {
+	Column string `json:"column"`
+	Op     string `json:"op"`
+	Value  any    `json:"value,omitempty"`
+}
+
 // Response types
 type Response struct {
 	Type     string        `json:"type"`
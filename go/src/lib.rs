@@ -141,6 +141,7 @@ pub extern "C" fn qail_offset(handle: *mut QailHandle, offset: i64) {
 /// Encode command to PostgreSQL wire protocol bytes
 /// Returns pointer to bytes, sets out_len to length
 /// Caller must free with qail_bytes_free
+/// Returns NULL (out_len 0) if the command cannot be encoded
 #[unsafe(no_mangle)]
 pub extern "C" fn qail_encode(handle: *const QailHandle, out_len: *mut usize) -> *mut u8 {
     if handle.is_null() {
@@ -151,7 +152,12 @@ pub extern "C" fn qail_encode(handle: *const QailHandle, out_len: *mut usize) ->
     }
 
     let cmd = unsafe { &(*handle).cmd };
-    let (wire_bytes, _params) = AstEncoder::encode_cmd(cmd);
+    let Ok((wire_bytes, _params)) = AstEncoder::encode_cmd(cmd) else {
+        unsafe {
+            *out_len = 0;
+        }
+        return std::ptr::null_mut();
+    };
     let bytes = wire_bytes.to_vec();
 
     let len = bytes.len();
@@ -189,7 +195,12 @@ pub extern "C" fn qail_batch_encode(
     }
 
     // Encode batch
-    let wire_bytes = AstEncoder::encode_batch(&cmds);
+    let Ok(wire_bytes) = AstEncoder::encode_batch(&cmds) else {
+        unsafe {
+            *out_len = 0;
+        }
+        return std::ptr::null_mut();
+    };
     let bytes = wire_bytes.to_vec();
 
     let len = bytes.len();
@@ -275,7 +286,12 @@ pub extern "C" fn qail_encode_select_fast(
     }
 
     // Encode directly
-    let (wire_bytes, _params) = AstEncoder::encode_cmd(&cmd);
+    let Ok((wire_bytes, _params)) = AstEncoder::encode_cmd(&cmd) else {
+        unsafe {
+            *out_len = 0;
+        }
+        return std::ptr::null_mut();
+    };
     let bytes = wire_bytes.to_vec();
 
     let len = bytes.len();
@@ -324,7 +340,12 @@ pub extern "C" fn qail_encode_select_batch_fast(
     }
 
     // Encode batch
-    let wire_bytes = AstEncoder::encode_batch(&cmds);
+    let Ok(wire_bytes) = AstEncoder::encode_batch(&cmds) else {
+        unsafe {
+            *out_len = 0;
+        }
+        return std::ptr::null_mut();
+    };
     let bytes = wire_bytes.to_vec();
 
     let len = bytes.len();
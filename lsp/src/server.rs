@@ -102,10 +102,26 @@ impl QailLanguageServer {
                     if let Some(query_end) = query_line.rfind("\"")
                         && let Err(e) = parse(&query_line[..query_end])
                     {
+                        // Underline the offending token when the parser located it
+                        let (start, end) = match e.span() {
+                            Some(span) => {
+                                let start = query_start + span.start;
+                                // Widen empty spans to the next whole character
+                                let end = (query_start + span.end).max(
+                                    start
+                                        + line
+                                            .get(start..)
+                                            .and_then(|rest| rest.chars().next())
+                                            .map_or(0, char::len_utf8),
+                                );
+                                (utf16_col(line, start), utf16_col(line, end))
+                            }
+                            None => (utf16_col(line, query_start), utf16_col(line, line.len())),
+                        };
                         diagnostics.push(Diagnostic {
                             range: Range {
-                                start: Position { line: line_num as u32, character: col as u32 },
-                                end: Position { line: line_num as u32, character: (col + query_line.len()) as u32 },
+                                start: Position { line: line_num as u32, character: start as u32 },
+                                end: Position { line: line_num as u32, character: end as u32 },
                             },
                             severity: Some(DiagnosticSeverity::ERROR),
                             source: Some("qail".to_string()),
@@ -120,6 +136,12 @@ impl QailLanguageServer {
     }
 }
 
+/// LSP column (UTF-16 code units) of a byte offset in `line`.
+/// Offsets inside a multi-byte character resolve to the end of the line.
+fn utf16_col(line: &str, byte: usize) -> usize {
+    line.get(..byte).unwrap_or(line).encode_utf16().count()
+}
+
 #[tower_lsp::async_trait]
 impl LanguageServer for QailLanguageServer {
     async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
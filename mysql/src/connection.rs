@@ -247,10 +247,33 @@ impl MySqlConnection {
     }
 
     /// Execute a SELECT query and stream rows to callback.
-    pub async fn query_stream<F>(&mut self, sql: &str, mut callback: F) -> MySqlResult<u64>
+    pub async fn query_stream<F>(&mut self, sql: &str, callback: F) -> MySqlResult<u64>
     where
         F: FnMut(&[Vec<u8>]),
     {
+        let columns = self.send_query(sql).await?;
+        if columns.is_empty() {
+            return Ok(0);
+        }
+        self.read_rows(columns.len(), callback).await
+    }
+
+    /// Column definitions of a query's result, without reading any rows.
+    /// Feed them to [`crate::types::create_table_sql`] to create the
+    /// PostgreSQL target of a migration. The query runs as a `LIMIT 0`
+    /// derived table, so MySQL may not report primary key flags.
+    pub async fn describe(&mut self, sql: &str) -> MySqlResult<Vec<ColumnDef>> {
+        let probe = format!("SELECT * FROM ({}) AS qail_describe LIMIT 0", sql);
+        let columns = self.send_query(&probe).await?;
+        if !columns.is_empty() {
+            self.read_rows(columns.len(), |_| {}).await?;
+        }
+        Ok(columns)
+    }
+
+    /// Send COM_QUERY and read the result set header: the column count,
+    /// the column definitions and the EOF packet that ends them.
+    async fn send_query(&mut self, sql: &str) -> MySqlResult<Vec<ColumnDef>> {
         // Send COM_QUERY
         self.sequence_id = 0;
         let query = encode_query(sql);
@@ -267,7 +290,7 @@ impl MySqlConnection {
         let column_count = crate::protocol::read_len_enc_int(&mut buf) as usize;
 
         if column_count == 0 {
-            return Ok(0);
+            return Ok(Vec::new());
         }
 
         // Read column definitions
@@ -282,7 +305,14 @@ impl MySqlConnection {
         // Read EOF packet
         let _eof = self.read_packet().await?;
 
-        // Read rows
+        Ok(columns)
+    }
+
+    /// Read text-protocol rows up to the EOF packet that ends the result set.
+    async fn read_rows<F>(&mut self, column_count: usize, mut callback: F) -> MySqlResult<u64>
+    where
+        F: FnMut(&[Vec<u8>]),
+    {
         let mut row_count = 0u64;
         let mut row_values: Vec<Vec<u8>> = vec![Vec::new(); column_count];
 
@@ -8,6 +8,7 @@
 pub mod auth;
 pub mod connection;
 pub mod protocol;
+pub mod types;
 
 pub use connection::MySqlConnection;
 
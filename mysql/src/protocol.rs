@@ -248,6 +248,14 @@ pub fn encode_query(sql: &str) -> BytesMut {
 pub struct ColumnDef {
     pub name: String,
     pub column_type: u8,
+    /// Character set (collation id); 63 is `binary`
+    pub charset: u16,
+    /// Maximum display length in bytes
+    pub column_length: u32,
+    /// `NOT_NULL`, `UNSIGNED`, `PRI_KEY`, ... (see [`crate::types::flags`])
+    pub flags: u16,
+    /// Digits after the decimal point for DECIMAL and fractional seconds
+    pub decimals: u8,
 }
 
 impl ColumnDef {
@@ -273,14 +281,27 @@ impl ColumnDef {
         let _fixed_len = buf[0]; // 0x0c
         buf = &buf[1..];
 
-        let _charset = u16::from_le_bytes([buf[0], buf[1]]);
+        let charset = u16::from_le_bytes([buf[0], buf[1]]);
         buf = &buf[2..];
 
-        let _column_length = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+        let column_length = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
         buf = &buf[4..];
 
         let column_type = buf[0];
+        buf = &buf[1..];
+
+        let flags = u16::from_le_bytes([buf[0], buf[1]]);
+        buf = &buf[2..];
 
-        Some(Self { name, column_type })
+        let decimals = buf[0];
+
+        Some(Self {
+            name,
+            column_type,
+            charset,
+            column_length,
+            flags,
+            decimals,
+        })
     }
 }
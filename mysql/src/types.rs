@@ -0,0 +1,235 @@
+//! MySQL → PostgreSQL type mapping.
+//!
+//! Suggests a PostgreSQL column type for each column of a MySQL result set,
+//! using only the result metadata (type code, length, charset, flags), and
+//! builds the matching `CREATE TABLE` so data copied with
+//! [`query_to_tsv`](crate::MySqlConnection::query_to_tsv) has a target:
+//!
+//! ```ignore
+//! let columns = mysql.describe("SELECT * FROM users").await?;
+//! pg.execute_raw(&create_table_sql("users", &columns)).await?;
+//! ```
+
+use crate::protocol::ColumnDef;
+
+/// Column type codes (`MYSQL_TYPE_*`).
+pub mod column_type {
+    pub const DECIMAL: u8 = 0x00;
+    pub const TINY: u8 = 0x01;
+    pub const SHORT: u8 = 0x02;
+    pub const LONG: u8 = 0x03;
+    pub const FLOAT: u8 = 0x04;
+    pub const DOUBLE: u8 = 0x05;
+    pub const NULL: u8 = 0x06;
+    pub const TIMESTAMP: u8 = 0x07;
+    pub const LONGLONG: u8 = 0x08;
+    pub const INT24: u8 = 0x09;
+    pub const DATE: u8 = 0x0a;
+    pub const TIME: u8 = 0x0b;
+    pub const DATETIME: u8 = 0x0c;
+    pub const YEAR: u8 = 0x0d;
+    pub const NEWDATE: u8 = 0x0e;
+    pub const VARCHAR: u8 = 0x0f;
+    pub const BIT: u8 = 0x10;
+    pub const JSON: u8 = 0xf5;
+    pub const NEWDECIMAL: u8 = 0xf6;
+    pub const ENUM: u8 = 0xf7;
+    pub const SET: u8 = 0xf8;
+    pub const TINY_BLOB: u8 = 0xf9;
+    pub const MEDIUM_BLOB: u8 = 0xfa;
+    pub const LONG_BLOB: u8 = 0xfb;
+    pub const BLOB: u8 = 0xfc;
+    pub const VAR_STRING: u8 = 0xfd;
+    pub const STRING: u8 = 0xfe;
+    pub const GEOMETRY: u8 = 0xff;
+}
+
+/// Column definition flags.
+pub mod flags {
+    pub const NOT_NULL: u16 = 0x0001;
+    pub const PRI_KEY: u16 = 0x0002;
+    pub const UNIQUE_KEY: u16 = 0x0004;
+    pub const BLOB: u16 = 0x0010;
+    pub const UNSIGNED: u16 = 0x0020;
+    pub const BINARY: u16 = 0x0080;
+    pub const ENUM: u16 = 0x0100;
+    pub const AUTO_INCREMENT: u16 = 0x0200;
+    pub const SET: u16 = 0x0800;
+}
+
+/// The `binary` character set: byte strings rather than text.
+pub const BINARY_CHARSET: u16 = 63;
+
+/// Suggest the PostgreSQL type for a MySQL result column,
+/// e.g. `TINYINT(1)` → `boolean`, `DATETIME` → `timestamp`, `LONGTEXT` → `text`.
+///
+/// Unsigned integers widen to the next type that holds their range.
+/// Unknown types map to `text`.
+pub fn pg_type(col: &ColumnDef) -> String {
+    use column_type::*;
+
+    let unsigned = col.flags & flags::UNSIGNED != 0;
+    let binary = col.charset == BINARY_CHARSET;
+
+    match col.column_type {
+        TINY if col.column_length == 1 && !unsigned => "boolean".to_string(),
+        TINY => "smallint".to_string(),
+        SHORT if unsigned => "integer".to_string(),
+        SHORT | YEAR => "smallint".to_string(),
+        INT24 => "integer".to_string(),
+        LONG if unsigned => "bigint".to_string(),
+        LONG => "integer".to_string(),
+        LONGLONG if unsigned => "numeric(20)".to_string(),
+        LONGLONG => "bigint".to_string(),
+        FLOAT => "real".to_string(),
+        DOUBLE => "double precision".to_string(),
+        DECIMAL | NEWDECIMAL => {
+            // The display length counts the sign and the decimal point
+            let precision = col
+                .column_length
+                .saturating_sub(u32::from(col.decimals > 0) + u32::from(!unsigned));
+            format!("numeric({}, {})", precision, col.decimals)
+        }
+        // MySQL stores TIMESTAMP in UTC and converts to the session time zone
+        TIMESTAMP => "timestamptz".to_string(),
+        DATETIME => "timestamp".to_string(),
+        DATE | NEWDATE => "date".to_string(),
+        TIME => "time".to_string(),
+        BIT if col.column_length == 1 => "boolean".to_string(),
+        BIT => format!("bit({})", col.column_length),
+        JSON => "jsonb".to_string(),
+        ENUM | SET => "text".to_string(),
+        STRING if col.flags & (flags::ENUM | flags::SET) != 0 => "text".to_string(),
+        STRING | VARCHAR | VAR_STRING | TINY_BLOB | MEDIUM_BLOB | LONG_BLOB | BLOB
+            if binary =>
+        {
+            "bytea".to_string()
+        }
+        STRING => format!("char({})", char_length(col)),
+        VARCHAR | VAR_STRING => format!("varchar({})", char_length(col)),
+        TINY_BLOB | MEDIUM_BLOB | LONG_BLOB | BLOB => "text".to_string(),
+        GEOMETRY => "bytea".to_string(),
+        _ => "text".to_string(),
+    }
+}
+
+/// Build a PostgreSQL `CREATE TABLE` for the columns of a MySQL result.
+///
+/// `NOT NULL` and primary key flags are carried over; they are only present
+/// when the columns come straight from a table (not expressions).
+pub fn create_table_sql(table: &str, columns: &[ColumnDef]) -> String {
+    let mut defs: Vec<String> = columns
+        .iter()
+        .map(|col| {
+            let mut def = format!("{} {}", quote_ident(&col.name), pg_type(col));
+            if col.flags & flags::NOT_NULL != 0 {
+                def.push_str(" NOT NULL");
+            }
+            def
+        })
+        .collect();
+
+    let primary_key: Vec<String> = columns
+        .iter()
+        .filter(|col| col.flags & flags::PRI_KEY != 0)
+        .map(|col| quote_ident(&col.name))
+        .collect();
+    if !primary_key.is_empty() {
+        defs.push(format!("PRIMARY KEY ({})", primary_key.join(", ")));
+    }
+
+    format!("CREATE TABLE {} ({})", quote_ident(table), defs.join(", "))
+}
+
+/// Characters in a string column, from its byte length and character set.
+fn char_length(col: &ColumnDef) -> u32 {
+    let max_bytes = match col.charset {
+        // utf8mb4 collations
+        45 | 46 | 224..=247 | 255..=323 => 4,
+        // utf8mb3 collations
+        33 | 76 | 83 | 192..=215 => 3,
+        _ => 1,
+    };
+    (col.column_length / max_bytes).max(1)
+}
+
+/// Quote an identifier unless it is a plain lowercase name.
+fn quote_ident(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UTF8MB4: u16 = 255;
+
+    fn col(name: &str, column_type: u8, column_length: u32, flags: u16) -> ColumnDef {
+        ColumnDef {
+            name: name.to_string(),
+            column_type,
+            charset: UTF8MB4,
+            column_length,
+            flags,
+            decimals: 0,
+        }
+    }
+
+    #[test]
+    fn test_pg_type_mapping() {
+        use column_type::*;
+
+        let binary = |mut c: ColumnDef| {
+            c.charset = BINARY_CHARSET;
+            c
+        };
+        let mut price = col("price", NEWDECIMAL, 12, 0);
+        price.decimals = 2;
+
+        let cases = [
+            (col("active", TINY, 1, 0), "boolean"),
+            (col("level", TINY, 4, 0), "smallint"),
+            (col("id", LONG, 10, flags::UNSIGNED), "bigint"),
+            (col("big", LONGLONG, 20, flags::UNSIGNED), "numeric(20)"),
+            (price, "numeric(10, 2)"),
+            (col("created", DATETIME, 19, 0), "timestamp"),
+            (col("seen", TIMESTAMP, 19, 0), "timestamptz"),
+            (col("body", BLOB, u32::MAX, 0), "text"),
+            (binary(col("data", BLOB, 65535, 0)), "bytea"),
+            (col("email", VAR_STRING, 1020, 0), "varchar(255)"),
+            (col("code", STRING, 8, 0), "char(2)"),
+            (col("state", STRING, 28, flags::ENUM), "text"),
+            (col("meta", JSON, u32::MAX, 0), "jsonb"),
+        ];
+        for (column, expected) in cases {
+            assert_eq!(pg_type(&column), expected, "{}", column.name);
+        }
+    }
+
+    #[test]
+    fn test_create_table_sql() {
+        use column_type::*;
+
+        let columns = [
+            col("id", LONG, 11, flags::NOT_NULL | flags::PRI_KEY),
+            col("Email", VAR_STRING, 1020, flags::NOT_NULL),
+            col("deleted", TINY, 1, 0),
+        ];
+        assert_eq!(
+            create_table_sql("users", &columns),
+            "CREATE TABLE users (id integer NOT NULL, \"Email\" varchar(255) NOT NULL, \
+             deleted boolean, PRIMARY KEY (id))"
+        );
+    }
+}
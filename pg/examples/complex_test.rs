@@ -83,6 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut filter_query = Qail::get("messages");
     filter_query.columns = vec![Expr::Aggregate {
         col: "*".to_string(),
+        arg: None,
         func: AggregateFunc::Count,
         distinct: false,
         filter: Some(vec![Condition {
@@ -91,6 +92,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             value: Value::String("outbound".to_string()),
             is_array_unnest: false,
         }]),
+        delimiter: None,
+        order_by: None,
         alias: Some("outbound_count".to_string()),
     }];
 
@@ -116,6 +119,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     multi_filter.columns = vec![
         Expr::Aggregate {
             col: "*".to_string(),
+            arg: None,
             func: AggregateFunc::Count,
             distinct: false,
             filter: Some(vec![Condition {
@@ -124,10 +128,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 value: Value::String("inbound".to_string()),
                 is_array_unnest: false,
             }]),
+            delimiter: None,
+            order_by: None,
             alias: Some("inbound".to_string()),
         },
         Expr::Aggregate {
             col: "*".to_string(),
+            arg: None,
             func: AggregateFunc::Count,
             distinct: false,
             filter: Some(vec![Condition {
@@ -136,6 +143,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 value: Value::String("outbound".to_string()),
                 is_array_unnest: false,
             }]),
+            delimiter: None,
+            order_by: None,
             alias: Some("outbound".to_string()),
         },
     ];
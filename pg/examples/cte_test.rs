@@ -86,6 +86,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         base_query: Box::new(high_earners_subquery),
         recursive_query: None,
         source_table: Some("employees".to_string()),
+        materialized: None,
     }];
 
     match driver.fetch_all(&cte_query).await {
@@ -115,6 +116,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         base_query: Box::new(summary_query),
         recursive_query: None,
         source_table: Some("employees".to_string()),
+        materialized: None,
     }];
 
     match driver.fetch_all(&cte_with_cols).await {
@@ -148,6 +150,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             base_query: Box::new(eng_query),
             recursive_query: None,
             source_table: Some("employees".to_string()),
+            materialized: None,
         },
         CTEDef {
             name: "sales_team".to_string(),
@@ -156,6 +159,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             base_query: Box::new(sales_query),
             recursive_query: None,
             source_table: Some("employees".to_string()),
+            materialized: None,
         },
     ];
 
@@ -199,6 +203,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         base_query: Box::new(base_query),
         recursive_query: Some(Box::new(recursive_query)),
         source_table: Some("employees".to_string()),
+        materialized: None,
     }];
 
     match driver.fetch_all(&recursive_cte).await {
@@ -231,6 +236,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         base_query: Box::new(top_salary_query),
         recursive_query: None,
         source_table: Some("employees".to_string()),
+        materialized: None,
     }];
 
     match driver.fetch_all(&cte_ordered).await {
@@ -79,6 +79,7 @@ fn build_test_query() -> Qail {
         base_query: Box::new(high_earners),
         recursive_query: None,
         source_table: Some("employees".to_string()),
+        materialized: None,
     }];
     query
 }
@@ -0,0 +1,66 @@
+//! Advisory lock methods for PostgreSQL connection.
+//!
+//! Session-level locks are held until released or the connection closes;
+//! transaction-level (`xact`) locks are released at COMMIT or ROLLBACK and
+//! have no unlock call. Session locks are reentrant: each successful lock
+//! needs its own unlock.
+
+use super::{PgConnection, PgError, PgResult};
+
+impl PgConnection {
+    /// Wait for the session-level advisory lock `key` (`pg_advisory_lock`).
+    pub async fn advisory_lock(&mut self, key: i64) -> PgResult<()> {
+        self.advisory_call("SELECT pg_advisory_lock($1)", key)
+            .await
+            .map(|_| ())
+    }
+
+    /// Take the session-level advisory lock `key` if it is free
+    /// (`pg_try_advisory_lock`). Returns whether the lock was acquired.
+    pub async fn try_advisory_lock(&mut self, key: i64) -> PgResult<bool> {
+        self.advisory_call("SELECT pg_try_advisory_lock($1)", key)
+            .await
+    }
+
+    /// Release one hold of the session-level advisory lock `key`
+    /// (`pg_advisory_unlock`). Returns `false` if this session did not hold it.
+    pub async fn advisory_unlock(&mut self, key: i64) -> PgResult<bool> {
+        self.advisory_call("SELECT pg_advisory_unlock($1)", key)
+            .await
+    }
+
+    /// Release every session-level advisory lock held by this connection
+    /// (`pg_advisory_unlock_all`).
+    pub async fn advisory_unlock_all(&mut self) -> PgResult<()> {
+        self.execute_simple("SELECT pg_advisory_unlock_all()").await
+    }
+
+    /// Wait for the transaction-level advisory lock `key`
+    /// (`pg_advisory_xact_lock`). Outside an explicit transaction the lock
+    /// is released as soon as the statement finishes.
+    pub async fn advisory_xact_lock(&mut self, key: i64) -> PgResult<()> {
+        self.advisory_call("SELECT pg_advisory_xact_lock($1)", key)
+            .await
+            .map(|_| ())
+    }
+
+    /// Take the transaction-level advisory lock `key` if it is free
+    /// (`pg_try_advisory_xact_lock`). Returns whether the lock was acquired.
+    pub async fn try_advisory_xact_lock(&mut self, key: i64) -> PgResult<bool> {
+        self.advisory_call("SELECT pg_try_advisory_xact_lock($1)", key)
+            .await
+    }
+
+    /// Run a one-row advisory lock function and read its boolean result.
+    /// Functions returning void read as `false`.
+    async fn advisory_call(&mut self, sql: &str, key: i64) -> PgResult<bool> {
+        let rows = self
+            .query_cached(sql, &[Some(key.to_string().into_bytes())])
+            .await?;
+        match rows.first().and_then(|row| row.first()) {
+            Some(Some(value)) => Ok(value.as_slice() == b"t"),
+            Some(None) => Ok(false),
+            None => Err(PgError::NoRows),
+        }
+    }
+}
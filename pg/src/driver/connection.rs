@@ -7,12 +7,15 @@
 //! - `io.rs` - Core I/O (send, recv)
 //! - `query.rs` - Query execution
 //! - `transaction.rs` - Transaction control
+//! - `advisory.rs` - Advisory locks
 //! - `cursor.rs` - Streaming cursors
 //! - `copy.rs` - COPY protocol
 //! - `pipeline.rs` - High-performance pipelining
 //! - `cancel.rs` - Query cancellation
 
+use super::cursor::DEFAULT_FETCH_SIZE;
 use super::stream::PgStream;
+use super::url::ConnectUrl;
 use super::{PgError, PgResult};
 use crate::protocol::{BackendMessage, FrontendMessage, ScramClient, TransactionStatus};
 use bytes::BytesMut;
@@ -20,9 +23,13 @@ use lru::LruCache;
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpStream;
 
+/// Callback invoked with the SQL text and bind parameter count before a query is sent.
+pub type QueryLogger = Box<dyn Fn(&str, usize) + Send + Sync>;
+
 /// Initial buffer capacity (64KB for pipeline performance)
 pub(crate) const BUFFER_CAPACITY: usize = 65536;
 
@@ -32,6 +39,21 @@ const SSL_REQUEST: [u8; 8] = [0, 0, 0, 8, 4, 210, 22, 47];
 /// CancelRequest protocol code: 80877102
 pub(crate) const CANCEL_REQUEST_CODE: i32 = 80877102;
 
+/// Startup timings recorded while establishing a connection.
+///
+/// Each field is measured from the start of the connect call, so
+/// `connect <= auth <= ready` always holds. A slow TLS handshake shows up
+/// in `connect`; slow SCRAM or `pg_hba` lookups show up in `auth - connect`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectTiming {
+    /// Socket (and TLS handshake, if any) established.
+    pub connect: Duration,
+    /// `AuthenticationOk` received.
+    pub auth: Duration,
+    /// First `ReadyForQuery` received; the connection can run queries.
+    pub ready: Duration,
+}
+
 /// TLS configuration for mutual TLS (client certificate authentication).
 #[derive(Clone)]
 pub struct TlsConfig {
@@ -58,6 +80,38 @@ impl TlsConfig {
     }
 }
 
+/// `application_name` sent at startup unless overridden.
+pub const DEFAULT_APPLICATION_NAME: &str = concat!("qail/", env!("CARGO_PKG_VERSION"));
+
+/// Prepared statements `fetch_all_cached` keeps per connection unless overridden.
+pub const DEFAULT_STATEMENT_CACHE_SIZE: usize = 100;
+
+/// How a connection negotiates TLS, mirroring libpq's `sslmode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SslMode {
+    /// Plain TCP; never send an SSLRequest.
+    #[default]
+    Disable,
+    /// Try TLS, fall back to plain TCP if the server refuses it.
+    Prefer,
+    /// TLS or fail. The server certificate is not verified.
+    Require,
+    /// TLS or fail, verifying the certificate chain and the hostname.
+    VerifyFull,
+}
+
+/// How `fetch_all_cached` names server-side prepared statements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatementNaming {
+    /// `qail_{hash}` of the command's [`cache_key`](qail_core::ast::Qail::cache_key);
+    /// commands of the same shape get the same name.
+    #[default]
+    Hashed,
+    /// `qail_{action}_{table}_{n}`, numbered per connection, for readable
+    /// `pg_prepared_statements` and server logs.
+    Readable,
+}
+
 /// A raw PostgreSQL connection.
 pub struct PgConnection {
     pub(crate) stream: PgStream,
@@ -67,8 +121,21 @@ pub struct PgConnection {
     pub(crate) params_buf: Vec<Option<Vec<u8>>>,
     pub(crate) prepared_statements: HashMap<String, String>,
     pub(crate) stmt_cache: LruCache<u64, String>,
+    /// Capacity of `stmt_cache`; 0 disables statement caching
+    pub(crate) stmt_cache_size: usize,
+    /// Statements evicted from `stmt_cache`, closed on the next cached query
+    pub(crate) evicted_stmts: Vec<String>,
+    pub(crate) statement_naming: StatementNaming,
+    /// Last sequence number used for a `Readable` statement name
+    pub(crate) stmt_seq: u64,
+    /// Default cursor batch size; see `set_fetch_size`
+    pub(crate) fetch_size: usize,
     pub(crate) process_id: i32,
     pub(crate) secret_key: i32,
+    pub(crate) timing: ConnectTiming,
+    pub(crate) query_logger: Option<QueryLogger>,
+    /// Transaction status from the last `ReadyForQuery`
+    pub(crate) transaction_status: TransactionStatus,
 }
 
 impl PgConnection {
@@ -85,6 +152,7 @@ impl PgConnection {
         database: &str,
         password: Option<&str>,
     ) -> PgResult<Self> {
+        let started = Instant::now();
         let addr = format!("{}:{}", host, port);
         let tcp_stream = TcpStream::connect(&addr).await?;
 
@@ -98,94 +166,165 @@ impl PgConnection {
             sql_buf: BytesMut::with_capacity(512),
             params_buf: Vec::with_capacity(16), // SQL encoding buffer
             prepared_statements: HashMap::new(),
-            stmt_cache: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            stmt_cache: LruCache::new(NonZeroUsize::new(DEFAULT_STATEMENT_CACHE_SIZE).unwrap()),
+            stmt_cache_size: DEFAULT_STATEMENT_CACHE_SIZE,
+            evicted_stmts: Vec::new(),
+            statement_naming: StatementNaming::Hashed,
+            stmt_seq: 0,
+            fetch_size: DEFAULT_FETCH_SIZE,
             process_id: 0,
             secret_key: 0,
+            timing: ConnectTiming::default(),
+            query_logger: None,
+            transaction_status: TransactionStatus::Idle,
         };
+        conn.timing.connect = started.elapsed();
 
         conn.send(FrontendMessage::Startup {
             user: user.to_string(),
             database: database.to_string(),
+            application_name: DEFAULT_APPLICATION_NAME.to_string(),
         })
         .await?;
 
-        conn.handle_startup(user, password).await?;
+        conn.handle_startup(user, password, started).await?;
 
         Ok(conn)
     }
 
-    /// Connect to PostgreSQL server with TLS encryption.
-    pub async fn connect_tls(
+    /// Connect with an explicit [`SslMode`].
+    /// `ca_cert_pem` is the root store for `VerifyFull`; system roots are used if `None`.
+    /// `Require` and `VerifyFull` fail closed when the server refuses TLS.
+    /// # Example
+    /// ```ignore
+    /// let conn = PgConnection::connect_with_sslmode(
+    ///     "db.internal", 5432, "app", "app", Some("secret"),
+    ///     SslMode::VerifyFull, Some(&std::fs::read("root.crt")?),
+    /// ).await?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn connect_with_sslmode(
         host: &str,
         port: u16,
         user: &str,
         database: &str,
         password: Option<&str>,
+        sslmode: SslMode,
+        ca_cert_pem: Option<&[u8]>,
     ) -> PgResult<Self> {
-        use tokio::io::AsyncReadExt;
-        use tokio_rustls::TlsConnector;
-        use tokio_rustls::rustls::ClientConfig;
-        use tokio_rustls::rustls::pki_types::ServerName;
+        Self::connect_configured(
+            host,
+            port,
+            user,
+            database,
+            password,
+            sslmode,
+            ca_cert_pem,
+            DEFAULT_APPLICATION_NAME,
+        )
+        .await
+    }
 
+    /// TCP connect with every option; backs the builder and `connect_url`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn connect_configured(
+        host: &str,
+        port: u16,
+        user: &str,
+        database: &str,
+        password: Option<&str>,
+        sslmode: SslMode,
+        ca_cert_pem: Option<&[u8]>,
+        application_name: &str,
+    ) -> PgResult<Self> {
+        let started = Instant::now();
         let addr = format!("{}:{}", host, port);
-        let mut tcp_stream = TcpStream::connect(&addr).await?;
-
-        // Send SSLRequest
-        tcp_stream.write_all(&SSL_REQUEST).await?;
-
-        // Read response
-        let mut response = [0u8; 1];
-        tcp_stream.read_exact(&mut response).await?;
-
-        if response[0] != b'S' {
-            return Err(PgError::Connection(
-                "Server does not support TLS".to_string(),
-            ));
-        }
-
-        // TLS handshake
-        let certs = rustls_native_certs::load_native_certs();
-        let mut root_cert_store = tokio_rustls::rustls::RootCertStore::empty();
-        for cert in certs.certs {
-            let _ = root_cert_store.add(cert);
-        }
-
-        let config = ClientConfig::builder()
-            .with_root_certificates(root_cert_store)
-            .with_no_client_auth();
-
-        let connector = TlsConnector::from(Arc::new(config));
-        let server_name = ServerName::try_from(host.to_string())
-            .map_err(|_| PgError::Connection("Invalid hostname for TLS".to_string()))?;
+        let tcp_stream = TcpStream::connect(&addr).await?;
+        tcp_stream.set_nodelay(true)?;
 
-        let tls_stream = connector
-            .connect(server_name, tcp_stream)
-            .await
-            .map_err(|e| PgError::Connection(format!("TLS handshake failed: {}", e)))?;
+        let stream = negotiate_tls(tcp_stream, host, sslmode, ca_cert_pem).await?;
 
         let mut conn = Self {
-            stream: PgStream::Tls(tls_stream),
+            stream,
             buffer: BytesMut::with_capacity(BUFFER_CAPACITY),
             write_buf: BytesMut::with_capacity(BUFFER_CAPACITY),
             sql_buf: BytesMut::with_capacity(512),
             params_buf: Vec::with_capacity(16),
             prepared_statements: HashMap::new(),
-            stmt_cache: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            stmt_cache: LruCache::new(NonZeroUsize::new(DEFAULT_STATEMENT_CACHE_SIZE).unwrap()),
+            stmt_cache_size: DEFAULT_STATEMENT_CACHE_SIZE,
+            evicted_stmts: Vec::new(),
+            statement_naming: StatementNaming::Hashed,
+            stmt_seq: 0,
+            fetch_size: DEFAULT_FETCH_SIZE,
             process_id: 0,
             secret_key: 0,
+            timing: ConnectTiming::default(),
+            query_logger: None,
+            transaction_status: TransactionStatus::Idle,
         };
+        conn.timing.connect = started.elapsed();
 
         conn.send(FrontendMessage::Startup {
             user: user.to_string(),
             database: database.to_string(),
+            application_name: application_name.to_string(),
         })
         .await?;
 
-        conn.handle_startup(user, password).await?;
+        conn.handle_startup(user, password, started).await?;
 
         Ok(conn)
     }
 
+    /// Connect using a libpq-style URL.
+    /// Honors `sslmode`, `connect_timeout` and `application_name` from the query string; see [`ConnectUrl`].
+    /// # Example
+    /// ```ignore
+    /// let conn = PgConnection::connect_url("postgres://app:s%40cret@db:5432/app?sslmode=require").await?;
+    /// ```
+    pub async fn connect_url(url: &str) -> PgResult<Self> {
+        let url = ConnectUrl::parse(url)?;
+        let connect = Self::connect_configured(
+            &url.host,
+            url.port,
+            &url.user,
+            &url.database,
+            url.password.as_deref(),
+            url.sslmode,
+            None,
+            url.application_name.as_deref().unwrap_or(DEFAULT_APPLICATION_NAME),
+        );
+        match url.connect_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, connect)
+                .await
+                .map_err(|_| PgError::Connection(format!("Connection timeout after {:?}", timeout)))?,
+            None => connect.await,
+        }
+    }
+
+    /// Connect to PostgreSQL server with TLS encryption.
+    /// Same as [`SslMode::VerifyFull`] against the system root certificates.
+    pub async fn connect_tls(
+        host: &str,
+        port: u16,
+        user: &str,
+        database: &str,
+        password: Option<&str>,
+    ) -> PgResult<Self> {
+        Self::connect_configured(
+            host,
+            port,
+            user,
+            database,
+            password,
+            SslMode::VerifyFull,
+            None,
+            DEFAULT_APPLICATION_NAME,
+        )
+        .await
+    }
+
     /// Connect with mutual TLS (client certificate authentication).
     /// # Arguments
     /// * `host` - PostgreSQL server hostname
@@ -209,50 +348,23 @@ impl PgConnection {
         database: &str,
         config: TlsConfig,
     ) -> PgResult<Self> {
-        use tokio::io::AsyncReadExt;
-        use tokio_rustls::TlsConnector;
-        use tokio_rustls::rustls::{
-            ClientConfig,
-            pki_types::{CertificateDer, ServerName},
-        };
+        use tokio_rustls::rustls::{ClientConfig, pki_types::CertificateDer};
 
+        let started = Instant::now();
         let addr = format!("{}:{}", host, port);
         let mut tcp_stream = TcpStream::connect(&addr).await?;
 
-        // Send SSLRequest
-        tcp_stream.write_all(&SSL_REQUEST).await?;
-
-        // Read response
-        let mut response = [0u8; 1];
-        tcp_stream.read_exact(&mut response).await?;
-
-        if response[0] != b'S' {
+        if !request_ssl(&mut tcp_stream).await? {
             return Err(PgError::Connection(
                 "Server does not support TLS".to_string(),
             ));
         }
 
-        let mut root_cert_store = tokio_rustls::rustls::RootCertStore::empty();
-
-        if let Some(ca_pem) = &config.ca_cert_pem {
-            let certs = rustls_pemfile::certs(&mut ca_pem.as_slice())
-                .filter_map(|r| r.ok())
-                .collect::<Vec<_>>();
-            for cert in certs {
-                let _ = root_cert_store.add(cert);
-            }
-        } else {
-            // Use system certs
-            let certs = rustls_native_certs::load_native_certs();
-            for cert in certs.certs {
-                let _ = root_cert_store.add(cert);
-            }
-        }
+        let root_cert_store = root_cert_store(config.ca_cert_pem.as_deref())?;
 
-        let client_certs: Vec<CertificateDer<'static>> =
-            rustls_pemfile::certs(&mut config.client_cert_pem.as_slice())
-                .filter_map(|r| r.ok())
-                .collect();
+        let client_certs = rustls_pemfile::certs(&mut config.client_cert_pem.as_slice())
+            .collect::<Result<Vec<CertificateDer<'static>>, _>>()
+            .map_err(|e| PgError::Connection(format!("Invalid client certificate PEM: {}", e)))?;
 
         let client_key = rustls_pemfile::private_key(&mut config.client_key_pem.as_slice())
             .map_err(|e| PgError::Connection(format!("Invalid client key: {:?}", e)))?
@@ -263,35 +375,38 @@ impl PgConnection {
             .with_client_auth_cert(client_certs, client_key)
             .map_err(|e| PgError::Connection(format!("Invalid client cert/key: {}", e)))?;
 
-        let connector = TlsConnector::from(Arc::new(tls_config));
-        let server_name = ServerName::try_from(host.to_string())
-            .map_err(|_| PgError::Connection("Invalid hostname for TLS".to_string()))?;
-
-        let tls_stream = connector
-            .connect(server_name, tcp_stream)
-            .await
-            .map_err(|e| PgError::Connection(format!("mTLS handshake failed: {}", e)))?;
+        let stream = tls_handshake(tcp_stream, host, tls_config).await?;
 
         let mut conn = Self {
-            stream: PgStream::Tls(tls_stream),
+            stream,
             buffer: BytesMut::with_capacity(BUFFER_CAPACITY),
             write_buf: BytesMut::with_capacity(BUFFER_CAPACITY),
             sql_buf: BytesMut::with_capacity(512),
             params_buf: Vec::with_capacity(16),
             prepared_statements: HashMap::new(),
-            stmt_cache: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            stmt_cache: LruCache::new(NonZeroUsize::new(DEFAULT_STATEMENT_CACHE_SIZE).unwrap()),
+            stmt_cache_size: DEFAULT_STATEMENT_CACHE_SIZE,
+            evicted_stmts: Vec::new(),
+            statement_naming: StatementNaming::Hashed,
+            stmt_seq: 0,
+            fetch_size: DEFAULT_FETCH_SIZE,
             process_id: 0,
             secret_key: 0,
+            timing: ConnectTiming::default(),
+            query_logger: None,
+            transaction_status: TransactionStatus::Idle,
         };
+        conn.timing.connect = started.elapsed();
 
         conn.send(FrontendMessage::Startup {
             user: user.to_string(),
             database: database.to_string(),
+            application_name: DEFAULT_APPLICATION_NAME.to_string(),
         })
         .await?;
 
         // mTLS typically uses cert auth, no password needed
-        conn.handle_startup(user, None).await?;
+        conn.handle_startup(user, None, started).await?;
 
         Ok(conn)
     }
@@ -306,6 +421,7 @@ impl PgConnection {
     ) -> PgResult<Self> {
         use tokio::net::UnixStream;
 
+        let started = Instant::now();
         let unix_stream = UnixStream::connect(socket_path).await?;
 
         let mut conn = Self {
@@ -315,30 +431,48 @@ impl PgConnection {
             sql_buf: BytesMut::with_capacity(512),
             params_buf: Vec::with_capacity(16),
             prepared_statements: HashMap::new(),
-            stmt_cache: LruCache::new(NonZeroUsize::new(100).unwrap()),
+            stmt_cache: LruCache::new(NonZeroUsize::new(DEFAULT_STATEMENT_CACHE_SIZE).unwrap()),
+            stmt_cache_size: DEFAULT_STATEMENT_CACHE_SIZE,
+            evicted_stmts: Vec::new(),
+            statement_naming: StatementNaming::Hashed,
+            stmt_seq: 0,
+            fetch_size: DEFAULT_FETCH_SIZE,
             process_id: 0,
             secret_key: 0,
+            timing: ConnectTiming::default(),
+            query_logger: None,
+            transaction_status: TransactionStatus::Idle,
         };
+        conn.timing.connect = started.elapsed();
 
         conn.send(FrontendMessage::Startup {
             user: user.to_string(),
             database: database.to_string(),
+            application_name: DEFAULT_APPLICATION_NAME.to_string(),
         })
         .await?;
 
-        conn.handle_startup(user, password).await?;
+        conn.handle_startup(user, password, started).await?;
 
         Ok(conn)
     }
 
     /// Handle startup sequence (auth + params).
-    async fn handle_startup(&mut self, user: &str, password: Option<&str>) -> PgResult<()> {
+    /// Records `auth` and `ready` timings relative to `started`.
+    async fn handle_startup(
+        &mut self,
+        user: &str,
+        password: Option<&str>,
+        started: Instant,
+    ) -> PgResult<()> {
         let mut scram_client: Option<ScramClient> = None;
 
         loop {
             let msg = self.recv().await?;
             match msg {
-                BackendMessage::AuthenticationOk => {}
+                BackendMessage::AuthenticationOk => {
+                    self.timing.auth = started.elapsed();
+                }
                 BackendMessage::AuthenticationMD5Password(_salt) => {
                     return Err(PgError::Auth(
                         "MD5 auth not supported. Use SCRAM-SHA-256.".to_string(),
@@ -397,6 +531,7 @@ impl PgConnection {
                 BackendMessage::ReadyForQuery(TransactionStatus::Idle)
                 | BackendMessage::ReadyForQuery(TransactionStatus::InBlock)
                 | BackendMessage::ReadyForQuery(TransactionStatus::Failed) => {
+                    self.timing.ready = started.elapsed();
                     return Ok(());
                 }
                 BackendMessage::ErrorResponse(err) => {
@@ -407,6 +542,88 @@ impl PgConnection {
         }
     }
 
+    /// Timings recorded while this connection was established.
+    pub fn connect_timing(&self) -> ConnectTiming {
+        self.timing
+    }
+
+    /// Register a callback invoked with the SQL and bind parameter count
+    /// before each query is sent. Replaces any previous callback.
+    ///
+    /// ```ignore
+    /// conn.on_query(Box::new(|sql, params| tracing::debug!(params, "{sql}")));
+    /// ```
+    pub fn on_query(&mut self, logger: QueryLogger) {
+        self.query_logger = Some(logger);
+    }
+
+    /// Remove the callback registered with [`on_query`](Self::on_query).
+    pub fn clear_query_logger(&mut self) {
+        self.query_logger = None;
+    }
+
+    /// Choose how statements prepared by `fetch_all_cached` are named.
+    /// Only affects statements prepared after the call.
+    pub fn set_statement_naming(&mut self, naming: StatementNaming) {
+        self.statement_naming = naming;
+    }
+
+    /// Set how many prepared statements `fetch_all_cached` keeps
+    /// (default [`DEFAULT_STATEMENT_CACHE_SIZE`]).
+    ///
+    /// When the cache is full the least recently used statement is closed
+    /// on the server. `0` disables caching: every query is parsed as an
+    /// unnamed statement, like `fetch_all_uncached`.
+    pub fn set_statement_cache_size(&mut self, size: usize) {
+        while self.stmt_cache.len() > size {
+            if let Some((_, name)) = self.stmt_cache.pop_lru() {
+                self.forget_statement(name);
+            }
+        }
+        if let Some(cap) = NonZeroUsize::new(size) {
+            self.stmt_cache.resize(cap);
+        }
+        self.stmt_cache_size = size;
+    }
+
+    /// Remember a statement prepared by `fetch_all_cached`, queueing the
+    /// statement it evicts (if any) for Close.
+    pub(crate) fn cache_statement(&mut self, key: u64, name: String) {
+        if let Some((evicted_key, evicted)) = self.stmt_cache.push(key, name)
+            && evicted_key != key
+        {
+            self.forget_statement(evicted);
+        }
+    }
+
+    fn forget_statement(&mut self, name: String) {
+        self.prepared_statements.remove(&name);
+        self.evicted_stmts.push(name);
+    }
+
+    /// Append a Close message for each evicted statement to `buf`.
+    pub(crate) fn encode_evicted_closes(&mut self, buf: &mut BytesMut) {
+        for name in self.evicted_stmts.drain(..) {
+            buf.extend(crate::protocol::PgEncoder::encode_close(false, &name));
+        }
+    }
+
+    /// Report a query to the registered logger, if any.
+    #[inline]
+    pub(crate) fn log_query(&self, sql: &str, params: usize) {
+        if let Some(logger) = &self.query_logger {
+            logger(sql, params);
+        }
+    }
+
+    /// Report the query currently encoded in `sql_buf` / `params_buf`.
+    #[inline]
+    pub(crate) fn log_encoded_query(&self) {
+        if let Some(logger) = &self.query_logger {
+            logger(&String::from_utf8_lossy(&self.sql_buf), self.params_buf.len());
+        }
+    }
+
     /// Gracefully close the connection by sending a Terminate message.
     /// This tells the server we're done and allows proper cleanup.
     pub async fn close(mut self) -> PgResult<()> {
@@ -447,9 +664,225 @@ impl Drop for PgConnection {
     }
 }
 
+/// Send SSLRequest (unless disabled) and wrap the socket per `sslmode`.
+async fn negotiate_tls(
+    mut tcp_stream: TcpStream,
+    host: &str,
+    sslmode: SslMode,
+    ca_cert_pem: Option<&[u8]>,
+) -> PgResult<PgStream> {
+    use tokio_rustls::rustls::ClientConfig;
+
+    if sslmode == SslMode::Disable {
+        return Ok(PgStream::Tcp(tcp_stream));
+    }
+
+    if !request_ssl(&mut tcp_stream).await? {
+        if sslmode == SslMode::Prefer {
+            return Ok(PgStream::Tcp(tcp_stream));
+        }
+        return Err(PgError::Connection(format!(
+            "Server does not support TLS (sslmode={:?})",
+            sslmode
+        )));
+    }
+
+    let config = if sslmode == SslMode::VerifyFull {
+        ClientConfig::builder()
+            .with_root_certificates(root_cert_store(ca_cert_pem)?)
+            .with_no_client_auth()
+    } else {
+        // Require / Prefer: encrypted, but the certificate is not checked (as in libpq)
+        let builder = ClientConfig::builder();
+        let provider = builder.crypto_provider().clone();
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoCertVerification(provider)))
+            .with_no_client_auth()
+    };
+
+    tls_handshake(tcp_stream, host, config).await
+}
+
+/// Send SSLRequest; `true` if the server agreed to TLS (`S`), `false` if it refused (`N`).
+async fn request_ssl(tcp_stream: &mut TcpStream) -> PgResult<bool> {
+    use tokio::io::AsyncReadExt;
+
+    tcp_stream.write_all(&SSL_REQUEST).await?;
+    let mut response = [0u8; 1];
+    tcp_stream.read_exact(&mut response).await?;
+
+    match response[0] {
+        b'S' => Ok(true),
+        b'N' => Ok(false),
+        other => Err(PgError::Protocol(format!(
+            "Unexpected SSLRequest response byte: {:#04x}",
+            other
+        ))),
+    }
+}
+
+/// Root store from `ca_cert_pem`, or the system roots if `None`.
+/// A CA PEM entry that fails to parse is an error, not silently skipped.
+fn root_cert_store(ca_cert_pem: Option<&[u8]>) -> PgResult<tokio_rustls::rustls::RootCertStore> {
+    let mut root_cert_store = tokio_rustls::rustls::RootCertStore::empty();
+    if let Some(ca_pem) = ca_cert_pem {
+        for cert in rustls_pemfile::certs(&mut &ca_pem[..]) {
+            let cert = cert
+                .map_err(|e| PgError::Connection(format!("Invalid CA certificate PEM: {}", e)))?;
+            root_cert_store
+                .add(cert)
+                .map_err(|e| PgError::Connection(format!("Invalid CA certificate: {}", e)))?;
+        }
+    } else {
+        for cert in rustls_native_certs::load_native_certs().certs {
+            let _ = root_cert_store.add(cert);
+        }
+    }
+    if root_cert_store.is_empty() {
+        return Err(PgError::Connection(
+            "Certificate verification needs at least one root certificate".to_string(),
+        ));
+    }
+    Ok(root_cert_store)
+}
+
+/// TLS handshake on a socket whose SSLRequest was accepted.
+async fn tls_handshake(
+    tcp_stream: TcpStream,
+    host: &str,
+    config: tokio_rustls::rustls::ClientConfig,
+) -> PgResult<PgStream> {
+    use tokio_rustls::TlsConnector;
+    use tokio_rustls::rustls::pki_types::ServerName;
+
+    let connector = TlsConnector::from(Arc::new(config));
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|_| PgError::Connection("Invalid hostname for TLS".to_string()))?;
+
+    let tls_stream = connector
+        .connect(server_name, tcp_stream)
+        .await
+        .map_err(|e| PgError::Connection(format!("TLS handshake failed: {}", e)))?;
+
+    Ok(PgStream::Tls(tls_stream))
+}
+
+/// Accepts any server certificate while still checking handshake signatures.
+/// Used for `SslMode::Require` / `SslMode::Prefer`.
+#[derive(Debug)]
+struct NoCertVerification(Arc<tokio_rustls::rustls::crypto::CryptoProvider>);
+
+impl tokio_rustls::rustls::client::danger::ServerCertVerifier for NoCertVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &tokio_rustls::rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[tokio_rustls::rustls::pki_types::CertificateDer<'_>],
+        _server_name: &tokio_rustls::rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: tokio_rustls::rustls::pki_types::UnixTime,
+    ) -> Result<tokio_rustls::rustls::client::danger::ServerCertVerified, tokio_rustls::rustls::Error>
+    {
+        Ok(tokio_rustls::rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &tokio_rustls::rustls::pki_types::CertificateDer<'_>,
+        dss: &tokio_rustls::rustls::DigitallySignedStruct,
+    ) -> Result<
+        tokio_rustls::rustls::client::danger::HandshakeSignatureValid,
+        tokio_rustls::rustls::Error,
+    > {
+        tokio_rustls::rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &tokio_rustls::rustls::pki_types::CertificateDer<'_>,
+        dss: &tokio_rustls::rustls::DigitallySignedStruct,
+    ) -> Result<
+        tokio_rustls::rustls::client::danger::HandshakeSignatureValid,
+        tokio_rustls::rustls::Error,
+    > {
+        tokio_rustls::rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<tokio_rustls::rustls::SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
 pub(crate) fn parse_affected_rows(tag: &str) -> u64 {
     tag.split_whitespace()
         .last()
         .and_then(|s| s.parse().ok())
         .unwrap_or(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// Mock server that answers the SSLRequest with 'N'.
+    async fn refusing_server() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 8];
+            socket.read_exact(&mut request).await.unwrap();
+            assert_eq!(request, SSL_REQUEST);
+            socket.write_all(b"N").await.unwrap();
+            // Keep the socket open so a plaintext fallback would be observable
+            let mut rest = Vec::new();
+            let _ = socket.read_to_end(&mut rest).await;
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_sslmode_require_fails_closed() {
+        for mode in [SslMode::Require, SslMode::VerifyFull] {
+            let port = refusing_server().await;
+            let result = tokio::time::timeout(
+                Duration::from_secs(5),
+                PgConnection::connect_with_sslmode("127.0.0.1", port, "u", "db", None, mode, None),
+            )
+            .await
+            .expect("must not hang");
+            match result {
+                Err(PgError::Connection(msg)) => assert!(msg.contains("does not support TLS")),
+                Err(other) => panic!("unexpected error for {:?}: {:?}", mode, other),
+                Ok(_) => panic!("{:?} connected without TLS", mode),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_ca_pem_is_an_error() {
+        let pem = b"-----BEGIN CERTIFICATE-----\n!!not base64!!\n-----END CERTIFICATE-----\n";
+        match root_cert_store(Some(pem)) {
+            Err(PgError::Connection(msg)) => assert!(msg.contains("Invalid CA certificate"), "{}", msg),
+            Err(other) => panic!("unexpected error: {:?}", other),
+            Ok(_) => panic!("malformed CA PEM was accepted"),
+        }
+
+        // No certificate at all cannot verify anything
+        assert!(root_cert_store(Some(b"")).is_err());
+    }
+}
@@ -3,9 +3,89 @@
 
 use super::{PgConnection, PgError, PgResult, parse_affected_rows};
 use crate::protocol::{AstEncoder, BackendMessage, PgEncoder};
-use bytes::BytesMut;
+use bytes::{BufMut, BytesMut};
 use qail_core::ast::{Action, Qail};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+/// Largest CopyData message sent by [`PgConnection::copy_in_raw_progress`].
+const COPY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Escape a value for the COPY text format.
+/// `\` → `\\`, tab → `\t`, newline → `\n`, carriage return → `\r`;
+/// everything else is copied through unchanged.
+#[inline]
+pub fn copy_escape<B: BufMut>(buf: &mut B, value: &[u8]) {
+    for &byte in value {
+        match byte {
+            b'\\' => buf.put_slice(b"\\\\"),
+            b'\t' => buf.put_slice(b"\\t"),
+            b'\n' => buf.put_slice(b"\\n"),
+            b'\r' => buf.put_slice(b"\\r"),
+            _ => buf.put_u8(byte),
+        }
+    }
+}
+
+/// Decode a single COPY text field: the inverse of [`copy_escape`], plus
+/// the rest of PostgreSQL's escapes (`\b`, `\f`, `\v`, octal `\NNN` and
+/// hex `\xHH`). Any other escaped character stands for itself. Bytes that
+/// do not form valid UTF-8 are replaced with U+FFFD.
+pub fn copy_unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        i += 1;
+        let Some(&c) = bytes.get(i) else {
+            out.push(b'\\');
+            break;
+        };
+        i += 1;
+        match c {
+            b'b' => out.push(0x08),
+            b'f' => out.push(0x0c),
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'v' => out.push(0x0b),
+            b'0'..=b'7' => {
+                // Up to three octal digits
+                let mut value = u32::from(c - b'0');
+                for _ in 0..2 {
+                    match bytes.get(i) {
+                        Some(&d @ b'0'..=b'7') => {
+                            value = value * 8 + u32::from(d - b'0');
+                            i += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                out.push(value as u8);
+            }
+            b'x' if bytes.get(i).is_some_and(u8::is_ascii_hexdigit) => {
+                // Up to two hex digits
+                let mut value = 0u8;
+                for _ in 0..2 {
+                    match bytes.get(i).and_then(|&d| (d as char).to_digit(16)) {
+                        Some(d) => {
+                            value = value * 16 + d as u8;
+                            i += 1;
+                        }
+                        None => break,
+                    }
+                }
+                out.push(value);
+            }
+            other => out.push(other),
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
 
 impl PgConnection {
     /// **Fast** bulk insert using COPY protocol with zero-allocation encoding.
@@ -19,51 +99,16 @@ impl PgConnection {
     ) -> PgResult<u64> {
         use crate::protocol::encode_copy_batch;
 
-        let cols = columns.join(", ");
-        let sql = format!("COPY {} ({}) FROM STDIN", table, cols);
-
-        // Send COPY command
-        let bytes = PgEncoder::encode_query_string(&sql);
-        self.stream.write_all(&bytes).await?;
-
-        // Wait for CopyInResponse
-        loop {
-            let msg = self.recv().await?;
-            match msg {
-                BackendMessage::CopyInResponse { .. } => break,
-                BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
-                }
-                _ => {}
-            }
-        }
+        // Encode ALL rows into a single buffer (zero-allocation per value),
+        // before COPY starts so a bad value leaves the connection usable
+        let batch_data = encode_copy_batch(rows)?;
 
-        // Encode ALL rows into a single buffer (zero-allocation per value)
-        let batch_data = encode_copy_batch(rows);
+        self.begin_copy_in(table, columns).await?;
 
         // Single write for entire batch!
         self.send_copy_data(&batch_data).await?;
 
-        // Send CopyDone
-        self.send_copy_done().await?;
-
-        // Wait for CommandComplete
-        let mut affected = 0u64;
-        loop {
-            let msg = self.recv().await?;
-            match msg {
-                BackendMessage::CommandComplete(tag) => {
-                    affected = parse_affected_rows(&tag);
-                }
-                BackendMessage::ReadyForQuery(_) => {
-                    return Ok(affected);
-                }
-                BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
-                }
-                _ => {}
-            }
-        }
+        self.finish_copy_in().await
     }
 
     /// **Fastest** bulk insert using COPY protocol with pre-encoded data.
@@ -78,6 +123,62 @@ impl PgConnection {
         columns: &[String],
         data: &[u8],
     ) -> PgResult<u64> {
+        self.begin_copy_in(table, columns).await?;
+
+        // Single write - data is already encoded!
+        self.send_copy_data(data).await?;
+
+        self.finish_copy_in().await
+    }
+
+    /// Streaming variant of [`copy_in_raw`](Self::copy_in_raw) for imports
+    /// too large to hold in memory.
+    /// Reads COPY text format from `reader` in chunks of up to
+    /// `COPY_CHUNK_SIZE` bytes, sends each as one CopyData message, and calls
+    /// `on_progress` with the total bytes sent so far after every chunk.
+    /// If reading fails, the COPY is aborted with CopyFail and nothing is
+    /// inserted.
+    /// # Example
+    /// ```ignore
+    /// let file = tokio::fs::File::open("events.tsv").await?;
+    /// let rows = conn
+    ///     .copy_in_raw_progress("events", &columns, file, |sent| println!("{sent} bytes"))
+    ///     .await?;
+    /// ```
+    pub async fn copy_in_raw_progress<R, F>(
+        &mut self,
+        table: &str,
+        columns: &[String],
+        mut reader: R,
+        mut on_progress: F,
+    ) -> PgResult<u64>
+    where
+        R: AsyncRead + Unpin,
+        F: FnMut(u64),
+    {
+        self.begin_copy_in(table, columns).await?;
+
+        let mut chunk = vec![0u8; COPY_CHUNK_SIZE];
+        let mut sent = 0u64;
+        loop {
+            let n = match reader.read(&mut chunk).await {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) => {
+                    self.abort_copy_in(&format!("COPY source read failed: {}", e)).await?;
+                    return Err(PgError::Io(e));
+                }
+            };
+            self.send_copy_data(&chunk[..n]).await?;
+            sent += n as u64;
+            on_progress(sent);
+        }
+
+        self.finish_copy_in().await
+    }
+
+    /// Send `COPY table (columns) FROM STDIN` and wait for CopyInResponse.
+    async fn begin_copy_in(&mut self, table: &str, columns: &[String]) -> PgResult<()> {
         let cols = columns.join(", ");
         let sql = format!("COPY {} ({}) FROM STDIN", table, cols);
 
@@ -89,18 +190,17 @@ impl PgConnection {
         loop {
             let msg = self.recv().await?;
             match msg {
-                BackendMessage::CopyInResponse { .. } => break,
+                BackendMessage::CopyInResponse { .. } => return Ok(()),
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
         }
+    }
 
-        // Single write - data is already encoded!
-        self.send_copy_data(data).await?;
-
-        // Send CopyDone
+    /// Send CopyDone and return the row count from CommandComplete.
+    async fn finish_copy_in(&mut self) -> PgResult<u64> {
         self.send_copy_done().await?;
 
         // Wait for CommandComplete
@@ -115,13 +215,32 @@ impl PgConnection {
                     return Ok(affected);
                 }
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
         }
     }
 
+    /// Send CopyFail and wait until the server is ready again.
+    async fn abort_copy_in(&mut self, reason: &str) -> PgResult<()> {
+        // CopyFail: 'f' + length + reason + NUL
+        let len = (4 + reason.len() + 1) as i32;
+        let mut buf = BytesMut::with_capacity(1 + len as usize);
+        buf.extend_from_slice(b"f");
+        buf.extend_from_slice(&len.to_be_bytes());
+        buf.extend_from_slice(reason.as_bytes());
+        buf.put_u8(0);
+        self.stream.write_all(&buf).await?;
+
+        // The server answers with ErrorResponse, which is expected here
+        loop {
+            if let BackendMessage::ReadyForQuery(_) = self.recv().await? {
+                return Ok(());
+            }
+        }
+    }
+
     /// Send CopyData message (raw bytes).
     async fn send_copy_data(&mut self, data: &[u8]) -> PgResult<()> {
         // CopyData: 'd' + length + data
@@ -158,7 +277,7 @@ impl PgConnection {
         }
 
         // Encode command to SQL using AST encoder
-        let (sql, _params) = AstEncoder::encode_cmd_sql(cmd);
+        let (sql, _params) = AstEncoder::encode_cmd_sql(cmd)?;
 
         // Send COPY command
         let bytes = PgEncoder::encode_query_string(&sql);
@@ -170,7 +289,7 @@ impl PgConnection {
             match msg {
                 BackendMessage::CopyOutResponse { .. } => break,
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
@@ -184,7 +303,10 @@ impl PgConnection {
                 BackendMessage::CopyData(data) => {
                     let line = String::from_utf8_lossy(&data);
                     let line = line.trim_end_matches('\n');
-                    let cols: Vec<String> = line.split('\t').map(|s| s.to_string()).collect();
+                    let cols: Vec<String> = line
+                        .split('\t')
+                        .map(|s| if s == "\\N" { s.to_string() } else { copy_unescape(s) })
+                        .collect();
                     rows.push(cols);
                 }
                 BackendMessage::CopyDone => {}
@@ -193,7 +315,7 @@ impl PgConnection {
                     return Ok(rows);
                 }
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
@@ -214,7 +336,7 @@ impl PgConnection {
             match msg {
                 BackendMessage::CopyOutResponse { .. } => break,
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
@@ -234,10 +356,122 @@ impl PgConnection {
                     return Ok(data);
                 }
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Read one frontend message: (type byte, body).
+    async fn read_message(socket: &mut tokio::net::TcpStream) -> (u8, Vec<u8>) {
+        let tag = socket.read_u8().await.unwrap();
+        let len = socket.read_i32().await.unwrap() as usize;
+        let mut body = vec![0u8; len - 4];
+        socket.read_exact(&mut body).await.unwrap();
+        (tag, body)
+    }
+
+    /// Mock server that accepts a trust login and one `COPY FROM STDIN`,
+    /// returning the CopyData payloads it received.
+    async fn copy_server() -> (u16, tokio::task::JoinHandle<Vec<Vec<u8>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let len = socket.read_i32().await.unwrap() as usize;
+            let mut startup = vec![0u8; len - 4];
+            socket.read_exact(&mut startup).await.unwrap();
+            // AuthenticationOk, ReadyForQuery
+            socket
+                .write_all(&[b'R', 0, 0, 0, 8, 0, 0, 0, 0, b'Z', 0, 0, 0, 5, b'I'])
+                .await
+                .unwrap();
+
+            let (tag, _) = read_message(&mut socket).await;
+            assert_eq!(tag, b'Q');
+            // CopyInResponse: text format, one text column
+            socket
+                .write_all(&[b'G', 0, 0, 0, 9, 0, 0, 1, 0, 0])
+                .await
+                .unwrap();
+
+            let mut chunks = Vec::new();
+            loop {
+                match read_message(&mut socket).await {
+                    (b'd', data) => chunks.push(data),
+                    (b'c', _) => break,
+                    (other, _) => panic!("unexpected message {:?}", other as char),
+                }
+            }
+            let mut reply = vec![b'C', 0, 0, 0, 11];
+            reply.extend_from_slice(b"COPY 3\0");
+            reply.extend_from_slice(&[b'Z', 0, 0, 0, 5, b'I']);
+            socket.write_all(&reply).await.unwrap();
+            chunks
+        });
+        (port, handle)
+    }
+
+    #[tokio::test]
+    async fn test_copy_in_raw_progress_chunks() {
+        let (port, server) = copy_server().await;
+        let mut conn = PgConnection::connect("127.0.0.1", port, "u", "db")
+            .await
+            .unwrap();
+
+        // Three source reads; the middle one is larger than a CopyData message
+        let big = vec![b'x'; COPY_CHUNK_SIZE + 5];
+        let source = (&b"1\n"[..]).chain(&big[..]).chain(&b"3\n"[..]);
+
+        let mut progress = Vec::new();
+        let rows = conn
+            .copy_in_raw_progress("t", &["v".to_string()], source, |sent| progress.push(sent))
+            .await
+            .unwrap();
+
+        assert_eq!(rows, 3);
+        let total = (COPY_CHUNK_SIZE + 9) as u64;
+        assert_eq!(
+            progress,
+            vec![2, 2 + COPY_CHUNK_SIZE as u64, total - 2, total]
+        );
+        let chunks = server.await.unwrap();
+        let sizes: Vec<usize> = chunks.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, COPY_CHUNK_SIZE, 5, 2]);
+        assert_eq!(chunks.concat().len() as u64, total);
+    }
+
+    #[test]
+    fn test_copy_escape_special_bytes() {
+        let mut buf = Vec::new();
+        copy_escape(&mut buf, b"a\tb\nc\rd\\e");
+        assert_eq!(buf, b"a\\tb\\nc\\rd\\\\e");
+    }
+
+    #[test]
+    fn test_copy_unescape_round_trip() {
+        let original = "line1\nline2\twith \\ backslash\r";
+        let mut buf = Vec::new();
+        copy_escape(&mut buf, original.as_bytes());
+        let escaped = String::from_utf8(buf).unwrap();
+        assert!(!escaped.contains('\n'));
+        assert_eq!(copy_unescape(&escaped), original);
+    }
+
+    #[test]
+    fn test_copy_unescape_all_escapes() {
+        assert_eq!(copy_unescape("a\\bb\\fc\\vd"), "a\u{8}b\u{c}c\u{b}d");
+        // Octal and hex bytes, including a two-byte UTF-8 sequence
+        assert_eq!(copy_unescape("\\101\\x42\\303\\251"), "ABé");
+        assert_eq!(copy_unescape("\\7x\\x4"), "\u{7}x\u{4}");
+        // `\x` without hex digits and unknown escapes stand for themselves
+        assert_eq!(copy_unescape("\\xz\\q\\"), "xzq\\");
+    }
+}
@@ -1,32 +1,124 @@
 //! Streaming cursor methods for PostgreSQL connection.
 
-use super::{PgConnection, PgResult};
+use super::{ColumnInfo, PgConnection, PgError, PgResult, PgRow};
+use crate::protocol::{BackendMessage, PgEncoder};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::AsyncWriteExt;
+
+/// Rows fetched per `FETCH` when a stream is opened with a batch size of 0.
+pub const DEFAULT_FETCH_SIZE: usize = 1000;
+
+/// An open server-side cursor, read batch by batch with
+/// [`PgDriver::next_batch`](super::PgDriver::next_batch).
+///
+/// The cursor lives until it is closed or its transaction ends.
+#[derive(Debug)]
+pub struct PgCursor {
+    name: String,
+    batch_size: usize,
+    exhausted: bool,
+}
+
+impl PgCursor {
+    pub(crate) fn new(batch_size: usize) -> Self {
+        static CURSOR_ID: AtomicU64 = AtomicU64::new(0);
+        Self {
+            name: format!("qail_cursor_{}", CURSOR_ID.fetch_add(1, Ordering::SeqCst)),
+            batch_size,
+            exhausted: false,
+        }
+    }
+
+    /// Server-side cursor name, e.g. for a `CLOSE` issued elsewhere in the
+    /// same transaction.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Rows requested per `FETCH`.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Whether a `FETCH` has come back empty.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    pub(crate) fn mark_exhausted(&mut self) {
+        self.exhausted = true;
+    }
+}
 
 impl PgConnection {
+    /// Set the batch size used by cursor streams opened with a batch size
+    /// of 0 (default [`DEFAULT_FETCH_SIZE`]).
+    pub fn set_fetch_size(&mut self, size: usize) {
+        self.fetch_size = size.max(1);
+    }
+
+    /// Batch size used by cursor streams opened with a batch size of 0.
+    pub fn fetch_size(&self) -> usize {
+        self.fetch_size
+    }
+
     /// Declare a cursor for streaming large result sets.
     /// This uses PostgreSQL's DECLARE CURSOR to avoid loading all rows into memory.
-    pub(crate) async fn declare_cursor(&mut self, name: &str, sql: &str) -> PgResult<()> {
+    /// `params` bind the `$n` placeholders of `sql`. Must run inside a transaction.
+    pub async fn declare_cursor(
+        &mut self,
+        name: &str,
+        sql: &str,
+        params: &[Option<Vec<u8>>],
+    ) -> PgResult<()> {
         let declare_sql = format!("DECLARE {} CURSOR FOR {}", name, sql);
-        self.execute_simple(&declare_sql).await
+        self.query(&declare_sql, params).await.map(|_| ())
     }
 
-    /// Fetch rows from a cursor in batches.
-    pub(crate) async fn fetch_cursor(
+    /// Fetch the next batch of at most `batch_size` rows from a cursor.
+    /// Returns `None` once the cursor is exhausted. Rows carry column names.
+    pub async fn fetch_cursor(
         &mut self,
         name: &str,
         batch_size: usize,
-    ) -> PgResult<Option<Vec<Vec<Option<Vec<u8>>>>>> {
+    ) -> PgResult<Option<Vec<PgRow>>> {
         let fetch_sql = format!("FETCH {} FROM {}", batch_size, name);
-        let rows = self.query(&fetch_sql, &[]).await?;
+        self.stream
+            .write_all(&PgEncoder::encode_query_string(&fetch_sql))
+            .await?;
+
+        let mut rows = Vec::new();
+        let mut column_info: Option<Arc<ColumnInfo>> = None;
+        let mut error: Option<PgError> = None;
 
-        if rows.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(rows))
+        loop {
+            match self.recv().await? {
+                BackendMessage::RowDescription(fields) => {
+                    column_info = Some(Arc::new(ColumnInfo::from_fields(&fields)));
+                }
+                BackendMessage::DataRow(columns) if error.is_none() => {
+                    rows.push(PgRow {
+                        columns,
+                        column_info: column_info.clone(),
+                    });
+                }
+                BackendMessage::ReadyForQuery(_) => {
+                    if let Some(err) = error {
+                        return Err(err);
+                    }
+                    return Ok(if rows.is_empty() { None } else { Some(rows) });
+                }
+                BackendMessage::ErrorResponse(err) if error.is_none() => {
+                    error = Some(PgError::from(err));
+                }
+                _ => {}
+            }
         }
     }
 
-    pub(crate) async fn close_cursor(&mut self, name: &str) -> PgResult<()> {
+    /// Close a cursor declared with [`declare_cursor`](Self::declare_cursor).
+    pub async fn close_cursor(&mut self, name: &str) -> PgResult<()> {
         let close_sql = format!("CLOSE {}", name);
         self.execute_simple(&close_sql).await
     }
@@ -3,12 +3,25 @@
 //! This module provides low-level send/receive methods.
 
 use super::{PgConnection, PgError, PgResult};
-use crate::protocol::{BackendMessage, FrontendMessage};
+use crate::protocol::{BackendMessage, FrontendMessage, TransactionStatus};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 const MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1 GB
 
 impl PgConnection {
+    /// Record the status byte of a buffered `ReadyForQuery` that a fast
+    /// path is about to skip without decoding.
+    #[inline]
+    fn track_ready_for_query(&mut self, msg_type: u8) {
+        if msg_type == b'Z' && self.buffer.len() > 5 {
+            self.transaction_status = match self.buffer[5] {
+                b'T' => TransactionStatus::InBlock,
+                b'E' => TransactionStatus::Failed,
+                _ => TransactionStatus::Idle,
+            };
+        }
+    }
+
     /// Send a frontend message.
     pub async fn send(&mut self, msg: FrontendMessage) -> PgResult<()> {
         let bytes = msg.encode();
@@ -39,6 +52,9 @@ impl PgConnection {
                     // We have a complete message - zero-copy split
                     let msg_bytes = self.buffer.split_to(msg_len + 1);
                     let (msg, _) = BackendMessage::decode(&msg_bytes).map_err(PgError::Protocol)?;
+                    if let BackendMessage::ReadyForQuery(status) = msg {
+                        self.transaction_status = status;
+                    }
                     return Ok(msg);
                 }
             }
@@ -109,10 +125,11 @@ impl PgConnection {
                         let (msg, _) =
                             BackendMessage::decode(&msg_bytes).map_err(PgError::Protocol)?;
                         if let BackendMessage::ErrorResponse(err) = msg {
-                            return Err(PgError::Query(err.message));
+                            return Err(PgError::from(err));
                         }
                     }
 
+                    self.track_ready_for_query(msg_type);
                     let _ = self.buffer.split_to(msg_len + 1);
                     return Ok(msg_type);
                 }
@@ -162,7 +179,7 @@ impl PgConnection {
                         let (msg, _) =
                             BackendMessage::decode(&msg_bytes).map_err(PgError::Protocol)?;
                         if let BackendMessage::ErrorResponse(err) = msg {
-                            return Err(PgError::Query(err.message));
+                            return Err(PgError::from(err));
                         }
                     }
 
@@ -206,6 +223,7 @@ impl PgConnection {
                     }
 
                     // Other messages - skip
+                    self.track_ready_for_query(msg_type);
                     let _ = self.buffer.split_to(msg_len + 1);
                     return Ok((msg_type, None));
                 }
@@ -257,7 +275,7 @@ impl PgConnection {
                         let (msg, _) =
                             BackendMessage::decode(&msg_bytes).map_err(PgError::Protocol)?;
                         if let BackendMessage::ErrorResponse(err) = msg {
-                            return Err(PgError::Query(err.message));
+                            return Err(PgError::from(err));
                         }
                     }
 
@@ -297,6 +315,7 @@ impl PgConnection {
                     }
 
                     // Other messages - skip
+                    self.track_ready_for_query(msg_type);
                     let _ = self.buffer.split_to(msg_len + 1);
                     return Ok((msg_type, None));
                 }
@@ -347,7 +366,7 @@ impl PgConnection {
                         let (msg, _) =
                             BackendMessage::decode(&msg_bytes).map_err(PgError::Protocol)?;
                         if let BackendMessage::ErrorResponse(err) = msg {
-                            return Err(PgError::Query(err.message));
+                            return Err(PgError::from(err));
                         }
                     }
 
@@ -376,6 +395,7 @@ impl PgConnection {
                     }
 
                     // Other messages - skip
+                    self.track_ready_for_query(msg_type);
                     let _ = self.buffer.split_to(msg_len + 1);
                     return Ok((msg_type, None));
                 }
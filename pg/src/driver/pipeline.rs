@@ -9,12 +9,97 @@
 //! 5. `pipeline_ast_fast` - Fast extended query, count only
 //! 6. `pipeline_ast` - Full results collection
 //! 7. `query_pipeline` - SQL-based pipelining
+//!
+//! `pipeline_query` is the ergonomic entry: prepare once, bind `Value` rows.
 
-use super::{PgConnection, PgError, PgResult};
+use super::{PgConnection, PgError, PgResult, parse_affected_rows};
+use crate::protocol::ast_encoder::values::encode_value;
 use crate::protocol::{AstEncoder, BackendMessage, PgEncoder};
 use bytes::BytesMut;
+use qail_core::ast::{Qail, Value};
+#[cfg(feature = "pipeline_timing")]
+use std::time::{Duration, Instant};
 use tokio::io::AsyncWriteExt;
 
+/// Statement for [`PgConnection::pipeline_query`]: raw SQL with `$n`
+/// placeholders, a Qail command using `Value::Param(n)`, or a statement
+/// already prepared on the connection.
+#[derive(Debug, Clone, Copy)]
+pub enum PipelineQuery<'a> {
+    Sql(&'a str),
+    Cmd(&'a Qail),
+    Prepared(&'a super::PreparedStatement),
+}
+
+impl<'a> From<&'a str> for PipelineQuery<'a> {
+    fn from(sql: &'a str) -> Self {
+        PipelineQuery::Sql(sql)
+    }
+}
+
+impl<'a> From<&'a String> for PipelineQuery<'a> {
+    fn from(sql: &'a String) -> Self {
+        PipelineQuery::Sql(sql)
+    }
+}
+
+impl<'a> From<&'a Qail> for PipelineQuery<'a> {
+    fn from(cmd: &'a Qail) -> Self {
+        PipelineQuery::Cmd(cmd)
+    }
+}
+
+impl<'a> From<&'a super::PreparedStatement> for PipelineQuery<'a> {
+    fn from(stmt: &'a super::PreparedStatement) -> Self {
+        PipelineQuery::Prepared(stmt)
+    }
+}
+
+/// Encode one row of `Value`s into text-format bind parameters.
+/// Values that render as SQL (columns, functions, subqueries, `Param`) cannot be bound.
+pub(crate) fn encode_bind_params(values: &[Value]) -> PgResult<Vec<Option<Vec<u8>>>> {
+    let mut scratch = BytesMut::new();
+    let mut params = Vec::with_capacity(values.len());
+    for value in values {
+        let before = params.len();
+        encode_value(value, &mut scratch, &mut params).map_err(PgError::from)?;
+        if params.len() != before + 1 {
+            return Err(PgError::Query(format!(
+                "Value cannot be bound as a parameter: {}",
+                value
+            )));
+        }
+        scratch.clear();
+    }
+    Ok(params)
+}
+
+/// Raw rows of one pipelined query plus its affected-row count.
+type RowsWithAffected = (Vec<Vec<Option<Vec<u8>>>>, u64);
+
+/// Where the time of one pipeline call went (feature `pipeline_timing`).
+///
+/// The stages run back to back, so `encode + send + recv == total`.
+/// `recv` includes the server's execution time: a large `recv` next to a
+/// small `send` means the batch is server-bound, while a large `encode`
+/// means the batch is big enough to be CPU-bound on the client.
+#[cfg(feature = "pipeline_timing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineTiming {
+    /// Building the wire buffer (Parse/Bind/Execute/Sync).
+    pub encode: Duration,
+    /// Writing the buffer to the socket.
+    pub send: Duration,
+    /// Waiting for the server and parsing its replies up to `ReadyForQuery`.
+    pub recv: Duration,
+    /// Whole call, from the first encoded byte to the last reply.
+    pub total: Duration,
+    /// Queries in the batch.
+    pub queries: usize,
+    /// Bytes written to the socket.
+    pub bytes_sent: usize,
+}
+
 impl PgConnection {
     /// Execute multiple SQL queries in a single network round-trip (PIPELINING).
     pub async fn query_pipeline(
@@ -25,7 +110,7 @@ impl PgConnection {
         let mut buf = BytesMut::new();
         for (sql, params) in queries {
             buf.extend_from_slice(&PgEncoder::encode_extended_query(sql, params)
-                .map_err(|e| PgError::Encode(e.to_string()))?);
+                .map_err(PgError::from)?);
         }
 
         // Send all queries in ONE write
@@ -58,7 +143,7 @@ impl PgConnection {
                     }
                 }
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
@@ -70,10 +155,50 @@ impl PgConnection {
         &mut self,
         cmds: &[qail_core::ast::Qail],
     ) -> PgResult<Vec<Vec<Vec<Option<Vec<u8>>>>>> {
-        let buf = AstEncoder::encode_batch(cmds);
+        let results = self.pipeline_ast_with_affected(cmds).await?;
+        Ok(results.into_iter().map(|(rows, _)| rows).collect())
+    }
+
+    /// Execute multiple Qail ASTs in a single network round-trip,
+    /// returning each query's rows together with its affected-row count
+    /// (parsed from the CommandComplete tag, e.g. `UPDATE 3` -> 3).
+    pub async fn pipeline_ast_with_affected(
+        &mut self,
+        cmds: &[qail_core::ast::Qail],
+    ) -> PgResult<Vec<RowsWithAffected>> {
+        let buf = AstEncoder::encode_batch(cmds)?;
         self.stream.write_all(&buf).await?;
+        self.recv_pipeline_rows(cmds.len()).await
+    }
 
-        let mut all_results: Vec<Vec<Vec<Option<Vec<u8>>>>> = Vec::with_capacity(cmds.len());
+    /// [`pipeline_ast`](Self::pipeline_ast) with a per-stage [`PipelineTiming`].
+    #[cfg(feature = "pipeline_timing")]
+    pub async fn pipeline_ast_timed(
+        &mut self,
+        cmds: &[qail_core::ast::Qail],
+    ) -> PgResult<(Vec<Vec<Vec<Option<Vec<u8>>>>>, PipelineTiming)> {
+        let start = Instant::now();
+        let buf = AstEncoder::encode_batch(cmds)?;
+        let encoded = Instant::now();
+        self.stream.write_all(&buf).await?;
+        let sent = Instant::now();
+        let results = self.recv_pipeline_rows(cmds.len()).await?;
+        let done = Instant::now();
+
+        let timing = PipelineTiming {
+            encode: encoded - start,
+            send: sent - encoded,
+            recv: done - sent,
+            total: done - start,
+            queries: cmds.len(),
+            bytes_sent: buf.len(),
+        };
+        Ok((results.into_iter().map(|(rows, _)| rows).collect(), timing))
+    }
+
+    /// Collect rows and affected counts for `expected` pipelined queries.
+    async fn recv_pipeline_rows(&mut self, expected: usize) -> PgResult<Vec<RowsWithAffected>> {
+        let mut all_results: Vec<RowsWithAffected> = Vec::with_capacity(expected);
         let mut current_rows: Vec<Vec<Option<Vec<u8>>>> = Vec::new();
         let mut queries_completed = 0;
 
@@ -85,21 +210,21 @@ impl PgConnection {
                 BackendMessage::DataRow(data) => {
                     current_rows.push(data);
                 }
-                BackendMessage::CommandComplete(_) => {
-                    all_results.push(std::mem::take(&mut current_rows));
+                BackendMessage::CommandComplete(tag) => {
+                    all_results.push((std::mem::take(&mut current_rows), parse_affected_rows(&tag)));
                     queries_completed += 1;
                 }
                 BackendMessage::NoData => {
-                    all_results.push(Vec::new());
+                    all_results.push((Vec::new(), 0));
                     queries_completed += 1;
                 }
                 BackendMessage::ReadyForQuery(_) => {
-                    if queries_completed == cmds.len() {
+                    if queries_completed == expected {
                         return Ok(all_results);
                     }
                 }
                 BackendMessage::ErrorResponse(err) => {
-                    return Err(PgError::Query(err.message));
+                    return Err(PgError::from(err));
                 }
                 _ => {}
             }
@@ -108,7 +233,7 @@ impl PgConnection {
 
     /// FAST AST pipeline - returns only query count, no result parsing.
     pub async fn pipeline_ast_fast(&mut self, cmds: &[qail_core::ast::Qail]) -> PgResult<usize> {
-        let buf = AstEncoder::encode_batch(cmds);
+        let buf = AstEncoder::encode_batch(cmds)?;
 
         self.stream.write_all(&buf).await?;
         self.stream.flush().await?;
@@ -161,7 +286,7 @@ impl PgConnection {
         &mut self,
         cmds: &[qail_core::ast::Qail],
     ) -> PgResult<usize> {
-        let buf = AstEncoder::encode_batch_simple(cmds);
+        let buf = AstEncoder::encode_batch_simple(cmds)?;
 
         self.stream.write_all(&buf).await?;
         self.stream.flush().await?;
@@ -224,7 +349,7 @@ impl PgConnection {
         let mut buf = BytesMut::with_capacity(cmds.len() * 64);
 
         for cmd in cmds {
-            let (sql, params) = AstEncoder::encode_cmd_sql(cmd);
+            let (sql, params) = AstEncoder::encode_cmd_sql(cmd)?;
             let stmt_name = Self::sql_to_stmt_name(&sql);
 
             if !self.prepared_statements.contains_key(&stmt_name) {
@@ -233,7 +358,7 @@ impl PgConnection {
             }
 
             buf.extend_from_slice(&PgEncoder::encode_bind("", &stmt_name, &params)
-                .map_err(|e| PgError::Encode(e.to_string()))?);
+                .map_err(PgError::from)?);
             buf.extend(PgEncoder::encode_execute("", 0));
         }
 
@@ -282,29 +407,75 @@ impl PgConnection {
             return Ok(0);
         }
 
-        // Local buffer - faster than reusing connection buffer
-        let mut buf = BytesMut::with_capacity(params_batch.len() * 64);
+        let buf = self.encode_prepared_batch(stmt, params_batch)?;
 
-        let is_new = !self.prepared_statements.contains_key(&stmt.name);
+        self.stream.write_all(&buf).await?;
+        self.stream.flush().await?;
+
+        self.recv_pipeline_count(params_batch.len()).await
+    }
+
+    /// [`pipeline_prepared_fast`](Self::pipeline_prepared_fast) with a
+    /// per-stage [`PipelineTiming`].
+    #[cfg(feature = "pipeline_timing")]
+    pub async fn pipeline_prepared_fast_timed(
+        &mut self,
+        stmt: &super::PreparedStatement,
+        params_batch: &[Vec<Option<Vec<u8>>>],
+    ) -> PgResult<(usize, PipelineTiming)> {
+        if params_batch.is_empty() {
+            return Ok((0, PipelineTiming::default()));
+        }
+
+        let start = Instant::now();
+        let buf = self.encode_prepared_batch(stmt, params_batch)?;
+        let encoded = Instant::now();
+        self.stream.write_all(&buf).await?;
+        self.stream.flush().await?;
+        let sent = Instant::now();
+        let completed = self.recv_pipeline_count(params_batch.len()).await?;
+        let done = Instant::now();
+
+        let timing = PipelineTiming {
+            encode: encoded - start,
+            send: sent - encoded,
+            recv: done - sent,
+            total: done - start,
+            queries: params_batch.len(),
+            bytes_sent: buf.len(),
+        };
+        Ok((completed, timing))
+    }
 
-        if is_new {
+    /// Bind+Execute per parameter row of an already prepared statement, then Sync.
+    fn encode_prepared_batch(
+        &self,
+        stmt: &super::PreparedStatement,
+        params_batch: &[Vec<Option<Vec<u8>>>],
+    ) -> PgResult<BytesMut> {
+        if !self.prepared_statements.contains_key(&stmt.name) {
             return Err(PgError::Query(
                 "Statement not prepared. Call prepare() first.".to_string(),
             ));
         }
 
+        // Local buffer - faster than reusing connection buffer
+        let mut buf = BytesMut::with_capacity(params_batch.len() * 64);
+
         // ZERO ALLOCATION: write directly to local buffer
         for params in params_batch {
             PgEncoder::encode_bind_to(&mut buf, &stmt.name, params)
-                .map_err(|e| PgError::Encode(e.to_string()))?;
+                .map_err(PgError::from)?;
             PgEncoder::encode_execute_to(&mut buf);
         }
 
         PgEncoder::encode_sync_to(&mut buf);
+        Ok(buf)
+    }
 
-        self.stream.write_all(&buf).await?;
-        self.stream.flush().await?;
-
+    /// Count completions (`CommandComplete` / `NoData`) until the final
+    /// `ReadyForQuery` of a pipeline of `expected` queries.
+    async fn recv_pipeline_count(&mut self, expected: usize) -> PgResult<usize> {
         let mut queries_completed = 0;
 
         loop {
@@ -312,7 +483,7 @@ impl PgConnection {
             match msg_type {
                 b'C' | b'n' => queries_completed += 1,
                 b'Z' => {
-                    if queries_completed == params_batch.len() {
+                    if queries_completed == expected {
                         return Ok(queries_completed);
                     }
                 }
@@ -321,6 +492,40 @@ impl PgConnection {
         }
     }
 
+    /// Prepare `query` once (reused if already prepared) and pipeline one
+    /// Bind/Execute per row of `params_batch`. Returns the completed count.
+    /// A [`PipelineQuery::Prepared`] handle is used as is, without a lookup.
+    pub async fn pipeline_query(
+        &mut self,
+        query: PipelineQuery<'_>,
+        params_batch: &[Vec<Value>],
+    ) -> PgResult<usize> {
+        let wire_params = params_batch
+            .iter()
+            .map(|row| encode_bind_params(row))
+            .collect::<PgResult<Vec<_>>>()?;
+
+        let sql = match query {
+            PipelineQuery::Prepared(stmt) => {
+                return self.pipeline_prepared_fast(stmt, &wire_params).await;
+            }
+            PipelineQuery::Sql(sql) => sql.to_string(),
+            PipelineQuery::Cmd(cmd) => {
+                let (sql, inline) = AstEncoder::encode_cmd_sql(cmd)?;
+                if !inline.is_empty() {
+                    return Err(PgError::Query(
+                        "pipeline_query: command has inline values; use Value::Param(n) placeholders"
+                            .to_string(),
+                    ));
+                }
+                sql
+            }
+        };
+
+        let stmt = self.prepare(&sql).await?;
+        self.pipeline_prepared_fast(&stmt, &wire_params).await
+    }
+
     /// Prepare a statement and return a handle for fast execution.
     /// PreparedStatement handle for use with pipeline_prepared_fast.
     pub async fn prepare(&mut self, sql: &str) -> PgResult<super::PreparedStatement> {
@@ -357,6 +562,108 @@ impl PgConnection {
         })
     }
 
+    /// Pre-prepare the statements `fetch_all_cached` would use for `cmds`,
+    /// so the first real execution skips the Parse round-trip.
+    /// All Parse messages are sent in one pipeline with a single Sync.
+    /// Returns how many statements were newly prepared.
+    pub async fn warmup(&mut self, cmds: &[qail_core::ast::Qail]) -> PgResult<usize> {
+        use crate::protocol::ast_encoder::dml;
+        use qail_core::ast::Action;
+
+        // (stmt_cache key, statement name, sql) in the order Parse is sent.
+        // Actions without a cache key go through `query_cached` instead.
+        let mut pending: Vec<(Option<u64>, String, String)> = Vec::new();
+        let mut buf = BytesMut::new();
+        let mut sql_buf = BytesMut::with_capacity(256);
+        let mut params: Vec<Option<Vec<u8>>> = Vec::new();
+
+        for cmd in cmds {
+            sql_buf.clear();
+            params.clear();
+
+            let cacheable = matches!(
+                cmd.action,
+                Action::Get | Action::With | Action::Add | Action::Set | Action::Del
+            );
+            match cmd.action {
+                Action::Get | Action::With => dml::encode_select(cmd, &mut sql_buf, &mut params)?,
+                Action::Add => dml::encode_insert(cmd, &mut sql_buf, &mut params)?,
+                Action::Set => dml::encode_update(cmd, &mut sql_buf, &mut params)?,
+                Action::Del => dml::encode_delete(cmd, &mut sql_buf, &mut params)?,
+                _ => {
+                    let (sql, _) = AstEncoder::encode_cmd_sql(cmd)?;
+                    sql_buf.extend_from_slice(sql.as_bytes());
+                }
+            }
+            // With caching disabled `fetch_all_cached` never uses a named statement
+            if cacheable && self.stmt_cache_size == 0 {
+                continue;
+            }
+            let key = cacheable.then(|| cmd.cache_key());
+
+            let sql = String::from_utf8_lossy(&sql_buf).into_owned();
+            let known = match key {
+                Some(key) => {
+                    self.stmt_cache.contains(&key)
+                        || pending.iter().any(|(k, _, _)| *k == Some(key))
+                }
+                None => {
+                    let name = Self::sql_to_stmt_name(&sql);
+                    self.prepared_statements.contains_key(&name)
+                        || pending.iter().any(|(_, n, _)| *n == name)
+                }
+            };
+            if known {
+                continue;
+            }
+            let name = match key {
+                Some(key) => self.next_cached_stmt_name(key, cmd),
+                None => Self::sql_to_stmt_name(&sql),
+            };
+
+            buf.extend(PgEncoder::encode_parse(&name, &sql, &[]));
+            pending.push((key, name, sql));
+        }
+
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        // Close statements evicted earlier, as the single-statement path does
+        let mut msg = BytesMut::with_capacity(buf.len() + 5);
+        self.encode_evicted_closes(&mut msg);
+        msg.extend_from_slice(&buf);
+        msg.extend(PgEncoder::encode_sync());
+        self.stream.write_all(&msg).await?;
+        self.stream.flush().await?;
+
+        // Parse messages complete in order; after an error the rest are skipped.
+        let mut prepared = 0;
+        let mut error: Option<PgError> = None;
+        loop {
+            match self.recv().await? {
+                BackendMessage::ParseComplete => {
+                    let (key, name, sql) = &pending[prepared];
+                    if let Some(key) = key {
+                        self.cache_statement(*key, name.clone());
+                    }
+                    self.prepared_statements.insert(name.clone(), sql.clone());
+                    prepared += 1;
+                }
+                BackendMessage::ErrorResponse(err) if error.is_none() => {
+                    error = Some(PgError::from(err));
+                }
+                BackendMessage::ReadyForQuery(_) => {
+                    return match error {
+                        Some(err) => Err(err),
+                        None => Ok(prepared),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Execute a prepared statement pipeline and return all row data.
     pub async fn pipeline_prepared_results(
         &mut self,
@@ -377,7 +684,7 @@ impl PgConnection {
 
         for params in params_batch {
             PgEncoder::encode_bind_to(&mut buf, &stmt.name, params)
-                .map_err(|e| PgError::Encode(e.to_string()))?;
+                .map_err(PgError::from)?;
             PgEncoder::encode_execute_to(&mut buf);
         }
 
@@ -422,6 +729,61 @@ impl PgConnection {
         }
     }
 
+    /// Execute several different prepared statements in one pipeline round-trip.
+    /// Each item is a statement plus one param set; Bind/Execute pairs are
+    /// interleaved in order before a single Sync, and results come back in the
+    /// same order.
+    pub async fn pipeline_prepared_multi(
+        &mut self,
+        items: &[(&super::PreparedStatement, Vec<Option<Vec<u8>>>)],
+    ) -> PgResult<Vec<Vec<Vec<Option<Vec<u8>>>>>> {
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if let Some((stmt, _)) = items
+            .iter()
+            .find(|(stmt, _)| !self.prepared_statements.contains_key(&stmt.name))
+        {
+            return Err(PgError::Query(format!(
+                "Statement {} not prepared. Call prepare() first.",
+                stmt.name
+            )));
+        }
+
+        let mut buf = BytesMut::with_capacity(items.len() * 64);
+
+        for (stmt, params) in items {
+            PgEncoder::encode_bind_to(&mut buf, &stmt.name, params)
+                .map_err(PgError::from)?;
+            PgEncoder::encode_execute_to(&mut buf);
+        }
+
+        PgEncoder::encode_sync_to(&mut buf);
+
+        self.stream.write_all(&buf).await?;
+        self.stream.flush().await?;
+
+        let mut all_results: Vec<Vec<Vec<Option<Vec<u8>>>>> = Vec::with_capacity(items.len());
+        let mut current_rows: Vec<Vec<Option<Vec<u8>>>> = Vec::new();
+
+        loop {
+            let (msg_type, data) = self.recv_with_data_fast().await?;
+
+            match msg_type {
+                b'D' => {
+                    if let Some(row) = data {
+                        current_rows.push(row);
+                    }
+                }
+                b'C' => all_results.push(std::mem::take(&mut current_rows)),
+                b'n' => all_results.push(Vec::new()),
+                b'Z' if all_results.len() == items.len() => return Ok(all_results),
+                _ => {}
+            }
+        }
+    }
+
     /// ZERO-COPY pipeline execution with Bytes for column data.
     pub async fn pipeline_prepared_zerocopy(
         &mut self,
@@ -442,7 +804,7 @@ impl PgConnection {
 
         for params in params_batch {
             PgEncoder::encode_bind_to(&mut buf, &stmt.name, params)
-                .map_err(|e| PgError::Encode(e.to_string()))?;
+                .map_err(PgError::from)?;
             PgEncoder::encode_execute_to(&mut buf);
         }
 
@@ -507,7 +869,7 @@ impl PgConnection {
 
         for params in params_batch {
             PgEncoder::encode_bind_to(&mut buf, &stmt.name, params)
-                .map_err(|e| PgError::Encode(e.to_string()))?;
+                .map_err(PgError::from)?;
             PgEncoder::encode_execute_to(&mut buf);
         }
 
@@ -548,3 +910,34 @@ impl PgConnection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_bind_params() {
+        let params = encode_bind_params(&[
+            Value::Int(42),
+            Value::String("harbor".to_string()),
+            Value::Null,
+            Value::Bool(true),
+        ])
+        .unwrap();
+        assert_eq!(
+            params,
+            vec![
+                Some(b"42".to_vec()),
+                Some(b"harbor".to_vec()),
+                None,
+                Some(b"t".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_bind_params_rejects_sql_values() {
+        assert!(encode_bind_params(&[Value::Column("id".to_string())]).is_err());
+        assert!(encode_bind_params(&[Value::Param(1)]).is_err());
+    }
+}
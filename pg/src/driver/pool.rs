@@ -147,8 +147,9 @@ impl PooledConnection {
             cmd,
             &mut conn.sql_buf,
             &mut conn.params_buf,
-        );
+        )?;
 
+        conn.log_encoded_query();
         conn.send_bytes(&wire_bytes).await?;
 
         let mut rows: Vec<super::PgRow> = Vec::new();
@@ -180,7 +181,7 @@ impl PooledConnection {
                 }
                 crate::protocol::BackendMessage::ErrorResponse(err) => {
                     if error.is_none() {
-                        error = Some(PgError::Query(err.message));
+                        error = Some(PgError::from(err));
                     }
                 }
                 _ => {}
@@ -191,7 +192,9 @@ impl PooledConnection {
 
 impl Drop for PooledConnection {
     fn drop(&mut self) {
-        if let Some(conn) = self.conn.take() {
+        if let Some(mut conn) = self.conn.take() {
+            // A borrower's query logger must not follow the connection back into the pool
+            conn.query_logger = None;
             let pool = self.pool.clone();
             tokio::spawn(async move {
                 pool.return_connection(conn).await;
@@ -2,8 +2,8 @@
 //!
 //! This module provides query, query_cached, and execute_simple.
 
-use super::{PgConnection, PgError, PgResult};
-use crate::protocol::{BackendMessage, PgEncoder};
+use super::{PgConnection, PgError, PgResult, StatementNaming};
+use crate::protocol::{BackendMessage, PgEncoder, TransactionStatus};
 use bytes::BytesMut;
 use tokio::io::AsyncWriteExt;
 
@@ -19,7 +19,8 @@ impl PgConnection {
         params: &[Option<Vec<u8>>],
     ) -> PgResult<Vec<Vec<Option<Vec<u8>>>>> {
         let bytes = PgEncoder::encode_extended_query(sql, params)
-            .map_err(|e| PgError::Encode(e.to_string()))?;
+            .map_err(PgError::from)?;
+        self.log_query(sql, params.len());
         self.stream.write_all(&bytes).await?;
 
         let mut rows = Vec::new();
@@ -48,7 +49,7 @@ impl PgConnection {
                 }
                 BackendMessage::ErrorResponse(err) => {
                     if error.is_none() {
-                        error = Some(PgError::Query(err.message));
+                        error = Some(PgError::from(err));
                     }
                 }
                 _ => {}
@@ -90,10 +91,11 @@ impl PgConnection {
 
         // Use ULTRA-OPTIMIZED encoders - write directly to buffer
         PgEncoder::encode_bind_to(&mut buf, &stmt_name, params)
-            .map_err(|e| PgError::Encode(e.to_string()))?;
+            .map_err(PgError::from)?;
         PgEncoder::encode_execute_to(&mut buf);
         PgEncoder::encode_sync_to(&mut buf);
 
+        self.log_query(sql, params.len());
         self.stream.write_all(&buf).await?;
 
         let mut rows = Vec::new();
@@ -123,7 +125,7 @@ impl PgConnection {
                 }
                 BackendMessage::ErrorResponse(err) => {
                     if error.is_none() {
-                        error = Some(PgError::Query(err.message));
+                        error = Some(PgError::from(err));
                         // Invalidate cache to prevent "prepared statement does not exist"
                         // on next retry.
                         self.prepared_statements.remove(&stmt_name);
@@ -145,9 +147,34 @@ impl PgConnection {
         format!("s{:016x}", hasher.finish())
     }
 
+    /// Server-side statement name for a statement cache key
+    /// ([`Qail::cache_key`](qail_core::ast::Qail::cache_key)).
+    pub(crate) fn cached_stmt_name(key: u64) -> String {
+        format!("qail_{:x}", key)
+    }
+
+    /// Name for a statement about to be cached under `key`, following
+    /// the connection's [`StatementNaming`].
+    pub(crate) fn next_cached_stmt_name(&mut self, key: u64, cmd: &qail_core::ast::Qail) -> String {
+        match self.statement_naming {
+            StatementNaming::Hashed => Self::cached_stmt_name(key),
+            StatementNaming::Readable => {
+                self.stmt_seq += 1;
+                readable_stmt_name(&cmd.action.to_string(), &cmd.table, self.stmt_seq)
+            }
+        }
+    }
+
     /// Execute a simple SQL statement (no parameters).
     pub(crate) async fn execute_simple(&mut self, sql: &str) -> PgResult<()> {
+        self.execute_simple_status(sql).await.map(|_| ())
+    }
+
+    /// [`execute_simple`](Self::execute_simple), returning the transaction
+    /// status reported by the final `ReadyForQuery`.
+    pub(crate) async fn execute_simple_status(&mut self, sql: &str) -> PgResult<TransactionStatus> {
         let bytes = PgEncoder::encode_query_string(sql);
+        self.log_query(sql, 0);
         self.stream.write_all(&bytes).await?;
 
         let mut error: Option<PgError> = None;
@@ -156,15 +183,15 @@ impl PgConnection {
             let msg = self.recv().await?;
             match msg {
                 BackendMessage::CommandComplete(_) => {}
-                BackendMessage::ReadyForQuery(_) => {
+                BackendMessage::ReadyForQuery(status) => {
                     if let Some(err) = error {
                         return Err(err);
                     }
-                    return Ok(());
+                    return Ok(status);
                 }
                 BackendMessage::ErrorResponse(err) => {
                     if error.is_none() {
-                        error = Some(PgError::Query(err.message));
+                        error = Some(PgError::from(err));
                     }
                 }
                 _ => {}
@@ -201,7 +228,7 @@ impl PgConnection {
 
         // ZERO HASH, ZERO LOOKUP - just encode and send!
         PgEncoder::encode_bind_to(&mut buf, &stmt.name, params)
-            .map_err(|e| PgError::Encode(e.to_string()))?;
+            .map_err(PgError::from)?;
         PgEncoder::encode_execute_to(&mut buf);
         PgEncoder::encode_sync_to(&mut buf);
 
@@ -231,7 +258,7 @@ impl PgConnection {
                 }
                 BackendMessage::ErrorResponse(err) => {
                     if error.is_none() {
-                        error = Some(PgError::Query(err.message));
+                        error = Some(PgError::from(err));
                     }
                 }
                 _ => {}
@@ -239,3 +266,36 @@ impl PgConnection {
         }
     }
 }
+
+/// Longest identifier PostgreSQL keeps (NAMEDATALEN - 1); longer names are
+/// truncated by the server, which could make two statements collide.
+const MAX_STMT_NAME_LEN: usize = 63;
+
+/// `qail_{action}_{table}_{seq}`, lowercased, with non-identifier characters
+/// replaced by `_` and the table shortened so the sequence always survives.
+fn readable_stmt_name(action: &str, table: &str, seq: u64) -> String {
+    let prefix = format!("qail_{}_", action.to_ascii_lowercase());
+    let suffix = format!("_{}", seq);
+    let room = MAX_STMT_NAME_LEN.saturating_sub(prefix.len() + suffix.len());
+    let table: String = table
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .take(room)
+        .collect();
+    format!("{}{}{}", prefix, table, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::readable_stmt_name;
+
+    #[test]
+    fn test_readable_stmt_name() {
+        assert_eq!(readable_stmt_name("GET", "users", 1), "qail_get_users_1");
+        assert_eq!(readable_stmt_name("SET", "Public.Users", 12), "qail_set_public_users_12");
+
+        let long = readable_stmt_name("GET", &"t".repeat(100), 123);
+        assert_eq!(long.len(), 63);
+        assert!(long.ends_with("_123"));
+    }
+}
@@ -4,6 +4,7 @@
 //! PostgreSQL Simple Query protocol returns all values as text format.
 
 use super::PgRow;
+use crate::types::{FromPg, TypeError};
 
 /// Trait for types that can be constructed from a database row.
 /// 
@@ -147,6 +148,31 @@ impl PgRow {
         crate::protocol::types::decode_int_array(s).ok()
     }
 
+    /// Decode a column into any `FromPg` type.
+    /// NULL yields `Ok(None)` for `Option<T>` and `TypeError::UnexpectedNull`
+    /// otherwise; an out-of-range index is `TypeError::ColumnNotFound`.
+    /// # Example
+    /// ```ignore
+    /// let bio: Option<String> = row.try_get(2)?;
+    /// ```
+    pub fn try_get<T: FromPg>(&self, idx: usize) -> Result<T, TypeError> {
+        let value = self.columns.get(idx).ok_or(TypeError::ColumnNotFound(idx))?;
+        let Some(bytes) = value else {
+            return T::from_pg_null();
+        };
+        let (oid, format) = self
+            .column_info
+            .as_ref()
+            .map(|info| {
+                (
+                    info.oids.get(idx).copied().unwrap_or(0),
+                    info.formats.get(idx).copied().unwrap_or(0),
+                )
+            })
+            .unwrap_or((0, 0));
+        T::from_pg(bytes, oid, format)
+    }
+
     // ==================== ERGONOMIC SHORTCUTS ====================
     // These methods reduce boilerplate by providing sensible defaults
 
@@ -243,6 +269,15 @@ impl PgRow {
         self.get_json(self.column_index(name)?)
     }
 
+    /// Decode a column by name; see [`PgRow::try_get`].
+    /// An unknown name is reported as `TypeError::UnknownColumn`.
+    pub fn try_get_by_name<T: FromPg>(&self, name: &str) -> Result<T, TypeError> {
+        let idx = self
+            .column_index(name)
+            .ok_or_else(|| TypeError::UnknownColumn(name.to_string()))?;
+        self.try_get(idx)
+    }
+
     /// Check if a column is NULL by name.
     pub fn is_null_by_name(&self, name: &str) -> bool {
         self.column_index(name)
@@ -312,4 +347,21 @@ mod tests {
         assert!(row.is_null(1));
         assert!(row.is_null(99)); // Out of bounds
     }
+
+    #[test]
+    fn test_try_get_null_vs_missing() {
+        let row = PgRow {
+            columns: vec![Some(b"42".to_vec()), None],
+            column_info: None,
+        };
+
+        assert_eq!(row.try_get::<Option<i32>>(0).unwrap(), Some(42));
+        assert_eq!(row.try_get::<Option<i32>>(1).unwrap(), None);
+        assert_eq!(row.try_get::<i32>(0).unwrap(), 42);
+        assert!(matches!(row.try_get::<i32>(1), Err(TypeError::UnexpectedNull)));
+        assert!(matches!(
+            row.try_get::<Option<i32>>(5),
+            Err(TypeError::ColumnNotFound(5))
+        ));
+    }
 }
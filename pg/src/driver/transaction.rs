@@ -1,6 +1,31 @@
 //! Transaction control methods for PostgreSQL connection.
 
-use super::{PgConnection, PgResult};
+use super::{PgConnection, PgError, PgResult};
+use crate::protocol::TransactionStatus;
+use qail_core::transpiler::escape_identifier;
+
+/// Transaction isolation level for [`PgConnection::begin_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IsolationLevel {
+    /// PostgreSQL's default: each statement sees data committed before it began
+    #[default]
+    ReadCommitted,
+    /// All statements see a snapshot taken at the first statement
+    RepeatableRead,
+    /// Repeatable read plus detection of serialization anomalies
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// SQL keyword form, e.g. `REPEATABLE READ`.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
 
 impl PgConnection {
     /// Begin a new transaction.
@@ -10,6 +35,27 @@ impl PgConnection {
         self.execute_simple("BEGIN").await
     }
 
+    /// Begin a read-only transaction.
+    /// Writes inside it fail at the server, which suits replica reads.
+    pub async fn begin_read_only(&mut self) -> PgResult<()> {
+        self.execute_simple("BEGIN TRANSACTION READ ONLY").await
+    }
+
+    /// Begin a transaction with an explicit isolation level and access mode.
+    pub async fn begin_with(
+        &mut self,
+        isolation: IsolationLevel,
+        read_only: bool,
+    ) -> PgResult<()> {
+        let mode = if read_only { "READ ONLY" } else { "READ WRITE" };
+        self.execute_simple(&format!(
+            "BEGIN TRANSACTION ISOLATION LEVEL {} {}",
+            isolation.as_sql(),
+            mode
+        ))
+        .await
+    }
+
     /// Commit the current transaction.
     /// Makes all changes since `begin_transaction()` permanent.
     pub async fn commit(&mut self) -> PgResult<()> {
@@ -42,4 +88,30 @@ impl PgConnection {
         self.execute_simple(&format!("RELEASE SAVEPOINT {}", name))
             .await
     }
+
+    /// Defer all `DEFERRABLE` constraints until `commit()`.
+    /// Errors if no transaction is open.
+    pub async fn defer_constraints(&mut self) -> PgResult<()> {
+        self.set_constraints_deferred("ALL").await
+    }
+
+    /// Defer one `DEFERRABLE` constraint (optionally schema-qualified) until `commit()`.
+    /// Errors if no transaction is open.
+    pub async fn defer_constraint(&mut self, name: &str) -> PgResult<()> {
+        self.set_constraints_deferred(&escape_identifier(name)).await
+    }
+
+    async fn set_constraints_deferred(&mut self, target: &str) -> PgResult<()> {
+        let status = self
+            .execute_simple_status(&format!("SET CONSTRAINTS {} DEFERRED", target))
+            .await?;
+        // Outside a transaction PostgreSQL only warns, and the setting is lost
+        if matches!(status, TransactionStatus::Idle) {
+            return Err(PgError::Query(
+                "SET CONSTRAINTS is only valid inside a transaction; call begin() first"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
@@ -12,8 +12,10 @@ pub mod protocol;
 pub mod types;
 
 pub use driver::{
-    PgConnection, PgDriver, PgDriverBuilder, PgError, PgPool, PgResult, PgRow, PoolConfig, PoolStats,
-    PooledConnection, QailRow,
+    ConnectTiming, ConnectUrl, DatabaseError, IsolationLevel, PgConnection, PgCursor, PgDriver, PgDriverBuilder, PgError, PgPool, PgResult, PgRow, PipelineQuery, PoolConfig, PoolStats,
+    PooledConnection, QailRow, QueryLogger, SslMode, StatementNaming,
 };
+#[cfg(feature = "pipeline_timing")]
+pub use driver::PipelineTiming;
 pub use protocol::PgEncoder;
-pub use types::{Date, FromPg, Json, Numeric, Time, Timestamp, ToPg, TypeError, Uuid};
+pub use types::{Composite, Date, FromPg, FromPgEnum, Json, Numeric, Time, Timestamp, ToPg, TypeError, Uuid};
@@ -7,12 +7,12 @@ use qail_core::ast::{Action, Qail};
 
 use super::dml::{encode_delete, encode_insert, encode_select, encode_update};
 
-use crate::protocol::EncodeError;
+use crate::protocol::{EncodeError, MAX_BIND_PARAMS};
 
 /// Build Extended Query protocol: Parse + Bind + Describe + Execute + Sync.
 /// Includes Describe to get RowDescription (column metadata).
 pub fn build_extended_query(sql: &[u8], params: &[Option<Vec<u8>>]) -> Result<BytesMut, EncodeError> {
-    if params.len() > i16::MAX as usize {
+    if params.len() > MAX_BIND_PARAMS {
         return Err(EncodeError::TooManyParameters(params.len()));
     }
 
@@ -41,7 +41,7 @@ pub fn build_extended_query(sql: &[u8], params: &[Option<Vec<u8>>]) -> Result<By
     buf.extend_from_slice(&[0]); // Unnamed portal
     buf.extend_from_slice(&[0]); // Unnamed statement
     buf.extend_from_slice(&0i16.to_be_bytes()); // Format codes
-    buf.extend_from_slice(&(params.len() as i16).to_be_bytes());
+    buf.extend_from_slice(&(params.len() as u16).to_be_bytes());
     for param in params {
         match param {
             None => buf.extend_from_slice(&(-1i32).to_be_bytes()),
@@ -72,8 +72,9 @@ pub fn build_extended_query(sql: &[u8], params: &[Option<Vec<u8>>]) -> Result<By
     Ok(buf)
 }
 
-/// Encode multiple Qails as a pipeline batch.
-pub fn encode_batch(cmds: &[Qail]) -> BytesMut {
+/// Encode get/add/set/del Qails as a pipeline batch, failing on any other
+/// action or a command that cannot be encoded.
+pub fn encode_batch(cmds: &[Qail]) -> Result<BytesMut, EncodeError> {
     let mut total_buf = BytesMut::with_capacity(cmds.len() * 256);
 
     for cmd in cmds {
@@ -81,12 +82,17 @@ pub fn encode_batch(cmds: &[Qail]) -> BytesMut {
         let mut params: Vec<Option<Vec<u8>>> = Vec::new();
 
         match cmd.action {
-            Action::Get => encode_select(cmd, &mut sql_buf, &mut params),
-            Action::Add => encode_insert(cmd, &mut sql_buf, &mut params),
-            Action::Set => encode_update(cmd, &mut sql_buf, &mut params),
-            Action::Del => encode_delete(cmd, &mut sql_buf, &mut params),
-            _ => panic!("Unsupported action {:?} in AST-native batch encoder.", cmd.action),
-        }.ok();
+            Action::Get => encode_select(cmd, &mut sql_buf, &mut params)?,
+            Action::Add => encode_insert(cmd, &mut sql_buf, &mut params)?,
+            Action::Set => encode_update(cmd, &mut sql_buf, &mut params)?,
+            Action::Del => encode_delete(cmd, &mut sql_buf, &mut params)?,
+            action => return Err(EncodeError::UnsupportedAction(action)),
+        }
+
+        super::helpers::check_param_mix(cmd, &params)?;
+        if params.len() > MAX_BIND_PARAMS {
+            return Err(EncodeError::TooManyParameters(params.len()));
+        }
 
         let sql_bytes = sql_buf.freeze();
         let params_size: usize = params
@@ -110,7 +116,7 @@ pub fn encode_batch(cmds: &[Qail]) -> BytesMut {
         total_buf.extend_from_slice(&[0]);
         total_buf.extend_from_slice(&[0]);
         total_buf.extend_from_slice(&0i16.to_be_bytes());
-        total_buf.extend_from_slice(&(params.len() as i16).to_be_bytes());
+        total_buf.extend_from_slice(&(params.len() as u16).to_be_bytes());
         for param in &params {
             match param {
                 None => total_buf.extend_from_slice(&(-1i32).to_be_bytes()),
@@ -132,11 +138,11 @@ pub fn encode_batch(cmds: &[Qail]) -> BytesMut {
     // Single SYNC at the end
     total_buf.extend_from_slice(&[b'S', 0, 0, 0, 4]);
 
-    total_buf
+    Ok(total_buf)
 }
 
-/// Encode multiple Qails using Simple Query Protocol.
-pub fn encode_batch_simple(cmds: &[Qail]) -> BytesMut {
+/// Encode get/add/set/del Qails using Simple Query Protocol.
+pub fn encode_batch_simple(cmds: &[Qail]) -> Result<BytesMut, EncodeError> {
     let estimated_sql_size = cmds.len() * 48;
     let mut total_buf = BytesMut::with_capacity(5 + estimated_sql_size + 1);
 
@@ -148,12 +154,12 @@ pub fn encode_batch_simple(cmds: &[Qail]) -> BytesMut {
         params.clear();
 
         match cmd.action {
-            Action::Get => encode_select(cmd, &mut total_buf, &mut params),
-            Action::Add => encode_insert(cmd, &mut total_buf, &mut params),
-            Action::Set => encode_update(cmd, &mut total_buf, &mut params),
-            Action::Del => encode_delete(cmd, &mut total_buf, &mut params),
-            _ => panic!("Unsupported action {:?}", cmd.action),
-        }.ok();
+            Action::Get => encode_select(cmd, &mut total_buf, &mut params)?,
+            Action::Add => encode_insert(cmd, &mut total_buf, &mut params)?,
+            Action::Set => encode_update(cmd, &mut total_buf, &mut params)?,
+            Action::Del => encode_delete(cmd, &mut total_buf, &mut params)?,
+            action => return Err(EncodeError::UnsupportedAction(action)),
+        }
         total_buf.extend_from_slice(b";");
     }
 
@@ -162,5 +168,5 @@ pub fn encode_batch_simple(cmds: &[Qail]) -> BytesMut {
     let msg_len = (total_buf.len() - 1) as i32;
     total_buf[1..5].copy_from_slice(&msg_len.to_be_bytes());
 
-    total_buf
+    Ok(total_buf)
 }
@@ -5,6 +5,8 @@
 use bytes::BytesMut;
 use qail_core::ast::{Constraint, Expr, Qail, TableConstraint};
 
+use super::helpers::write_table_ref;
+
 /// Map QAIL types to PostgreSQL types.
 #[inline]
 pub fn map_type(t: &str) -> &'static str {
@@ -30,7 +32,10 @@ pub fn map_type(t: &str) -> &'static str {
 /// Encode CREATE TABLE statement.
 pub fn encode_make(cmd: &Qail, buf: &mut BytesMut) {
     buf.extend_from_slice(b"CREATE TABLE ");
-    buf.extend_from_slice(cmd.table.as_bytes());
+    if cmd.if_not_exists {
+        buf.extend_from_slice(b"IF NOT EXISTS ");
+    }
+    write_table_ref(cmd, &cmd.table, buf);
     buf.extend_from_slice(b" (");
 
     let mut first = true;
@@ -92,7 +97,7 @@ pub fn encode_make(cmd: &Qail, buf: &mut BytesMut) {
                             buf.extend_from_slice(b", ");
                         }
                         buf.extend_from_slice(b"'");
-                        buf.extend_from_slice(v.as_bytes());
+                        buf.extend_from_slice(v.replace('\'', "''").as_bytes());
                         buf.extend_from_slice(b"'");
                     }
                     buf.extend_from_slice(b"))");
@@ -139,6 +144,9 @@ pub fn encode_index(cmd: &Qail, buf: &mut BytesMut) {
         } else {
             buf.extend_from_slice(b"CREATE INDEX ");
         }
+        if cmd.if_not_exists {
+            buf.extend_from_slice(b"IF NOT EXISTS ");
+        }
         buf.extend_from_slice(idx.name.as_bytes());
         buf.extend_from_slice(b" ON ");
         buf.extend_from_slice(idx.table.as_bytes());
@@ -156,7 +164,19 @@ pub fn encode_index(cmd: &Qail, buf: &mut BytesMut) {
 /// Encode DROP TABLE statement.
 pub fn encode_drop_table(cmd: &Qail, buf: &mut BytesMut) {
     buf.extend_from_slice(b"DROP TABLE IF EXISTS ");
-    buf.extend_from_slice(cmd.table.as_bytes());
+    write_table_ref(cmd, &cmd.table, buf);
+}
+
+/// Encode TRUNCATE TABLE statement.
+pub fn encode_truncate(cmd: &Qail, buf: &mut BytesMut) {
+    buf.extend_from_slice(b"TRUNCATE TABLE ");
+    write_table_ref(cmd, &cmd.table, buf);
+    if cmd.restart_identity {
+        buf.extend_from_slice(b" RESTART IDENTITY");
+    }
+    if cmd.cascade {
+        buf.extend_from_slice(b" CASCADE");
+    }
 }
 
 /// Encode DROP INDEX statement.
@@ -170,7 +190,7 @@ pub fn encode_alter_add_column(cmd: &Qail, buf: &mut BytesMut) {
     for col in &cmd.columns {
         if let Expr::Def { name, data_type, constraints } = col {
             buf.extend_from_slice(b"ALTER TABLE ");
-            buf.extend_from_slice(cmd.table.as_bytes());
+            write_table_ref(cmd, &cmd.table, buf);
             buf.extend_from_slice(b" ADD COLUMN ");
             buf.extend_from_slice(name.as_bytes());
             buf.extend_from_slice(b" ");
@@ -204,7 +224,7 @@ pub fn encode_alter_drop_column(cmd: &Qail, buf: &mut BytesMut) {
             _ => continue,
         };
         buf.extend_from_slice(b"ALTER TABLE ");
-        buf.extend_from_slice(cmd.table.as_bytes());
+        write_table_ref(cmd, &cmd.table, buf);
         buf.extend_from_slice(b" DROP COLUMN ");
         buf.extend_from_slice(col_name.as_bytes());
     }
@@ -215,7 +235,7 @@ pub fn encode_alter_column_type(cmd: &Qail, buf: &mut BytesMut) {
     for col in &cmd.columns {
         if let Expr::Def { name, data_type, .. } = col {
             buf.extend_from_slice(b"ALTER TABLE ");
-            buf.extend_from_slice(cmd.table.as_bytes());
+            write_table_ref(cmd, &cmd.table, buf);
             buf.extend_from_slice(b" ALTER COLUMN ");
             buf.extend_from_slice(name.as_bytes());
             buf.extend_from_slice(b" TYPE ");
@@ -3,13 +3,19 @@
 //! SELECT, INSERT, UPDATE, DELETE, EXPORT, and CTE statements.
 
 use bytes::BytesMut;
-use qail_core::ast::{CTEDef, CageKind, Expr, GroupByMode, JoinKind, Qail, SortOrder};
+use qail_core::ast::{CTEDef, CageKind, Expr, GroupByMode, JoinKind, LogicalOp, Qail, SortOrder};
 
-use super::helpers::write_usize;
+use super::helpers::{write_table_ref, write_usize};
 use super::values::{encode_columns, encode_conditions, encode_expr, encode_join_value, encode_value};
 
 /// Encode SELECT statement directly to bytes.
 pub fn encode_select(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
+    write_tag_comment(cmd, buf);
+    encode_select_body(cmd, buf, params)
+}
+
+/// SELECT without the leading tag comment.
+fn encode_select_body(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
     // CTE prefix
     encode_cte_prefix(cmd, buf, params);
 
@@ -34,7 +40,15 @@ pub fn encode_select(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
 
     // FROM
     buf.extend_from_slice(b" FROM ");
-    buf.extend_from_slice(cmd.table.as_bytes());
+    if let Some(ref derived) = cmd.source_query {
+        // Derived table: shares our params, numbered before the outer WHERE
+        buf.extend_from_slice(b"(");
+        encode_select(derived, buf, params)?;
+        buf.extend_from_slice(b") AS ");
+        buf.extend_from_slice(cmd.table.as_bytes());
+    } else {
+        write_table_ref(cmd, &cmd.table, buf);
+    }
 
     // JOINs
     for join in &cmd.joins {
@@ -46,13 +60,21 @@ pub fn encode_select(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
             JoinKind::Cross => buf.extend_from_slice(b" CROSS JOIN "),
             JoinKind::Lateral => buf.extend_from_slice(b" LEFT JOIN LATERAL "),
         }
-        buf.extend_from_slice(join.table.as_bytes());
+        match &join.subquery {
+            // Correlated subquery, sharing our params like a derived table
+            Some(query) => {
+                buf.extend_from_slice(b"(");
+                encode_select(query, buf, params)?;
+                buf.extend_from_slice(b") AS ");
+                buf.extend_from_slice(join.table.as_bytes());
+            }
+            None => write_table_ref(cmd, &join.table, buf),
+        }
 
-        if join.on_true {
+        let conditions = join.on.as_deref().filter(|c| !c.is_empty());
+        if join.on_true || (join.subquery.is_some() && conditions.is_none()) {
             buf.extend_from_slice(b" ON TRUE");
-        } else if let Some(conditions) = &join.on
-            && !conditions.is_empty()
-        {
+        } else if let Some(conditions) = conditions {
             buf.extend_from_slice(b" ON ");
             for (i, cond) in conditions.iter().enumerate() {
                 if i > 0 {
@@ -66,13 +88,7 @@ pub fn encode_select(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
     }
 
     // WHERE
-    let filter_cage = cmd.cages.iter().find(|c| c.kind == CageKind::Filter);
-    if let Some(cage) = filter_cage
-        && !cage.conditions.is_empty()
-    {
-        buf.extend_from_slice(b" WHERE ");
-        encode_conditions(&cage.conditions, buf, params)?;
-    }
+    encode_where(cmd, buf, params)?;
 
     // GROUP BY - prefer explicit Partition cage, fall back to auto-extraction from columns
     let partition_cage = cmd.cages.iter().find(|c| c.kind == CageKind::Partition);
@@ -173,22 +189,30 @@ pub fn encode_select(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
                 first = false;
                 encode_expr(&cond.left, buf);
                 match order {
-                    SortOrder::Desc | SortOrder::DescNullsFirst | SortOrder::DescNullsLast => {
-                        buf.extend_from_slice(b" DESC");
-                    }
-                    SortOrder::Asc | SortOrder::AscNullsFirst | SortOrder::AscNullsLast => {}
+                    SortOrder::Asc => {}
+                    SortOrder::Desc => buf.extend_from_slice(b" DESC"),
+                    SortOrder::AscNullsFirst => buf.extend_from_slice(b" NULLS FIRST"),
+                    SortOrder::AscNullsLast => buf.extend_from_slice(b" NULLS LAST"),
+                    SortOrder::DescNullsFirst => buf.extend_from_slice(b" DESC NULLS FIRST"),
+                    SortOrder::DescNullsLast => buf.extend_from_slice(b" DESC NULLS LAST"),
                 }
             }
         }
     }
 
-    // LIMIT
-    for cage in &cmd.cages {
-        if let CageKind::Limit(n) = cage.kind {
+    // LIMIT - the last limit wins, so LIMIT ALL can override a default limit
+    let limit = cmd.cages.iter().rev().find_map(|cage| match cage.kind {
+        CageKind::Limit(n) => Some(Some(n)),
+        CageKind::LimitAll => Some(None),
+        _ => None,
+    });
+    match limit {
+        Some(Some(n)) => {
             buf.extend_from_slice(b" LIMIT ");
             write_usize(buf, n);
-            break;
         }
+        Some(None) => buf.extend_from_slice(b" LIMIT ALL"),
+        None => {}
     }
 
     // OFFSET
@@ -199,6 +223,20 @@ pub fn encode_select(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
             break;
         }
     }
+
+    // FETCH FIRST (after OFFSET)
+    if let Some((count, with_ties)) = cmd.fetch {
+        if with_ties && !cmd.cages.iter().any(|c| matches!(c.kind, CageKind::Sort(_))) {
+            return Err(crate::protocol::EncodeError::WithTiesWithoutOrderBy);
+        }
+        buf.extend_from_slice(b" FETCH FIRST ");
+        write_usize(buf, count as usize);
+        if with_ties {
+            buf.extend_from_slice(b" ROWS WITH TIES");
+        } else {
+            buf.extend_from_slice(b" ROWS ONLY");
+        }
+    }
     Ok(())
 }
 
@@ -241,7 +279,12 @@ fn encode_single_cte(cte: &CTEDef, buf: &mut BytesMut, params: &mut Vec<Option<V
         buf.extend_from_slice(b")");
     }
 
-    buf.extend_from_slice(b" AS (");
+    let as_clause: &[u8] = match cte.materialized {
+        Some(true) => b" AS MATERIALIZED (",
+        Some(false) => b" AS NOT MATERIALIZED (",
+        None => b" AS (",
+    };
+    buf.extend_from_slice(as_clause);
 
     // Encode base query recursively
     encode_select(&cte.base_query, buf, params).ok();
@@ -259,14 +302,16 @@ fn encode_single_cte(cte: &CTEDef, buf: &mut BytesMut, params: &mut Vec<Option<V
 
 /// Encode INSERT statement.
 pub fn encode_insert(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
+    write_tag_comment(cmd, buf);
     buf.extend_from_slice(b"INSERT INTO ");
-    buf.extend_from_slice(cmd.table.as_bytes());
+    write_table_ref(cmd, &cmd.table, buf);
 
     // Find payload cage
     let payload_cage = cmd.cages.iter().find(|c| c.kind == CageKind::Payload);
     
     // Column list - prefer cmd.columns, but extract from conditions if empty (set_value pattern)
-    if !cmd.columns.is_empty() {
+    let has_columns = !cmd.columns.is_empty() && !matches!(cmd.columns.as_slice(), [Expr::Star]);
+    if has_columns {
         buf.extend_from_slice(b" (");
         encode_columns(&cmd.columns, buf);
         buf.extend_from_slice(b")");
@@ -282,8 +327,12 @@ pub fn encode_insert(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
         buf.extend_from_slice(b")");
     }
 
-    // VALUES
-    if let Some(cage) = payload_cage {
+    // INSERT ... SELECT: the source query shares our params, so its
+    // placeholders continue the numbering.
+    if let Some(ref source) = cmd.source_query {
+        buf.extend_from_slice(b" ");
+        encode_select(source, buf, params)?;
+    } else if let Some(cage) = payload_cage {
         buf.extend_from_slice(b" VALUES (");
         for (i, cond) in cage.conditions.iter().enumerate() {
             if i > 0 {
@@ -311,6 +360,16 @@ pub fn encode_insert(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
             }
             buf.extend_from_slice(b") ");
         }
+
+        // Partial-index target: ON CONFLICT (cols) WHERE ...
+        if !on_conflict.target_where.is_empty() {
+            if on_conflict.columns.is_empty() {
+                return Err(crate::protocol::EncodeError::ConflictWhereWithoutColumns);
+            }
+            buf.extend_from_slice(b"WHERE ");
+            encode_conditions(&on_conflict.target_where, buf, params)?;
+            buf.extend_from_slice(b" ");
+        }
         
         // Conflict action
         match &on_conflict.action {
@@ -342,15 +401,17 @@ pub fn encode_insert(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
 
 /// Encode UPDATE statement.
 pub fn encode_update(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
+    write_tag_comment(cmd, buf);
     buf.extend_from_slice(b"UPDATE ");
-    buf.extend_from_slice(cmd.table.as_bytes());
+    write_table_ref(cmd, &cmd.table, buf);
     buf.extend_from_slice(b" SET ");
 
     // SET clause - pair columns with payload values
     if let Some(cage) = cmd.cages.iter().find(|c| c.kind == CageKind::Payload) {
         // Use cmd.columns if available (from .columns([...]).values([...]) pattern)
-        // Otherwise use cage.conditions.left (from .set("col", value) pattern)
-        if !cmd.columns.is_empty() {
+        // Otherwise use cage.conditions.left (from .set("col", value) pattern
+        // and parsed `set ... values col = val`, which leave columns as `*`)
+        if !cmd.columns.is_empty() && !matches!(cmd.columns.as_slice(), [Expr::Star]) {
             // Zip columns with values
             for (i, (col, cond)) in cmd.columns.iter().zip(cage.conditions.iter()).enumerate() {
                 if i > 0 {
@@ -375,35 +436,84 @@ pub fn encode_update(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec
         }
     }
 
+    // FROM (multi-table update)
+    write_aux_tables(cmd, b" FROM ", &cmd.from_tables, buf);
+
     // WHERE
-    if let Some(cage) = cmd.cages.iter().find(|c| c.kind == CageKind::Filter)
-        && !cage.conditions.is_empty()
-    {
-        buf.extend_from_slice(b" WHERE ");
-        encode_conditions(&cage.conditions, buf, params)?;
-    }
+    encode_where(cmd, buf, params)?;
     Ok(())
 }
 
 /// Encode DELETE statement.
 pub fn encode_delete(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
+    write_tag_comment(cmd, buf);
     buf.extend_from_slice(b"DELETE FROM ");
-    buf.extend_from_slice(cmd.table.as_bytes());
+    write_table_ref(cmd, &cmd.table, buf);
+
+    // USING (multi-table delete)
+    write_aux_tables(cmd, b" USING ", &cmd.using_tables, buf);
 
     // WHERE
-    if let Some(cage) = cmd.cages.iter().find(|c| c.kind == CageKind::Filter)
-        && !cage.conditions.is_empty()
-    {
-        buf.extend_from_slice(b" WHERE ");
-        encode_conditions(&cage.conditions, buf, params)?;
+    encode_where(cmd, buf, params)?;
+    Ok(())
+}
+
+/// Encode ` WHERE ...` from every filter cage.
+///
+/// Cages are ANDed together; the conditions of an OR cage are ORed and
+/// parenthesized, as in the transpiler.
+fn encode_where(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
+    let cages = cmd
+        .cages
+        .iter()
+        .filter(|c| c.kind == CageKind::Filter && !c.conditions.is_empty());
+    for (i, cage) in cages.enumerate() {
+        buf.extend_from_slice(if i == 0 { b" WHERE " } else { b" AND " });
+        if cage.logical_op == LogicalOp::Or && cage.conditions.len() > 1 {
+            buf.extend_from_slice(b"(");
+            for (j, cond) in cage.conditions.iter().enumerate() {
+                if j > 0 {
+                    buf.extend_from_slice(b" OR ");
+                }
+                encode_conditions(std::slice::from_ref(cond), buf, params)?;
+            }
+            buf.extend_from_slice(b")");
+        } else {
+            encode_conditions(&cage.conditions, buf, params)?;
+        }
     }
     Ok(())
 }
 
+/// Write the command's tags as a leading `/* ... */` comment, if any.
+/// The comment is part of the SQL text, so differently tagged commands are
+/// prepared as separate statements.
+fn write_tag_comment(cmd: &Qail, buf: &mut BytesMut) {
+    if let Some(comment) = cmd.tag_comment() {
+        buf.extend_from_slice(comment.as_bytes());
+        buf.extend_from_slice(b" ");
+    }
+}
+
+/// Write `USING t1, t2` / `FROM t1, t2` auxiliary tables, if any.
+fn write_aux_tables(cmd: &Qail, keyword: &[u8], tables: &[String], buf: &mut BytesMut) {
+    if tables.is_empty() {
+        return;
+    }
+    buf.extend_from_slice(keyword);
+    for (i, table) in tables.iter().enumerate() {
+        if i > 0 {
+            buf.extend_from_slice(b", ");
+        }
+        write_table_ref(cmd, table, buf);
+    }
+}
+
 /// Encode EXPORT command as COPY (SELECT ...) TO STDOUT.
 pub fn encode_export(cmd: &Qail, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
+    write_tag_comment(cmd, buf);
     buf.extend_from_slice(b"COPY (");
-    encode_select(cmd, buf, params)?;
+    encode_select_body(cmd, buf, params)?;
     buf.extend_from_slice(b") TO STDOUT");
     Ok(())
 }
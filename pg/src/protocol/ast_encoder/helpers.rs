@@ -4,6 +4,12 @@
 //! to avoid heap allocations in the hot path.
 
 use bytes::BytesMut;
+use qail_core::ast::Qail;
+use qail_core::ast::Value;
+use qail_core::ast::visit::Visitor;
+use qail_core::transpiler::escape_identifier;
+
+use crate::protocol::EncodeError;
 
 /// Pre-computed parameter placeholders $1-$99 (covers 99% of cases)
 pub const PARAM_PLACEHOLDERS: [&[u8]; 100] = [
@@ -63,6 +69,16 @@ pub fn write_usize(buf: &mut BytesMut, n: usize) {
     }
 }
 
+/// Write a table reference, prefixed with the command's schema when set.
+#[inline]
+pub fn write_table_ref(cmd: &Qail, table: &str, buf: &mut BytesMut) {
+    if let Some(schema) = cmd.schema_for(table) {
+        buf.extend_from_slice(escape_identifier(schema).as_bytes());
+        buf.extend_from_slice(b".");
+    }
+    buf.extend_from_slice(table.as_bytes());
+}
+
 /// Write i64 to buffer.
 /// Zero allocation for 0-99.
 #[inline(always)]
@@ -87,3 +103,29 @@ pub fn i64_to_bytes(n: i64) -> Vec<u8> {
         n.to_string().into_bytes()
     }
 }
+
+/// Reject a command whose `Value::Param(n)` placeholders would collide with
+/// the `$n` numbers given to its inline values.
+pub fn check_param_mix(cmd: &Qail, params: &[Option<Vec<u8>>]) -> Result<(), EncodeError> {
+    struct HasParam(bool);
+
+    impl Visitor for HasParam {
+        fn visit_value(&mut self, value: &Value) {
+            if matches!(value, Value::Param(_)) {
+                self.0 = true;
+            }
+            qail_core::ast::visit::walk_value(self, value);
+        }
+    }
+
+    if params.is_empty() {
+        return Ok(());
+    }
+    let mut found = HasParam(false);
+    cmd.walk(&mut found);
+    if found.0 {
+        Err(EncodeError::MixedParams)
+    } else {
+        Ok(())
+    }
+}
@@ -19,7 +19,7 @@ mod ddl;
 pub(crate) mod dml;  // pub(crate) for internal use in driver
 pub use crate::protocol::EncodeError;
 mod helpers;
-mod values;
+pub(crate) mod values; // pub(crate) for bind-param encoding in driver
 
 use bytes::BytesMut;
 use qail_core::ast::{Action, Qail};
@@ -29,58 +29,35 @@ pub struct AstEncoder;
 
 impl AstEncoder {
     /// Encode a Qail directly to Extended Query protocol bytes.
-    /// Returns (wire_bytes, extracted_params_as_bytes)
-    pub fn encode_cmd(cmd: &Qail) -> (BytesMut, Vec<Option<Vec<u8>>>) {
+    /// Returns (wire_bytes, extracted_params_as_bytes), or an error if the
+    /// action has no wire form or the command cannot be encoded.
+    pub fn encode_cmd(cmd: &Qail) -> Result<(BytesMut, Vec<Option<Vec<u8>>>), EncodeError> {
         let mut sql_buf = BytesMut::with_capacity(256);
         let mut params: Vec<Option<Vec<u8>>> = Vec::new();
-
-        match cmd.action {
-            Action::Get | Action::With => { dml::encode_select(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Add => { dml::encode_insert(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Set => { dml::encode_update(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Del => { dml::encode_delete(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Export => { dml::encode_export(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Make => ddl::encode_make(cmd, &mut sql_buf),
-            Action::Index => ddl::encode_index(cmd, &mut sql_buf),
-            Action::Drop => ddl::encode_drop_table(cmd, &mut sql_buf),
-            Action::DropIndex => ddl::encode_drop_index(cmd, &mut sql_buf),
-            Action::Alter => ddl::encode_alter_add_column(cmd, &mut sql_buf),
-            Action::AlterDrop => ddl::encode_alter_drop_column(cmd, &mut sql_buf),
-            Action::AlterType => ddl::encode_alter_column_type(cmd, &mut sql_buf),
-            Action::CreateView => ddl::encode_create_view(cmd, &mut sql_buf, &mut params),
-            Action::DropView => ddl::encode_drop_view(cmd, &mut sql_buf),
-            _ => panic!(
-                "Unsupported action {:?} in AST-native encoder. Use legacy encoder for DDL.",
-                cmd.action
-            ),
-        }
-
-        let sql_bytes = sql_buf.freeze();
-        let wire = batch::build_extended_query(&sql_bytes, &params)
-            .expect("Parameter limit exceeded in AST encoder");
-
-        (wire, params)
+        let wire = Self::encode_cmd_reuse(cmd, &mut sql_buf, &mut params)?;
+        Ok((wire, params))
     }
 
     /// Encode a Qail using CALLER'S BUFFERS (ZERO-ALLOC).
     /// Clears and reuses the provided buffers to avoid allocations.
-    /// Returns wire protocol bytes ready to send.
+    /// Returns wire protocol bytes ready to send, or an error if the command
+    /// cannot be encoded (e.g. more than `MAX_BIND_PARAMS` bind parameters).
     #[inline]
     pub fn encode_cmd_reuse(
         cmd: &Qail,
         sql_buf: &mut BytesMut,
         params: &mut Vec<Option<Vec<u8>>>,
-    ) -> BytesMut {
+    ) -> Result<BytesMut, EncodeError> {
         // Clear buffers (but keep capacity!)
         sql_buf.clear();
         params.clear();
 
         match cmd.action {
-            Action::Get | Action::With => { dml::encode_select(cmd, sql_buf, params).ok(); }
-            Action::Add => { dml::encode_insert(cmd, sql_buf, params).ok(); }
-            Action::Set => { dml::encode_update(cmd, sql_buf, params).ok(); }
-            Action::Del => { dml::encode_delete(cmd, sql_buf, params).ok(); }
-            Action::Export => { dml::encode_export(cmd, sql_buf, params).ok(); }
+            Action::Get | Action::With => dml::encode_select(cmd, sql_buf, params)?,
+            Action::Add => dml::encode_insert(cmd, sql_buf, params)?,
+            Action::Set => dml::encode_update(cmd, sql_buf, params)?,
+            Action::Del => dml::encode_delete(cmd, sql_buf, params)?,
+            Action::Export => dml::encode_export(cmd, sql_buf, params)?,
             Action::Make => ddl::encode_make(cmd, sql_buf),
             Action::Index => ddl::encode_index(cmd, sql_buf),
             Action::Drop => ddl::encode_drop_table(cmd, sql_buf),
@@ -90,52 +67,56 @@ impl AstEncoder {
             Action::AlterType => ddl::encode_alter_column_type(cmd, sql_buf),
             Action::CreateView => ddl::encode_create_view(cmd, sql_buf, params),
             Action::DropView => ddl::encode_drop_view(cmd, sql_buf),
-            _ => panic!(
-                "Unsupported action {:?} in AST-native encoder.",
-                cmd.action
-            ),
+            Action::Truncate => ddl::encode_truncate(cmd, sql_buf),
+            action => return Err(EncodeError::UnsupportedAction(action)),
         }
 
+        helpers::check_param_mix(cmd, params)?;
+
         // Build wire protocol (reuses internal allocation in batch module)
         batch::build_extended_query(sql_buf, params)
-            .expect("Parameter limit exceeded in AST encoder")
     }
 
     /// Encode a Qail to SQL string + params (for prepared statement caching).
-    pub fn encode_cmd_sql(cmd: &Qail) -> (String, Vec<Option<Vec<u8>>>) {
+    pub fn encode_cmd_sql(cmd: &Qail) -> Result<(String, Vec<Option<Vec<u8>>>), EncodeError> {
         let mut sql_buf = BytesMut::with_capacity(256);
         let mut params: Vec<Option<Vec<u8>>> = Vec::new();
 
         match cmd.action {
-            Action::Get | Action::With => { dml::encode_select(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Add => { dml::encode_insert(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Set => { dml::encode_update(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Del => { dml::encode_delete(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Export => { dml::encode_export(cmd, &mut sql_buf, &mut params).ok(); }
+            Action::Get | Action::With => dml::encode_select(cmd, &mut sql_buf, &mut params)?,
+            Action::Add => dml::encode_insert(cmd, &mut sql_buf, &mut params)?,
+            Action::Set => dml::encode_update(cmd, &mut sql_buf, &mut params)?,
+            Action::Del => dml::encode_delete(cmd, &mut sql_buf, &mut params)?,
+            Action::Export => dml::encode_export(cmd, &mut sql_buf, &mut params)?,
             Action::Make => ddl::encode_make(cmd, &mut sql_buf),
             Action::Index => ddl::encode_index(cmd, &mut sql_buf),
-            _ => panic!("Unsupported action {:?} in AST-native encoder.", cmd.action),
+            Action::Truncate => ddl::encode_truncate(cmd, &mut sql_buf),
+            action => return Err(EncodeError::UnsupportedAction(action)),
         }
 
+        helpers::check_param_mix(cmd, &params)?;
+
         let sql = String::from_utf8_lossy(&sql_buf).to_string();
-        (sql, params)
+        Ok((sql, params))
     }
 
     /// Extract ONLY params from a Qail (for reusing cached SQL template).
+    /// Actions other than get/add/set/del have no params.
     #[inline]
-    pub fn encode_cmd_params_only(cmd: &Qail) -> Vec<Option<Vec<u8>>> {
+    pub fn encode_cmd_params_only(cmd: &Qail) -> Result<Vec<Option<Vec<u8>>>, EncodeError> {
         let mut sql_buf = BytesMut::with_capacity(256);
         let mut params: Vec<Option<Vec<u8>>> = Vec::new();
 
         match cmd.action {
-            Action::Get => { dml::encode_select(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Add => { dml::encode_insert(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Set => { dml::encode_update(cmd, &mut sql_buf, &mut params).ok(); }
-            Action::Del => { dml::encode_delete(cmd, &mut sql_buf, &mut params).ok(); }
+            Action::Get => dml::encode_select(cmd, &mut sql_buf, &mut params)?,
+            Action::Add => dml::encode_insert(cmd, &mut sql_buf, &mut params)?,
+            Action::Set => dml::encode_update(cmd, &mut sql_buf, &mut params)?,
+            Action::Del => dml::encode_delete(cmd, &mut sql_buf, &mut params)?,
             _ => {}
         }
 
-        params
+        helpers::check_param_mix(cmd, &params)?;
+        Ok(params)
     }
 
     /// Generate just SQL bytes for a SELECT statement.
@@ -143,18 +124,20 @@ impl AstEncoder {
         cmd: &Qail,
         buf: &mut BytesMut,
         params: &mut Vec<Option<Vec<u8>>>,
-    ) {
-        dml::encode_select(cmd, buf, params).ok();
+    ) -> Result<(), EncodeError> {
+        dml::encode_select(cmd, buf, params)
     }
 
-    /// Encode multiple Qails as a pipeline batch.
-    pub fn encode_batch(cmds: &[Qail]) -> BytesMut {
+    /// Encode multiple get/add/set/del Qails as a pipeline batch. Fails on any
+    /// other action or a command that cannot be encoded (e.g. more than
+    /// `MAX_BIND_PARAMS` bind parameters).
+    pub fn encode_batch(cmds: &[Qail]) -> Result<BytesMut, EncodeError> {
         batch::encode_batch(cmds)
     }
 
-    /// Encode multiple Qails using Simple Query Protocol.
+    /// Encode multiple get/add/set/del Qails using Simple Query Protocol.
     #[inline]
-    pub fn encode_batch_simple(cmds: &[Qail]) -> BytesMut {
+    pub fn encode_batch_simple(cmds: &[Qail]) -> Result<BytesMut, EncodeError> {
         batch::encode_batch_simple(cmds)
     }
 }
@@ -167,7 +150,7 @@ mod tests {
     fn test_encode_select() {
         let cmd = Qail::get("users").columns(["id", "name"]);
 
-        let (wire, params) = AstEncoder::encode_cmd(&cmd);
+        let (wire, params) = AstEncoder::encode_cmd(&cmd).unwrap();
 
         let wire_str = String::from_utf8_lossy(&wire);
         assert!(wire_str.contains("SELECT"));
@@ -183,7 +166,7 @@ mod tests {
             .columns(["id", "name"])
             .filter("active", Operator::Eq, true);
 
-        let (wire, params) = AstEncoder::encode_cmd(&cmd);
+        let (wire, params) = AstEncoder::encode_cmd(&cmd).unwrap();
 
         let wire_str = String::from_utf8_lossy(&wire);
         assert!(wire_str.contains("WHERE"));
@@ -195,7 +178,7 @@ mod tests {
     fn test_encode_export() {
         let cmd = Qail::export("users").columns(["id", "name"]);
 
-        let (sql, _params) = AstEncoder::encode_cmd_sql(&cmd);
+        let (sql, _params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
 
         assert!(sql.starts_with("COPY (SELECT"));
         assert!(sql.contains("FROM users"));
@@ -210,7 +193,7 @@ mod tests {
             .columns(["id", "name"])
             .filter("active", Operator::Eq, true);
 
-        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
 
         assert!(sql.contains("COPY (SELECT"));
         assert!(sql.contains("WHERE"));
@@ -229,7 +212,7 @@ mod tests {
 
         let cmd = Qail::get("active_users").with("active_users", users_query);
 
-        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
 
         assert!(sql.starts_with("WITH active_users"), "SQL should start with WITH: {}", sql);
         assert!(sql.contains("AS (SELECT id, name FROM users"), "CTE should have subquery: {}", sql);
@@ -237,6 +220,472 @@ mod tests {
         assert_eq!(params.len(), 1);
     }
 
+    #[test]
+    fn test_encode_schema_qualified() {
+        let cmd = Qail::get("users")
+            .schema("tenant_42")
+            .columns(["id"])
+            .join(qail_core::ast::JoinKind::Left, "orders", "users.id", "orders.user_id");
+
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert!(sql.contains("FROM tenant_42.users"), "SQL: {}", sql);
+        assert!(sql.contains("LEFT JOIN tenant_42.orders"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_encode_delete_using_update_from() {
+        let cmd = Qail::del("orders")
+            .using("customers")
+            .filter("orders.customer_id", qail_core::ast::Operator::Eq, 7);
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert!(sql.starts_with("DELETE FROM orders USING customers WHERE"), "SQL: {}", sql);
+
+        let cmd = Qail::set("orders")
+            .set_value("status", "vip")
+            .from("customers")
+            .filter("orders.customer_id", qail_core::ast::Operator::Eq, 7);
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert!(sql.contains(" FROM customers WHERE"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_encode_update_expression_and_subquery_values() {
+        let cmd = qail_core::parser::parse(
+            "set users values hits = hits + 1, \
+             total = (get orders fields sum(amount) where user_id = users.id and status = 'paid') \
+             where id = 7",
+        )
+        .unwrap();
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "UPDATE users SET hits = (hits + 1), \
+             total = (SELECT SUM(amount) FROM orders WHERE user_id = users.id AND status = $1) \
+             WHERE id = $2"
+        );
+        assert_eq!(params, vec![Some(b"paid".to_vec()), Some(b"7".to_vec())]);
+    }
+
+    #[test]
+    fn test_encode_too_many_bind_params() {
+        let cmd = Qail::add("events").values((0..70_000).map(|i| i as i64));
+        let mut sql_buf = BytesMut::new();
+        let mut params = Vec::new();
+
+        let err = AstEncoder::encode_cmd_reuse(&cmd, &mut sql_buf, &mut params).unwrap_err();
+        assert_eq!(err, EncodeError::TooManyParameters(70_000));
+
+        let err = crate::PgError::from(err);
+        assert!(matches!(err, crate::PgError::Protocol(_)));
+        assert_eq!(err.to_string(), "Protocol error: too many bind parameters: 70000 > 65535");
+
+        let batch = AstEncoder::encode_batch(std::slice::from_ref(&cmd));
+        assert!(matches!(batch, Err(EncodeError::TooManyParameters(70_000))));
+
+        // Counts above i16::MAX are still valid on the wire.
+        let cmd = Qail::add("events").values((0..40_000).map(|i| i as i64));
+        assert!(AstEncoder::encode_cmd_reuse(&cmd, &mut sql_buf, &mut params).is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_action_is_an_error() {
+        let cmd = Qail::listen("events");
+        let unsupported = Err(EncodeError::UnsupportedAction(Action::Listen));
+
+        assert_eq!(AstEncoder::encode_cmd(&cmd).map(|_| ()), unsupported);
+        assert_eq!(AstEncoder::encode_cmd_sql(&cmd).map(|_| ()), unsupported);
+        let batch = [Qail::get("events"), cmd];
+        assert_eq!(AstEncoder::encode_batch(&batch).map(|_| ()), unsupported);
+        assert_eq!(AstEncoder::encode_batch_simple(&batch).map(|_| ()), unsupported);
+
+        // Only queries can be subqueries
+        use qail_core::ast::{Operator, Value};
+        let cmd = Qail::get("users").columns(["id"]).filter(
+            "id",
+            Operator::In,
+            Value::Subquery(Box::new(Qail::del("bans"))),
+        );
+        assert_eq!(
+            AstEncoder::encode_cmd(&cmd).map(|_| ()),
+            Err(EncodeError::UnsupportedAction(Action::Del))
+        );
+    }
+
+    #[test]
+    fn test_encode_insert_select() {
+        use qail_core::ast::Operator;
+
+        let cmd = Qail::add("archive").from_select(
+            Qail::get("orders")
+                .filter("created", Operator::Lt, "2024-01-01")
+                .filter("status", Operator::Eq, "closed"),
+        );
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO archive SELECT * FROM orders WHERE created < $1 AND status = $2"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_encode_insert_default_is_not_a_param() {
+        use qail_core::ast::Value;
+
+        let cmd = Qail::add("users")
+            .columns(["id", "name", "email"])
+            .values([Value::Default, Value::from("Alice"), Value::from("a@b.c")]);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(sql, "INSERT INTO users (id, name, email) VALUES (DEFAULT, $1, $2)");
+        assert_eq!(params, vec![Some(b"Alice".to_vec()), Some(b"a@b.c".to_vec())]);
+    }
+
+    #[test]
+    fn test_encode_rejects_mixed_param_styles() {
+        let cmd = qail_core::parse("add users fields id, name, email values default, 'Alice', $1")
+            .unwrap();
+        assert_eq!(AstEncoder::encode_cmd_sql(&cmd), Err(EncodeError::MixedParams));
+        assert!(matches!(AstEncoder::encode_cmd(&cmd), Err(EncodeError::MixedParams)));
+        assert_eq!(AstEncoder::encode_cmd_params_only(&cmd), Err(EncodeError::MixedParams));
+        assert!(matches!(
+            AstEncoder::encode_batch(std::slice::from_ref(&cmd)),
+            Err(EncodeError::MixedParams)
+        ));
+
+        // Placeholders alone keep their numbers
+        let cmd = qail_core::parse("add users fields id, name values default, $1").unwrap();
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert_eq!(sql, "INSERT INTO users (id, name) VALUES (DEFAULT, $1)");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_encode_tag_comment() {
+        use qail_core::ast::Operator;
+
+        let cmd = Qail::get("orders")
+            .columns(["id"])
+            .filter("user_id", Operator::Eq, 7)
+            .tag("app:checkout")
+            .tag("route:/pay");
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert_eq!(
+            sql,
+            "/* app:checkout route:/pay */ SELECT id FROM orders WHERE user_id = $1"
+        );
+        assert_eq!(params.len(), 1);
+
+        let export = Qail::export("orders").columns(["id"]).tag("job:nightly");
+        let (sql, _) = AstEncoder::encode_cmd_sql(&export).unwrap();
+        assert_eq!(sql, "/* job:nightly */ COPY (SELECT id FROM orders) TO STDOUT");
+
+        // The tag is part of the SQL text, so it changes the statement cache key
+        let untagged = Qail::get("orders").columns(["id"]).filter("user_id", Operator::Eq, 7);
+        let (untagged_sql, _) = AstEncoder::encode_cmd_sql(&untagged).unwrap();
+        assert_eq!(untagged_sql, "SELECT id FROM orders WHERE user_id = $1");
+        assert_ne!(cmd.cache_key(), untagged.cache_key());
+    }
+
+    #[test]
+    fn test_encode_quantified_subquery_shares_params() {
+        use qail_core::ast::{Operator, Value};
+
+        let allowed = Qail::get("allowed")
+            .columns(["user_id"])
+            .filter("role", Operator::Eq, "admin");
+        let cmd = Qail::get("users")
+            .columns(["id"])
+            .filter("org", Operator::Eq, 7)
+            .filter("id", Operator::Eq, Value::AnySubquery(Box::new(allowed)))
+            .filter("score", Operator::Gt, Value::AllSubquery(Box::new(
+                Qail::get("scores").columns(["score"]).filter("org", Operator::Eq, 8),
+            )));
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE org = $1 AND id = ANY (SELECT user_id FROM allowed WHERE role = $2) \
+             AND score > ALL (SELECT score FROM scores WHERE org = $3)"
+        );
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn test_encode_in_list_and_in_subquery() {
+        use qail_core::ast::{Operator, Value};
+        use qail_core::transpiler::ToSql;
+
+        let bans = Qail::get("bans")
+            .columns(["user_id"])
+            .filter("reason", Operator::Eq, "spam");
+        let cmd = Qail::get("users")
+            .columns(["id"])
+            .filter("org", Operator::Eq, 7)
+            .filter("id", Operator::In, Value::Array(vec![Value::Int(1), Value::Int(2)]))
+            .filter("id", Operator::NotIn, Value::Subquery(Box::new(bans)))
+            .filter("role", Operator::Eq, "admin");
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE org = $1 AND id IN ($2, $3) \
+             AND id NOT IN (SELECT user_id FROM bans WHERE reason = $4) AND role = $5"
+        );
+        assert_eq!(params.len(), 5);
+        assert_eq!(params[3], Some(b"spam".to_vec()));
+
+        // An empty list is never `IN ()`, as in the transpiler
+        let cmd = Qail::get("users")
+            .columns(["id"])
+            .filter("id", Operator::In, Value::Array(vec![]))
+            .filter("org", Operator::NotIn, Value::Array(vec![]));
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert_eq!(sql, "SELECT id FROM users WHERE FALSE AND TRUE");
+        assert!(params.is_empty());
+        assert_eq!(cmd.to_sql(), "SELECT id FROM users WHERE FALSE AND TRUE");
+    }
+
+    #[test]
+    fn test_encode_every_filter_cage() {
+        use qail_core::ast::{Cage, CageKind, Condition, Expr, LogicalOp, Operator};
+
+        let cond = |col: &str, value: &str| Condition {
+            left: Expr::Named(col.to_string()),
+            op: Operator::Eq,
+            value: value.into(),
+            is_array_unnest: false,
+        };
+        let mut cmd = Qail::del("users").filter("org", Operator::Eq, 7);
+        cmd.cages.push(Cage {
+            kind: CageKind::Filter,
+            conditions: vec![cond("role", "admin"), cond("role", "owner")],
+            logical_op: LogicalOp::Or,
+        });
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(sql, "DELETE FROM users WHERE org = $1 AND (role = $2 OR role = $3)");
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn test_encode_custom_operators() {
+        use qail_core::ast::{Operator, Value};
+
+        let cmd = Qail::get("items")
+            .columns(["id"])
+            .filter("tags", Operator::Overlaps, Value::Array(vec!["a".into(), "b".into()]))
+            .filter("embedding", Operator::custom("<->").unwrap(), "[1,2,3]");
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(sql, "SELECT id FROM items WHERE tags && $1 AND embedding <-> $2");
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[1], Some(b"[1,2,3]".to_vec()));
+    }
+
+    #[test]
+    fn test_encode_keyset_after_binds_each_key() {
+        use qail_core::ast::Operator;
+
+        let cmd = Qail::get("posts")
+            .columns(["id"])
+            .filter("author", Operator::Eq, 9)
+            .keyset_after(("created", "id"), ("2024-05-01", 42))
+            .unwrap()
+            .limit(20);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT id FROM posts WHERE author = $1 AND (created, id) > ($2, $3) \
+             ORDER BY created, id LIMIT 20"
+        );
+        assert_eq!(
+            params,
+            vec![
+                Some(b"9".to_vec()),
+                Some(b"2024-05-01".to_vec()),
+                Some(b"42".to_vec())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_on_conflict_target_where() {
+        use qail_core::ast::{Expr, Operator, Value};
+
+        let cmd = Qail::add("users")
+            .columns(["email", "name"])
+            .values(["a@b.c", "Al"])
+            .on_conflict_update(&["email"], &[("name", Expr::Named("EXCLUDED.name".into()))])
+            .on_conflict_where("tenant", Operator::Eq, 3)
+            .on_conflict_where("deleted_at", Operator::IsNull, Value::Null);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert!(
+            sql.contains("ON CONFLICT (email) WHERE tenant = $3 AND deleted_at IS NULL DO UPDATE SET"),
+            "{}",
+            sql
+        );
+        assert_eq!(params[2], Some(b"3".to_vec()));
+
+        let cmd = Qail::add("users")
+            .columns(["email"])
+            .values(["a@b.c"])
+            .on_conflict_where("deleted_at", Operator::IsNull, Value::Null);
+        assert_eq!(
+            AstEncoder::encode_cmd_sql(&cmd).map(|_| ()),
+            Err(EncodeError::ConflictWhereWithoutColumns)
+        );
+    }
+
+    #[test]
+    fn test_encode_on_conflict_accumulating_update() {
+        let cmd = qail_core::parse(
+            "add counters fields id, count values 1, 5 \
+             conflict (id) update count = counters.count + excluded.count, hits = 1 + counters.hits",
+        )
+        .unwrap();
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert!(
+            sql.contains(
+                "ON CONFLICT (id) DO UPDATE SET count = (counters.count + excluded.count), \
+                 hits = (1 + counters.hits)"
+            ),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_encode_fuzzy_escaped_binds_literal_pattern() {
+        use qail_core::ast::Operator;
+
+        let cmd = Qail::get("products")
+            .columns(["id"])
+            .filter("label", Operator::FuzzyEscaped, "100%");
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(sql, "SELECT id FROM products WHERE label ILIKE $1 ESCAPE '\\'");
+        assert_eq!(params, vec![Some(b"%100\\%%".to_vec())]);
+    }
+
+    #[test]
+    fn test_encode_multi_column_sort_keeps_nulls_ordering() {
+        use qail_core::ast::SortOrder;
+
+        let cmd = Qail::get("users")
+            .columns(["id"])
+            .order_by("last_name", SortOrder::Asc)
+            .order_by("created_at", SortOrder::DescNullsLast)
+            .order_by("score", SortOrder::AscNullsFirst);
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT id FROM users ORDER BY last_name, created_at DESC NULLS LAST, score NULLS FIRST"
+        );
+    }
+
+    #[test]
+    fn test_encode_derived_table_shares_params() {
+        use qail_core::ast::Operator;
+
+        let paid = Qail::get("orders").filter("status", Operator::Eq, "paid");
+        let cmd = Qail::get_from(paid, "p").filter("total", Operator::Gt, 100);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT * FROM (SELECT * FROM orders WHERE status = $1) AS p WHERE total > $2"
+        );
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0], Some(b"paid".to_vec()));
+    }
+
+    #[test]
+    fn test_encode_lateral_subquery_join() {
+        use qail_core::ast::{Operator, Value};
+
+        let latest = Qail::get("orders")
+            .columns(["id", "total"])
+            .filter("orders.user_id", Operator::Eq, Value::Column("users.id".into()))
+            .filter("status", Operator::Eq, "paid")
+            .order_desc("created_at")
+            .limit(3);
+        let cmd = Qail::get("users")
+            .columns(["users.id", "latest.total"])
+            .left_join_lateral(latest, "latest")
+            .filter("users.active", Operator::Eq, true);
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT users.id, latest.total FROM users LEFT JOIN LATERAL \
+             (SELECT id, total FROM orders WHERE orders.user_id = users.id AND status = $1 \
+             ORDER BY created_at DESC LIMIT 3) AS latest ON TRUE WHERE users.active = $2"
+        );
+        assert_eq!(params[0], Some(b"paid".to_vec()));
+    }
+
+    #[test]
+    fn test_encode_limit_all_and_fetch_with_ties() {
+        let cmd = Qail::get("users").limit(100).limit_all().offset(20);
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert_eq!(sql, "SELECT * FROM users LIMIT ALL OFFSET 20");
+
+        let cmd = Qail::get("scores").order_desc("points").fetch_with_ties(5);
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert_eq!(sql, "SELECT * FROM scores ORDER BY points DESC FETCH FIRST 5 ROWS WITH TIES");
+
+        // Ties are undefined without an ORDER BY
+        let cmd = Qail::get("scores").fetch_with_ties(5);
+        let mut sql_buf = BytesMut::new();
+        let mut params = Vec::new();
+        let err = AstEncoder::encode_cmd_reuse(&cmd, &mut sql_buf, &mut params).unwrap_err();
+        assert_eq!(err, EncodeError::WithTiesWithoutOrderBy);
+    }
+
+    #[test]
+    fn test_encode_eq_any_binds_one_array() {
+        use qail_core::ast::builders::eq_any;
+
+        let ids: Vec<i64> = (0..1000).collect();
+        let cmd = Qail::get("users").filter_cond(eq_any("id", ids));
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(sql, "SELECT * FROM users WHERE id = ANY($1)");
+        assert_eq!(params.len(), 1);
+
+        // An empty list is still one (typed by the server) array parameter
+        let cmd = Qail::get("users").filter_cond(eq_any("id", Vec::<i64>::new()));
+        let (sql, params) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+        assert_eq!(sql, "SELECT * FROM users WHERE id = ANY($1)");
+        assert_eq!(params, vec![Some(b"{}".to_vec())]);
+    }
+
+    #[test]
+    fn test_encode_ordered_string_agg() {
+        use qail_core::ast::SortOrder;
+        use qail_core::ast::builders::aggregates::string_agg;
+
+        let cmd = Qail::get("users").column_expr(
+            string_agg("name", "; ")
+                .order_by("name", SortOrder::DescNullsLast)
+                .alias("names"),
+        );
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT STRING_AGG(name, '; ' ORDER BY name DESC NULLS LAST) AS names FROM users"
+        );
+    }
+
     #[test]
     fn test_encode_cte_multiple() {
         let users = Qail::get("users").columns(["id", "name"]);
@@ -246,7 +695,7 @@ mod tests {
             .with("active_users", users)
             .with("recent_orders", orders);
 
-        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd);
+        let (sql, _) = AstEncoder::encode_cmd_sql(&cmd).unwrap();
 
         assert!(sql.contains("active_users"), "SQL should have first CTE: {}", sql);
         assert!(sql.contains("recent_orders"), "SQL should have second CTE: {}", sql);
@@ -3,7 +3,12 @@
 //! Functions for encoding Expr, Value, Operator, and conditions to wire format.
 
 use bytes::BytesMut;
-use qail_core::ast::{Action, CageKind, Condition, Expr, FrameBound, Operator, SortOrder, Value, WindowFrame};
+use qail_core::ast::{
+    Action, CageKind, Condition, Expr, FrameBound, Operator, Qail, SortOrder, Value, WindowFrame,
+    escape_like,
+};
+use qail_core::transpiler::SqlGenerator;
+use qail_core::transpiler::sql::postgres::PostgresGenerator;
 
 use super::super::helpers::{i64_to_bytes, write_param_placeholder, NUMERIC_VALUES};
 
@@ -32,13 +37,33 @@ pub fn encode_column_expr(col: &Expr, buf: &mut BytesMut) {
             buf.extend_from_slice(b" AS ");
             buf.extend_from_slice(alias.as_bytes());
         }
-        Expr::Aggregate { col, func, distinct, filter, alias } => {
+        Expr::Aggregate { col, arg, func, distinct, filter, delimiter, order_by, alias } => {
             buf.extend_from_slice(func.to_string().as_bytes());
             buf.extend_from_slice(b"(");
             if *distinct {
                 buf.extend_from_slice(b"DISTINCT ");
             }
-            buf.extend_from_slice(col.as_bytes());
+            match arg {
+                Some(arg) => encode_column_expr(arg, buf),
+                None => buf.extend_from_slice(col.as_bytes()),
+            }
+            if let Some(sep) = delimiter {
+                buf.extend_from_slice(b", ");
+                buf.extend_from_slice(PostgresGenerator.string_literal(sep).as_bytes());
+            }
+            if let Some(order) = order_by
+                && !order.is_empty()
+            {
+                buf.extend_from_slice(b" ORDER BY ");
+                for (i, (c, o)) in order.iter().enumerate() {
+                    if i > 0 {
+                        buf.extend_from_slice(b", ");
+                    }
+                    buf.extend_from_slice(c.as_bytes());
+                    buf.extend_from_slice(b" ");
+                    buf.extend_from_slice(o.to_string().as_bytes());
+                }
+            }
             buf.extend_from_slice(b")");
             
             // FILTER (WHERE ...) clause for aggregates
@@ -60,7 +85,7 @@ pub fn encode_column_expr(col: &Expr, buf: &mut BytesMut) {
                         Value::Expr(expr) => encode_column_expr(expr, buf),
                         Value::String(s) => {
                             buf.extend_from_slice(b"'");
-                            buf.extend_from_slice(s.as_bytes());
+                            buf.extend_from_slice(s.replace('\'', "''").as_bytes());
                             buf.extend_from_slice(b"'");
                         }
                         Value::Int(n) => buf.extend_from_slice(n.to_string().as_bytes()),
@@ -74,7 +99,7 @@ pub fn encode_column_expr(col: &Expr, buf: &mut BytesMut) {
                                 }
                                 if let Value::String(s) = v {
                                     buf.extend_from_slice(b"'");
-                                    buf.extend_from_slice(s.as_bytes());
+                                    buf.extend_from_slice(s.replace('\'', "''").as_bytes());
                                     buf.extend_from_slice(b"'");
                                 } else {
                                     buf.extend_from_slice(v.to_string().as_bytes());
@@ -360,6 +385,8 @@ pub fn encode_column_expr(col: &Expr, buf: &mut BytesMut) {
                 buf.extend_from_slice(a.as_bytes());
             }
         }
+        // Verbatim, never parameterized
+        Expr::Raw(sql) => buf.extend_from_slice(sql.as_bytes()),
         _ => buf.extend_from_slice(b"*"),
     }
 }
@@ -378,7 +405,9 @@ pub fn encode_operator(op: &Operator, buf: &mut BytesMut) {
         Operator::ILike => b"ILIKE",
         Operator::NotILike => b"NOT ILIKE",
         Operator::Fuzzy => b"ILIKE",
+        Operator::FuzzyEscaped => b"ILIKE",
         Operator::In => b"IN",
+        Operator::EqAny => b"= ANY",
         Operator::NotIn => b"NOT IN",
         Operator::IsNull => b"IS NULL",
         Operator::IsNotNull => b"IS NOT NULL",
@@ -396,6 +425,7 @@ pub fn encode_operator(op: &Operator, buf: &mut BytesMut) {
         Operator::JsonValue => b"JSON_VALUE",
         Operator::Exists => b"EXISTS",
         Operator::NotExists => b"NOT EXISTS",
+        Operator::Custom(op) => op.as_str().as_bytes(),
     };
     buf.extend_from_slice(bytes);
 }
@@ -439,6 +469,41 @@ pub fn encode_conditions(
         if i > 0 {
             buf.extend_from_slice(b" AND ");
         }
+
+        // Row comparison: (a, b) > ($1, $2)
+        if let (Expr::RowConstructor { elements, .. }, Value::Array(vals)) =
+            (&cond.left, &cond.value)
+            && cond.op.is_simple_binary()
+        {
+            buf.extend_from_slice(b"(");
+            for (j, elem) in elements.iter().enumerate() {
+                if j > 0 {
+                    buf.extend_from_slice(b", ");
+                }
+                encode_expr(elem, buf);
+            }
+            buf.extend_from_slice(b") ");
+            buf.extend_from_slice(cond.op.sql_symbol().as_bytes());
+            buf.extend_from_slice(b" (");
+            for (j, v) in vals.iter().enumerate() {
+                if j > 0 {
+                    buf.extend_from_slice(b", ");
+                }
+                encode_value(v, buf, params)?;
+            }
+            buf.extend_from_slice(b")");
+            continue;
+        }
+
+        // SQL has no empty list: match the transpiler's FALSE / TRUE
+        if let Value::Array(vals) = &cond.value
+            && vals.is_empty()
+            && matches!(cond.op, Operator::In | Operator::NotIn)
+        {
+            buf.extend_from_slice(if cond.op == Operator::In { b"FALSE" } else { b"TRUE" });
+            continue;
+        }
+
         encode_expr(&cond.left, buf);
 
         match cond.op {
@@ -466,6 +531,13 @@ pub fn encode_conditions(
                 }
                 buf.extend_from_slice(b" IN ");
             }
+            // The whole list is one array parameter: col = ANY($1)
+            Operator::EqAny => {
+                buf.extend_from_slice(b" = ANY(");
+                encode_value(&cond.value, buf, params)?;
+                buf.extend_from_slice(b")");
+                continue;
+            }
             Operator::NotIn => {
                 if let Value::Array(vals) = &cond.value {
                     buf.extend_from_slice(b" NOT IN (");
@@ -518,7 +590,31 @@ pub fn encode_conditions(
             Operator::Contains => buf.extend_from_slice(b" @> "),
             Operator::ContainedBy => buf.extend_from_slice(b" <@ "),
             Operator::Overlaps => buf.extend_from_slice(b" && "),
+            Operator::Custom(op) => {
+                buf.extend_from_slice(b" ");
+                buf.extend_from_slice(op.as_str().as_bytes());
+                buf.extend_from_slice(b" ");
+            }
             Operator::Fuzzy => buf.extend_from_slice(b" ILIKE "),
+            Operator::FuzzyEscaped => {
+                buf.extend_from_slice(b" ILIKE ");
+                match &cond.value {
+                    // Escape client-side so the bound value is the full pattern
+                    Value::String(s) => {
+                        let pattern = format!("%{}%", escape_like(s));
+                        encode_value(&Value::String(pattern), buf, params)?;
+                    }
+                    other => {
+                        buf.extend_from_slice(b"'%' || replace(replace(replace(");
+                        encode_value(other, buf, params)?;
+                        buf.extend_from_slice(
+                            b", '\\', '\\\\'), '%', '\\%'), '_', '\\_') || '%'",
+                        );
+                    }
+                }
+                buf.extend_from_slice(b" ESCAPE '\\'");
+                continue;
+            }
             Operator::KeyExists => buf.extend_from_slice(b" ? "),
             Operator::JsonExists | Operator::JsonQuery | Operator::JsonValue => {
                 buf.extend_from_slice(b" = ");
@@ -533,6 +629,24 @@ pub fn encode_conditions(
     Ok(())
 }
 
+/// Encode a parenthesized subquery. Its parameters are appended to the
+/// outer list, so placeholders keep numbering from the enclosing query.
+fn encode_subquery(
+    q: &Qail,
+    buf: &mut BytesMut,
+    params: &mut Vec<Option<Vec<u8>>>,
+) -> Result<(), crate::protocol::EncodeError> {
+    match q.action {
+        Action::Get | Action::With => {
+            buf.extend_from_slice(b"(");
+            super::super::dml::encode_select(q, buf, params)?;
+            buf.extend_from_slice(b")");
+        }
+        action => return Err(crate::protocol::EncodeError::UnsupportedAction(action)),
+    }
+    Ok(())
+}
+
 /// Encode value - extract to parameter or inline.
 /// Returns Err if the value contains invalid data (e.g., NULL byte in string).
 pub fn encode_value(value: &Value, buf: &mut BytesMut, params: &mut Vec<Option<Vec<u8>>>) -> Result<(), crate::protocol::EncodeError> {
@@ -559,6 +673,11 @@ pub fn encode_value(value: &Value, buf: &mut BytesMut, params: &mut Vec<Option<V
             params.push(Some(f.to_string().into_bytes()));
             write_param_placeholder(buf, params.len());
         }
+        Value::Decimal(d) => {
+            // Sent as text so NUMERIC keeps every digit
+            params.push(Some(d.as_bytes().to_vec()));
+            write_param_placeholder(buf, params.len());
+        }
         Value::Bool(b) => {
             params.push(Some(if *b { b"t".to_vec() } else { b"f".to_vec() }));
             write_param_placeholder(buf, params.len());
@@ -602,19 +721,19 @@ pub fn encode_value(value: &Value, buf: &mut BytesMut, params: &mut Vec<Option<V
         Value::Function(f) => {
             buf.extend_from_slice(f.as_bytes());
         }
+        // Keyword, never a bind parameter
+        Value::Default => buf.extend_from_slice(b"DEFAULT"),
         Value::Column(col) => {
             buf.extend_from_slice(col.as_bytes());
         }
-        Value::Subquery(q) => {
-            let mut sub_buf = BytesMut::with_capacity(128);
-            let mut sub_params: Vec<Option<Vec<u8>>> = Vec::new();
-            match q.action {
-                Action::Get => super::super::dml::encode_select(q, &mut sub_buf, &mut sub_params)?,
-                _ => panic!("Unsupported subquery action {:?}", q.action),
-            }
-            buf.extend_from_slice(b"(");
-            buf.extend_from_slice(&sub_buf);
-            buf.extend_from_slice(b")");
+        Value::Subquery(q) => encode_subquery(q, buf, params)?,
+        Value::AnySubquery(q) => {
+            buf.extend_from_slice(b"ANY ");
+            encode_subquery(q, buf, params)?;
+        }
+        Value::AllSubquery(q) => {
+            buf.extend_from_slice(b"ALL ");
+            encode_subquery(q, buf, params)?;
         }
         Value::Timestamp(ts) => {
             params.push(Some(ts.as_bytes().to_vec()));
@@ -3,6 +3,7 @@
 //! Encodes `Value` rows directly to PostgreSQL COPY text format bytes
 //! without intermediate String allocations.
 
+use super::EncodeError;
 use bytes::BytesMut;
 use qail_core::ast::Value;
 
@@ -13,8 +14,11 @@ use qail_core::ast::Value;
 /// - Numeric: raw digits (no quotes)
 /// - String: escape special chars (\\, \t, \n, \r)
 /// - UUID: hyphenated lowercase
+///
+/// `Value::Default` has no COPY form and fails with
+/// `EncodeError::DefaultInCopy`; leave the column out of the COPY instead.
 #[inline]
-pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) {
+pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) -> Result<(), EncodeError> {
     match value {
         Value::Null | Value::NullUuid => buf.extend_from_slice(b"\\N"),
 
@@ -32,17 +36,12 @@ pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) {
             buf.extend_from_slice(tmp.format(*n).as_bytes());
         }
 
+        // Escaped like text: unchecked decimal text must not split the row
+        Value::Decimal(d) => crate::driver::copy_escape(buf, d.as_bytes()),
+
         Value::String(s) => {
             // COPY text format: escape tabs, newlines, backslashes
-            for c in s.bytes() {
-                match c {
-                    b'\\' => buf.extend_from_slice(b"\\\\"),
-                    b'\t' => buf.extend_from_slice(b"\\t"),
-                    b'\n' => buf.extend_from_slice(b"\\n"),
-                    b'\r' => buf.extend_from_slice(b"\\r"),
-                    _ => buf.extend_from_slice(&[c]),
-                }
-            }
+            crate::driver::copy_escape(buf, s.as_bytes());
         }
 
         Value::Uuid(u) => {
@@ -77,7 +76,7 @@ pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) {
                 if i > 0 {
                     buf.extend_from_slice(b",");
                 }
-                encode_copy_value(buf, v);
+                encode_copy_value(buf, v)?;
             }
             buf.extend_from_slice(b"}");
         }
@@ -90,7 +89,7 @@ pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) {
             buf.extend_from_slice(unit.to_string().as_bytes());
         }
 
-        Value::Subquery(_) => {
+        Value::Subquery(_) | Value::AnySubquery(_) | Value::AllSubquery(_) => {
             // Can't COPY a subquery - output NULL
             buf.extend_from_slice(b"\\N");
         }
@@ -108,6 +107,7 @@ pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) {
                 ]);
             }
         }
+        Value::Default => return Err(EncodeError::DefaultInCopy),
         Value::Expr(_) => {
             // Expr values shouldn't appear in COPY - output NULL
             buf.extend_from_slice(b"\\N");
@@ -126,24 +126,17 @@ pub fn encode_copy_value(buf: &mut BytesMut, value: &Value) {
         }
         Value::Json(json) => {
             // JSONB as raw JSON text (escape backslashes for COPY format)
-            for c in json.bytes() {
-                match c {
-                    b'\\' => buf.extend_from_slice(b"\\\\"),
-                    b'\t' => buf.extend_from_slice(b"\\t"),
-                    b'\n' => buf.extend_from_slice(b"\\n"),
-                    b'\r' => buf.extend_from_slice(b"\\r"),
-                    _ => buf.extend_from_slice(&[c]),
-                }
-            }
+            crate::driver::copy_escape(buf, json.as_bytes());
         }
     }
+    Ok(())
 }
 
 /// Encode a batch of rows into a single COPY data buffer.
 /// Returns a BytesMut containing all rows in tab-separated format,
 /// ready to be sent as a single CopyData message.
 #[inline]
-pub fn encode_copy_batch(rows: &[Vec<Value>]) -> BytesMut {
+pub fn encode_copy_batch(rows: &[Vec<Value>]) -> Result<BytesMut, EncodeError> {
     // Pre-allocate: estimate ~50 bytes per column, 7 columns avg
     let estimated_size = rows.len() * 7 * 50;
     let mut buf = BytesMut::with_capacity(estimated_size);
@@ -153,12 +146,12 @@ pub fn encode_copy_batch(rows: &[Vec<Value>]) -> BytesMut {
             if i > 0 {
                 buf.extend_from_slice(b"\t");
             }
-            encode_copy_value(&mut buf, val);
+            encode_copy_value(&mut buf, val)?;
         }
         buf.extend_from_slice(b"\n");
     }
 
-    buf
+    Ok(buf)
 }
 
 #[cfg(test)]
@@ -169,28 +162,28 @@ mod tests {
     #[test]
     fn test_encode_int() {
         let mut buf = BytesMut::new();
-        encode_copy_value(&mut buf, &Value::Int(12345));
+        encode_copy_value(&mut buf, &Value::Int(12345)).unwrap();
         assert_eq!(&buf[..], b"12345");
     }
 
     #[test]
     fn test_encode_float() {
         let mut buf = BytesMut::new();
-        encode_copy_value(&mut buf, &Value::Float(3.14159));
+        encode_copy_value(&mut buf, &Value::Float(3.14159)).unwrap();
         assert!(buf.starts_with(b"3.14"));
     }
 
     #[test]
     fn test_encode_string_escaping() {
         let mut buf = BytesMut::new();
-        encode_copy_value(&mut buf, &Value::String("hello\tworld\n".to_string()));
+        encode_copy_value(&mut buf, &Value::String("hello\tworld\n".to_string())).unwrap();
         assert_eq!(&buf[..], b"hello\\tworld\\n");
     }
 
     #[test]
     fn test_encode_null() {
         let mut buf = BytesMut::new();
-        encode_copy_value(&mut buf, &Value::Null);
+        encode_copy_value(&mut buf, &Value::Null).unwrap();
         assert_eq!(&buf[..], b"\\N");
     }
 
@@ -200,7 +193,7 @@ mod tests {
             vec![Value::Int(1), Value::String("foo".to_string())],
             vec![Value::Int(2), Value::String("bar".to_string())],
         ];
-        let buf = encode_copy_batch(&rows);
+        let buf = encode_copy_batch(&rows).unwrap();
         assert_eq!(&buf[..], b"1\tfoo\n2\tbar\n");
     }
 
@@ -208,7 +201,15 @@ mod tests {
     fn test_encode_uuid() {
         let mut buf = BytesMut::new();
         let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
-        encode_copy_value(&mut buf, &Value::Uuid(uuid));
+        encode_copy_value(&mut buf, &Value::Uuid(uuid)).unwrap();
         assert_eq!(&buf[..], b"550e8400-e29b-41d4-a716-446655440000");
     }
+
+    #[test]
+    fn test_encode_default_rejected() {
+        let mut buf = BytesMut::new();
+        assert_eq!(encode_copy_value(&mut buf, &Value::Default), Err(EncodeError::DefaultInCopy));
+        let rows = vec![vec![Value::Int(1), Value::Default]];
+        assert_eq!(encode_copy_batch(&rows), Err(EncodeError::DefaultInCopy));
+    }
 }
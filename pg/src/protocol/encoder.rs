@@ -12,7 +12,7 @@
 //! The async I/O layer (Layer 3) consumes these bytes.
 
 use bytes::BytesMut;
-use super::EncodeError;
+use super::{EncodeError, MAX_BIND_PARAMS};
 
 /// Takes a Qail and produces wire protocol bytes.
 /// This is the "Visitor" in the visitor pattern.
@@ -113,7 +113,7 @@ impl PgEncoder {
     /// - for each parameter: length (4 bytes, -1 for NULL), data
     /// - result format count (2 bytes) - we use 0 (all text)
     pub fn encode_bind(portal: &str, statement: &str, params: &[Option<Vec<u8>>]) -> Result<BytesMut, EncodeError> {
-        if params.len() > i16::MAX as usize {
+        if params.len() > MAX_BIND_PARAMS {
             return Err(EncodeError::TooManyParameters(params.len()));
         }
 
@@ -136,7 +136,7 @@ impl PgEncoder {
         content.extend_from_slice(&0i16.to_be_bytes());
 
         // Parameter count
-        content.extend_from_slice(&(params.len() as i16).to_be_bytes());
+        content.extend_from_slice(&(params.len() as u16).to_be_bytes());
 
         // Parameters
         for param in params {
@@ -221,11 +221,40 @@ impl PgEncoder {
         buf
     }
 
+    /// Encode a Close message (release a prepared statement or portal).
+    /// Wire format:
+    /// - 'C' (1 byte) - message type
+    /// - length (4 bytes)
+    /// - 'S' for statement or 'P' for portal
+    /// - name (null-terminated)
+    pub fn encode_close(is_portal: bool, name: &str) -> BytesMut {
+        let mut buf = BytesMut::new();
+
+        // Message type 'C'
+        buf.extend_from_slice(b"C");
+
+        let mut content = Vec::new();
+
+        // Type: 'S' for statement, 'P' for portal
+        content.push(if is_portal { b'P' } else { b'S' });
+
+        // Name (null-terminated)
+        content.extend_from_slice(name.as_bytes());
+        content.push(0);
+
+        // Length
+        let len = (content.len() + 4) as i32;
+        buf.extend_from_slice(&len.to_be_bytes());
+        buf.extend_from_slice(&content);
+
+        buf
+    }
+
     /// Encode a complete extended query pipeline (OPTIMIZED).
     /// This combines Parse + Bind + Execute + Sync in a single buffer.
     /// Zero intermediate allocations - writes directly to pre-sized BytesMut.
     pub fn encode_extended_query(sql: &str, params: &[Option<Vec<u8>>]) -> Result<BytesMut, EncodeError> {
-        if params.len() > i16::MAX as usize {
+        if params.len() > MAX_BIND_PARAMS {
             return Err(EncodeError::TooManyParameters(params.len()));
         }
 
@@ -257,7 +286,7 @@ impl PgEncoder {
         buf.extend_from_slice(&[0]); // Unnamed portal
         buf.extend_from_slice(&[0]); // Unnamed statement
         buf.extend_from_slice(&0i16.to_be_bytes()); // Format codes (default text)
-        buf.extend_from_slice(&(params.len() as i16).to_be_bytes());
+        buf.extend_from_slice(&(params.len() as u16).to_be_bytes());
         for param in params {
             match param {
                 None => buf.extend_from_slice(&(-1i32).to_be_bytes()),
@@ -358,6 +387,13 @@ mod tests {
         assert_eq!(len, 9);
     }
 
+    #[test]
+    fn test_encode_close() {
+        let bytes = PgEncoder::encode_close(false, "qail_1");
+
+        assert_eq!(&bytes[..], b"C\0\0\0\x0cSqail_1\0");
+    }
+
     #[test]
     fn test_encode_extended_query() {
         let params = vec![Some(b"hello".to_vec())];
@@ -407,7 +443,7 @@ impl PgEncoder {
     /// - Single allocation check
     #[inline]
     pub fn encode_bind_ultra<'a>(buf: &mut BytesMut, statement: &str, params: &[Param<'a>]) -> Result<(), EncodeError> {
-        if params.len() > i16::MAX as usize {
+        if params.len() > MAX_BIND_PARAMS {
             return Err(EncodeError::TooManyParameters(params.len()));
         }
 
@@ -441,7 +477,7 @@ impl PgEncoder {
         Self::put_i16_be(buf, 0);
 
         // Parameter count
-        Self::put_i16_be(buf, params.len() as i16);
+        buf.put_u16(params.len() as u16);
 
         // Parameters - ZERO COPY from borrowed slices
         for param in params {
@@ -479,7 +515,7 @@ impl PgEncoder {
     /// This is the hot path optimization - no intermediate Vec allocation.
     #[inline]
     pub fn encode_bind_to(buf: &mut BytesMut, statement: &str, params: &[Option<Vec<u8>>]) -> Result<(), EncodeError> {
-        if params.len() > i16::MAX as usize {
+        if params.len() > MAX_BIND_PARAMS {
             return Err(EncodeError::TooManyParameters(params.len()));
         }
 
@@ -510,7 +546,7 @@ impl PgEncoder {
         Self::put_i16_be(buf, 0);
 
         // Parameter count
-        Self::put_i16_be(buf, params.len() as i16);
+        buf.put_u16(params.len() as u16);
 
         // Parameters
         for param in params {
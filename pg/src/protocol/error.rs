@@ -2,15 +2,32 @@
 //!
 //! Shared by `PgEncoder` and `AstEncoder`.
 
+use qail_core::ast::Action;
 use std::fmt;
 
+/// Maximum number of bind parameters in a single Bind message.
+///
+/// The parameter count is sent as an unsigned 16-bit integer.
+pub const MAX_BIND_PARAMS: usize = u16::MAX as usize;
+
 /// Errors that can occur during wire protocol encoding.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EncodeError {
     /// A string value contains a literal NULL byte (0x00).
     NullByte,
-    /// Too many parameters for the protocol (limit is i16::MAX = 32767).
+    /// Too many parameters for the protocol (limit is `MAX_BIND_PARAMS` = 65535).
     TooManyParameters(usize),
+    /// `Value::Default` in COPY data, which has no way to ask for a column default.
+    DefaultInCopy,
+    /// `FETCH FIRST n ROWS WITH TIES` without an `ORDER BY` to define ties.
+    WithTiesWithoutOrderBy,
+    /// `ON CONFLICT ... WHERE` without conflict columns for the predicate to narrow.
+    ConflictWhereWithoutColumns,
+    /// Positional `Value::Param(n)` placeholders in a command that also binds
+    /// inline values, whose numbers would collide.
+    MixedParams,
+    /// An action the encoder has no wire form for (e.g. `Listen`, or DDL in a batch).
+    UnsupportedAction(Action),
 }
 
 impl fmt::Display for EncodeError {
@@ -20,7 +37,25 @@ impl fmt::Display for EncodeError {
                 write!(f, "Value contains NULL byte (0x00) which is invalid in PostgreSQL")
             }
             EncodeError::TooManyParameters(count) => {
-                write!(f, "Too many parameters: {} (Limit is 32767)", count)
+                write!(f, "too many bind parameters: {} > {}", count, MAX_BIND_PARAMS)
+            }
+            EncodeError::DefaultInCopy => {
+                write!(f, "DEFAULT cannot be sent in COPY data; leave the column out instead")
+            }
+            EncodeError::WithTiesWithoutOrderBy => {
+                write!(f, "FETCH FIRST ... WITH TIES requires an ORDER BY")
+            }
+            EncodeError::ConflictWhereWithoutColumns => {
+                write!(f, "ON CONFLICT ... WHERE requires conflict columns")
+            }
+            EncodeError::MixedParams => {
+                write!(
+                    f,
+                    "command mixes $n placeholders with inline values; use one or the other"
+                )
+            }
+            EncodeError::UnsupportedAction(action) => {
+                write!(f, "action {:?} is not supported by the AST encoder", action)
             }
         }
     }
@@ -10,7 +10,7 @@ pub mod types;
 pub mod error;
 pub mod wire;
 
-pub use error::EncodeError;
+pub use error::{EncodeError, MAX_BIND_PARAMS};
 
 pub use ast_encoder::AstEncoder;
 pub use auth::ScramClient;
@@ -28,6 +28,9 @@ pub mod oid {
     // OID
     pub const OID: u32 = 26;
 
+    // Untyped text, e.g. a composite field decoded without its attribute type
+    pub const UNKNOWN: u32 = 705;
+
     // JSON
     pub const JSON: u32 = 114;
     pub const JSONB: u32 = 3802;
@@ -60,6 +63,12 @@ pub mod oid {
     pub const FLOAT8_ARRAY: u32 = 1022;
     pub const UUID_ARRAY: u32 = 2951;
     pub const JSONB_ARRAY: u32 = 3807;
+    pub const BYTEA_ARRAY: u32 = 1001;
+    pub const NUMERIC_ARRAY: u32 = 1231;
+    pub const DATE_ARRAY: u32 = 1182;
+    pub const TIME_ARRAY: u32 = 1183;
+    pub const TIMESTAMP_ARRAY: u32 = 1115;
+    pub const TIMESTAMPTZ_ARRAY: u32 = 1185;
 }
 
 /// Map OID to a human-readable type name
@@ -76,6 +85,7 @@ pub fn oid_to_name(oid: u32) -> &'static str {
         oid::VARCHAR => "varchar",
         oid::BPCHAR => "bpchar",
         oid::OID => "oid",
+        oid::UNKNOWN => "unknown",
         oid::JSON => "json",
         oid::JSONB => "jsonb",
         oid::FLOAT4 => "float4",
@@ -97,6 +107,12 @@ pub fn oid_to_name(oid: u32) -> &'static str {
         oid::FLOAT8_ARRAY => "float8[]",
         oid::UUID_ARRAY => "uuid[]",
         oid::JSONB_ARRAY => "jsonb[]",
+        oid::BYTEA_ARRAY => "bytea[]",
+        oid::NUMERIC_ARRAY => "numeric[]",
+        oid::DATE_ARRAY => "date[]",
+        oid::TIME_ARRAY => "time[]",
+        oid::TIMESTAMP_ARRAY => "timestamp[]",
+        oid::TIMESTAMPTZ_ARRAY => "timestamptz[]",
         _ => "unknown",
     }
 }
@@ -115,9 +131,38 @@ pub fn is_array_oid(oid: u32) -> bool {
             | oid::FLOAT8_ARRAY
             | oid::UUID_ARRAY
             | oid::JSONB_ARRAY
+            | oid::BYTEA_ARRAY
+            | oid::NUMERIC_ARRAY
+            | oid::DATE_ARRAY
+            | oid::TIME_ARRAY
+            | oid::TIMESTAMP_ARRAY
+            | oid::TIMESTAMPTZ_ARRAY
     )
 }
 
+/// Array type OID for an element type OID, or 0 (unspecified) if unknown.
+pub fn array_oid(elem: u32) -> u32 {
+    match elem {
+        oid::BOOL => oid::BOOL_ARRAY,
+        oid::BYTEA => oid::BYTEA_ARRAY,
+        oid::INT2 => oid::INT2_ARRAY,
+        oid::INT4 => oid::INT4_ARRAY,
+        oid::INT8 => oid::INT8_ARRAY,
+        oid::TEXT => oid::TEXT_ARRAY,
+        oid::VARCHAR => oid::VARCHAR_ARRAY,
+        oid::FLOAT4 => oid::FLOAT4_ARRAY,
+        oid::FLOAT8 => oid::FLOAT8_ARRAY,
+        oid::NUMERIC => oid::NUMERIC_ARRAY,
+        oid::DATE => oid::DATE_ARRAY,
+        oid::TIME => oid::TIME_ARRAY,
+        oid::TIMESTAMP => oid::TIMESTAMP_ARRAY,
+        oid::TIMESTAMPTZ => oid::TIMESTAMPTZ_ARRAY,
+        oid::UUID => oid::UUID_ARRAY,
+        oid::JSONB => oid::JSONB_ARRAY,
+        _ => 0,
+    }
+}
+
 // ==================== UUID Encoding/Decoding ====================
 
 /// Encode a UUID string to 16-byte binary format for PostgreSQL wire protocol.
@@ -311,6 +356,9 @@ mod tests {
     fn test_is_array_oid() {
         assert!(is_array_oid(oid::INT4_ARRAY));
         assert!(is_array_oid(oid::UUID_ARRAY));
+        assert_eq!(array_oid(oid::INT4), oid::INT4_ARRAY);
+        assert_eq!(array_oid(oid::TIMESTAMPTZ), oid::TIMESTAMPTZ_ARRAY);
+        assert_eq!(array_oid(oid::INTERVAL), 0);
         assert!(!is_array_oid(oid::INT4));
         assert!(!is_array_oid(oid::UUID));
     }
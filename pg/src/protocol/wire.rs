@@ -7,7 +7,12 @@
 #[derive(Debug, Clone)]
 pub enum FrontendMessage {
     /// Startup message (sent first, no type byte)
-    Startup { user: String, database: String },
+    Startup {
+        user: String,
+        database: String,
+        /// Reported in `pg_stat_activity`; omitted when empty.
+        application_name: String,
+    },
     PasswordMessage(String),
     Query(String),
     /// Parse (prepared statement)
@@ -53,11 +58,14 @@ pub enum BackendMessage {
     },
     ReadyForQuery(TransactionStatus),
     RowDescription(Vec<FieldDescription>),
+    /// Parameter type OIDs of a described statement
+    ParameterDescription(Vec<u32>),
     DataRow(Vec<Option<Vec<u8>>>),
     CommandComplete(String),
     ErrorResponse(ErrorFields),
     ParseComplete,
     BindComplete,
+    CloseComplete,
     NoData,
     /// Copy in response (server ready to receive COPY data)
     CopyInResponse {
@@ -78,6 +86,8 @@ pub enum BackendMessage {
         payload: String,
     },
     EmptyQueryResponse,
+    /// Execute stopped at its row limit; the portal has more rows
+    PortalSuspended,
     /// Notice response (warning/info messages, not errors)
     NoticeResponse(ErrorFields),
 }
@@ -110,13 +120,20 @@ pub struct ErrorFields {
     pub message: String,
     pub detail: Option<String>,
     pub hint: Option<String>,
+    pub table: Option<String>,
+    pub column: Option<String>,
+    pub constraint: Option<String>,
 }
 
 impl FrontendMessage {
     /// Encode message to bytes for sending over the wire.
     pub fn encode(&self) -> Vec<u8> {
         match self {
-            FrontendMessage::Startup { user, database } => {
+            FrontendMessage::Startup {
+                user,
+                database,
+                application_name,
+            } => {
                 let mut buf = Vec::new();
                 // Protocol version 3.0
                 buf.extend_from_slice(&196608i32.to_be_bytes());
@@ -127,6 +144,11 @@ impl FrontendMessage {
                 buf.extend_from_slice(b"database\0");
                 buf.extend_from_slice(database.as_bytes());
                 buf.push(0);
+                if !application_name.is_empty() {
+                    buf.extend_from_slice(b"application_name\0");
+                    buf.extend_from_slice(application_name.as_bytes());
+                    buf.push(0);
+                }
                 buf.push(0); // Terminator
 
                 // Prepend length (includes length itself)
@@ -272,11 +294,13 @@ impl BackendMessage {
             b'K' => Self::decode_backend_key(payload)?,
             b'Z' => Self::decode_ready_for_query(payload)?,
             b'T' => Self::decode_row_description(payload)?,
+            b't' => Self::decode_parameter_description(payload)?,
             b'D' => Self::decode_data_row(payload)?,
             b'C' => Self::decode_command_complete(payload)?,
             b'E' => Self::decode_error_response(payload)?,
             b'1' => BackendMessage::ParseComplete,
             b'2' => BackendMessage::BindComplete,
+            b'3' => BackendMessage::CloseComplete,
             b'n' => BackendMessage::NoData,
             b'G' => Self::decode_copy_in_response(payload)?,
             b'H' => Self::decode_copy_out_response(payload)?,
@@ -284,6 +308,7 @@ impl BackendMessage {
             b'c' => BackendMessage::CopyDone,
             b'A' => Self::decode_notification_response(payload)?,
             b'I' => BackendMessage::EmptyQueryResponse,
+            b's' => BackendMessage::PortalSuspended,
             b'N' => BackendMessage::NoticeResponse(Self::parse_error_fields(payload)?),
             _ => return Err(format!("Unknown message type: {}", msg_type as char)),
         };
@@ -426,6 +451,25 @@ impl BackendMessage {
         Ok(BackendMessage::RowDescription(fields))
     }
 
+    fn decode_parameter_description(payload: &[u8]) -> Result<Self, String> {
+        if payload.len() < 2 {
+            return Err("ParameterDescription payload too short".to_string());
+        }
+
+        let count = u16::from_be_bytes([payload[0], payload[1]]) as usize;
+        let oids = &payload[2..];
+        if oids.len() < count * 4 {
+            return Err("ParameterDescription truncated".to_string());
+        }
+
+        Ok(BackendMessage::ParameterDescription(
+            oids.chunks_exact(4)
+                .take(count)
+                .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+                .collect(),
+        ))
+    }
+
     fn decode_data_row(payload: &[u8]) -> Result<Self, String> {
         if payload.len() < 2 {
             return Err("DataRow payload too short".to_string());
@@ -494,6 +538,9 @@ impl BackendMessage {
                 b'M' => fields.message = value,
                 b'D' => fields.detail = Some(value),
                 b'H' => fields.hint = Some(value),
+                b't' => fields.table = Some(value),
+                b'c' => fields.column = Some(value),
+                b'n' => fields.constraint = Some(value),
                 _ => {}
             }
         }
@@ -0,0 +1,260 @@
+//! Decoding PostgreSQL composite (ROW) values.
+//!
+//! Composite columns — `ROW(...)` expressions, table-typed columns and
+//! functions returning a composite type — arrive in text format as
+//! `(a,b,c)`. [`Composite`] holds the fields as text; structs can map them
+//! positionally with [`impl_pg_composite!`](crate::impl_pg_composite):
+//!
+//! ```ignore
+//! struct Item { id: i32, name: String, note: Option<String> }
+//!
+//! qail_pg::impl_pg_composite!(Item { id, name, note });
+//!
+//! let item: Item = row.try_get(0)?;
+//! ```
+
+use super::{FromPg, TypeError};
+use crate::protocol::types::oid;
+
+/// A composite value decoded from its text representation.
+/// Each field is `None` for SQL NULL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Composite(pub Vec<Option<String>>);
+
+impl Composite {
+    /// Number of fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the composite has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Decode field `idx` as any `FromPg` type, using text format.
+    /// NULL fields follow the same rules as `PgRow::try_get`.
+    ///
+    /// The text form carries no attribute types, so the field is passed as
+    /// `oid::UNKNOWN`, which the type-checked impls (uuid, numeric, temporal)
+    /// parse as text.
+    pub fn field<T: FromPg>(&self, idx: usize) -> Result<T, TypeError> {
+        match self.0.get(idx).ok_or(TypeError::ColumnNotFound(idx))? {
+            Some(text) => T::from_pg(text.as_bytes(), oid::UNKNOWN, 0),
+            None => T::from_pg_null(),
+        }
+    }
+
+    /// Fail unless the composite has exactly `expected` fields.
+    pub fn expect_len(&self, expected: usize) -> Result<(), TypeError> {
+        if self.0.len() == expected {
+            Ok(())
+        } else {
+            Err(TypeError::InvalidData(format!(
+                "Expected {} composite fields, got {}",
+                expected,
+                self.0.len()
+            )))
+        }
+    }
+}
+
+impl FromPg for Composite {
+    fn from_pg(bytes: &[u8], _oid: u32, format: i16) -> Result<Self, TypeError> {
+        if format == 1 {
+            return Err(TypeError::InvalidData(
+                "Binary composite format is not supported".to_string(),
+            ));
+        }
+        let s = std::str::from_utf8(bytes)
+            .map_err(|e| TypeError::InvalidData(format!("Invalid UTF-8: {}", e)))?;
+        parse_composite_text(s).map(Composite)
+    }
+}
+
+/// Parse the text form of a composite value, e.g. `(1,"a ""b""",)`.
+///
+/// An empty unquoted field is NULL and `""` is an empty string. Inside
+/// quotes, `""` and `\"` both stand for a literal quote; outside quotes a
+/// backslash escapes the next character.
+pub fn parse_composite_text(s: &str) -> Result<Vec<Option<String>>, TypeError> {
+    let inner = s
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(|| {
+            TypeError::InvalidData(format!("Composite value must be parenthesized: {}", s))
+        })?;
+
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    // A field that had any quoted or escaped content is never NULL
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped = chars.next().ok_or_else(|| {
+                    TypeError::InvalidData("Composite value ends in an escape".to_string())
+                })?;
+                current.push(escaped);
+                quoted = true;
+            }
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' if !in_quotes => {
+                fields.push(take_field(&mut current, quoted));
+                quoted = false;
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(TypeError::InvalidData(
+            "Unterminated quote in composite value".to_string(),
+        ));
+    }
+    // The last field follows the last comma; `()` is a single NULL field,
+    // which is how PostgreSQL prints a one-column row holding NULL
+    fields.push(take_field(&mut current, quoted));
+
+    Ok(fields)
+}
+
+fn take_field(current: &mut String, quoted: bool) -> Option<String> {
+    if current.is_empty() && !quoted {
+        None
+    } else {
+        Some(std::mem::take(current))
+    }
+}
+
+/// Implement [`FromPg`](crate::types::FromPg) for a struct whose fields map,
+/// in order, to the fields of a composite value.
+///
+/// Each struct field is decoded with its own `FromPg` impl, so nullable
+/// composite fields need `Option<T>`. A field count mismatch is
+/// `TypeError::InvalidData`.
+#[macro_export]
+macro_rules! impl_pg_composite {
+    ($ty:ident { $($field:ident),+ $(,)? }) => {
+        impl $crate::types::FromPg for $ty {
+            #[allow(unused_assignments)]
+            fn from_pg(
+                bytes: &[u8],
+                oid: u32,
+                format: i16,
+            ) -> Result<Self, $crate::types::TypeError> {
+                let composite =
+                    <$crate::types::Composite as $crate::types::FromPg>::from_pg(bytes, oid, format)?;
+                composite.expect_len([$(stringify!($field)),+].len())?;
+                let mut idx = 0usize;
+                Ok($ty {
+                    $($field: {
+                        let value = composite.field(idx)?;
+                        idx += 1;
+                        value
+                    },)+
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Date, Numeric, Timestamp, Uuid};
+
+    #[derive(Debug, PartialEq)]
+    struct Item {
+        id: i32,
+        name: String,
+        note: Option<String>,
+    }
+
+    crate::impl_pg_composite!(Item { id, name, note });
+
+    #[test]
+    fn test_composite_trailing_null() {
+        let composite = Composite::from_pg(b"(1,\"hello\",)", 0, 0).unwrap();
+        assert_eq!(
+            composite.0,
+            vec![Some("1".to_string()), Some("hello".to_string()), None]
+        );
+        assert_eq!(composite.field::<i32>(0).unwrap(), 1);
+        assert_eq!(composite.field::<Option<String>>(2).unwrap(), None);
+        assert!(matches!(composite.field::<String>(2), Err(TypeError::UnexpectedNull)));
+        assert!(matches!(composite.field::<String>(3), Err(TypeError::ColumnNotFound(3))));
+
+        let item = Item::from_pg(b"(1,\"hello\",)", oid::TEXT, 0).unwrap();
+        assert_eq!(
+            item,
+            Item { id: 1, name: "hello".to_string(), note: None }
+        );
+    }
+
+    #[test]
+    fn test_composite_typed_fields() {
+        let composite = Composite::from_pg(
+            br#"(a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11,"2024-01-15 10:30:00",2024-01-15,19.99)"#,
+            0,
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            composite.field::<Uuid>(0).unwrap(),
+            Uuid("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string())
+        );
+        let ts = composite.field::<Timestamp>(1).unwrap();
+        assert_eq!(ts, Timestamp::from_pg(b"2024-01-15 10:30:00", oid::TIMESTAMP, 0).unwrap());
+        let date = composite.field::<Date>(2).unwrap();
+        assert_eq!(date, Date::from_pg(b"2024-01-15", oid::DATE, 0).unwrap());
+        assert_eq!(composite.field::<Numeric>(3).unwrap(), Numeric("19.99".to_string()));
+
+        // A real, mismatched OID is still rejected
+        assert!(matches!(
+            Uuid::from_pg(b"a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11", oid::TEXT, 0),
+            Err(TypeError::UnexpectedOid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_composite_quoting_and_escapes() {
+        let fields = parse_composite_text(r#"("a ""b""",c\,d,"",x\\y,"(1,2)")"#).unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                Some("a \"b\"".to_string()),
+                Some("c,d".to_string()),
+                Some(String::new()),
+                Some("x\\y".to_string()),
+                Some("(1,2)".to_string()),
+            ]
+        );
+        assert_eq!(parse_composite_text("(,)").unwrap(), vec![None, None]);
+        assert_eq!(parse_composite_text("()").unwrap(), vec![None]);
+    }
+
+    #[test]
+    fn test_composite_rejects_malformed() {
+        assert!(parse_composite_text("1,2").is_err());
+        assert!(parse_composite_text("(\"open)").is_err());
+        assert!(Composite::from_pg(b"(1)", 0, 1).is_err());
+
+        let err = Item::from_pg(b"(1,hello)", 0, 0).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid data: Expected 3 composite fields, got 2");
+    }
+}
@@ -0,0 +1,137 @@
+//! Mapping PostgreSQL enum columns to Rust enums.
+//!
+//! PostgreSQL sends enum values as their label in both text and binary
+//! format, so a mapping is just a label table. Use [`impl_pg_enum!`](crate::impl_pg_enum)
+//! to generate it:
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! enum Status { Active, Suspended }
+//!
+//! qail_pg::impl_pg_enum!(Status, {
+//!     "active" => Active,
+//!     "suspended" => Suspended,
+//! });
+//!
+//! let status: Status = row.try_get_by_name("status")?;
+//! ```
+
+use super::TypeError;
+
+/// A Rust enum backed by a PostgreSQL enum type.
+pub trait FromPgEnum: Sized + 'static {
+    /// Rust type name, used in error messages.
+    const TYPE_NAME: &'static str;
+
+    /// Look up a variant by its PostgreSQL label.
+    fn from_label(label: &str) -> Option<Self>;
+
+    /// PostgreSQL label of this variant.
+    fn label(&self) -> &'static str;
+
+    /// Decode a label from the wire, rejecting unknown labels.
+    fn decode_label(bytes: &[u8]) -> Result<Self, TypeError> {
+        let label = std::str::from_utf8(bytes)
+            .map_err(|e| TypeError::InvalidData(format!("Invalid UTF-8: {}", e)))?;
+        Self::from_label(label).ok_or_else(|| TypeError::UnknownEnumVariant {
+            type_name: Self::TYPE_NAME,
+            value: label.to_string(),
+        })
+    }
+}
+
+/// Implement [`FromPgEnum`], [`FromPg`](crate::types::FromPg) and
+/// [`ToPg`](crate::types::ToPg) for a fieldless enum from a label table.
+///
+/// Labels missing from the table decode as `TypeError::UnknownEnumVariant`.
+/// `ToPg` sends the label as text with an unspecified OID so the server
+/// infers the enum type from context.
+#[macro_export]
+macro_rules! impl_pg_enum {
+    ($ty:ty, { $($label:literal => $variant:ident),+ $(,)? }) => {
+        impl $crate::types::FromPgEnum for $ty {
+            const TYPE_NAME: &'static str = stringify!($ty);
+
+            fn from_label(label: &str) -> Option<Self> {
+                match label {
+                    $($label => Some(<$ty>::$variant),)+
+                    _ => None,
+                }
+            }
+
+            fn label(&self) -> &'static str {
+                match self {
+                    $(<$ty>::$variant => $label,)+
+                }
+            }
+        }
+
+        impl $crate::types::FromPg for $ty {
+            fn from_pg(
+                bytes: &[u8],
+                _oid: u32,
+                _format: i16,
+            ) -> Result<Self, $crate::types::TypeError> {
+                <$ty as $crate::types::FromPgEnum>::decode_label(bytes)
+            }
+        }
+
+        impl $crate::types::ToPg for $ty {
+            fn to_pg(&self) -> (Vec<u8>, u32, i16) {
+                let label = <$ty as $crate::types::FromPgEnum>::label(self);
+                (label.as_bytes().to_vec(), 0, 0)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::types::oid;
+    use crate::types::{FromPg, FromPgEnum, ToPg, TypeError};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Status {
+        Active,
+        Suspended,
+        PendingReview,
+    }
+
+    crate::impl_pg_enum!(Status, {
+        "active" => Active,
+        "suspended" => Suspended,
+        "pending_review" => PendingReview,
+    });
+
+    #[test]
+    fn test_enum_from_status_column() {
+        assert_eq!(Status::from_pg(b"active", oid::TEXT, 0).unwrap(), Status::Active);
+        assert_eq!(
+            Status::from_pg(b"pending_review", 16_385, 1).unwrap(),
+            Status::PendingReview
+        );
+        assert_eq!(
+            Option::<Status>::from_pg(b"suspended", oid::TEXT, 0).unwrap(),
+            Some(Status::Suspended)
+        );
+    }
+
+    #[test]
+    fn test_enum_rejects_unknown_label() {
+        match Status::from_pg(b"deleted", oid::TEXT, 0) {
+            Err(TypeError::UnknownEnumVariant { type_name, value }) => {
+                assert_eq!(type_name, "Status");
+                assert_eq!(value, "deleted");
+            }
+            other => panic!("expected UnknownEnumVariant, got {:?}", other),
+        }
+        let err = Status::from_pg(b"Active", oid::TEXT, 0).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown Status variant 'Active'");
+    }
+
+    #[test]
+    fn test_enum_to_pg_label() {
+        assert_eq!(Status::PendingReview.label(), "pending_review");
+        assert_eq!(Status::Suspended.to_pg(), (b"suspended".to_vec(), 0, 0));
+    }
+}
@@ -2,13 +2,19 @@
 //!
 //! This module provides traits for converting Rust types to/from PostgreSQL wire format.
 
+pub mod composite;
+pub mod enums;
 pub mod numeric;
 pub mod temporal;
 
+pub use composite::Composite;
+pub use enums::FromPgEnum;
 pub use numeric::Numeric;
 pub use temporal::{Date, Time, Timestamp};
 
-use crate::protocol::types::{decode_json, decode_jsonb, decode_text_array, decode_uuid, oid};
+use crate::protocol::types::{
+    array_oid, decode_json, decode_jsonb, decode_text_array, decode_uuid, oid,
+};
 
 /// Error type for type conversion failures.
 #[derive(Debug, Clone)]
@@ -19,6 +25,15 @@ pub enum TypeError {
     InvalidData(String),
     /// Null value where non-null expected
     UnexpectedNull,
+    /// Column index past the end of the row
+    ColumnNotFound(usize),
+    /// No column with this name in the row description
+    UnknownColumn(String),
+    /// Enum label with no matching Rust variant
+    UnknownEnumVariant {
+        type_name: &'static str,
+        value: String,
+    },
 }
 
 impl std::fmt::Display for TypeError {
@@ -29,6 +44,11 @@ impl std::fmt::Display for TypeError {
             }
             TypeError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
             TypeError::UnexpectedNull => write!(f, "Unexpected NULL value"),
+            TypeError::ColumnNotFound(idx) => write!(f, "Column {} not found in row", idx),
+            TypeError::UnknownColumn(name) => write!(f, "Unknown column '{}'", name),
+            TypeError::UnknownEnumVariant { type_name, value } => {
+                write!(f, "Unknown {} variant '{}'", type_name, value)
+            }
         }
     }
 }
@@ -43,6 +63,17 @@ pub trait FromPg: Sized {
     /// * `oid` - PostgreSQL type OID
     /// * `format` - 0 = text, 1 = binary
     fn from_pg(bytes: &[u8], oid: u32, format: i16) -> Result<Self, TypeError>;
+
+    /// Convert a SQL NULL. Only nullable targets (`Option<T>`) accept it.
+    fn from_pg_null() -> Result<Self, TypeError> {
+        Err(TypeError::UnexpectedNull)
+    }
+}
+
+/// Text of unknown type (`oid::UNKNOWN`), which type-checked impls accept
+/// and parse as their text format.
+pub(crate) fn is_untyped_text(oid_val: u32, format: i16) -> bool {
+    oid_val == oid::UNKNOWN && format == 0
 }
 
 /// Trait for converting Rust types to PostgreSQL wire format.
@@ -191,7 +222,7 @@ pub struct Uuid(pub String);
 
 impl FromPg for Uuid {
     fn from_pg(bytes: &[u8], oid_val: u32, format: i16) -> Result<Self, TypeError> {
-        if oid_val != oid::UUID {
+        if oid_val != oid::UUID && !is_untyped_text(oid_val, format) {
             return Err(TypeError::UnexpectedOid {
                 expected: "uuid",
                 got: oid_val,
@@ -260,13 +291,64 @@ impl FromPg for Vec<i64> {
     }
 }
 
+/// A whole list as one array parameter, e.g. for `id = ANY($1)`.
+///
+/// Elements sent in binary (integers, floats, bools, dates...) produce a
+/// one-dimensional binary array; text elements (strings, UUIDs, numerics,
+/// enums) produce a `{"a","b"}` literal. An empty list is sent as untyped
+/// `{}` so PostgreSQL infers the array type from the statement.
+impl<T: ToPg> ToPg for Vec<T> {
+    fn to_pg(&self) -> (Vec<u8>, u32, i16) {
+        let items: Vec<(Vec<u8>, u32, i16)> = self.iter().map(ToPg::to_pg).collect();
+        let Some(&(_, elem_oid, format)) = items.first() else {
+            return (b"{}".to_vec(), 0, 0);
+        };
+
+        if format == 1 {
+            let body: usize = items.iter().map(|(bytes, _, _)| 4 + bytes.len()).sum();
+            let mut buf = Vec::with_capacity(20 + body);
+            buf.extend_from_slice(&1i32.to_be_bytes()); // dimensions
+            buf.extend_from_slice(&0i32.to_be_bytes()); // no NULLs
+            buf.extend_from_slice(&elem_oid.to_be_bytes());
+            buf.extend_from_slice(&(items.len() as i32).to_be_bytes());
+            buf.extend_from_slice(&1i32.to_be_bytes()); // lower bound
+            for (bytes, _, _) in &items {
+                buf.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                buf.extend_from_slice(bytes);
+            }
+            return (buf, array_oid(elem_oid), 1);
+        }
+
+        // Quote every element so empty strings, spaces and "NULL" survive.
+        let mut buf = vec![b'{'];
+        for (i, (bytes, _, _)) in items.iter().enumerate() {
+            if i > 0 {
+                buf.push(b',');
+            }
+            buf.push(b'"');
+            for &b in bytes {
+                if b == b'"' || b == b'\\' {
+                    buf.push(b'\\');
+                }
+                buf.push(b);
+            }
+            buf.push(b'"');
+        }
+        buf.push(b'}');
+        (buf, array_oid(elem_oid), 0)
+    }
+}
+
 // ==================== Option<T> ====================
 
 impl<T: FromPg> FromPg for Option<T> {
     fn from_pg(bytes: &[u8], oid_val: u32, format: i16) -> Result<Self, TypeError> {
-        // This is for non-null; actual NULL handling is done at row level
         Ok(Some(T::from_pg(bytes, oid_val, format)?))
     }
+
+    fn from_pg_null() -> Result<Self, TypeError> {
+        Ok(None)
+    }
 }
 
 // ==================== Bytes ====================
@@ -320,6 +402,13 @@ mod tests {
         assert!(!bool::from_pg(&[0], oid::BOOL, 1).unwrap());
     }
 
+    #[test]
+    fn test_option_from_pg_null() {
+        assert_eq!(Option::<i32>::from_pg(b"7", oid::INT4, 0).unwrap(), Some(7));
+        assert_eq!(Option::<i32>::from_pg_null().unwrap(), None);
+        assert!(matches!(i32::from_pg_null(), Err(TypeError::UnexpectedNull)));
+    }
+
     #[test]
     fn test_uuid_from_pg_binary() {
         let uuid_bytes: [u8; 16] = [
@@ -329,4 +418,21 @@ mod tests {
         let result = Uuid::from_pg(&uuid_bytes, oid::UUID, 1).unwrap();
         assert_eq!(result.0, "550e8400-e29b-41d4-a716-446655440000");
     }
+
+    #[test]
+    fn test_vec_to_pg_single_array_param() {
+        let ids: Vec<i32> = (0..1000).collect();
+        let (bytes, oid_val, format) = ids.to_pg();
+        assert_eq!((oid_val, format), (oid::INT4_ARRAY, 1));
+        assert_eq!(bytes.len(), 20 + 1000 * 8);
+        assert_eq!(&bytes[8..12], &oid::INT4.to_be_bytes());
+        assert_eq!(&bytes[12..16], &1000i32.to_be_bytes());
+
+        let names = vec!["a", "", "b\"c", "d\\e"];
+        let (bytes, oid_val, format) = names.to_pg();
+        assert_eq!((oid_val, format), (oid::TEXT_ARRAY, 0));
+        assert_eq!(bytes, br#"{"a","","b\"c","d\\e"}"#);
+
+        assert_eq!(Vec::<i64>::new().to_pg(), (b"{}".to_vec(), 0, 0));
+    }
 }
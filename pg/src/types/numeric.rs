@@ -3,7 +3,7 @@
 //! PostgreSQL NUMERIC is a variable-precision type stored in a complex binary format.
 //! For simplicity, we use String representation and convert on demand.
 
-use super::{FromPg, ToPg, TypeError};
+use super::{is_untyped_text, FromPg, ToPg, TypeError};
 use crate::protocol::types::oid;
 
 /// NUMERIC/DECIMAL type (stored as string for precision)
@@ -36,7 +36,7 @@ impl Numeric {
 
 impl FromPg for Numeric {
     fn from_pg(bytes: &[u8], oid_val: u32, format: i16) -> Result<Self, TypeError> {
-        if oid_val != oid::NUMERIC {
+        if oid_val != oid::NUMERIC && !is_untyped_text(oid_val, format) {
             return Err(TypeError::UnexpectedOid {
                 expected: "numeric",
                 got: oid_val,
@@ -64,6 +64,12 @@ impl ToPg for Numeric {
     }
 }
 
+impl From<Numeric> for qail_core::ast::Value {
+    fn from(n: Numeric) -> Self {
+        qail_core::ast::Value::Decimal(n.0)
+    }
+}
+
 /// Decode PostgreSQL binary NUMERIC format
 fn decode_numeric_binary(bytes: &[u8]) -> Result<Numeric, TypeError> {
     if bytes.len() < 8 {
@@ -165,6 +171,13 @@ mod tests {
         assert_eq!(n.to_i64().unwrap(), 12345);
     }
 
+    #[test]
+    fn test_numeric_into_decimal_value() {
+        let n = Numeric::from_pg(b"19.99", oid::NUMERIC, 0).unwrap();
+        let v: qail_core::ast::Value = n.into();
+        assert_eq!(v, qail_core::ast::Value::Decimal("19.99".to_string()));
+    }
+
     #[test]
     fn test_numeric_negative() {
         let n = Numeric::new("-999.99");
@@ -2,7 +2,7 @@
 //!
 //! PostgreSQL timestamps are stored as microseconds since 2000-01-01 00:00:00 UTC.
 
-use super::{FromPg, ToPg, TypeError};
+use super::{is_untyped_text, FromPg, ToPg, TypeError};
 use crate::protocol::types::oid;
 
 /// PostgreSQL epoch: 2000-01-01 00:00:00 UTC
@@ -42,7 +42,7 @@ impl Timestamp {
 
 impl FromPg for Timestamp {
     fn from_pg(bytes: &[u8], oid_val: u32, format: i16) -> Result<Self, TypeError> {
-        if oid_val != oid::TIMESTAMP && oid_val != oid::TIMESTAMPTZ {
+        if oid_val != oid::TIMESTAMP && oid_val != oid::TIMESTAMPTZ && !is_untyped_text(oid_val, format) {
             return Err(TypeError::UnexpectedOid {
                 expected: "timestamp",
                 got: oid_val,
@@ -168,7 +168,7 @@ pub struct Date {
 
 impl FromPg for Date {
     fn from_pg(bytes: &[u8], oid_val: u32, format: i16) -> Result<Self, TypeError> {
-        if oid_val != oid::DATE {
+        if oid_val != oid::DATE && !is_untyped_text(oid_val, format) {
             return Err(TypeError::UnexpectedOid {
                 expected: "date",
                 got: oid_val,
@@ -246,7 +246,7 @@ impl Time {
 
 impl FromPg for Time {
     fn from_pg(bytes: &[u8], oid_val: u32, format: i16) -> Result<Self, TypeError> {
-        if oid_val != oid::TIME {
+        if oid_val != oid::TIME && !is_untyped_text(oid_val, format) {
             return Err(TypeError::UnexpectedOid {
                 expected: "time",
                 got: oid_val,
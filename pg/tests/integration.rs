@@ -5,7 +5,7 @@
 //! Then: `cargo test --test integration -- --nocapture`
 
 use qail_core::ast::Qail;
-use qail_pg::{PgDriver, PgResult};
+use qail_pg::{PgDriver, PgError, PgResult};
 
 /// Test connecting to PostgreSQL and running a simple query.
 #[tokio::test]
@@ -58,3 +58,1157 @@ async fn test_filtered_query() -> PgResult<()> {
 
     Ok(())
 }
+
+/// Test pipelining two different prepared statements in one round-trip
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_pipeline_prepared_multi() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    let add = driver.prepare("SELECT $1::int + 1").await?;
+    let upper = driver.prepare("SELECT upper($1::text)").await?;
+
+    let results = driver
+        .pipeline_prepared_multi(&[
+            (&add, vec![Some(b"41".to_vec())]),
+            (&upper, vec![Some(b"qail".to_vec())]),
+        ])
+        .await?;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0][0][0].as_deref(), Some(&b"42"[..]));
+    assert_eq!(results[1][0][0].as_deref(), Some(&b"QAIL"[..]));
+
+    Ok(())
+}
+
+/// Test that connect/auth/ready timings are recorded in order.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_connect_timing_recorded() -> PgResult<()> {
+    let driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    let timing = driver.connect_timing();
+    assert!(timing.ready > std::time::Duration::ZERO);
+    assert!(timing.connect <= timing.auth, "{:?}", timing);
+    assert!(timing.auth <= timing.ready, "{:?}", timing);
+
+    Ok(())
+}
+
+/// Test that pipeline timings are populated and add up to the total.
+#[cfg(feature = "pipeline_timing")]
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_pipeline_timing_breakdown() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    let stmt = driver.prepare("SELECT $1::int * 2").await?;
+    let batch: Vec<Vec<Option<Vec<u8>>>> = (1..=1000)
+        .map(|i: i32| vec![Some(i.to_string().into_bytes())])
+        .collect();
+    let (completed, timing) = driver.pipeline_prepared_fast_timed(&stmt, &batch).await?;
+
+    assert_eq!(completed, 1000);
+    assert_eq!(timing.queries, 1000);
+    assert!(timing.bytes_sent > 1000 * 10, "{:?}", timing);
+    assert!(timing.encode > std::time::Duration::ZERO, "{:?}", timing);
+    assert!(timing.recv > std::time::Duration::ZERO, "{:?}", timing);
+    assert_eq!(timing.encode + timing.send + timing.recv, timing.total);
+
+    let cmds: Vec<Qail> = (1..=50).map(|i| Qail::get("pg_class").columns(["relname"]).limit(i)).collect();
+    let (rows, timing) = driver.pipeline_fetch_timed(&cmds).await?;
+    assert_eq!(rows.len(), 50);
+    assert_eq!(timing.queries, 50);
+    assert_eq!(timing.encode + timing.send + timing.recv, timing.total);
+
+    Ok(())
+}
+
+/// Test that a per-query statement timeout does not stick to the connection.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_statement_timeout_is_per_query() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE OR REPLACE VIEW qail_slow_view AS SELECT pg_sleep(0.3)::text AS slept")
+        .await?;
+    let slow = Qail::get("qail_slow_view");
+
+    let err = driver.fetch_all_with_timeout_stmt(&slow, 50).await;
+    assert!(err.is_err(), "slow query should hit the 50ms timeout");
+
+    let rows = driver.fetch_all_with_timeout_stmt(&slow, 5_000).await?;
+    assert_eq!(rows.len(), 1);
+
+    // The timeout was SET LOCAL, so nothing is left on the connection
+    let setting = driver.fetch_raw("SHOW statement_timeout").await?;
+    assert_eq!(setting[0].get_string(0), Some("0".to_string()));
+    assert_eq!(driver.fetch_all(&slow).await?.len(), 1);
+
+    // Inside a transaction it must not COMMIT or abort the caller's work
+    driver.execute_raw("CREATE TEMP TABLE qail_timeout_tx (id int)").await?;
+    driver.begin().await?;
+    driver.execute_raw("SET LOCAL statement_timeout = 10000").await?;
+    driver.execute_raw("INSERT INTO qail_timeout_tx VALUES (1)").await?;
+    assert!(driver.fetch_all_with_timeout_stmt(&slow, 50).await.is_err());
+    assert_eq!(driver.fetch_all_with_timeout_stmt(&slow, 5_000).await?.len(), 1);
+    let setting = driver.fetch_raw("SHOW statement_timeout").await?;
+    assert_eq!(setting[0].get_string(0), Some("10s".to_string()));
+    driver.rollback().await?;
+    let rows = driver.fetch_raw("SELECT count(*) FROM qail_timeout_tx").await?;
+    assert_eq!(rows[0].get_string(0), Some("0".to_string()));
+
+    driver.execute_raw("DROP VIEW qail_slow_view").await?;
+    Ok(())
+}
+
+/// Test that server errors carry their SQLSTATE and constraint name.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_unique_violation_error_code() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE qail_uniq (id int CONSTRAINT qail_uniq_pk PRIMARY KEY)")
+        .await?;
+    driver.execute_raw("INSERT INTO qail_uniq VALUES (1)").await?;
+
+    let err = driver
+        .execute_raw("INSERT INTO qail_uniq VALUES (1)")
+        .await
+        .expect_err("duplicate key should fail");
+    assert_eq!(err.code(), Some("23505"));
+    match err {
+        PgError::Database(e) => {
+            assert_eq!(e.code, "23505");
+            assert_eq!(e.constraint.as_deref(), Some("qail_uniq_pk"));
+            assert!(e.detail.is_some());
+        }
+        other => panic!("expected PgError::Database, got {:?}", other),
+    }
+    Ok(())
+}
+
+/// Test that warmed-up statements are reused by `fetch_all_cached` without a Parse.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_warmup_prepares_cached_statements() -> PgResult<()> {
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE warmup_items (id INT PRIMARY KEY, name TEXT)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO warmup_items VALUES (1, 'a'), (2, 'b')")
+        .await?;
+
+    let by_id = Qail::get("warmup_items")
+        .columns(["id", "name"])
+        .filter("id", Operator::Eq, 2);
+    let all = Qail::get("warmup_items").column("id").order_asc("id");
+
+    assert_eq!(driver.warmup(&[by_id.clone(), all.clone()]).await?, 2);
+    assert_eq!(driver.warmup(std::slice::from_ref(&all)).await?, 0);
+    assert_eq!(driver.cache_stats().0, 2);
+
+    // A second Parse under an existing name would fail with
+    // "prepared statement already exists", so success means no Parse was sent.
+    let rows = driver.fetch_all_cached(&by_id).await?;
+    assert_eq!(rows[0].get_string(1), Some("b".to_string()));
+    let rows = driver.fetch_all_cached(&all).await?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(driver.cache_stats().0, 2);
+
+    let rows = driver
+        .fetch_raw("SELECT count(*) FROM pg_prepared_statements WHERE name LIKE 'qail_%'")
+        .await?;
+    assert_eq!(rows[0].get_string(0), Some("2".to_string()));
+
+    Ok(())
+}
+
+/// Test that warmup closes statements evicted from a bounded cache.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_warmup_closes_evicted_statements() -> PgResult<()> {
+    let queries: Vec<Qail> = (1..=3)
+        .map(|n| Qail::get("pg_class").column("relname").limit(n))
+        .collect();
+
+    let mut driver = PgDriver::builder()
+        .user("qail")
+        .password("qail")
+        .database("qail_test")
+        .statement_cache_size(2)
+        .connect()
+        .await?;
+    assert_eq!(driver.warmup(&queries).await?, 3);
+    // The first statement was evicted; preparing it again under the same
+    // hashed name only succeeds if the Close went out first
+    assert_eq!(driver.warmup(&queries[..1]).await?, 1);
+    // That evicted the second one in turn
+    assert_eq!(driver.warmup(&queries[1..2]).await?, 1);
+    assert_eq!(driver.cache_stats().0, 2);
+    Ok(())
+}
+
+/// Test that a failing savepoint closure keeps the outer transaction's work
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_with_savepoint_rolls_back_inner() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE sp_test (id INT PRIMARY KEY)")
+        .await?;
+
+    driver.begin().await?;
+    driver.execute_raw("INSERT INTO sp_test VALUES (1)").await?;
+
+    let inner = driver
+        .with_savepoint("sp_inner", async |d| {
+            d.execute_raw("INSERT INTO sp_test VALUES (2)").await?;
+            // Duplicate key aborts the inner work
+            d.execute_raw("INSERT INTO sp_test VALUES (1)").await
+        })
+        .await;
+    assert!(inner.is_err());
+
+    driver
+        .with_savepoint("sp_ok", async |d| {
+            d.execute_raw("INSERT INTO sp_test VALUES (3)").await
+        })
+        .await?;
+    driver.commit().await?;
+
+    let rows = driver
+        .fetch_all(&Qail::get("sp_test").column("id").order_asc("id"))
+        .await?;
+    let ids: Vec<Option<i32>> = rows.iter().map(|r| r.get_i32(0)).collect();
+    assert_eq!(ids, vec![Some(1), Some(3)]);
+
+    Ok(())
+}
+
+/// Test that values with tabs/newlines/backslashes survive a COPY round-trip
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_copy_escape_round_trip() -> PgResult<()> {
+    use qail_pg::driver::copy_escape;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE copy_escape_test (id INT, body TEXT)")
+        .await?;
+
+    let body = "line1\nline2\twith \\ backslash";
+    let mut data = b"1\t".to_vec();
+    copy_escape(&mut data, body.as_bytes());
+    data.push(b'\n');
+
+    let cmd = Qail::add("copy_escape_test").columns(["id", "body"]);
+    assert_eq!(driver.copy_bulk_bytes(&cmd, &data).await?, 1);
+
+    let rows = driver
+        .fetch_all(&Qail::get("copy_escape_test").column("body"))
+        .await?;
+    assert_eq!(rows[0].get_string(0).as_deref(), Some(body));
+
+    Ok(())
+}
+
+/// Test that each mutation in a pipeline reports its own affected-row count
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_pipeline_affected_rows() -> PgResult<()> {
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE pipeline_affected (id INT, grp INT, flag BOOL)")
+        .await?;
+    driver
+        .execute_raw(
+            "INSERT INTO pipeline_affected VALUES (1, 1, false), (2, 1, false), (3, 2, false)",
+        )
+        .await?;
+
+    let cmds = vec![
+        Qail::set("pipeline_affected")
+            .set_value("flag", true)
+            .filter("grp", Operator::Eq, 1),
+        Qail::set("pipeline_affected")
+            .set_value("flag", true)
+            .filter("grp", Operator::Eq, 2),
+    ];
+
+    let results = driver.pipeline_fetch_with_affected(&cmds).await?;
+    let affected: Vec<u64> = results.iter().map(|(_, n)| *n).collect();
+    assert_eq!(affected, vec![2, 1]);
+
+    Ok(())
+}
+
+/// Test that application_name reaches the server (and the default names qail).
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_application_name() -> PgResult<()> {
+    let mut driver = PgDriver::builder()
+        .user("qail")
+        .password("qail")
+        .database("qail_test")
+        .application_name("billing-worker")
+        .connect()
+        .await?;
+    let rows = driver.fetch_raw("SHOW application_name").await?;
+    assert_eq!(rows[0].get_string(0), Some("billing-worker".to_string()));
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    let rows = driver
+        .fetch_raw("SELECT current_setting('application_name')")
+        .await?;
+    assert_eq!(rows[0].get_string(0), Some(qail_pg::driver::DEFAULT_APPLICATION_NAME.to_string()));
+
+    Ok(())
+}
+
+/// Test that `~=` matches `%` literally instead of as a wildcard.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_fuzzy_escaped_matches_literal_percent() -> PgResult<()> {
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE fuzzy_escape (label TEXT)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO fuzzy_escape VALUES ('100% cotton'), ('1000 units')")
+        .await?;
+
+    let cmd = Qail::get("fuzzy_escape")
+        .columns(["label"])
+        .filter("label", Operator::FuzzyEscaped, "100%");
+    let rows = driver.fetch_all(&cmd).await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get_string(0), Some("100% cotton".to_string()));
+
+    Ok(())
+}
+
+/// Test pipelining many parameterized selects from `Value` rows.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_pipeline_query_values() -> PgResult<()> {
+    use qail_core::ast::{Operator, Value};
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    let batch: Vec<Vec<Value>> = (1..=1000).map(|i| vec![Value::Int(i)]).collect();
+    let count = driver
+        .pipeline_query("SELECT $1::bigint + 1", &batch)
+        .await?;
+    assert_eq!(count, 1000);
+
+    // Same through a Qail command with a placeholder
+    driver
+        .execute_raw("CREATE TEMP TABLE pipeline_values (id BIGINT)")
+        .await?;
+    let cmd = Qail::get("pipeline_values")
+        .columns(["id"])
+        .filter("id", Operator::Eq, Value::Param(1));
+    let count = driver.pipeline_query(&cmd, &batch).await?;
+    assert_eq!(count, 1000);
+
+    Ok(())
+}
+
+/// Test fetch_scalar for an aggregate and for an empty result.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_fetch_scalar() -> PgResult<()> {
+    use qail_core::ast::builders::count;
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE scalar_items (id BIGINT)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO scalar_items VALUES (1), (2), (3)")
+        .await?;
+
+    let total: Option<i64> = driver
+        .fetch_scalar(&Qail::get("scalar_items").column_expr(count().into()))
+        .await?;
+    assert_eq!(total, Some(3));
+
+    let missing: Option<i64> = driver
+        .fetch_scalar(
+            &Qail::get("scalar_items")
+                .columns(["id"])
+                .filter("id", Operator::Eq, 99),
+        )
+        .await?;
+    assert_eq!(missing, None);
+
+    // Many rows: only the first is fetched, and the connection stays usable
+    let first: Option<i64> = driver
+        .fetch_scalar(&Qail::get("scalar_items").columns(["id"]).order_desc("id"))
+        .await?;
+    assert_eq!(first, Some(3));
+
+    // A statement with RETURNING still runs to completion
+    let id: Option<i64> = driver
+        .fetch_scalar(
+            &Qail::add("scalar_items")
+                .columns(["id"])
+                .values([4])
+                .returning(["id"]),
+        )
+        .await?;
+    assert_eq!(id, Some(4));
+    let total: Option<i64> = driver
+        .fetch_scalar(&Qail::get("scalar_items").column_expr(count().into()))
+        .await?;
+    assert_eq!(total, Some(4));
+
+    Ok(())
+}
+
+/// Test describe returns column names and type OIDs without running the query.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_describe_columns() -> PgResult<()> {
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE describe_items (id INT, label TEXT, price NUMERIC)")
+        .await?;
+
+    let info = driver
+        .describe(
+            &Qail::get("describe_items")
+                .columns(["id", "label", "price"])
+                .filter("id", Operator::Eq, 1),
+        )
+        .await?;
+    assert_eq!(info.name_to_index["id"], 0);
+    assert_eq!(info.name_to_index["label"], 1);
+    assert_eq!(info.name_to_index["price"], 2);
+    assert_eq!(info.names, ["id", "label", "price"]);
+    assert_eq!(info.oids, vec![23, 25, 1700]); // int4, text, numeric
+
+    // Nothing was executed: a DELETE is only described
+    driver
+        .execute_raw("INSERT INTO describe_items VALUES (1, 'a', 1.5)")
+        .await?;
+    let info = driver.describe(&Qail::del("describe_items")).await?;
+    assert!(info.oids.is_empty());
+    let remaining: Option<i64> = driver
+        .fetch_scalar(&Qail::get("describe_items").columns(["count(*)"]))
+        .await?;
+    assert_eq!(remaining, Some(1));
+
+    // The connection stays usable after a describe error
+    assert!(driver.describe(&Qail::get("no_such_table")).await.is_err());
+    let rows = driver.fetch_all(&Qail::get("describe_items").columns(["id"])).await?;
+    assert_eq!(rows.len(), 1);
+
+    Ok(())
+}
+
+/// Test cursor streaming binds filter values and keeps column names.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_stream_cmd_batches() -> PgResult<()> {
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE stream_items AS SELECT g AS id FROM generate_series(1, 10) g")
+        .await?;
+
+    let cmd = Qail::get("stream_items")
+        .columns(["id"])
+        .filter("id", Operator::Gt, 3)
+        .order_by("id", qail_core::ast::SortOrder::Asc);
+    let batches = driver.stream_cmd(&cmd, 4).await?;
+
+    let sizes: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+    assert_eq!(sizes, vec![4, 3]);
+    assert_eq!(batches[0][0].get_string_by_name("id").as_deref(), Some("4"));
+
+    Ok(())
+}
+
+/// Test streaming inside a caller-managed transaction leaves it open.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_stream_cmd_in_tx() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE stream_tx AS SELECT g AS id FROM generate_series(1, 5) g")
+        .await?;
+    driver.set_fetch_size(2);
+
+    driver.begin().await?;
+    driver.execute_raw("INSERT INTO stream_tx VALUES (6)").await?;
+
+    let cmd = Qail::get("stream_tx")
+        .columns(["id"])
+        .order_by("id", qail_core::ast::SortOrder::Asc);
+    let mut cursor = driver.stream_cmd_in_tx(&cmd, 0).await?;
+    assert!(cursor.name().starts_with("qail_cursor_"));
+    assert_eq!(cursor.batch_size(), 2);
+
+    let mut sizes = Vec::new();
+    while let Some(batch) = driver.next_batch(&mut cursor).await? {
+        sizes.push(batch.len());
+    }
+    // The uncommitted row is visible to the cursor
+    assert_eq!(sizes, vec![2, 2, 2]);
+    assert!(driver.next_batch(&mut cursor).await?.is_none());
+    driver.close_cursor(cursor).await?;
+
+    // Nothing was committed on our behalf
+    driver.rollback().await?;
+    let rows = driver.fetch_raw("SELECT count(*) FROM stream_tx").await?;
+    assert_eq!(rows[0].get_string(0).as_deref(), Some("5"));
+
+    Ok(())
+}
+
+/// Test the query logging callback fires once per fetch_all with the SQL and param count.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_on_query_logger() -> PgResult<()> {
+    use qail_core::ast::Operator;
+    use std::sync::{Arc, Mutex};
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE logged_items AS SELECT g AS id FROM generate_series(1, 3) g")
+        .await?;
+
+    let seen: Arc<Mutex<Vec<(String, usize)>>> = Arc::default();
+    let sink = seen.clone();
+    driver.on_query(Box::new(move |sql, params| {
+        sink.lock().unwrap().push((sql.to_string(), params));
+    }));
+
+    let cmd = Qail::get("logged_items")
+        .columns(["id"])
+        .filter("id", Operator::Gt, 1);
+    driver.fetch_all(&cmd).await?;
+
+    {
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].0.starts_with("SELECT id FROM logged_items WHERE"), "{}", seen[0].0);
+        assert_eq!(seen[0].1, 1);
+    }
+
+    // Cached re-execution still reports the query
+    driver.fetch_all(&cmd).await?;
+    assert_eq!(seen.lock().unwrap().len(), 2);
+
+    Ok(())
+}
+
+/// Test TRUNCATE ... RESTART IDENTITY through the AST encoder.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_truncate_restart_identity() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE truncate_items (id SERIAL PRIMARY KEY, label TEXT)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO truncate_items (label) VALUES ('a'), ('b')")
+        .await?;
+
+    driver
+        .execute(&Qail::truncate("truncate_items").restart_identity().cascade())
+        .await?;
+
+    let count: Option<i64> = driver
+        .fetch_scalar(&Qail::get("truncate_items").columns(["count(*)"]))
+        .await?;
+    assert_eq!(count, Some(0));
+
+    // Sequence was reset: the next row gets id 1 again
+    driver
+        .execute_raw("INSERT INTO truncate_items (label) VALUES ('c')")
+        .await?;
+    let rows = driver
+        .fetch_all(&Qail::get("truncate_items").columns(["id"]))
+        .await?;
+    assert_eq!(rows[0].get_string(0).as_deref(), Some("1"));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_read_only_and_isolation_transactions() -> PgResult<()> {
+    use qail_pg::IsolationLevel;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    // Not TEMP: PostgreSQL allows writes to temp tables in read-only transactions
+    driver
+        .execute_raw("DROP TABLE IF EXISTS read_only_items")
+        .await?;
+    driver
+        .execute_raw("CREATE TABLE read_only_items (id INT)")
+        .await?;
+
+    // Writes are rejected by the server inside a read-only transaction
+    driver.begin_read_only().await?;
+    let err = driver
+        .execute_raw("INSERT INTO read_only_items VALUES (1)")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("read-only transaction"), "{}", err);
+    driver.rollback().await?;
+
+    driver.begin_with(IsolationLevel::Serializable, true).await?;
+    let rows = driver.fetch_raw("SHOW transaction_isolation").await?;
+    assert_eq!(rows[0].get_string(0).as_deref(), Some("serializable"));
+    let rows = driver.fetch_raw("SHOW transaction_read_only").await?;
+    assert_eq!(rows[0].get_string(0).as_deref(), Some("on"));
+    driver.commit().await?;
+
+    driver.begin_with(IsolationLevel::RepeatableRead, false).await?;
+    let rows = driver.fetch_raw("SHOW transaction_isolation").await?;
+    assert_eq!(rows[0].get_string(0).as_deref(), Some("repeatable read"));
+    driver
+        .execute_raw("INSERT INTO read_only_items VALUES (1)")
+        .await?;
+    driver.commit().await?;
+
+    driver.execute_raw("DROP TABLE read_only_items").await?;
+    Ok(())
+}
+
+/// Test decoding composite (ROW) values returned by the server.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_composite_decode() -> PgResult<()> {
+    use qail_pg::Composite;
+
+    #[derive(Debug, PartialEq)]
+    struct Item {
+        id: i32,
+        name: String,
+        note: Option<String>,
+    }
+    qail_pg::impl_pg_composite!(Item { id, name, note });
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    let rows = driver
+        .fetch_raw(r#"SELECT ROW(1, 'say "hi", \ok', NULL::text), ROW(2, '', 'x')"#)
+        .await?;
+
+    let first: Item = rows[0].try_get(0).unwrap();
+    assert_eq!(
+        first,
+        Item { id: 1, name: r#"say "hi", \ok"#.to_string(), note: None }
+    );
+    let second: Composite = rows[0].try_get(1).unwrap();
+    assert_eq!(
+        second.0,
+        vec![Some("2".to_string()), Some(String::new()), Some("x".to_string())]
+    );
+
+    #[derive(Debug, PartialEq)]
+    struct Event {
+        id: qail_pg::Uuid,
+        at: qail_pg::Timestamp,
+    }
+    qail_pg::impl_pg_composite!(Event { id, at });
+
+    let rows = driver
+        .fetch_raw(
+            "SELECT ROW('a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid, \
+             '2024-01-15 10:30:00'::timestamp)",
+        )
+        .await?;
+    let event: Event = rows[0].try_get(0).unwrap();
+    assert_eq!(event.id.0, "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+    let at = <qail_pg::Timestamp as qail_pg::FromPg>::from_pg(
+        b"2024-01-15 10:30:00",
+        qail_pg::protocol::oid::TIMESTAMP,
+        0,
+    )
+    .unwrap();
+    assert_eq!(event.at, at);
+    Ok(())
+}
+
+/// Test readable statement names for cached statements.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_readable_statement_names() -> PgResult<()> {
+    use qail_core::ast::Operator;
+    use qail_pg::StatementNaming;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver.set_statement_naming(StatementNaming::Readable);
+
+    let by_name = Qail::get("pg_class").column("relname").limit(1);
+    let by_kind = Qail::get("pg_class")
+        .column("relname")
+        .filter("relkind", Operator::Eq, "r")
+        .limit(1);
+    driver.fetch_all(&by_name).await?;
+    driver.fetch_all(&by_kind).await?;
+    // Same SQL reuses its statement instead of taking a new name
+    driver.fetch_all(&by_name).await?;
+
+    let rows = driver
+        .fetch_raw("SELECT name FROM pg_prepared_statements ORDER BY name")
+        .await?;
+    let names: Vec<String> = rows.iter().filter_map(|r| r.get_string(0)).collect();
+    assert_eq!(names, vec!["qail_get_pg_class_1", "qail_get_pg_class_2"]);
+    Ok(())
+}
+
+/// Test a bounded statement cache closing evicted statements, and a disabled one.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_statement_cache_size() -> PgResult<()> {
+    async fn prepared(driver: &mut PgDriver) -> PgResult<Vec<String>> {
+        let rows = driver
+            .fetch_raw("SELECT name FROM pg_prepared_statements ORDER BY name")
+            .await?;
+        Ok(rows.iter().filter_map(|r| r.get_string(0)).collect())
+    }
+
+    let queries: Vec<Qail> = (1..=3)
+        .map(|n| Qail::get("pg_class").column("relname").limit(n))
+        .collect();
+
+    let mut driver = PgDriver::builder()
+        .user("qail")
+        .password("qail")
+        .database("qail_test")
+        .statement_cache_size(2)
+        .connect()
+        .await?;
+    driver.set_statement_naming(qail_pg::StatementNaming::Readable);
+    for query in &queries {
+        driver.fetch_all(query).await?;
+    }
+    // Closing the oldest statement rides along with the next Parse
+    driver.fetch_all(&queries[2]).await?;
+    assert_eq!(driver.cache_stats(), (2, 2));
+    driver.fetch_all(&queries[0]).await?;
+    assert_eq!(
+        prepared(&mut driver).await?,
+        vec!["qail_get_pg_class_3", "qail_get_pg_class_4"]
+    );
+
+    // Hashed names are reused, so an evicted statement must be closed
+    // before it can be prepared again
+    driver.set_statement_naming(qail_pg::StatementNaming::Hashed);
+    for query in queries.iter().chain(&queries) {
+        driver.fetch_all(query).await?;
+    }
+
+    driver.set_statement_cache_size(0);
+    assert_eq!(driver.cache_stats(), (0, 0));
+    driver.fetch_all(&queries[0]).await?;
+    driver.fetch_all(&queries[0]).await?;
+    assert_eq!(driver.warmup(&queries).await?, 0);
+
+    let mut uncached = PgDriver::builder()
+        .user("qail")
+        .password("qail")
+        .database("qail_test")
+        .statement_cache_size(0)
+        .connect()
+        .await?;
+    for _ in 0..3 {
+        let rows = uncached.fetch_all(&queries[0]).await?;
+        assert_eq!(rows.len(), 1);
+    }
+    assert!(prepared(&mut uncached).await?.is_empty());
+    Ok(())
+}
+
+/// Test advisory locks contended between two sessions.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_advisory_locks() -> PgResult<()> {
+    const KEY: i64 = 0x5141_494c;
+
+    let mut a =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    let mut b =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    a.advisory_lock(KEY).await?;
+    assert!(!b.try_advisory_lock(KEY).await?);
+    // b never held it
+    assert!(!b.advisory_unlock(KEY).await?);
+    assert!(a.advisory_unlock(KEY).await?);
+    assert!(b.try_advisory_lock(KEY).await?);
+    b.advisory_unlock_all().await?;
+
+    // Transaction-scoped locks end with the transaction
+    a.begin().await?;
+    a.advisory_xact_lock(KEY).await?;
+    assert!(!b.try_advisory_xact_lock(KEY).await?);
+    a.commit().await?;
+    assert!(b.try_advisory_lock(KEY).await?);
+    b.begin().await?;
+    assert!(!a.try_advisory_lock(KEY).await?);
+    b.rollback().await?;
+    // A session lock outlives the transaction it was taken in
+    assert!(!a.try_advisory_lock(KEY).await?);
+    Ok(())
+}
+
+/// Test UPDATE with an expression and a correlated subquery on the right-hand side.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_update_expression_and_subquery() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE set_users (id INT, hits INT, total INT)")
+        .await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE set_orders (user_id INT, amount INT)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO set_users VALUES (1, 0, 0), (2, 5, 0)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO set_orders VALUES (1, 10), (1, 15), (2, 7)")
+        .await?;
+
+    let update = qail_core::parser::parse(
+        "set set_users values hits = hits + 1, \
+         total = (get set_orders fields sum(amount) where user_id = set_users.id)",
+    )
+    .unwrap();
+    driver.execute(&update).await?;
+
+    let rows = driver
+        .fetch_raw("SELECT hits, total FROM set_users ORDER BY id")
+        .await?;
+    let values: Vec<(i64, i64)> = rows
+        .iter()
+        .map(|r| (r.get_i64(0).unwrap(), r.get_i64(1).unwrap()))
+        .collect();
+    assert_eq!(values, vec![(1, 25), (6, 7)]);
+    Ok(())
+}
+
+/// Test keyset pagination walking a table with duplicate sort values.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_keyset_pagination() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE keyset_posts (id INT PRIMARY KEY, created DATE)")
+        .await?;
+    driver
+        .execute_raw(
+            "INSERT INTO keyset_posts \
+             SELECT n, DATE '2024-01-01' + n / 3 FROM generate_series(1, 10) n",
+        )
+        .await?;
+
+    let mut seen = Vec::new();
+    let mut last = ("0001-01-01".to_string(), 0i64);
+    loop {
+        let page = Qail::get("keyset_posts")
+            .columns(["created", "id"])
+            .keyset_after(("created", "id"), (last.0.clone(), last.1))
+            .unwrap()
+            .limit(4);
+        let rows = driver.fetch_all(&page).await?;
+        let Some(row) = rows.last() else { break };
+        last = (row.get_string(0).unwrap(), row.get_i64(1).unwrap());
+        seen.extend(rows.iter().map(|r| r.get_i64(1).unwrap()));
+    }
+    assert_eq!(seen, (1..=10).collect::<Vec<i64>>());
+    Ok(())
+}
+
+/// Test upserting against a partial unique index.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_upsert_partial_unique_index() -> PgResult<()> {
+    use qail_core::ast::{Expr, Operator, SortOrder, Value};
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw(
+            "CREATE TEMP TABLE upsert_users (id SERIAL, email TEXT, name TEXT, deleted_at DATE)",
+        )
+        .await?;
+    driver
+        .execute_raw(
+            "CREATE UNIQUE INDEX ON upsert_users (email) WHERE deleted_at IS NULL",
+        )
+        .await?;
+    driver
+        .execute_raw(
+            "INSERT INTO upsert_users (email, name, deleted_at) \
+             VALUES ('a@b.c', 'old', DATE '2024-01-01'), ('a@b.c', 'live', NULL)",
+        )
+        .await?;
+
+    let upsert = Qail::add("upsert_users")
+        .columns(["email", "name"])
+        .values(["a@b.c", "new"])
+        .on_conflict_update(&["email"], &[("name", Expr::Named("EXCLUDED.name".into()))])
+        .on_conflict_where("deleted_at", Operator::IsNull, Value::Null);
+    driver.execute(&upsert).await?;
+
+    let rows = driver
+        .fetch_all(&Qail::get("upsert_users").columns(["name"]).order_by("id", SortOrder::Asc))
+        .await?;
+    let names: Vec<String> = rows.iter().map(|r| r.get_string(0).unwrap()).collect();
+    assert_eq!(names, vec!["old", "new"]);
+    Ok(())
+}
+
+/// Test an accumulating upsert: DO UPDATE reads both the row and EXCLUDED.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_upsert_accumulates_with_excluded() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE upsert_counters (id INT PRIMARY KEY, count INT, hits INT)")
+        .await?;
+
+    for count in [5, 7, 30] {
+        let upsert = qail_core::parse(&format!(
+            "add upsert_counters fields id, count, hits values 1, {}, 1 \
+             conflict (id) update count = upsert_counters.count + excluded.count, \
+             hits = 1 + upsert_counters.hits",
+            count
+        ))
+        .unwrap();
+        driver.execute(&upsert).await?;
+    }
+
+    let rows = driver
+        .fetch_all(&Qail::get("upsert_counters").columns(["count", "hits"]))
+        .await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get_i32(0), Some(42));
+    assert_eq!(rows[0].get_i32(1), Some(3));
+    Ok(())
+}
+
+/// Test streaming COPY with progress reporting.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_copy_in_raw_progress() -> PgResult<()> {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tokio::io::{AsyncRead, ReadBuf};
+
+    /// Yields each chunk from a separate read, then EOF or an error.
+    struct Chunked {
+        chunks: Vec<&'static [u8]>,
+        fail_at_end: bool,
+    }
+
+    impl AsyncRead for Chunked {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            if self.chunks.is_empty() {
+                if self.fail_at_end {
+                    return Poll::Ready(Err(std::io::Error::other("disk gone")));
+                }
+                return Poll::Ready(Ok(()));
+            }
+            let chunk = self.chunks.remove(0);
+            buf.put_slice(chunk);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw("CREATE TEMP TABLE copy_progress (id INT, name TEXT)")
+        .await?;
+    let columns = vec!["id".to_string(), "name".to_string()];
+
+    let chunks: Vec<&'static [u8]> = vec![
+        b"1\talpha\n2\tbeta\n",
+        b"3\tgamma\n",
+        b"4\tdelta\n5\tepsilon\n",
+    ];
+    let total: u64 = chunks.iter().map(|c| c.len() as u64).sum();
+    let mut progress = Vec::new();
+    let rows = driver
+        .copy_in_raw_progress(
+            "copy_progress",
+            &columns,
+            Chunked { chunks, fail_at_end: false },
+            |sent| progress.push(sent),
+        )
+        .await?;
+    assert_eq!(rows, 5);
+    assert_eq!(progress, vec![15, 23, total]);
+
+    // A failing source aborts the COPY and leaves the connection usable
+    let err = driver
+        .copy_in_raw_progress(
+            "copy_progress",
+            &columns,
+            Chunked { chunks: vec![b"6\tzeta\n"], fail_at_end: true },
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PgError::Io(_)), "{:?}", err);
+    let count = driver.fetch_raw("SELECT count(*) FROM copy_progress").await?;
+    assert_eq!(count[0].get_string(0).as_deref(), Some("5"));
+    Ok(())
+}
+
+/// Test deferring circular foreign keys until commit.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_defer_constraints_until_commit() -> PgResult<()> {
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+    driver
+        .execute_raw(
+            "DROP TABLE IF EXISTS defer_child, defer_parent CASCADE; \
+             CREATE TABLE defer_parent (id int PRIMARY KEY, favorite_child int); \
+             CREATE TABLE defer_child (id int PRIMARY KEY, parent_id int NOT NULL \
+                 CONSTRAINT defer_child_parent_fk REFERENCES defer_parent \
+                 DEFERRABLE INITIALLY IMMEDIATE); \
+             ALTER TABLE defer_parent ADD CONSTRAINT defer_parent_child_fk \
+                 FOREIGN KEY (favorite_child) REFERENCES defer_child \
+                 DEFERRABLE INITIALLY IMMEDIATE",
+        )
+        .await?;
+
+    let child = |id: i64, parent: i64| {
+        Qail::add("defer_child").columns(["id", "parent_id"]).values([id, parent])
+    };
+    let parent = |id: i64, child: i64| {
+        Qail::add("defer_parent").columns(["id", "favorite_child"]).values([id, child])
+    };
+
+    // Outside a transaction there is nothing to defer
+    let err = driver.defer_constraints().await.unwrap_err();
+    assert!(matches!(err, PgError::Query(_)), "{:?}", err);
+
+    // Without deferral the child insert fails immediately
+    driver.begin().await?;
+    assert!(driver.execute(&child(1, 1)).await.is_err());
+    driver.rollback().await?;
+
+    // Deferred: both rows reference each other, checked at COMMIT
+    driver.begin().await?;
+    driver.defer_constraints().await?;
+    driver.execute(&child(1, 1)).await?;
+    driver.execute(&parent(1, 1)).await?;
+    driver.commit().await?;
+
+    // A single named constraint
+    driver.begin().await?;
+    driver.defer_constraint("defer_child_parent_fk").await?;
+    driver.execute(&child(2, 2)).await?;
+    driver.execute(&parent(2, 2)).await?;
+    driver.commit().await?;
+
+    let count = driver.fetch_raw("SELECT count(*) FROM defer_child").await?;
+    assert_eq!(count[0].get_string(0).as_deref(), Some("2"));
+
+    // Violations still fail, at COMMIT
+    driver.begin().await?;
+    driver.defer_constraints().await?;
+    driver.execute(&child(3, 99)).await?;
+    let err = driver.commit().await.unwrap_err();
+    assert!(matches!(&err, PgError::Database(e) if e.code == "23503"), "{:?}", err);
+
+    driver.execute_raw("DROP TABLE defer_child, defer_parent CASCADE").await?;
+    Ok(())
+}
+
+/// Test that cached queries differing only in literal values share one statement.
+#[tokio::test]
+#[ignore = "Requires PostgreSQL server - run manually"]
+async fn test_cached_statement_shared_across_values() -> PgResult<()> {
+    use qail_core::ast::Operator;
+
+    let mut driver =
+        PgDriver::connect_with_password("127.0.0.1", 5432, "qail", "qail_test", "qail").await?;
+
+    driver
+        .execute_raw("CREATE TEMP TABLE shape_items (id INT PRIMARY KEY, name TEXT)")
+        .await?;
+    driver
+        .execute_raw("INSERT INTO shape_items VALUES (1, 'a'), (2, 'b'), (3, 'c')")
+        .await?;
+
+    let by_id = |id: i64| {
+        Qail::get("shape_items")
+            .columns(["id", "name"])
+            .filter("id", Operator::Eq, id)
+    };
+    for (id, name) in [(1, "a"), (2, "b"), (3, "c")] {
+        let rows = driver.fetch_all_cached(&by_id(id)).await?;
+        assert_eq!(rows[0].get_string(1), Some(name.to_string()));
+    }
+    assert_eq!(driver.cache_stats().0, 1);
+
+    // A different column list is a different statement
+    let ids = Qail::get("shape_items").column("id").filter("id", Operator::Eq, 1);
+    driver.fetch_all_cached(&ids).await?;
+    assert_eq!(driver.cache_stats().0, 2);
+    Ok(())
+}
@@ -9,6 +9,7 @@
 use once_cell::sync::Lazy;
 use qail_core::prelude::*;
 use qail_pg::driver::PreparedStatement as PgPreparedStatement;
+use qail_pg::driver::copy_escape;
 use qail_pg::protocol::AstEncoder;
 use std::ffi::{CStr, c_char};
 use std::sync::Mutex as SyncMutex;
@@ -72,7 +73,12 @@ pub extern "C" fn qail_encode_select(
         cmd = cmd.limit(limit);
     }
 
-    let (wire_bytes, _params) = AstEncoder::encode_cmd(&cmd);
+    let Ok((wire_bytes, _params)) = AstEncoder::encode_cmd(&cmd) else {
+        unsafe {
+            *out_len = 0;
+        }
+        return std::ptr::null_mut();
+    };
     let bytes = wire_bytes.to_vec();
 
     let len = bytes.len();
@@ -126,7 +132,12 @@ pub extern "C" fn qail_encode_batch(
         cmds.push(cmd);
     }
 
-    let batch_bytes = AstEncoder::encode_batch(&cmds);
+    let Ok(batch_bytes) = AstEncoder::encode_batch(&cmds) else {
+        unsafe {
+            *out_len = 0;
+        }
+        return std::ptr::null_mut();
+    };
     let bytes = batch_bytes.to_vec();
 
     let len = bytes.len();
@@ -529,6 +540,16 @@ pub extern "C" fn qail_copy_start(
     Box::into_raw(stream)
 }
 
+/// Write one COPY field: `\N` for a NULL pointer, otherwise the escaped value.
+#[inline]
+fn push_copy_value(buffer: &mut Vec<u8>, ptr: *const c_char) {
+    if ptr.is_null() {
+        buffer.extend_from_slice(b"\\N");
+    } else {
+        copy_escape(buffer, unsafe { CStr::from_ptr(ptr) }.to_bytes());
+    }
+}
+
 /// Add a row to the COPY stream (3-column version for users table).
 /// Returns 1 on success, 0 on failure.
 #[unsafe(no_mangle)]
@@ -548,29 +569,12 @@ pub extern "C" fn qail_copy_row_3(
         Err(_) => return 0,
     };
 
-    // Encode values as TSV line
-    let v0 = if col0.is_null() {
-        "\\N"
-    } else {
-        unsafe { CStr::from_ptr(col0).to_str().unwrap_or("\\N") }
-    };
-    let v1 = if col1.is_null() {
-        "\\N"
-    } else {
-        unsafe { CStr::from_ptr(col1).to_str().unwrap_or("\\N") }
-    };
-    let v2 = if col2.is_null() {
-        "\\N"
-    } else {
-        unsafe { CStr::from_ptr(col2).to_str().unwrap_or("\\N") }
-    };
-
     // Write TSV line: col0\tcol1\tcol2\n
-    buffer.extend_from_slice(v0.as_bytes());
+    push_copy_value(&mut buffer, col0);
     buffer.push(b'\t');
-    buffer.extend_from_slice(v1.as_bytes());
+    push_copy_value(&mut buffer, col1);
     buffer.push(b'\t');
-    buffer.extend_from_slice(v2.as_bytes());
+    push_copy_value(&mut buffer, col2);
     buffer.push(b'\n');
 
     stream_ref
@@ -598,21 +602,13 @@ pub extern "C" fn qail_copy_row_4(
         Err(_) => return 0,
     };
 
-    fn get_val(ptr: *const c_char) -> &'static str {
-        if ptr.is_null() {
-            "\\N"
-        } else {
-            unsafe { CStr::from_ptr(ptr).to_str().unwrap_or("\\N") }
-        }
-    }
-
-    buffer.extend_from_slice(get_val(col0).as_bytes());
+    push_copy_value(&mut buffer, col0);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col1).as_bytes());
+    push_copy_value(&mut buffer, col1);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col2).as_bytes());
+    push_copy_value(&mut buffer, col2);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col3).as_bytes());
+    push_copy_value(&mut buffer, col3);
     buffer.push(b'\n');
 
     stream_ref
@@ -642,25 +638,63 @@ pub extern "C" fn qail_copy_row_6(
         Err(_) => return 0,
     };
 
-    fn get_val(ptr: *const c_char) -> &'static str {
-        if ptr.is_null() {
-            "\\N"
-        } else {
-            unsafe { CStr::from_ptr(ptr).to_str().unwrap_or("\\N") }
-        }
-    }
-
-    buffer.extend_from_slice(get_val(col0).as_bytes());
+    push_copy_value(&mut buffer, col0);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col1).as_bytes());
+    push_copy_value(&mut buffer, col1);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col2).as_bytes());
+    push_copy_value(&mut buffer, col2);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col3).as_bytes());
+    push_copy_value(&mut buffer, col3);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col4).as_bytes());
+    push_copy_value(&mut buffer, col4);
     buffer.push(b'\t');
-    buffer.extend_from_slice(get_val(col5).as_bytes());
+    push_copy_value(&mut buffer, col5);
+    buffer.push(b'\n');
+
+    stream_ref
+        .row_count
+        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    1
+}
+
+/// Add a row of any width to the COPY stream.
+/// `values` points to `count` column pointers; a NULL pointer is written as `\N`.
+/// Tabs, newlines, carriage returns and backslashes in values are escaped.
+/// Returns 1 on success, 0 on failure.
+/// # Example (PHP)
+/// ```php
+/// $row = $ffi->new("char*[5]");
+/// // ... fill $row[0..4] ...
+/// qail_copy_row($copy, $row, 5);
+/// ```
+#[unsafe(no_mangle)]
+pub extern "C" fn qail_copy_row(
+    stream: *mut QailCopyStream,
+    values: *const *const c_char,
+    count: usize,
+) -> i32 {
+    if stream.is_null() || (values.is_null() && count > 0) {
+        return 0;
+    }
+
+    let stream_ref = unsafe { &*stream };
+    let mut buffer = match stream_ref.buffer.lock() {
+        Ok(b) => b,
+        Err(_) => return 0,
+    };
+
+    let values = if count == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(values, count) }
+    };
+
+    for (i, &ptr) in values.iter().enumerate() {
+        if i > 0 {
+            buffer.push(b'\t');
+        }
+        push_copy_value(&mut buffer, ptr);
+    }
     buffer.push(b'\n');
 
     stream_ref
@@ -808,3 +842,41 @@ pub extern "C" fn qail_mysql_to_pg(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    fn test_stream() -> QailCopyStream {
+        QailCopyStream {
+            conn: std::ptr::null_mut(),
+            table: "t".to_string(),
+            columns: vec![],
+            buffer: SyncMutex::new(Vec::new()),
+            row_count: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    #[test]
+    fn test_copy_row_five_columns_escaped() {
+        let mut stream = test_stream();
+        let owned: Vec<CString> = ["1", "alice", "a\tb", "line1\nline2"]
+            .iter()
+            .map(|s| CString::new(*s).unwrap())
+            .collect();
+        let values = [
+            owned[0].as_ptr(),
+            owned[1].as_ptr(),
+            owned[2].as_ptr(),
+            std::ptr::null(),
+            owned[3].as_ptr(),
+        ];
+
+        assert_eq!(qail_copy_row(&mut stream, values.as_ptr(), values.len()), 1);
+
+        let buffer = stream.buffer.lock().unwrap();
+        assert_eq!(&buffer[..], b"1\talice\ta\\tb\t\\N\tline1\\nline2\n");
+        assert_eq!(stream.row_count.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+}
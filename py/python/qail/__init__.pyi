@@ -282,3 +282,24 @@ class PgDriver:
     async def rollback(self) -> PgDriver:
         """Rollback the current transaction."""
         ...
+    
+    def prepare(self, sql: str) -> PreparedStatement:
+        """Prepare a statement with $1, $2, ... placeholders for repeated execution."""
+        ...
+
+class PreparedStatement:
+    """A statement prepared on a PgDriver connection."""
+    
+    @property
+    def sql(self) -> str:
+        """The SQL this statement was prepared from."""
+        ...
+    
+    @property
+    def name(self) -> str:
+        """The server-side statement name."""
+        ...
+    
+    def execute_many(self, params_list: List[List[Any]]) -> int:
+        """Execute once per parameter row in one pipeline. Returns the execution count."""
+        ...
@@ -7,10 +7,13 @@
 
 use crate::cmd::PyQail;
 use crate::row::PyRow;
+use crate::types::py_to_value;
 use once_cell::sync::Lazy;
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
-use qail_pg::{PgDriver, PgError};
+use qail_core::ast::Value;
+use qail_pg::driver::PreparedStatement;
+use qail_pg::{PgDriver, PgError, PgResult};
 use std::sync::{Arc, Mutex};
 
 // Global Tokio runtime - shared across all connections
@@ -190,8 +193,6 @@ impl PyPgDriver {
         cmd: &PyQail,
         rows: Vec<Vec<Bound<'_, PyAny>>>,
     ) -> PyResult<u64> {
-        use qail_core::ast::Value;
-
         let cmd_clone = cmd.inner.clone();
         let driver_arc = Arc::clone(&self.inner);
 
@@ -255,6 +256,18 @@ impl PyPgDriver {
         result.map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
     }
 
+    /// Prepare a statement once for repeated execution.
+    /// `sql` uses `$1, $2, ...` placeholders; the statement is parsed by the
+    /// server now, so syntax errors surface here rather than on first use.
+    /// Example:
+    ///     stmt = driver.prepare("INSERT INTO events (kind, n) VALUES ($1, $2)")
+    ///     stmt.execute_many([["click", 1], ["view", 2]])
+    fn prepare(&self, py: Python<'_>, sql: String) -> PyResult<PyPreparedStatement> {
+        let result = py.detach(|| self.prepare_blocking(sql));
+
+        result.map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
     /// Close the connection.
     fn close(&self) -> PyResult<()> {
         let mut guard = self
@@ -265,3 +278,224 @@ impl PyPgDriver {
         Ok(())
     }
 }
+
+/// A statement prepared on a `PgDriver` connection.
+/// Executions reuse the server-side statement: one Bind/Execute per row,
+/// all rows pipelined in a single round-trip.
+#[pyclass(name = "PreparedStatement")]
+pub struct PyPreparedStatement {
+    driver: Arc<Mutex<Option<PgDriver>>>,
+    stmt: PreparedStatement,
+    sql: String,
+}
+
+#[pymethods]
+impl PyPreparedStatement {
+    /// Execute once per parameter row. Returns the number of executions.
+    /// BLOCKING with GIL release - params are converted first, then the
+    /// whole batch is pipelined without holding the GIL.
+    fn execute_many(
+        &self,
+        py: Python<'_>,
+        params_list: Vec<Vec<Bound<'_, PyAny>>>,
+    ) -> PyResult<usize> {
+        let batch = params_list
+            .iter()
+            .map(|row| row.iter().map(py_to_value).collect::<PyResult<Vec<_>>>())
+            .collect::<PyResult<Vec<_>>>()?;
+        let result = py.detach(|| self.execute_many_blocking(&batch));
+
+        result.map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    /// The SQL this statement was prepared from.
+    #[getter]
+    fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The server-side statement name.
+    #[getter]
+    fn name(&self) -> &str {
+        self.stmt.name()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PreparedStatement({:?})", self.sql)
+    }
+}
+
+impl PyPgDriver {
+    /// Parse `sql` on the driver's connection (no-op if already prepared there).
+    fn prepare_blocking(&self, sql: String) -> PgResult<PyPreparedStatement> {
+        let mut guard = self.inner.lock().unwrap();
+        let driver = guard
+            .as_mut()
+            .ok_or_else(|| PgError::Protocol("Connection closed".to_string()))?;
+
+        let stmt = RUNTIME.block_on(async { driver.prepare(&sql).await })?;
+        Ok(PyPreparedStatement {
+            driver: Arc::clone(&self.inner),
+            stmt,
+            sql,
+        })
+    }
+}
+
+impl PyPreparedStatement {
+    /// Pipeline one execution of the stored statement handle per row of
+    /// `batch`; the SQL is neither looked up nor re-parsed.
+    fn execute_many_blocking(&self, batch: &[Vec<Value>]) -> PgResult<usize> {
+        let mut guard = self.driver.lock().unwrap();
+        let driver = guard
+            .as_mut()
+            .ok_or_else(|| PgError::Protocol("Connection closed".to_string()))?;
+
+        RUNTIME.block_on(async { driver.pipeline_query(&self.stmt, batch).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn driver_handle(driver: PgDriver) -> PyPgDriver {
+        PyPgDriver {
+            inner: Arc::new(Mutex::new(Some(driver))),
+        }
+    }
+
+    /// Mock server that accepts a trust login, then answers every Sync:
+    /// ParseComplete if the batch held a Parse, otherwise one
+    /// BindComplete + CommandComplete per Execute. Returns every frontend
+    /// message type it received after startup.
+    async fn extended_query_server() -> (u16, tokio::task::JoinHandle<Vec<u8>>) {
+        async fn read_message(socket: &mut TcpStream) -> Option<u8> {
+            let tag = socket.read_u8().await.ok()?;
+            let len = socket.read_i32().await.ok()? as usize;
+            let mut body = vec![0u8; len - 4];
+            socket.read_exact(&mut body).await.ok()?;
+            Some(tag)
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let len = socket.read_i32().await.unwrap() as usize;
+            let mut startup = vec![0u8; len - 4];
+            socket.read_exact(&mut startup).await.unwrap();
+            // AuthenticationOk, ReadyForQuery
+            socket
+                .write_all(&[b'R', 0, 0, 0, 8, 0, 0, 0, 0, b'Z', 0, 0, 0, 5, b'I'])
+                .await
+                .unwrap();
+
+            let mut seen = Vec::new();
+            let mut reply = Vec::new();
+            while let Some(tag) = read_message(&mut socket).await {
+                seen.push(tag);
+                match tag {
+                    b'P' => reply.extend_from_slice(&[b'1', 0, 0, 0, 4]),
+                    b'E' => {
+                        reply.extend_from_slice(&[b'2', 0, 0, 0, 4, b'C', 0, 0, 0, 15]);
+                        reply.extend_from_slice(b"INSERT 0 1\0");
+                    }
+                    b'S' => {
+                        reply.extend_from_slice(&[b'Z', 0, 0, 0, 5, b'I']);
+                        socket.write_all(&reply).await.unwrap();
+                        reply.clear();
+                    }
+                    _ => {}
+                }
+            }
+            seen
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn test_execute_many_reuses_prepared_handle() {
+        let (port, server) = RUNTIME.block_on(extended_query_server());
+        let driver = RUNTIME
+            .block_on(PgDriver::connect("127.0.0.1", port, "u", "db"))
+            .unwrap();
+        let driver = driver_handle(driver);
+
+        let sql = "INSERT INTO events (kind, n) VALUES ($1, $2)";
+        let stmt = driver.prepare_blocking(sql.to_string()).unwrap();
+        assert_eq!(stmt.sql(), sql);
+        // Preparing again finds the same statement without another Parse
+        assert_eq!(
+            driver.prepare_blocking(sql.to_string()).unwrap().name(),
+            stmt.name()
+        );
+
+        let batch: Vec<Vec<Value>> = (1..=3)
+            .map(|i| vec![Value::String(format!("k{}", i)), Value::Int(i)])
+            .collect();
+        assert_eq!(stmt.execute_many_blocking(&batch).unwrap(), 3);
+        assert_eq!(stmt.execute_many_blocking(&batch[..1]).unwrap(), 1);
+
+        *driver.inner.lock().unwrap() = None;
+        assert!(stmt.execute_many_blocking(&batch).is_err());
+
+        let seen = RUNTIME.block_on(server).unwrap();
+        // One Parse, then Bind/Execute only; Terminate when the driver drops
+        assert_eq!(seen, b"PSBEBEBESBESX".to_vec());
+    }
+
+    #[test]
+    #[ignore = "Requires PostgreSQL server - run manually"]
+    fn test_prepare_execute_many() {
+        let driver = RUNTIME
+            .block_on(PgDriver::connect_with_password(
+                "127.0.0.1",
+                5432,
+                "qail",
+                "qail_test",
+                "qail",
+            ))
+            .unwrap();
+        let driver = driver_handle(driver);
+        let with_driver = |sql: &str| {
+            let mut guard = driver.inner.lock().unwrap();
+            RUNTIME
+                .block_on(guard.as_mut().unwrap().execute_raw(sql))
+                .unwrap();
+        };
+        with_driver("DROP TABLE IF EXISTS py_prepared_test");
+        with_driver("CREATE TABLE py_prepared_test (kind TEXT, n INT)");
+
+        let sql = "INSERT INTO py_prepared_test (kind, n) VALUES ($1, $2)";
+        let stmt = driver.prepare_blocking(sql.to_string()).unwrap();
+
+        let batch: Vec<Vec<Value>> = (1..=100)
+            .map(|i| vec![Value::String(format!("k{}", i % 3)), Value::Int(i)])
+            .collect();
+        assert_eq!(stmt.execute_many_blocking(&batch).unwrap(), 100);
+        assert_eq!(stmt.execute_many_blocking(&batch[..5]).unwrap(), 5);
+
+        let rows = {
+            let mut guard = driver.inner.lock().unwrap();
+            let cmd = qail_core::ast::Qail::get("py_prepared_test").column("n");
+            RUNTIME
+                .block_on(guard.as_mut().unwrap().fetch_all_uncached(&cmd))
+                .unwrap()
+        };
+        let total: i64 = rows.iter().filter_map(|row| row.get_i64(0)).sum();
+        assert_eq!((rows.len(), total), (105, 5065));
+
+        // A bad parameter fails without breaking the connection
+        let bad = vec![vec![
+            Value::String("x".to_string()),
+            Value::String("nan?".to_string()),
+        ]];
+        assert!(stmt.execute_many_blocking(&bad).is_err());
+        assert_eq!(stmt.execute_many_blocking(&batch[..1]).unwrap(), 1);
+
+        with_driver("DROP TABLE py_prepared_test");
+    }
+}
@@ -6,25 +6,30 @@
 //! Returns PyBytes directly for zero-copy to Python.
 
 use crate::cmd::PyQail;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use qail_pg::protocol::AstEncoder;
 
 /// Encode a single Qail to PostgreSQL wire protocol bytes.
 /// Returns bytes ready to send directly to PostgreSQL TCP socket.
+/// Raises `ValueError` if the command cannot be encoded.
 #[pyfunction]
-pub fn encode_cmd<'py>(py: Python<'py>, cmd: &PyQail) -> Bound<'py, PyBytes> {
-    let (wire_bytes, _) = AstEncoder::encode_cmd(&cmd.inner);
-    PyBytes::new(py, &wire_bytes)
+pub fn encode_cmd<'py>(py: Python<'py>, cmd: &PyQail) -> PyResult<Bound<'py, PyBytes>> {
+    let (wire_bytes, _) =
+        AstEncoder::encode_cmd(&cmd.inner).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(py, &wire_bytes))
 }
 
 /// Encode multiple Qails to wire bytes for pipeline execution.
 /// All commands in one buffer for single network round-trip.
+/// Raises `ValueError` if a command needs more than 65535 bind parameters.
 #[pyfunction]
-pub fn encode_batch<'py>(py: Python<'py>, cmds: Vec<PyQail>) -> Bound<'py, PyBytes> {
+pub fn encode_batch<'py>(py: Python<'py>, cmds: Vec<PyQail>) -> PyResult<Bound<'py, PyBytes>> {
     let inner: Vec<_> = cmds.into_iter().map(|c| c.inner).collect();
-    let wire_bytes = AstEncoder::encode_batch(&inner);
-    PyBytes::new(py, &wire_bytes)
+    let wire_bytes =
+        AstEncoder::encode_batch(&inner).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(py, &wire_bytes))
 }
 
 /// Register encoder functions with the module.
@@ -27,7 +27,7 @@ mod types;
 mod driver;
 
 pub use cmd::PyQail;
-pub use driver::PyPgDriver;
+pub use driver::{PyPgDriver, PyPreparedStatement};
 pub use row::PyRow;
 pub use types::PyOperator;
 
@@ -38,6 +38,7 @@ fn qail(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyQail>()?;
     m.add_class::<PyRow>()?;
     m.add_class::<PyPgDriver>()?;
+    m.add_class::<PyPreparedStatement>()?;
 
     // Register sync encoder functions
     encoder::register(m)?;
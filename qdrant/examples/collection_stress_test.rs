@@ -39,7 +39,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let name = format!("stress_test_{}", i);
         let start = Instant::now();
         
-        match driver.create_collection(&name, 1536, Distance::Cosine, false).await {
+        match driver.create_collection(&name, qail_qdrant::VectorParams::new(1536, Distance::Cosine)).await {
             Ok(_) => {
                 created += 1;
                 println!("  ✓ {} ({:.2}ms)", name, start.elapsed().as_secs_f64() * 1000.0);
@@ -37,7 +37,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Cleanup and create collection
     let _ = rest_driver.delete_collection(COLLECTION_NAME).await;
     rest_driver
-        .create_collection(COLLECTION_NAME, VECTOR_DIM as u64, Distance::Cosine, false)
+        .create_collection(COLLECTION_NAME, qail_qdrant::VectorParams::new(VECTOR_DIM as u64, Distance::Cosine))
         .await?;
     println!("   ✓ Collection '{}' created ({} dimensions)", COLLECTION_NAME, VECTOR_DIM);
 
@@ -39,7 +39,7 @@ async fn main() -> QdrantResult<()> {
     let _ = rest_driver.delete_collection(COLLECTION_NAME).await;
     
     rest_driver
-        .create_collection(COLLECTION_NAME, 4, Distance::Cosine, false)
+        .create_collection(COLLECTION_NAME, qail_qdrant::VectorParams::new(4, Distance::Cosine))
         .await?;
     println!("   ✓ Collection created with 4D vectors, Cosine distance");
 
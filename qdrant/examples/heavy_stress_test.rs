@@ -32,6 +32,7 @@ fn random_points(count: usize, dim: usize) -> Vec<Point> {
                 id: PointId::Uuid(uuid::Uuid::new_v4().to_string()),
                 vector: random_vector(dim),
                 payload,
+                named_vectors: HashMap::new(),
             }
         })
         .collect()
@@ -64,7 +65,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     for i in 0..NUM_COLLECTIONS {
         let name = format!("heavy_stress_{}", i);
-        if driver.create_collection(&name, VECTOR_DIM as u64, Distance::Cosine, false).await.is_ok() {
+        if driver.create_collection(&name, qail_qdrant::VectorParams::new(VECTOR_DIM as u64, Distance::Cosine)).await.is_ok() {
             created += 1;
             if (i + 1) % 10 == 0 {
                 println!("  Created {}/{}", i + 1, NUM_COLLECTIONS);
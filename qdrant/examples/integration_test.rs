@@ -33,7 +33,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // --- Create collection ---
     println!("📁 Creating collection '{}'...", collection);
     driver
-        .create_collection(collection, 4, Distance::Cosine, false)
+        .create_collection(collection, qail_qdrant::VectorParams::new(4, Distance::Cosine))
         .await?;
     println!("✅ Collection created");
 
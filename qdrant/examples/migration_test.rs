@@ -8,7 +8,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let collection = "test_migration_collection";
 
     println!("Creating collection: {}", collection);
-    driver.create_collection(collection, 128, Distance::Cosine, false).await?;
+    driver.create_collection(collection, qail_qdrant::VectorParams::new(128, Distance::Cosine)).await?;
     println!("Collection created successfully!");
 
     // Wait a bit to ensure it's ready
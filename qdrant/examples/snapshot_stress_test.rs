@@ -38,6 +38,7 @@ fn random_points(count: usize, dim: usize) -> Vec<Point> {
             id: PointId::Num(i as u64),
             vector: (0..dim).map(|j| ((i * 7 + j * 13) % 100) as f32 / 100.0).collect(),
             payload: HashMap::new(),
+            named_vectors: HashMap::new(),
         })
         .collect()
 }
@@ -63,7 +64,7 @@ async fn main() -> Result<()> {
         let name = format!("snapshot_stress_{}", i);
         
         // Create collection
-        driver.create_collection(&name, VECTOR_DIM as u64, Distance::Cosine, false).await?;
+        driver.create_collection(&name, qail_qdrant::VectorParams::new(VECTOR_DIM as u64, Distance::Cosine)).await?;
         
         // Insert points
         let points = random_points(POINTS_PER_COLLECTION, VECTOR_DIM);
@@ -18,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _ = driver.delete_collection(collection).await;
     
     // Create fresh
-    driver.create_collection(collection, 4, Distance::Cosine, false).await?;
+    driver.create_collection(collection, qail_qdrant::VectorParams::new(4, Distance::Cosine)).await?;
     println!("✓ Collection created");
     
     // Create a simple point
@@ -26,6 +26,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         id: PointId::Num(1),
         vector: vec![0.1, 0.2, 0.3, 0.4],
         payload: HashMap::new(),
+        named_vectors: HashMap::new(),
     };
     
     println!("Upserting 1 point...");
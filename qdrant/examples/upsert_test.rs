@@ -15,9 +15,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Creating test collection...");
     driver.create_collection(
         "upsert_debug_test",
-        4,  // Small vector for testing
-        qail_core::ast::Distance::Cosine,
-        false,
+        qail_qdrant::VectorParams::new(4, qail_core::ast::Distance::Cosine), // Small vector for testing
     ).await?;
     println!("Collection created");
     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
@@ -28,11 +26,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             id: PointId::Num(1),
             vector: vec![0.1, 0.2, 0.3, 0.4],
             payload: std::collections::HashMap::new(),
+            named_vectors: std::collections::HashMap::new(),
         },
         Point {
             id: PointId::Num(2),
             vector: vec![0.5, 0.6, 0.7, 0.8],
             payload: std::collections::HashMap::new(),
+            named_vectors: std::collections::HashMap::new(),
         },
     ];
 
@@ -0,0 +1,105 @@
+//! Collection configuration for Qdrant.
+
+use qail_core::ast::{Action, Distance, Qail};
+
+use crate::error::{QdrantError, QdrantResult};
+
+/// Vector parameters of a collection: dimension, distance metric and index.
+///
+/// # Example
+/// ```ignore
+/// use qail_qdrant::{VectorParams, HnswConfig};
+/// use qail_core::ast::Distance;
+///
+/// let params = VectorParams::new(384, Distance::Cosine)
+///     .hnsw(HnswConfig { m: Some(32), ef_construct: Some(200), ..Default::default() });
+/// driver.create_collection("products", params).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VectorParams {
+    /// Vector dimension.
+    pub size: u64,
+    /// Similarity metric.
+    pub distance: Distance,
+    /// Keep vectors (and payloads) on disk instead of in memory.
+    pub on_disk: bool,
+    /// HNSW index settings; `None` uses the collection defaults.
+    pub hnsw: Option<HnswConfig>,
+}
+
+impl VectorParams {
+    /// Vectors of `size` dimensions compared with `distance`.
+    pub fn new(size: u64, distance: Distance) -> Self {
+        Self {
+            size,
+            distance,
+            ..Default::default()
+        }
+    }
+
+    /// Store vectors and payloads on disk.
+    pub fn on_disk(mut self) -> Self {
+        self.on_disk = true;
+        self
+    }
+
+    /// Set HNSW index parameters.
+    pub fn hnsw(mut self, config: HnswConfig) -> Self {
+        self.hnsw = Some(config);
+        self
+    }
+
+    /// Read the parameters of a `Qail::create_collection` command.
+    ///
+    /// `vector_size` is required; distance defaults to cosine.
+    pub fn from_qail(cmd: &Qail) -> QdrantResult<Self> {
+        if cmd.action != Action::CreateCollection {
+            return Err(QdrantError::Encode(format!(
+                "Expected CreateCollection, got {:?}",
+                cmd.action
+            )));
+        }
+        let size = cmd.vector_size.ok_or_else(|| {
+            QdrantError::Encode("Vector size required for collection".to_string())
+        })?;
+        Ok(Self {
+            size,
+            distance: cmd.distance.unwrap_or_default(),
+            on_disk: cmd.on_disk.unwrap_or(false),
+            hnsw: None,
+        })
+    }
+}
+
+/// HNSW index parameters. Unset fields keep the server defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HnswConfig {
+    /// Edges per node in the index graph.
+    pub m: Option<u64>,
+    /// Neighbours considered while building the index.
+    pub ef_construct: Option<u64>,
+    /// Segment size (in KB) below which searches skip the index and scan.
+    pub full_scan_threshold: Option<u64>,
+    /// Keep the index on disk.
+    pub on_disk: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_params_from_qail() {
+        let cmd = Qail::create_collection("products")
+            .vector_size(384)
+            .distance(Distance::Dot)
+            .on_disk(true);
+        let params = VectorParams::from_qail(&cmd).unwrap();
+        assert_eq!(params, VectorParams::new(384, Distance::Dot).on_disk());
+
+        // Size is required; distance defaults to cosine
+        assert!(VectorParams::from_qail(&Qail::create_collection("products")).is_err());
+        let params = VectorParams::from_qail(&Qail::create_collection("p").vector_size(4)).unwrap();
+        assert_eq!(params.distance, Distance::Cosine);
+    }
+}
@@ -7,12 +7,17 @@
 use bytes::BytesMut;
 use qail_core::ast::Qail;
 
+use crate::collection::VectorParams;
 use crate::error::{QdrantError, QdrantResult};
 use crate::transport::GrpcClient;
 use crate::point::{Point, ScoredPoint};
 use crate::decoder;
 use crate::encoder;
 
+/// Maximum points per UpsertPoints request, keeping large batches well
+/// under the server's gRPC message size limit.
+pub const UPSERT_CHUNK_SIZE: usize = 256;
+
 /// High-performance gRPC driver for Qdrant.
 ///
 /// Uses gRPC/HTTP2 with zero-copy protobuf encoding:
@@ -184,45 +189,63 @@ impl QdrantDriver {
     }
 
     /// Upsert points with zero-copy encoding.
+    ///
+    /// Payloads and named (dense or sparse) vectors are sent with each point.
+    /// Batches larger than [`UPSERT_CHUNK_SIZE`] are split into several
+    /// UpsertPoints requests; an error stops at the failing chunk, leaving
+    /// earlier chunks written.
     pub async fn upsert(
         &mut self,
         collection: &str,
         points: &[Point],
         wait: bool,
     ) -> QdrantResult<()> {
-        // Clear buffer for reuse
-        self.buffer.clear();
-        
-        // Encode request using zero-copy encoder
-        encoder::encode_upsert_proto(&mut self.buffer, collection, points, wait);
+        for chunk in points.chunks(UPSERT_CHUNK_SIZE) {
+            // Clear buffer for reuse
+            self.buffer.clear();
 
-        // Send via gRPC (split to avoid clone)
-        let request_bytes = self.buffer.split().freeze();
-        let _response = self.client.upsert(request_bytes).await?;
+            // Encode request using zero-copy encoder
+            encoder::encode_upsert_proto(&mut self.buffer, collection, chunk, wait);
+
+            // Send via gRPC (split to avoid clone)
+            let request_bytes = self.buffer.split().freeze();
+            let _response = self.client.upsert(request_bytes).await?;
+        }
         Ok(())
     }
 
     /// Create a collection with specific vector parameters.
+    ///
+    /// # Example
+    /// ```ignore
+    /// driver.create_collection("products", VectorParams::new(384, Distance::Cosine)).await?;
+    /// ```
     pub async fn create_collection(
         &mut self,
         collection_name: &str,
-        vector_size: u64,
-        distance: qail_core::ast::Distance,
-        on_disk: bool,
+        params: VectorParams,
     ) -> QdrantResult<()> {
         self.buffer.clear();
-        encoder::encode_create_collection_proto(
-            &mut self.buffer,
-            collection_name,
-            vector_size,
-            distance,
-            on_disk,
-        );
+        encoder::encode_create_collection_proto(&mut self.buffer, collection_name, &params);
         let request = self.buffer.split().freeze();
         self.client.create_collection(request).await?;
         Ok(())
     }
 
+    /// Create a collection from a `Qail::create_collection` command.
+    ///
+    /// ```ignore
+    /// let cmd = Qail::create_collection("products").vector_size(384).distance(Distance::Cosine);
+    /// driver.create_collection_ast(&cmd).await?;
+    /// ```
+    pub async fn create_collection_ast(&mut self, cmd: &Qail) -> QdrantResult<()> {
+        if cmd.table.is_empty() {
+            return Err(QdrantError::Encode("Collection name required".to_string()));
+        }
+        let params = VectorParams::from_qail(cmd)?;
+        self.create_collection(&cmd.table, params).await
+    }
+
     /// Delete a collection.
     pub async fn delete_collection(&mut self, collection_name: &str) -> QdrantResult<()> {
         self.buffer.clear();
@@ -63,9 +63,42 @@ const POINT_ID: u8 = 0x0A;
 /// Field 4: vectors (Vectors) -> (4 << 3) | 2 = 0x22 (field 2 is deprecated)
 const POINT_VECTORS: u8 = 0x22;
 /// Field 3: payload (map) -> (3 << 3) | 2 = 0x1A
-#[allow(dead_code)]
 const POINT_PAYLOAD: u8 = 0x1A;
 
+// ============================================================================
+// Vectors / Vector Field Tags
+// ============================================================================
+
+/// Vectors field 1: vector (Vector) -> 0x0A
+const VECTORS_VECTOR: u8 = 0x0A;
+/// Vectors field 2: vectors (NamedVectors) -> (2 << 3) | 2 = 0x12
+const VECTORS_NAMED: u8 = 0x12;
+/// NamedVectors field 1: vectors (map<string, Vector>) -> 0x0A
+const NAMED_VECTORS_MAP: u8 = 0x0A;
+/// Vector field 1: data (packed float) -> 0x0A
+const VECTOR_DATA: u8 = 0x0A;
+/// Vector field 2: indices (SparseIndices) -> (2 << 3) | 2 = 0x12
+const VECTOR_INDICES: u8 = 0x12;
+
+// ============================================================================
+// Value (payload) Field Tags
+// ============================================================================
+
+/// Field 1: null_value (enum) -> 0x08
+const VALUE_NULL: u8 = 0x08;
+/// Field 2: double_value (double) -> (2 << 3) | 1 = 0x11
+const VALUE_DOUBLE: u8 = 0x11;
+/// Field 3: integer_value (int64) -> (3 << 3) | 0 = 0x18
+const VALUE_INTEGER: u8 = 0x18;
+/// Field 4: string_value (string) -> (4 << 3) | 2 = 0x22
+const VALUE_STRING: u8 = 0x22;
+/// Field 5: bool_value (bool) -> (5 << 3) | 0 = 0x28
+const VALUE_BOOL: u8 = 0x28;
+/// Field 6: struct_value (Struct) -> (6 << 3) | 2 = 0x32
+const VALUE_STRUCT: u8 = 0x32;
+/// Field 7: list_value (ListValue) -> (7 << 3) | 2 = 0x3A
+const VALUE_LIST: u8 = 0x3A;
+
 // ============================================================================
 // PointId Field Tags
 // ============================================================================
@@ -187,6 +220,9 @@ pub fn encode_with_payload_true(buf: &mut BytesMut) {
 // ============================================================================
 
 /// Encode an UpsertPoints request to protobuf wire format.
+///
+/// Each point carries its payload and either its unnamed vector or, when
+/// `named_vectors` is set, a NamedVectors map (dense and sparse entries).
 pub fn encode_upsert_proto(
     buf: &mut BytesMut,
     collection: &str,
@@ -238,41 +274,146 @@ fn encode_point_struct(buf: &mut BytesMut, point: &crate::Point) {
         }
     }
     
-    // Field 4: vectors (Vectors -> Vector)
-    // Using the simpler deprecated approach: Vector.data = repeated float (field 1)
-    // This encodes as packed floats
-    let vector_bytes_len = point.vector.len() * 4;
-    
-    // Vector message: field 1 = data (packed floats)
-    // Tag for field 1, wire type 2 (length-delimited) = 0x0A
-    let vector_inner_len = 1 + varint_len(vector_bytes_len as u64) + vector_bytes_len;
-    
-    // Vectors message: field 1 = vector (Vector message)  
-    // Tag for field 1, wire type 2 (length-delimited) = 0x0A
-    let vectors_len = 1 + varint_len(vector_inner_len as u64) + vector_inner_len;
+    // Field 3: payload (map<string, Value>), keys sorted for stable output
+    for (key, value) in sorted(&point.payload) {
+        put_message(&mut point_buf, POINT_PAYLOAD, &encode_map_entry(key, &encode_payload_value(value)));
+    }
     
-    // Write Vectors field (field 4 of PointStruct)
-    point_buf.put_u8(POINT_VECTORS);  // 0x22 = field 4, wire type 2
-    encode_varint(&mut point_buf, vectors_len);
+    // Field 4: vectors (Vectors oneof)
+    let mut vectors_buf = BytesMut::new();
+    if point.named_vectors.is_empty() {
+        // Vectors.vector (field 1): the collection's single unnamed vector
+        put_message(&mut vectors_buf, VECTORS_VECTOR, &encode_vector(&point.vector, None));
+    } else {
+        // Vectors.vectors (field 2): NamedVectors { map<string, Vector> vectors = 1 }
+        // The unnamed vector, if any, is the default vector named ""
+        let mut named_buf = BytesMut::new();
+        if !point.vector.is_empty() {
+            let entry = encode_map_entry("", &encode_vector(&point.vector, None));
+            put_message(&mut named_buf, NAMED_VECTORS_MAP, &entry);
+        }
+        for (name, data) in sorted(&point.named_vectors) {
+            encode_named_vector(&mut named_buf, name, data);
+        }
+        put_message(&mut vectors_buf, VECTORS_NAMED, &named_buf);
+    }
+    put_message(&mut point_buf, POINT_VECTORS, &vectors_buf);
     
-    // Vectors.vector (field 1)
-    point_buf.put_u8(0x0A);  // field 1, wire type 2
-    encode_varint(&mut point_buf, vector_inner_len);
+    // Write to main buffer with length prefix
+    put_message(buf, UPSERT_POINTS, &point_buf);
+}
+
+/// Write one `NamedVectors` map entry per vector in `data`.
+///
+/// A nested `VectorData::Named` is flattened into its own entries.
+fn encode_named_vector(buf: &mut BytesMut, name: &str, data: &crate::VectorData) {
+    let vector = match data {
+        crate::VectorData::Single(values) => encode_vector(values, None),
+        crate::VectorData::Sparse { indices, values } => encode_vector(values, Some(indices)),
+        crate::VectorData::Named(vectors) => {
+            for (name, values) in sorted(vectors) {
+                let entry = encode_map_entry(name, &encode_vector(values, None));
+                put_message(buf, NAMED_VECTORS_MAP, &entry);
+            }
+            return;
+        }
+    };
+    put_message(buf, NAMED_VECTORS_MAP, &encode_map_entry(name, &vector));
+}
+
+/// Encode a Vector message: dense when `indices` is `None`, sparse otherwise.
+fn encode_vector(values: &[f32], indices: Option<&[u32]>) -> BytesMut {
+    let mut vector_buf = BytesMut::with_capacity(values.len() * 4 + 8);
     
     // Vector.data (field 1, packed floats) - deprecated but still works
-    point_buf.put_u8(0x0A);  // field 1, wire type 2
-    encode_varint(&mut point_buf, vector_bytes_len);
+    vector_buf.put_u8(VECTOR_DATA);
+    let vector_bytes_len = values.len() * 4;
+    encode_varint(&mut vector_buf, vector_bytes_len);
     let float_bytes = unsafe {
-        std::slice::from_raw_parts(point.vector.as_ptr() as *const u8, vector_bytes_len)
+        std::slice::from_raw_parts(values.as_ptr() as *const u8, vector_bytes_len)
     };
-    point_buf.extend_from_slice(float_bytes);
+    vector_buf.extend_from_slice(float_bytes);
     
-    // TODO: Field 3: payload (map) - skipped for now
+    // Vector.indices (field 2): SparseIndices { repeated uint32 data = 1 (packed) }
+    if let Some(indices) = indices {
+        let mut packed = BytesMut::with_capacity(indices.len() * 2);
+        for &index in indices {
+            encode_varint_u64(&mut packed, u64::from(index));
+        }
+        let mut indices_buf = BytesMut::with_capacity(packed.len() + 4);
+        put_message(&mut indices_buf, 0x0A, &packed);
+        put_message(&mut vector_buf, VECTOR_INDICES, &indices_buf);
+    }
+    vector_buf
+}
+
+/// Encode a payload value as a `qdrant.Value` message.
+fn encode_payload_value(value: &crate::point::PayloadValue) -> BytesMut {
+    use crate::point::PayloadValue;
     
-    // Write to main buffer with length prefix
-    buf.put_u8(UPSERT_POINTS);
-    encode_varint(buf, point_buf.len());
-    buf.extend_from_slice(&point_buf);
+    let mut value_buf = BytesMut::new();
+    match value {
+        PayloadValue::Null => {
+            value_buf.put_u8(VALUE_NULL);
+            value_buf.put_u8(0x00); // NullValue::NULL_VALUE
+        }
+        PayloadValue::Float(f) => {
+            value_buf.put_u8(VALUE_DOUBLE);
+            value_buf.put_f64_le(*f);
+        }
+        PayloadValue::Integer(n) => {
+            // int64: negative numbers take all 10 varint bytes
+            value_buf.put_u8(VALUE_INTEGER);
+            encode_varint_u64(&mut value_buf, *n as u64);
+        }
+        PayloadValue::String(s) => {
+            put_message(&mut value_buf, VALUE_STRING, s.as_bytes());
+        }
+        PayloadValue::Bool(b) => {
+            value_buf.put_u8(VALUE_BOOL);
+            value_buf.put_u8(u8::from(*b));
+        }
+        PayloadValue::Object(fields) => {
+            // Struct { map<string, Value> fields = 1 }
+            let mut struct_buf = BytesMut::new();
+            for (key, field) in sorted(fields) {
+                put_message(&mut struct_buf, 0x0A, &encode_map_entry(key, &encode_payload_value(field)));
+            }
+            put_message(&mut value_buf, VALUE_STRUCT, &struct_buf);
+        }
+        PayloadValue::List(items) => {
+            // ListValue { repeated Value values = 1 }
+            let mut list_buf = BytesMut::new();
+            for item in items {
+                put_message(&mut list_buf, 0x0A, &encode_payload_value(item));
+            }
+            put_message(&mut value_buf, VALUE_LIST, &list_buf);
+        }
+    }
+    value_buf
+}
+
+/// Encode a protobuf map entry: `key` (field 1, string), `value` (field 2, message).
+fn encode_map_entry(key: &str, value: &[u8]) -> BytesMut {
+    let mut entry = BytesMut::with_capacity(key.len() + value.len() + 6);
+    put_message(&mut entry, 0x0A, key.as_bytes());
+    put_message(&mut entry, 0x12, value);
+    entry
+}
+
+/// Write a length-delimited field: tag, length, bytes.
+#[inline]
+fn put_message(buf: &mut BytesMut, tag: u8, message: &[u8]) {
+    buf.put_u8(tag);
+    encode_varint(buf, message.len());
+    buf.extend_from_slice(message);
+}
+
+/// Map entries in key order, so encoding does not depend on `HashMap` iteration.
+fn sorted<V>(map: &std::collections::HashMap<String, V>) -> Vec<(&str, &V)> {
+    let mut entries: Vec<_> = map.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    entries.sort_unstable_by_key(|(k, _)| *k);
+    entries
 }
 
 // ============================================================================
@@ -297,9 +438,7 @@ const DELETE_COLLECTION_NAME: u8 = 0x0A;
 pub fn encode_create_collection_proto(
     buf: &mut BytesMut,
     collection_name: &str,
-    vector_size: u64,
-    distance: qail_core::ast::Distance,
-    on_disk: bool,
+    params: &crate::VectorParams,
 ) {
     buf.clear();
 
@@ -308,48 +447,74 @@ pub fn encode_create_collection_proto(
     encode_varint(buf, collection_name.len());
     buf.extend_from_slice(collection_name.as_bytes());
 
-    // Field 2: vectors_config (VectorsConfig -> VectorParams)
+    // Field 10: vectors_config (VectorsConfig -> VectorParams)
     // Need to construct nested messages for VectorParams
     let mut params_buf = BytesMut::with_capacity(32);
     
     // VectorParams.size (field 1, uint64)
     params_buf.put_u8(0x08);
-    encode_varint_u64(&mut params_buf, vector_size);
+    encode_varint_u64(&mut params_buf, params.size);
 
     // VectorParams.distance (field 2, enum)
-    // Per Qdrant proto: UnknownDistance=0, Cosine=1, Euclid=2, Dot=3
     params_buf.put_u8(0x10);
-    let distance_val = match distance {
-        qail_core::ast::Distance::Cosine => 1,
-        qail_core::ast::Distance::Euclid => 2,
-        qail_core::ast::Distance::Dot => 3,
-    };
-    encode_varint(&mut params_buf, distance_val);
+    encode_varint(&mut params_buf, distance_to_proto(params.distance));
+
+    // VectorParams.hnsw_config (field 3, HnswConfigDiff) - optional
+    if let Some(hnsw) = &params.hnsw {
+        put_message(&mut params_buf, 0x1A, &encode_hnsw_config(hnsw));
+    }
 
     // VectorParams.on_disk (field 5, bool) - optional but useful
-    if on_disk {
+    if params.on_disk {
         params_buf.put_u8(0x28); // field 5
         params_buf.put_u8(0x01);
     }
 
     // VectorsConfig.params (field 1) wraps VectorParams
     let mut config_buf = BytesMut::with_capacity(params_buf.len() + 4);
-    config_buf.put_u8(0x0A); // field 1
-    encode_varint(&mut config_buf, params_buf.len());
-    config_buf.extend_from_slice(&params_buf);
+    put_message(&mut config_buf, 0x0A, &params_buf);
 
     // Write to main buffer
-    buf.put_u8(CREATE_VECTORS_CONFIG);
-    encode_varint(buf, config_buf.len());
-    buf.extend_from_slice(&config_buf);
+    put_message(buf, CREATE_VECTORS_CONFIG, &config_buf);
 
-    // Field 5: on_disk_payload (bool) - optional
-    if on_disk {
+    // Field 8: on_disk_payload (bool) - optional
+    if params.on_disk {
         buf.put_u8(CREATE_ON_DISK);
         buf.put_u8(0x01);
     }
 }
 
+/// Qdrant proto `Distance` value: UnknownDistance=0, Cosine=1, Euclid=2, Dot=3.
+fn distance_to_proto(distance: qail_core::ast::Distance) -> usize {
+    match distance {
+        qail_core::ast::Distance::Cosine => 1,
+        qail_core::ast::Distance::Euclid => 2,
+        qail_core::ast::Distance::Dot => 3,
+    }
+}
+
+/// Encode an HnswConfigDiff message; unset fields are omitted.
+fn encode_hnsw_config(config: &crate::HnswConfig) -> BytesMut {
+    let mut hnsw_buf = BytesMut::with_capacity(16);
+    // Fields 1-3: m, ef_construct, full_scan_threshold (uint64)
+    for (tag, value) in [
+        (0x08, config.m),
+        (0x10, config.ef_construct),
+        (0x18, config.full_scan_threshold),
+    ] {
+        if let Some(value) = value {
+            hnsw_buf.put_u8(tag);
+            encode_varint_u64(&mut hnsw_buf, value);
+        }
+    }
+    // Field 5: on_disk (bool)
+    if let Some(on_disk) = config.on_disk {
+        hnsw_buf.put_u8(0x28);
+        hnsw_buf.put_u8(u8::from(on_disk));
+    }
+    hnsw_buf
+}
+
 /// Encode DeleteCollection request.
 pub fn encode_delete_collection_proto(buf: &mut BytesMut, collection_name: &str) {
     buf.clear();
@@ -433,6 +598,99 @@ mod tests {
         assert_eq!(&vector_bytes[0..4], &float_bytes);
     }
 
+    #[test]
+    fn test_encode_upsert_batch_with_payload() {
+        let points = [
+            crate::Point::new_num(1, vec![1.0, 2.0]).with_payload("a", "x"),
+            crate::Point::new_num(2, vec![0.5]).with_payload("n", 7i64),
+        ];
+        let mut buf = BytesMut::new();
+        encode_upsert_proto(&mut buf, "c", &points, true);
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            0x0A, 0x01, b'c', // collection_name
+            0x10, 0x01, // wait
+            0x1A, 0x1C, // PointStruct
+                0x0A, 0x02, 0x08, 0x01, // id { num: 1 }
+                0x1A, 0x08, 0x0A, 0x01, b'a', 0x12, 0x03, 0x22, 0x01, b'x', // payload a = "x"
+                0x22, 0x0C, 0x0A, 0x0A, 0x0A, 0x08, // vectors { vector { data } }
+                    0x00, 0x00, 0x80, 0x3F, 0x00, 0x00, 0x00, 0x40,
+            0x1A, 0x17, // PointStruct
+                0x0A, 0x02, 0x08, 0x02, // id { num: 2 }
+                0x1A, 0x07, 0x0A, 0x01, b'n', 0x12, 0x02, 0x18, 0x07, // payload n = 7
+                0x22, 0x08, 0x0A, 0x06, 0x0A, 0x04, 0x00, 0x00, 0x00, 0x3F,
+        ];
+        assert_eq!(&buf[..], expected);
+    }
+
+    #[test]
+    fn test_encode_upsert_named_and_sparse_vectors() {
+        let point = crate::Point::new_num(1, vec![])
+            .with_named_vector("dense", vec![1.0])
+            .with_sparse_vector("sparse", crate::SparseVector::new(vec![3, 200], vec![0.5, 2.0]));
+        let mut buf = BytesMut::new();
+        encode_upsert_proto(&mut buf, "c", &[point], false);
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            0x0A, 0x01, b'c',
+            0x1A, 0x36,
+                0x0A, 0x02, 0x08, 0x01,
+                0x22, 0x30, 0x12, 0x2E, // vectors { vectors: NamedVectors }
+                    // "dense" => { data: [1.0] }
+                    0x0A, 0x0F, 0x0A, 0x05, b'd', b'e', b'n', b's', b'e',
+                        0x12, 0x06, 0x0A, 0x04, 0x00, 0x00, 0x80, 0x3F,
+                    // "sparse" => { data: [0.5, 2.0], indices: { data: [3, 200] } }
+                    0x0A, 0x1B, 0x0A, 0x06, b's', b'p', b'a', b'r', b's', b'e',
+                        0x12, 0x11, 0x0A, 0x08,
+                            0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x40,
+                            0x12, 0x05, 0x0A, 0x03, 0x03, 0xC8, 0x01,
+        ];
+        assert_eq!(&buf[..], expected);
+    }
+
+    #[test]
+    fn test_encode_create_collection_cosine() {
+        let mut buf = BytesMut::new();
+        let params = crate::VectorParams::new(384, qail_core::ast::Distance::Cosine);
+        encode_create_collection_proto(&mut buf, "c", &params);
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            0x0A, 0x01, b'c', // collection_name
+            0x52, 0x07, 0x0A, 0x05, // vectors_config { params }
+                0x08, 0x80, 0x03, // size: 384
+                0x10, 0x01, // distance: Cosine
+        ];
+        assert_eq!(&buf[..], expected);
+    }
+
+    #[test]
+    fn test_encode_create_collection_with_hnsw() {
+        let mut buf = BytesMut::new();
+        let params = crate::VectorParams::new(4, qail_core::ast::Distance::Dot)
+            .on_disk()
+            .hnsw(crate::HnswConfig {
+                m: Some(32),
+                on_disk: Some(true),
+                ..Default::default()
+            });
+        encode_create_collection_proto(&mut buf, "c", &params);
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            0x0A, 0x01, b'c',
+            0x52, 0x0E, 0x0A, 0x0C,
+                0x08, 0x04, // size: 4
+                0x10, 0x03, // distance: Dot
+                0x1A, 0x04, 0x08, 0x20, 0x28, 0x01, // hnsw_config { m: 32, on_disk }
+                0x28, 0x01, // on_disk
+            0x40, 0x01, // on_disk_payload
+        ];
+        assert_eq!(&buf[..], expected);
+    }
+
     #[test]
     fn test_varint_len() {
         assert_eq!(varint_len(0), 1);
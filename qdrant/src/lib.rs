@@ -15,6 +15,7 @@
 //! let results = driver.search("products", &embedding, 10, None).await?;
 //! ```
 
+pub mod collection;
 pub mod driver;
 pub mod error;
 pub mod transport;
@@ -24,6 +25,7 @@ pub mod encoder;
 pub mod protocol;
 pub mod pool;
 
+pub use collection::{HnswConfig, VectorParams};
 pub use driver::QdrantDriver;
 pub use error::{QdrantError, QdrantResult};
 pub use point::{Point, PointId, Payload, SparseVector, VectorData, MultiVectorPoint};
@@ -34,6 +36,7 @@ pub mod prelude {
     pub use qail_core::prelude::*;
     pub use crate::{QdrantDriver, QdrantError, QdrantResult, Point, PointId, Payload};
     pub use crate::{SparseVector, VectorData, MultiVectorPoint};
+    pub use crate::{VectorParams, HnswConfig};
     pub use crate::{QdrantPool, PoolConfig};
 }
 
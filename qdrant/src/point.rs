@@ -48,9 +48,13 @@ pub type Payload = HashMap<String, PayloadValue>;
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Point {
     pub id: PointId,
+    /// The unnamed vector (empty for points that only have named vectors).
     pub vector: Vec<f32>,
     #[serde(default)]
     pub payload: Payload,
+    /// Named dense or sparse vectors, for collections with several vector fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub named_vectors: HashMap<String, VectorData>,
 }
 
 impl Point {
@@ -60,6 +64,7 @@ impl Point {
             id: id.into(),
             vector,
             payload: HashMap::new(),
+            named_vectors: HashMap::new(),
         }
     }
 
@@ -69,6 +74,7 @@ impl Point {
             id: PointId::Num(id),
             vector,
             payload: HashMap::new(),
+            named_vectors: HashMap::new(),
         }
     }
 
@@ -77,6 +83,24 @@ impl Point {
         self.payload.insert(key.into(), value.into());
         self
     }
+
+    /// Add a named dense vector.
+    pub fn with_named_vector(mut self, name: impl Into<String>, vector: Vec<f32>) -> Self {
+        self.named_vectors.insert(name.into(), VectorData::Single(vector));
+        self
+    }
+
+    /// Add a named sparse vector.
+    pub fn with_sparse_vector(mut self, name: impl Into<String>, vector: SparseVector) -> Self {
+        self.named_vectors.insert(
+            name.into(),
+            VectorData::Sparse {
+                indices: vector.indices,
+                values: vector.values,
+            },
+        );
+        self
+    }
 }
 
 /// Sparse vector - only non-zero indices and their values.
@@ -153,6 +177,21 @@ impl MultiVectorPoint {
     }
 }
 
+impl From<MultiVectorPoint> for Point {
+    fn from(point: MultiVectorPoint) -> Self {
+        Self {
+            id: point.id,
+            vector: Vec::new(),
+            payload: point.payload,
+            named_vectors: point
+                .vectors
+                .into_iter()
+                .map(|(name, vector)| (name, VectorData::Single(vector)))
+                .collect(),
+        }
+    }
+}
+
 /// Search result - point with similarity score.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScoredPoint {
@@ -1,6 +1,7 @@
-//! RESP3 protocol decoder.
+//! RESP2 / RESP3 protocol decoder.
 //!
-//! Parses Redis wire protocol responses into Value types.
+//! Parses Redis wire protocol responses into Value types. RESP3-only types
+//! (map, double, boolean, push, ...) only arrive after `HELLO 3`.
 
 use bytes::Buf;
 use crate::error::{RedisError, RedisResult};
@@ -8,7 +9,8 @@ use crate::value::Value;
 
 /// Decode a RESP3 response from the buffer.
 ///
-/// Returns the parsed value and the number of bytes consumed.
+/// Returns the parsed value and the number of bytes consumed, or
+/// [`RedisError::Incomplete`] if the buffer ends mid-value.
 pub fn decode(buf: &[u8]) -> RedisResult<(Value, usize)> {
     if buf.is_empty() {
         return Err(RedisError::Protocol("Empty buffer".into()));
@@ -22,7 +24,7 @@ pub fn decode(buf: &[u8]) -> RedisResult<(Value, usize)> {
 /// Decode a single RESP value.
 fn decode_value(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<Value> {
     if !cursor.has_remaining() {
-        return Err(RedisError::Protocol("Unexpected end of buffer".into()));
+        return Err(RedisError::Incomplete);
     }
 
     let type_byte = cursor.get_u8();
@@ -62,7 +64,7 @@ fn decode_value(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<Value> {
 
             let len = len as usize;
             if cursor.remaining() < len + 2 {
-                return Err(RedisError::Protocol("Incomplete bulk string".into()));
+                return Err(RedisError::Incomplete);
             }
 
             let mut data = vec![0u8; len];
@@ -133,6 +135,55 @@ fn decode_value(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<Value> {
             Ok(Value::Map(pairs))
         }
 
+        // Set: ~2\r\n... (RESP3), decoded like an array
+        b'~' => {
+            let line = read_line(cursor)?;
+            let count: usize = line.parse().map_err(|_| {
+                RedisError::Protocol(format!("Invalid set length: {}", line))
+            })?;
+            Ok(Value::Array(decode_items(cursor, count)?))
+        }
+
+        // Push: >2\r\n... (RESP3) - out-of-band, e.g. invalidation messages
+        b'>' => {
+            let line = read_line(cursor)?;
+            let count: usize = line.parse().map_err(|_| {
+                RedisError::Protocol(format!("Invalid push length: {}", line))
+            })?;
+            Ok(Value::Push(decode_items(cursor, count)?))
+        }
+
+        // Attribute: |1\r\n... (RESP3) - metadata ahead of the real reply, skipped
+        b'|' => {
+            let line = read_line(cursor)?;
+            let count: usize = line.parse().map_err(|_| {
+                RedisError::Protocol(format!("Invalid attribute length: {}", line))
+            })?;
+            decode_items(cursor, count * 2)?;
+            decode_value(cursor)
+        }
+
+        // Verbatim string: =15\r\ntxt:Some string\r\n (RESP3), format prefix dropped
+        b'=' => {
+            let mut data = read_blob(cursor)?;
+            if data.len() >= 4 && data[3] == b':' {
+                data.drain(..4);
+            }
+            Ok(Value::Bulk(data))
+        }
+
+        // Blob error: !21\r\nSYNTAX invalid syntax\r\n (RESP3)
+        b'!' => {
+            let data = read_blob(cursor)?;
+            Ok(Value::Error(String::from_utf8_lossy(&data).into_owned()))
+        }
+
+        // Big number: (3492890328409238509324850943850943825024385\r\n (RESP3)
+        b'(' => {
+            let line = read_line(cursor)?;
+            Ok(Value::String(line))
+        }
+
         _ => Err(RedisError::Protocol(format!(
             "Unknown type byte: {}",
             type_byte as char
@@ -140,6 +191,30 @@ fn decode_value(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<Value> {
     }
 }
 
+/// Decode `count` consecutive values.
+fn decode_items(cursor: &mut std::io::Cursor<&[u8]>, count: usize) -> RedisResult<Vec<Value>> {
+    let mut items = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        items.push(decode_value(cursor)?);
+    }
+    Ok(items)
+}
+
+/// Read a length-prefixed payload: `<len>\r\n<bytes>\r\n`.
+fn read_blob(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<Vec<u8>> {
+    let line = read_line(cursor)?;
+    let len: usize = line.parse().map_err(|_| {
+        RedisError::Protocol(format!("Invalid blob length: {}", line))
+    })?;
+    if cursor.remaining() < len + 2 {
+        return Err(RedisError::Incomplete);
+    }
+    let mut data = vec![0u8; len];
+    cursor.copy_to_slice(&mut data);
+    cursor.advance(2);
+    Ok(data)
+}
+
 /// Read a line until \r\n.
 fn read_line(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<String> {
     let start = cursor.position() as usize;
@@ -147,7 +222,7 @@ fn read_line(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<String> {
 
     // Find \r\n
     let mut end = start;
-    while end < buf.len() - 1 {
+    while end + 1 < buf.len() {
         if buf[end] == b'\r' && buf[end + 1] == b'\n' {
             let line = std::str::from_utf8(&buf[start..end])
                 .map_err(|_| RedisError::Protocol("Invalid UTF-8".into()))?;
@@ -157,7 +232,7 @@ fn read_line(cursor: &mut std::io::Cursor<&[u8]>) -> RedisResult<String> {
         end += 1;
     }
 
-    Err(RedisError::Protocol("Incomplete line".into()))
+    Err(RedisError::Incomplete)
 }
 
 #[cfg(test)]
@@ -218,4 +293,59 @@ mod tests {
         let (value, _) = decode(b"_\r\n").unwrap();
         assert_eq!(value, Value::Null);
     }
+
+    #[test]
+    fn test_decode_resp3_map() {
+        let buf = b"%2\r\n+server\r\n$5\r\nredis\r\n+proto\r\n:3\r\n";
+        let (value, len) = decode(buf).unwrap();
+        assert_eq!(len, buf.len());
+        assert_eq!(
+            value,
+            Value::Map(vec![
+                (Value::String("server".into()), Value::Bulk(b"redis".to_vec())),
+                (Value::String("proto".into()), Value::Integer(3)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_resp3_double() {
+        let (value, _) = decode(b",3.25\r\n").unwrap();
+        assert_eq!(value, Value::Double(3.25));
+        let (value, _) = decode(b",-inf\r\n").unwrap();
+        assert_eq!(value, Value::Double(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_decode_resp3_push_and_other_types() {
+        let (value, _) = decode(b">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nkey\r\n").unwrap();
+        assert_eq!(
+            value,
+            Value::Push(vec![
+                Value::Bulk(b"invalidate".to_vec()),
+                Value::Array(vec![Value::Bulk(b"key".to_vec())]),
+            ])
+        );
+
+        let (value, _) = decode(b"=15\r\ntxt:Some string\r\n").unwrap();
+        assert_eq!(value, Value::Bulk(b"Some string".to_vec()));
+        let (value, _) = decode(b"~1\r\n:7\r\n").unwrap();
+        assert_eq!(value, Value::Array(vec![Value::Integer(7)]));
+        let (value, _) = decode(b"!10\r\nSYNTAX bad\r\n").unwrap();
+        assert_eq!(value, Value::Error("SYNTAX bad".into()));
+        // Attributes are skipped; the reply that follows is returned
+        let (value, _) = decode(b"|1\r\n+ttl\r\n:60\r\n+OK\r\n").unwrap();
+        assert_eq!(value, Value::String("OK".into()));
+    }
+
+    #[test]
+    fn test_decode_incomplete() {
+        for partial in [&b"$5\r\nhel"[..], b"%2\r\n+a\r\n:1\r\n", b",3.2", b"*"] {
+            assert!(
+                matches!(decode(partial), Err(RedisError::Incomplete)),
+                "{:?}",
+                partial
+            );
+        }
+    }
 }
@@ -12,29 +12,42 @@
 //! let val = driver.execute(&Qail::redis_get("key")).await?;
 //! ```
 
+use bytes::BytesMut;
 use qail_core::ast::Qail;
 
+use crate::encoder;
 use crate::error::{RedisError, RedisResult};
 use crate::transport::Transport;
 use crate::value::Value;
 
 /// High-level Redis driver with connection management.
 pub struct RedisDriver {
-    transport: Transport,
+    pub(crate) transport: Transport,
     resp3: bool,
 }
 
 impl RedisDriver {
-    /// Connect to Redis server.
+    /// Connect to Redis server and switch to RESP3 with `HELLO 3`, for typed
+    /// maps, doubles, booleans and push messages.
+    ///
+    /// Falls back to RESP2 if the server rejects `HELLO` (Redis < 6);
+    /// check [`is_resp3`](Self::is_resp3) for the outcome.
     pub async fn connect(host: &str, port: u16) -> RedisResult<Self> {
         let mut transport = Transport::connect(host, port).await?;
-
-        // Upgrade to RESP3
-        let resp3 = transport.upgrade_to_resp3().await.unwrap_or(false);
-
+        let resp3 = transport.upgrade_to_resp3().await?;
         Ok(Self { transport, resp3 })
     }
 
+    /// Connect without sending `HELLO`, staying on RESP2.
+    /// Use this for servers or proxies that mishandle `HELLO`.
+    pub async fn connect_resp2(host: &str, port: u16) -> RedisResult<Self> {
+        let transport = Transport::connect(host, port).await?;
+        Ok(Self {
+            transport,
+            resp3: false,
+        })
+    }
+
     /// Execute a Qail command.
     pub async fn execute(&mut self, cmd: &Qail) -> RedisResult<Value> {
         self.transport.execute(cmd).await
@@ -45,6 +58,15 @@ impl RedisDriver {
         self.resp3
     }
 
+    /// Remove and return the push messages (RESP3 `>` frames, e.g. client
+    /// tracking invalidations) received so far, oldest first.
+    /// Pushes are never returned as command replies. At most
+    /// [`MAX_QUEUED_PUSHES`](crate::transport::MAX_QUEUED_PUSHES) are kept;
+    /// older ones are dropped if they are not taken.
+    pub fn take_pushes(&mut self) -> Vec<Vec<Value>> {
+        self.transport.take_pushes()
+    }
+
     // ========== Convenience Methods ==========
 
     /// GET key
@@ -157,4 +179,104 @@ impl RedisDriver {
             _ => Ok(false),
         }
     }
+
+    /// PUBLISH channel message. Returns the number of subscribers that received it.
+    pub async fn publish(&mut self, channel: &str, message: &[u8]) -> RedisResult<i64> {
+        let mut buf = BytesMut::with_capacity(32 + channel.len() + message.len());
+        encoder::encode_publish(&mut buf, channel, message);
+        match self.transport.execute_raw(&buf).await? {
+            Value::Integer(n) => Ok(n),
+            Value::Error(e) => Err(RedisError::Redis(e)),
+            other => Err(RedisError::Protocol(format!(
+                "Unexpected PUBLISH response: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MAX_QUEUED_PUSHES;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one connection, answering each request with the next canned reply.
+    async fn fake_server(replies: Vec<&'static [u8]>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            for reply in replies {
+                if socket.read(&mut buf).await.unwrap_or(0) == 0 {
+                    return;
+                }
+                socket.write_all(reply).await.unwrap();
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_connect_queues_pushes() {
+        let port = fake_server(vec![
+            b"%1\r\n+proto\r\n:3\r\n",
+            b">2\r\n$10\r\ninvalidate\r\n*1\r\n$1\r\nk\r\n+PONG\r\n",
+        ])
+        .await;
+
+        let mut driver = RedisDriver::connect("127.0.0.1", port).await.unwrap();
+        assert!(driver.is_resp3());
+        assert!(driver.ping().await.unwrap());
+        assert_eq!(
+            driver.take_pushes(),
+            vec![vec![
+                Value::Bulk(b"invalidate".to_vec()),
+                Value::Array(vec![Value::Bulk(b"k".to_vec())]),
+            ]]
+        );
+        assert!(driver.take_pushes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_connect_falls_back_to_resp2() {
+        let port = fake_server(vec![
+            b"-ERR unknown command 'HELLO'\r\n",
+            b"+PONG\r\n",
+        ])
+        .await;
+
+        let mut driver = RedisDriver::connect("127.0.0.1", port).await.unwrap();
+        assert!(!driver.is_resp3());
+        assert!(driver.ping().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_connect_resp2_skips_hello() {
+        let port = fake_server(vec![b"+PONG\r\n"]).await;
+
+        let mut driver = RedisDriver::connect_resp2("127.0.0.1", port).await.unwrap();
+        assert!(!driver.is_resp3());
+        assert!(driver.ping().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_queued_pushes_are_capped() {
+        let mut reply = Vec::new();
+        for i in 0..MAX_QUEUED_PUSHES + 5 {
+            let n = i.to_string();
+            reply.extend_from_slice(format!(">1\r\n${}\r\n{}\r\n", n.len(), n).as_bytes());
+        }
+        reply.extend_from_slice(b"+PONG\r\n");
+        let reply: &'static [u8] = reply.leak();
+        let port = fake_server(vec![b"%1\r\n+proto\r\n:3\r\n", reply]).await;
+
+        let mut driver = RedisDriver::connect("127.0.0.1", port).await.unwrap();
+        assert!(driver.ping().await.unwrap());
+        let pushes = driver.take_pushes();
+        assert_eq!(pushes.len(), MAX_QUEUED_PUSHES);
+        assert_eq!(pushes[0], vec![Value::Bulk(b"5".to_vec())]);
+    }
 }
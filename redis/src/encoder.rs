@@ -127,6 +127,23 @@ pub fn encode_hello(buf: &mut BytesMut, version: u8) {
     encode_bulk_string(buf, version.to_string().as_bytes());
 }
 
+/// Encode SUBSCRIBE channel [channel ...]
+pub fn encode_subscribe(buf: &mut BytesMut, channels: &[&str]) {
+    encode_array_header(buf, channels.len() + 1);
+    encode_bulk_string(buf, b"SUBSCRIBE");
+    for channel in channels {
+        encode_bulk_string(buf, channel.as_bytes());
+    }
+}
+
+/// Encode PUBLISH channel message
+pub fn encode_publish(buf: &mut BytesMut, channel: &str, message: &[u8]) {
+    encode_array_header(buf, 3);
+    encode_bulk_string(buf, b"PUBLISH");
+    encode_bulk_string(buf, channel.as_bytes());
+    encode_bulk_string(buf, message);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,6 +24,7 @@ pub mod driver;
 pub mod encoder;
 pub mod error;
 pub mod pool;
+pub mod pubsub;
 pub mod transport;
 pub mod value;
 
@@ -0,0 +1,189 @@
+//! Pub/Sub subscriptions.
+//!
+//! # Example
+//! ```ignore
+//! use futures_util::StreamExt;
+//!
+//! let driver = RedisDriver::connect("localhost", 6379).await?;
+//! let mut messages = driver.subscribe(&["jobs", "alerts"]).await?;
+//!
+//! while let Some(message) = messages.next().await {
+//!     let (channel, payload) = message?;
+//!     println!("{}: {:?}", channel, payload.as_str());
+//! }
+//! ```
+
+use std::collections::VecDeque;
+
+use bytes::BytesMut;
+use futures_util::Stream;
+use futures_util::stream;
+
+use crate::driver::RedisDriver;
+use crate::encoder;
+use crate::error::{RedisError, RedisResult};
+use crate::transport::Transport;
+use crate::value::Value;
+
+impl RedisDriver {
+    /// SUBSCRIBE to `channels` and stream `(channel, payload)` for every
+    /// message published to them.
+    ///
+    /// Takes the driver by value: a RESP2 connection in subscribe mode
+    /// only accepts (P)SUBSCRIBE / UNSUBSCRIBE / PING / QUIT, so it cannot
+    /// go back to running normal commands. Publish and run other commands
+    /// on a second connection. Dropping the stream closes the connection,
+    /// which ends the subscription.
+    ///
+    /// Returns once the server has confirmed every channel. The stream ends
+    /// after the first error.
+    pub async fn subscribe(
+        mut self,
+        channels: &[&str],
+    ) -> RedisResult<impl Stream<Item = RedisResult<(String, Value)>> + Send + Unpin> {
+        if channels.is_empty() {
+            return Err(RedisError::Protocol(
+                "SUBSCRIBE needs at least one channel".into(),
+            ));
+        }
+
+        let mut buf = BytesMut::with_capacity(64);
+        encoder::encode_subscribe(&mut buf, channels);
+        self.transport.send_raw(&buf).await?;
+
+        // Each channel is confirmed separately; messages on channels that are
+        // already confirmed may arrive in between
+        let mut early = VecDeque::new();
+        let mut pending = channels.len();
+        while pending > 0 {
+            match classify(self.transport.read_frame().await?) {
+                Frame::Subscribed => pending -= 1,
+                Frame::Message(channel, payload) => early.push_back((channel, payload)),
+                Frame::Error(e) => return Err(RedisError::Redis(e)),
+                Frame::Other => {}
+            }
+        }
+
+        let state = Subscription {
+            transport: self.transport,
+            early,
+        };
+        Ok(Box::pin(stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            if let Some(message) = state.early.pop_front() {
+                return Some((Ok(message), Some(state)));
+            }
+            loop {
+                match state.transport.read_frame().await.map(classify) {
+                    Ok(Frame::Message(channel, payload)) => {
+                        return Some((Ok((channel, payload)), Some(state)));
+                    }
+                    Ok(Frame::Error(e)) => return Some((Err(RedisError::Redis(e)), None)),
+                    Ok(Frame::Subscribed | Frame::Other) => {}
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+        })))
+    }
+}
+
+/// A subscribed connection and the messages read before the stream started.
+struct Subscription {
+    transport: Transport,
+    early: VecDeque<(String, Value)>,
+}
+
+/// What a frame on a subscribed connection means.
+enum Frame {
+    Subscribed,
+    Message(String, Value),
+    Error(String),
+    Other,
+}
+
+/// Classify a subscribe-mode frame: an array in RESP2, a push in RESP3,
+/// `[kind, channel, ...]` either way.
+fn classify(frame: Value) -> Frame {
+    let items = match frame {
+        Value::Array(items) | Value::Push(items) => items,
+        Value::Error(e) => return Frame::Error(e),
+        _ => return Frame::Other,
+    };
+    let mut items = items.into_iter();
+    let kind = items.next();
+    let channel = items.next().and_then(|c| c.as_str().map(str::to_string));
+    match (kind.as_ref().and_then(Value::as_str), channel) {
+        (Some("subscribe"), Some(_)) => Frame::Subscribed,
+        (Some("message"), Some(channel)) => match items.next() {
+            Some(payload) => Frame::Message(channel, payload),
+            None => Frame::Other,
+        },
+        _ => Frame::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_subscribe_streams_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            assert_eq!(
+                &buf[..n],
+                b"*3\r\n$9\r\nSUBSCRIBE\r\n$1\r\na\r\n$1\r\nb\r\n"
+            );
+            // A message on `a` arrives before `b` is confirmed
+            socket
+                .write_all(
+                    b"*3\r\n$9\r\nsubscribe\r\n$1\r\na\r\n:1\r\n\
+                      *3\r\n$7\r\nmessage\r\n$1\r\na\r\n$5\r\nfirst\r\n\
+                      *3\r\n$9\r\nsubscribe\r\n$1\r\nb\r\n:2\r\n",
+                )
+                .await
+                .unwrap();
+            socket
+                .write_all(b">3\r\n$7\r\nmessage\r\n$1\r\nb\r\n$6\r\nsecond\r\n")
+                .await
+                .unwrap();
+        });
+
+        let driver = RedisDriver::connect_resp2("127.0.0.1", port).await.unwrap();
+        let mut messages = driver.subscribe(&["a", "b"]).await.unwrap();
+
+        let (channel, payload) = messages.next().await.unwrap().unwrap();
+        assert_eq!((channel.as_str(), payload.as_str()), ("a", Some("first")));
+        let (channel, payload) = messages.next().await.unwrap().unwrap();
+        assert_eq!((channel.as_str(), payload.as_str()), ("b", Some("second")));
+
+        // The server closed the connection: one error, then the end
+        assert!(messages.next().await.unwrap().is_err());
+        assert!(messages.next().await.is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires Redis server - run manually"]
+    async fn test_subscribe_receives_published_message() {
+        let subscriber = RedisDriver::connect("127.0.0.1", 6379).await.unwrap();
+        let mut messages = subscriber.subscribe(&["qail:test:pubsub"]).await.unwrap();
+
+        let mut publisher = RedisDriver::connect("127.0.0.1", 6379).await.unwrap();
+        let receivers = publisher
+            .publish("qail:test:pubsub", b"hello")
+            .await
+            .unwrap();
+        assert_eq!(receivers, 1);
+
+        let (channel, payload) = messages.next().await.unwrap().unwrap();
+        assert_eq!(channel, "qail:test:pubsub");
+        assert_eq!(payload.as_bytes(), Some(&b"hello"[..]));
+    }
+}
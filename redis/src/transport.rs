@@ -2,6 +2,8 @@
 //!
 //! Handles raw TCP connection and RESP3 message exchange.
 
+use std::collections::VecDeque;
+
 use bytes::BytesMut;
 use qail_core::ast::Qail;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -12,10 +14,16 @@ use crate::encoder;
 use crate::error::{RedisError, RedisResult};
 use crate::value::Value;
 
+/// Push messages kept for [`Transport::take_pushes`]; older ones are
+/// dropped once the queue is full.
+pub const MAX_QUEUED_PUSHES: usize = 1024;
+
 /// Low-level Redis transport over TCP.
 pub struct Transport {
     stream: TcpStream,
     read_buf: BytesMut,
+    /// Push messages received while waiting for replies (RESP3 only).
+    pushes: VecDeque<Vec<Value>>,
 }
 
 impl Transport {
@@ -29,6 +37,7 @@ impl Transport {
         Ok(Self {
             stream,
             read_buf: BytesMut::with_capacity(4096),
+            pushes: VecDeque::new(),
         })
     }
 
@@ -47,16 +56,36 @@ impl Transport {
 
     /// Send raw bytes and read response.
     pub async fn execute_raw(&mut self, bytes: &[u8]) -> RedisResult<Value> {
+        self.send_raw(bytes).await?;
+        self.read_response().await
+    }
+
+    /// Send raw bytes without waiting for a response.
+    pub async fn send_raw(&mut self, bytes: &[u8]) -> RedisResult<()> {
         self.stream
             .write_all(bytes)
             .await
-            .map_err(RedisError::Io)?;
-
-        self.read_response().await
+            .map_err(RedisError::Io)
     }
 
     /// Read a RESP3 response from the connection.
+    /// Push messages are not replies; they are queued for [`take_pushes`](Self::take_pushes).
     async fn read_response(&mut self) -> RedisResult<Value> {
+        loop {
+            match self.read_frame().await? {
+                Value::Push(items) => {
+                    if self.pushes.len() == MAX_QUEUED_PUSHES {
+                        self.pushes.pop_front();
+                    }
+                    self.pushes.push_back(items);
+                }
+                value => return Ok(value),
+            }
+        }
+    }
+
+    /// Read the next frame of any kind, push messages included.
+    pub async fn read_frame(&mut self) -> RedisResult<Value> {
         loop {
             // Try to decode from existing buffer
             if !self.read_buf.is_empty() {
@@ -84,12 +113,19 @@ impl Transport {
         }
     }
 
-    /// Upgrade connection to RESP3.
+    /// Upgrade connection to RESP3 with `HELLO 3`.
+    /// Returns `false` if the server refused (Redis < 6 has no HELLO);
+    /// the connection then stays on RESP2.
     pub async fn upgrade_to_resp3(&mut self) -> RedisResult<bool> {
         let mut buf = BytesMut::with_capacity(64);
         encoder::encode_hello(&mut buf, 3);
 
-        self.execute_raw(&buf).await?;
-        Ok(true)
+        let reply = self.execute_raw(&buf).await?;
+        Ok(!reply.is_error())
+    }
+
+    /// Remove and return the push messages received so far, oldest first.
+    pub fn take_pushes(&mut self) -> Vec<Vec<Value>> {
+        self.pushes.drain(..).collect()
     }
 }
@@ -19,6 +19,8 @@ pub enum Value {
     Array(Vec<Value>),
     /// Map of key-value pairs (RESP3)
     Map(Vec<(Value, Value)>),
+    /// Out-of-band push message (RESP3), e.g. a client tracking invalidation
+    Push(Vec<Value>),
     /// Error from Redis
     Error(String),
 }